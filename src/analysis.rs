@@ -0,0 +1,143 @@
+use crate::MathParse;
+use crate::MathParseErrors;
+use crate::MathParseWarning;
+use crate::Number;
+use crate::Tree;
+use crate::RPN;
+use crate::parse::parse_warnings;
+use crate::solve::free_variables;
+use serde_json::{json, Map, Value};
+
+/* -------------------------------- Analysis -------------------------------- */
+
+/// Version of the schema of the JSON given by `MathParse::to_analysis_json`.
+/// New fields can be added without changing it, but it is increased when a
+/// field is removed, renamed, or changes meaning.
+pub const ANALYSIS_SCHEMA: u64 = 1;
+
+/// Describe everything about a parsed expression as a JSON object, as
+/// documented in `MathParse::to_analysis_json`.
+pub fn analysis_json(parsed: &MathParse) -> Result<Value, MathParseErrors> {
+    let rpn_actions = parsed.internal.to_rpn();
+    let mut ret = Map::new();
+    ret.insert("schema".to_string(), json!(ANALYSIS_SCHEMA));
+    ret.insert("rpn".to_string(), rpn_actions.iter().map(|action| json!(action.to_string())).collect());
+    ret.insert("trees".to_string(), parsed.to_trees()?.iter().map(tree_json).collect());
+    ret.insert("variables".to_string(), json!(free_variables(&rpn_actions, &parsed.options)));
+    ret.insert("literals".to_string(), parsed.literals_with_spans().into_iter()
+        .map(|(num, span)| {
+            let mut literal = Map::new();
+            literal.insert("value".to_string(), number_json(num));
+            if let Some(span) = span {
+                literal.insert("span".to_string(), json!([span.start, span.end]));
+            }
+            Value::Object(literal)
+        })
+        .collect());
+
+    let mut unary = vec![];
+    let mut binary = vec![];
+    for action in &rpn_actions {
+        let (symbol, list) = match action {
+            RPN::Unary(op) => (op.to_string(), &mut unary),
+            RPN::Binary(op) => (op.to_string(), &mut binary),
+            RPN::Name(_) | RPN::Call(_, _) => continue,
+        };
+        if !list.contains(&symbol) {
+            list.push(symbol);
+        }
+    }
+    ret.insert("operators".to_string(), json!({"unary": unary, "binary": binary}));
+
+    if let Some(source) = &parsed.source {
+        ret.insert("warnings".to_string(), parse_warnings(source).iter().map(warning_json).collect());
+    }
+    Ok(Value::Object(ret))
+}
+
+/// A node of a tree, with the nodes below it.
+fn tree_json(tree: &Tree) -> Value {
+    match tree {
+        Tree::Name(name) => json!({"name": name}),
+        Tree::Unary(op, x) => json!({"unary": op.to_string(), "operand": tree_json(x)}),
+        Tree::Binary(op, x, y) => json!({"binary": op.to_string(), "lhs": tree_json(x), "rhs": tree_json(y)}),
+        Tree::Call(name, args) => json!({"call": name, "args": args.iter().map(tree_json).collect::<Vec<_>>()}),
+    }
+}
+
+/// A number, integers being written without a decimal point.
+fn number_json(num: Number) -> Value {
+    match num {
+        Number::Int(i) => json!(i),
+        Number::Float(f) => json!(f),
+    }
+}
+
+/// A warning, with the code of its kind and its message.
+fn warning_json(warning: &MathParseWarning) -> Value {
+    let mut ret = Map::new();
+    let kind = match warning {
+        MathParseWarning::MixedNumberTypes(_) => "mixed_number_types",
+        MathParseWarning::PrecisionLoss(_) => "precision_loss",
+        MathParseWarning::ShadowedConstant(_) => "shadowed_constant",
+        MathParseWarning::ConfusingOperator(_, span) => {
+            ret.insert("span".to_string(), json!([span.start, span.end]));
+            "confusing_operator"
+        },
+    };
+    ret.insert("kind".to_string(), json!(kind));
+    ret.insert("message".to_string(), json!(warning.to_string()));
+    Value::Object(ret)
+}
+
+/* --------------------------------- Testing -------------------------------- */
+
+#[test]
+fn test_analysis_snapshot() {
+    let analysis = MathParse::parse("(a+2)*b").unwrap().to_analysis_json().unwrap();
+    assert_eq!(analysis, json!({
+        "schema": 1,
+        "rpn": ["a", "2", "+", "b", "*"],
+        "trees": [{
+            "binary": "*",
+            "lhs": {"binary": "+", "lhs": {"name": "a"}, "rhs": {"name": "2"}},
+            "rhs": {"name": "b"},
+        }],
+        "variables": ["a", "b"],
+        "literals": [{"value": 2, "span": [3, 4]}],
+        "operators": {"unary": [], "binary": ["+", "*"]},
+        "warnings": [],
+    }));
+}
+
+#[test]
+fn test_analysis_optional_fields() {
+    let analysis = MathParse::parse("-x ^ max(2.5, pi)").unwrap().to_analysis_json().unwrap();
+    assert_eq!(analysis["trees"][0]["rhs"], json!({"call": "max", "args": [{"name": "2.5"}, {"name": "pi"}]}));
+    assert_eq!(analysis["variables"], json!(["x"]));
+    assert_eq!(analysis["literals"], json!([{"value": 2.5, "span": [9, 12]}]));
+    assert_eq!(analysis["operators"], json!({"unary": ["-"], "binary": ["⊕"]}));
+    assert_eq!(analysis["warnings"][0]["kind"], json!("confusing_operator"));
+    assert_eq!(analysis["warnings"][0]["span"], json!([3, 4]));
+
+    // Without the source of the expression, there are no spans nor warnings.
+    let analysis = MathParse::parse_rpn("x 2 ^").unwrap().to_analysis_json().unwrap();
+    assert_eq!(analysis["literals"], json!([{"value": 2}]));
+    assert_eq!(analysis.get("warnings"), None);
+
+    let analysis = MathParse::parse("1, x").unwrap().to_analysis_json().unwrap();
+    assert_eq!(analysis["trees"], json!([{"name": "1"}, {"name": "x"}]));
+}
+
+#[test]
+fn test_analysis_schema_version() {
+    // The schema version must be increased when a field of this list is
+    // removed, renamed, or changes meaning, and this test updated along with
+    // it. Adding a field is compatible and keeps the version.
+    let analysis = MathParse::parse("a").unwrap().to_analysis_json().unwrap();
+    assert_eq!(analysis["schema"], json!(ANALYSIS_SCHEMA));
+    assert_eq!(ANALYSIS_SCHEMA, 1);
+    for field in ["schema", "rpn", "trees", "variables", "literals", "operators", "warnings"] {
+        assert!(analysis.get(field).is_some(), "{field}");
+    }
+}
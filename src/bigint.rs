@@ -0,0 +1,341 @@
+use crate::MathParseErrors;
+use crate::MathParseErrors::*;
+use crate::RPN;
+use crate::MathParseOptions;
+use crate::BinaryOp;
+use crate::UnaryOp;
+use crate::BinaryOp::*;
+use crate::rpn_stack_manipulation::*;
+use crate::solve::{Number, CONDITIONAL_FUNCTION, binder_function, binder_variable, compute_binary_with, compute_call as compute_number_call, compute_unary_with, constant_or_number, lazy_call, lazy_calls};
+use num_integer::Integer;
+use num_traits::{Signed, ToPrimitive, Zero};
+
+/// Integer of arbitrary size, as given by `MathParse::solve_big`. This is the
+/// `BigInt` of the num-bigint crate.
+pub use num_bigint::BigInt;
+
+/* ------------------------------ Big integers ------------------------------ */
+
+/// Maximum number of bits of the integers computed by `MathParse::solve_big`.
+/// Bigger results are solved as floats instead.
+const MAX_BITS: u64 = 1 << 14;
+
+/// Raise an integer to a power, or `None` if the result has more than
+/// `MAX_BITS` bits. The size is checked before computing the power so that
+/// huge powers are not computed.
+fn pow(a: &BigInt, exponent: u32) -> Option<BigInt> {
+    if a.bits() > 1 && (a.bits() - 1).saturating_mul(u64::from(exponent)) > MAX_BITS {
+        return None;
+    }
+    bounded(a.pow(exponent))
+}
+
+/* --------------------------------- Solving -------------------------------- */
+
+/// Value computed while solving. `None` means that the value is not an exact
+/// integer, which then spreads to the result of all the operations using it.
+type Big = Option<BigInt>;
+
+/// Keep an integer only if it has at most `MAX_BITS` bits.
+fn bounded(num: BigInt) -> Big {
+    (num.bits() <= MAX_BITS).then_some(num)
+}
+
+/// The result of a comparison, 1 when it is true and 0 otherwise.
+fn from_bool(b: bool) -> BigInt {
+    BigInt::from(i64::from(b))
+}
+
+/// Apply an operation with no exact version on big integers as it is done
+/// on `Number`s, when its operands fit in `i64`s and its result is an
+/// integer.
+fn with_numbers(nums: &[&BigInt], compute: impl FnOnce(&[Number]) -> Result<Number, MathParseErrors>) -> Result<Big, MathParseErrors> {
+    let Some(nums) = nums.iter().map(|num| num.to_i64().map(Number::Int)).collect::<Option<Vec<_>>>() else {
+        return Ok(None);
+    };
+    Ok(match compute(&nums)? {
+        Number::Int(i) => Some(BigInt::from(i)),
+        Number::Float(_) => None,
+    })
+}
+
+fn compute_unary(num: Big, op: UnaryOp, options: &MathParseOptions) -> Result<Big, MathParseErrors> {
+    match (num, op) {
+        (Some(num), UnaryOp::Minus) => Ok(Some(-num)),
+        (num, UnaryOp::Plus) => Ok(num),
+        (Some(num), op) => with_numbers(&[&num], |nums| compute_unary_with(nums[0], op, options)),
+        (None, _) => Ok(None),
+    }
+}
+
+fn compute_binary(num_1: Big, num_2: Big, op: BinaryOp, options: &MathParseOptions) -> Result<Big, MathParseErrors> {
+    if num_2.as_ref().is_some_and(BigInt::is_zero) && matches!(op, Division | IntegerDivision | Reminder) {
+        return Err(UnexpectedZero);
+    }
+    let (Some(num_1), Some(num_2)) = (num_1, num_2) else {
+        return Ok(None);
+    };
+    Ok(match op {
+        Addition        => bounded(num_1 + num_2),
+        Subtraction     => bounded(num_1 - num_2),
+        Multiplication  => bounded(num_1 * num_2),
+        Division        => {
+            let (quotient, rem) = num_1.div_rem(&num_2);
+            rem.is_zero().then_some(quotient)
+        },
+        IntegerDivision if options.floor_division => Some(num_1.div_floor(&num_2)),
+        IntegerDivision => Some(num_1 / num_2),
+        Reminder        => {
+            let rem = num_1 % &num_2;
+            if options.euclidean_remainder && rem.is_negative() {
+                Some(rem + num_2.abs())
+            } else {
+                Some(rem)
+            }
+        },
+        Exponent if num_2.is_negative() => {
+            if num_1.is_zero() {
+                return Err(UnexpectedZero);
+            }
+            // Only 1 and -1 have integer inverses.
+            match num_1.to_i64() {
+                Some(1) => Some(num_1),
+                Some(-1) => Some(BigInt::from(if num_2.is_odd() { -1 } else { 1 })),
+                _ => None,
+            }
+        },
+        Exponent        => match num_2.to_u32() {
+            Some(exponent) => pow(&num_1, exponent),
+            // Only 0, 1, and -1 can be raised to huge powers, which give the
+            // same results as their square or themselves.
+            None if num_1.bits() <= 1 => pow(&num_1, if num_2.is_odd() { 1 } else { 2 }),
+            None => None,
+        },
+        LessThan        => Some(from_bool(num_1 < num_2)),
+        LessOrEqual     => Some(from_bool(num_1 <= num_2)),
+        GreaterThan     => Some(from_bool(num_1 > num_2)),
+        GreaterOrEqual  => Some(from_bool(num_1 >= num_2)),
+        Equal           => Some(from_bool(num_1 == num_2)),
+        NotEqual        => Some(from_bool(num_1 != num_2)),
+        LogicalAnd      => Some(from_bool(!num_1.is_zero() && !num_2.is_zero())),
+        LogicalOr       => Some(from_bool(!num_1.is_zero() || !num_2.is_zero())),
+        // The shifts and the bitwise operators work on 64 bits.
+        ShiftLeft | ShiftRight | BitwiseAnd | BitwiseOr | BitwiseXor => {
+            return with_numbers(&[&num_1, &num_2], |nums| compute_binary_with(nums[0], nums[1], op, options));
+        },
+    })
+}
+
+/// Execute a call to a function that is not a binder function. The
+/// functions picking or combining integers are exact, the other ones are
+/// computed as on `Number`s.
+fn compute_call(name: &str, args: Vec<Big>, options: &MathParseOptions) -> Result<Big, MathParseErrors> {
+    let Some(args) = args.into_iter().collect::<Option<Vec<BigInt>>>() else {
+        return Ok(None);
+    };
+    Ok(match (name, &args[..]) {
+        ("min", [first, rest @ ..]) => Some(rest.iter().fold(first, |a, b| a.min(b)).clone()),
+        ("max", [first, rest @ ..]) => Some(rest.iter().fold(first, |a, b| a.max(b)).clone()),
+        ("abs", [x]) => Some(x.abs()),
+        ("clamp", [x, low, high]) if low <= high => Some(x.clamp(low, high).clone()),
+        ("gcd", [a, b]) => Some(a.gcd(b)),
+        ("lcm", [a, b]) => bounded(a.lcm(b)),
+        _ => {
+            let args = args.iter().collect::<Vec<_>>();
+            return with_numbers(&args, |nums| compute_number_call(name, nums.to_vec(), options));
+        },
+    })
+}
+
+/// Keeps track of the state needed to solve RPN actions with big integers,
+/// as `solve::Solver` does with `Number`s.
+struct BigSolver<'a> {
+    map: &'a dyn Fn(&str) -> Option<String>,
+    options: &'a MathParseOptions,
+    /// Variables bound by binder functions, the last one shadowing the
+    /// previous ones.
+    bindings: Vec<(String, BigInt)>,
+    /// Number of operations already performed.
+    operations: usize,
+}
+
+impl BigSolver<'_> {
+    /// Count a new operation and error out if there is too many of them.
+    fn count_operation(&mut self) -> Result<(), MathParseErrors> {
+        self.operations += 1;
+        match self.options.max_operations {
+            Some(max) if self.operations > max => Err(TooManyOperations(max)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Reads a name, the variables bound by binder functions shadowing the
+    /// named variables from the map.
+    fn read_name(&self, name: &str) -> Result<Big, MathParseErrors> {
+        if let Some((_, value)) = self.bindings.iter().rev().find(|(bound_name, _)| bound_name == name) {
+            return Ok(Some(value.clone()));
+        }
+        if let Some(new_name) = (self.map)(name) {
+            let parsed = crate::MathParse::parse_with_options(&new_name, self.options)?;
+            return big_solve(&parsed.internal.to_rpn(), &|_| None, self.options);
+        }
+        Ok(match constant_or_number(name, self.options)? {
+            Number::Int(i) => Some(BigInt::from(i)),
+            // Decimal integers too big for an `i64` are read as floats.
+            Number::Float(_) if name.bytes().all(|c| c.is_ascii_digit()) => name.parse().ok().and_then(bounded),
+            Number::Float(_) => None,
+        })
+    }
+
+    /// Solve RPN actions that should leave a single number on the stack.
+    fn solve(&mut self, rpn_actions: &[RPN]) -> Result<Big, MathParseErrors> {
+        let lazy_calls = lazy_calls(rpn_actions)?;
+
+        let mut number_stack = Vec::<Big>::new();
+        let mut index = 0;
+        while index < rpn_actions.len() {
+            self.count_operation()?;
+            if let Some((call_index, starts)) = lazy_call(&lazy_calls, index, rpn_actions.len()) {
+                let result = match &rpn_actions[*call_index] {
+                    RPN::Call(name, _) if name == CONDITIONAL_FUNCTION => self.solve_conditional(rpn_actions, *call_index, starts)?,
+                    _ => self.solve_binder(rpn_actions, *call_index, starts)?,
+                };
+                number_stack.push(result);
+                index = call_index + 1;
+                continue;
+            }
+            match &rpn_actions[index] {
+                RPN::Name(name)        => number_stack.push(self.read_name(name)?),
+                RPN::Unary(op)         => execute_unary(&mut number_stack, *op, &|num, op| compute_unary(num, op, self.options))?,
+                RPN::Binary(op)        => execute_binary(&mut number_stack, *op, &|num_1, num_2, op| compute_binary(num_1, num_2, op, self.options))?,
+                RPN::Call(name, arity) => {
+                    let args = pop_n(&mut number_stack, *arity)?;
+                    number_stack.push(compute_call(name, args, self.options)?);
+                },
+            }
+            index += 1;
+        }
+        if number_stack.len() == 1 {
+            pop_one(&mut number_stack)
+        } else {
+            Err(UnbalancedStack)
+        }
+    }
+
+    /// Solve a call to the conditional function, only solving the argument
+    /// chosen by the condition.
+    fn solve_conditional(&mut self, rpn_actions: &[RPN], call_index: usize, starts: &[usize]) -> Result<Big, MathParseErrors> {
+        match self.solve(&rpn_actions[starts[0]..starts[1]])? {
+            Some(condition) if condition.is_zero() => self.solve(&rpn_actions[starts[2]..call_index]),
+            Some(_) => self.solve(&rpn_actions[starts[1]..starts[2]]),
+            None => Ok(None),
+        }
+    }
+
+    /// Solve a call to a binder function, given the index of the call and the
+    /// start of each of its arguments.
+    fn solve_binder(&mut self, rpn_actions: &[RPN], call_index: usize, starts: &[usize]) -> Result<Big, MathParseErrors> {
+        let (name, init) = match &rpn_actions[call_index] {
+            RPN::Call(name, _) => match binder_function(name) {
+                Some((Number::Int(init), _)) => (name, init),
+                _ => return Err(MathParseInternalBug(format!("{name} should have been a binder function."))),
+            },
+            action => return Err(MathParseInternalBug(format!("{action:?} should have been a function call."))),
+        };
+        let variable = binder_variable(rpn_actions, name, starts)?;
+        let from = self.solve(&rpn_actions[starts[1]..starts[2]])?.and_then(|x| x.to_i64());
+        let to = self.solve(&rpn_actions[starts[2]..starts[3]])?.and_then(|x| x.to_i64());
+        let (Some(from), Some(to)) = (from, to) else {
+            return Ok(None);
+        };
+        let body = &rpn_actions[starts[3]..call_index];
+
+        let mut ret = BigInt::from(init);
+        for value in from..=to {
+            self.count_operation()?;
+            self.bindings.push((variable.clone(), BigInt::from(value)));
+            let computed = self.solve(body);
+            self.bindings.pop();
+            let Some(computed) = computed? else {
+                return Ok(None);
+            };
+            let accumulated = if name == "product" { ret * computed } else { ret + computed };
+            let Some(accumulated) = bounded(accumulated) else {
+                return Ok(None);
+            };
+            ret = accumulated;
+        }
+        Ok(Some(ret))
+    }
+}
+
+fn big_solve(rpn_actions: &[RPN], map: &dyn Fn(&str) -> Option<String>, options: &MathParseOptions) -> Result<Big, MathParseErrors> {
+    BigSolver{map, options, bindings: vec![], operations: 0}.solve(rpn_actions)
+}
+
+/// Solve a list of RPN actions with big integers. The result is `None` when
+/// it is not an integer or when it is too big.
+pub fn math_solve_big(rpn_actions: &[RPN], map: &dyn Fn(&str) -> Option<String>, options: &MathParseOptions) -> Result<Option<BigInt>, MathParseErrors> {
+    big_solve(rpn_actions, map, options)
+}
+
+/* --------------------------------- Testing -------------------------------- */
+
+#[test]
+fn test_pow() {
+    assert_eq!(pow(&BigInt::from(2), 100).map(|x| x.to_string()), Some("1267650600228229401496703205376".to_string()));
+    assert_eq!(pow(&BigInt::from(2), MAX_BITS as u32), None);
+    assert_eq!(pow(&BigInt::from(2), MAX_BITS as u32 - 1).map(|x| x.bits()), Some(MAX_BITS));
+    assert_eq!(pow(&BigInt::from(-1), u32::MAX), Some(BigInt::from(-1)));
+    assert_eq!(pow(&BigInt::from(3), u32::MAX), None);
+}
+
+#[test]
+fn test_solve_big() {
+    let solve = |expression: &str| crate::MathParse::parse(expression)?.solve_big(None).map(|num| num.to_string());
+    assert_eq!(solve("2**200"), Ok("1606938044258990275541962092341162602522202993782792835301376".to_string()));
+    assert_eq!(solve("product(i, 1, 30, i)"), Ok("265252859812191058636308480000000".to_string()));
+    assert_eq!(solve("9223372036854775807 + 1"), Ok("9223372036854775808".to_string()));
+    assert_eq!(solve("-(-9223372036854775807 - 1)"), Ok("9223372036854775808".to_string()));
+    assert_eq!(solve("100000000000000000000 // 3"), Ok("33333333333333333333".to_string()));
+    assert_eq!(solve("-(10**20) % 7"), Ok("-2".to_string()));
+    assert_eq!(solve("10**20 / 4"), Ok("25000000000000000000".to_string()));
+    assert_eq!(solve("2**64 > 2**63"), Ok("1".to_string()));
+    assert_eq!(solve("2**64 == 2**64 + 0"), Ok("1".to_string()));
+    assert_eq!(solve("(-1) ** -3"), Ok("-1".to_string()));
+    assert_eq!(solve("0xFF & 0x0F"), Ok("15".to_string()));
+    assert_eq!(solve("2**70 / 2**70 * 1.5"), Err(ReturnFloatExpectedInt(1.5)));
+    assert_eq!(solve("2**70 // 0"), Err(UnexpectedZero));
+    assert_eq!(solve("0 ** -1"), Err(UnexpectedZero));
+    assert_eq!(solve("max(2, 3) * 2"), Ok("6".to_string()));
+    assert_eq!(solve("product(i, 1, 30, i) // product(i, 1, 29, i)"), Ok("30".to_string()));
+    assert_eq!(solve("sum(i, 1, 3, i * 2**64)"), Ok("110680464442257309696".to_string()));
+    assert_eq!(solve("sum(i, 1, 3, 0.5)"), Err(ReturnFloatExpectedInt(1.5)));
+    assert_eq!(solve("max(2**64, 3, -(2**70))"), Ok("18446744073709551616".to_string()));
+    assert_eq!(solve("min(2**64, 3, -(2**70))"), Ok("-1180591620717411303424".to_string()));
+    assert_eq!(solve("abs(-(2**70))"), Ok("1180591620717411303424".to_string()));
+    assert_eq!(solve("clamp(2**70, 0, 2**64)"), Ok("18446744073709551616".to_string()));
+    assert_eq!(solve("gcd(2**70, 6**40)"), Ok("1099511627776".to_string()));
+    assert_eq!(solve("lcm(2**64, 3)"), Ok("55340232221128654848".to_string()));
+    assert_eq!(solve("2**64 > 1 ? 2**65 : 0"), Ok("36893488147419103232".to_string()));
+    assert_eq!(solve("popcount(2**10 - 1)"), Ok("10".to_string()));
+    assert_eq!(solve("f(2**64)"), Err(UnknownFunction("f".to_string())));
+    assert!(matches!(solve("2 ** 20000"), Err(IntConversion(_))));
+    assert_eq!(crate::MathParse::parse("2**200").unwrap().solve_int(None), Err(IntConversion(2f64.powi(200))));
+
+    let options = MathParseOptions {
+        floor_division: true,
+        euclidean_remainder: true,
+        integer_overflow_errors: true,
+        ..MathParseOptions::default()
+    };
+    let solve = |expression: &str| crate::MathParse::parse_with_options(expression, &options)?.solve_big(None).map(|num| num.to_string());
+    assert_eq!(solve("-(10**20) // 7"), Ok("-14285714285714285715".to_string()));
+    assert_eq!(solve("-(10**20) % 7"), Ok("5".to_string()));
+    assert_eq!(solve("9223372036854775807 * 2"), Ok("18446744073709551614".to_string()));
+
+    let variables = std::collections::HashMap::from([
+        ("a".to_string(), "2**100".to_string()),
+    ]);
+    assert_eq!(crate::MathParse::parse("a - a + 1").unwrap().solve_big(Some(&variables)), Ok(BigInt::from(1)));
+}
@@ -0,0 +1,190 @@
+use crate::MathParse;
+use crate::MathParseErrors;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/* ---------------------------------- Cache --------------------------------- */
+
+/// Number of hits and misses of an `ExprCache`.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
+pub struct CacheStats {
+    /// Number of times an expression was already parsed.
+    pub hits: u64,
+    /// Number of times an expression had to be parsed.
+    pub misses: u64,
+}
+
+/// A parsed expression and the last time it was used.
+struct CacheEntry {
+    parsed: Arc<MathParse>,
+    last_use: u64,
+}
+
+/// Content of the cache, protected by the mutex of `ExprCache`.
+struct CacheContent {
+    entries: HashMap<String, CacheEntry>,
+    /// Incremented on each use of the cache, used to find the least recently
+    /// used entry.
+    clock: u64,
+    stats: CacheStats,
+}
+
+/// Cache of parsed expressions, keyed by their exact source string. When the
+/// cache is full, the least recently used expression is evicted. The cache
+/// can be shared between threads.
+///
+/// ```
+/// use math_parse::ExprCache;
+///
+/// let cache = ExprCache::new(16);
+/// assert_eq!(cache.solve_float("3 * 4", None), Ok(12.0));
+/// assert_eq!(cache.solve_float("3 * 4", None), Ok(12.0));
+/// assert_eq!(cache.stats().hits, 1);
+/// assert_eq!(cache.stats().misses, 1);
+/// ```
+pub struct ExprCache {
+    capacity: usize,
+    content: Mutex<CacheContent>,
+}
+
+impl ExprCache {
+    /// Create a cache that can hold up to `capacity` parsed expressions.
+    pub fn new(capacity: usize) -> Self {
+        ExprCache {
+            capacity,
+            content: Mutex::new(CacheContent {
+                entries: HashMap::new(),
+                clock: 0,
+                stats: CacheStats::default(),
+            }),
+        }
+    }
+
+    /// Parse the expression, or take it from the cache if it was already
+    /// parsed, and solve it as a float. Expressions that can't be parsed are
+    /// not kept in the cache.
+    pub fn solve_float(&self, expression: &str, variable_map: Option<&HashMap<String, String>>) -> Result<f64, MathParseErrors> {
+        self.get(expression)?.solve_float(variable_map)
+    }
+
+    /// Return the number of hits and misses since the creation of the cache.
+    pub fn stats(&self) -> CacheStats {
+        self.lock().stats
+    }
+
+    /// Return the number of expressions in the cache.
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    /// Return true if there is no expression in the cache.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return the parsed expression from the cache, parsing it and adding it
+    /// to the cache on a miss.
+    fn get(&self, expression: &str) -> Result<Arc<MathParse>, MathParseErrors> {
+        {
+            let mut content = self.lock();
+            content.clock += 1;
+            let clock = content.clock;
+            if let Some(entry) = content.entries.get_mut(expression) {
+                entry.last_use = clock;
+                let parsed = entry.parsed.clone();
+                content.stats.hits += 1;
+                return Ok(parsed);
+            }
+            content.stats.misses += 1;
+        }
+
+        // Parsing is done without holding the lock.
+        let parsed = Arc::new(MathParse::parse(expression)?);
+
+        let mut content = self.lock();
+        if self.capacity == 0 {
+            return Ok(parsed);
+        }
+        if !content.entries.contains_key(expression) && content.entries.len() >= self.capacity {
+            let oldest = content.entries.iter()
+                .min_by_key(|(_, entry)| entry.last_use)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                content.entries.remove(&oldest);
+            }
+        }
+        let last_use = content.clock;
+        content.entries.insert(expression.to_string(), CacheEntry{parsed: parsed.clone(), last_use});
+        Ok(parsed)
+    }
+
+    /// Lock the content of the cache. As the content is always left in a
+    /// coherent state, a poisoned lock is still used.
+    fn lock(&self) -> std::sync::MutexGuard<'_, CacheContent> {
+        match self.content.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+/* --------------------------------- Testing -------------------------------- */
+
+#[test]
+fn test_cache_hits() {
+    let cache = ExprCache::new(4);
+    assert_eq!(cache.solve_float("1 + 2", None), Ok(3.0));
+    assert_eq!(cache.solve_float("1 + 2", None), Ok(3.0));
+    assert_eq!(cache.solve_float("1+2", None), Ok(3.0));
+    assert_eq!(cache.stats(), CacheStats{hits: 1, misses: 2});
+    assert_eq!(cache.len(), 2);
+
+    assert_eq!(cache.solve_float("1 +", None), Err(MathParseErrors::EmptyLine));
+    assert_eq!(cache.stats(), CacheStats{hits: 1, misses: 3});
+    assert_eq!(cache.len(), 2);
+
+    let variables = HashMap::from([("a".to_string(), "5".to_string())]);
+    assert_eq!(cache.solve_float("a * 2", Some(&variables)), Ok(10.0));
+    assert_eq!(cache.solve_float("a * 2", None), Err(MathParseErrors::InvalidNumber("a".to_string())));
+    assert_eq!(cache.stats(), CacheStats{hits: 2, misses: 4});
+}
+
+#[test]
+fn test_cache_eviction() {
+    let cache = ExprCache::new(2);
+    cache.solve_float("1", None).unwrap();
+    cache.solve_float("2", None).unwrap();
+    cache.solve_float("1", None).unwrap(); // "2" is now the least recently used.
+    cache.solve_float("3", None).unwrap();
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.stats(), CacheStats{hits: 1, misses: 3});
+    cache.solve_float("1", None).unwrap();
+    assert_eq!(cache.stats(), CacheStats{hits: 2, misses: 3});
+    cache.solve_float("2", None).unwrap();
+    assert_eq!(cache.stats(), CacheStats{hits: 2, misses: 4});
+
+    let no_cache = ExprCache::new(0);
+    no_cache.solve_float("1", None).unwrap();
+    no_cache.solve_float("1", None).unwrap();
+    assert!(no_cache.is_empty());
+    assert_eq!(no_cache.stats(), CacheStats{hits: 0, misses: 2});
+}
+
+#[test]
+fn test_cache_threads() {
+    let cache = ExprCache::new(8);
+    std::thread::scope(|scope| {
+        for thread in 0..4 {
+            let cache = &cache;
+            scope.spawn(move || {
+                for i in 0..100 {
+                    let expression = format!("{} * 2", (i + thread) % 4);
+                    assert_eq!(cache.solve_float(&expression, None), Ok((((i + thread) % 4) * 2) as f64));
+                }
+            });
+        }
+    });
+    let stats = cache.stats();
+    assert_eq!(stats.hits + stats.misses, 400);
+    assert_eq!(cache.len(), 4);
+}
@@ -0,0 +1,303 @@
+use crate::MathParseErrors;
+use crate::MathParseErrors::*;
+use crate::RPN;
+use crate::MathParseOptions;
+use crate::BinaryOp;
+use crate::UnaryOp;
+use crate::BinaryOp::*;
+use crate::rpn_stack_manipulation::*;
+use crate::solve::{Number, compute_binary_with, compute_unary_with, constant_or_number};
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+use std::str::FromStr;
+
+/* --------------------------------- Decimals ------------------------------- */
+
+/// Maximum number of digits after the decimal point of a `Decimal`.
+const MAX_SCALE: u32 = 28;
+
+/// The number as an `i64`, if it is an integer that fits in one.
+fn to_i64(num: Decimal) -> Option<i64> {
+    if num.fract().is_zero() {
+        num.to_i64()
+    } else {
+        None
+    }
+}
+
+/// Divide with trailing zeros removed, so that `10 / 4` is written `2.5`,
+/// `b` not being 0.
+fn div(a: Decimal, b: Decimal) -> Option<Decimal> {
+    a.checked_div(b).map(|num| num.normalize())
+}
+
+/// Integer division, rounded toward 0 or down, `b` not being 0. It is done
+/// on `a` minus the remainder so that the quotient is exact.
+fn integer_div(a: Decimal, b: Decimal, floor: bool) -> Option<Decimal> {
+    let remainder = a.checked_rem(b)?;
+    let quotient = a.checked_sub(remainder)?.checked_div(b)?.trunc();
+    if floor && !remainder.is_zero() && a.is_sign_negative() != b.is_sign_negative() {
+        quotient.checked_sub(Decimal::ONE)
+    } else {
+        Some(quotient)
+    }
+}
+
+/// Remainder of the sign of `a`, or never negative if `euclidean` is set,
+/// `b` not being 0.
+fn rem(a: Decimal, b: Decimal, euclidean: bool) -> Option<Decimal> {
+    let remainder = a.checked_rem(b)?;
+    if euclidean && remainder.is_sign_negative() && !remainder.is_zero() {
+        remainder.checked_add(b.abs())
+    } else {
+        Some(remainder)
+    }
+}
+
+fn pow(a: Decimal, mut exponent: u32) -> Option<Decimal> {
+    let mut ret = Decimal::ONE;
+    let mut square = a;
+    while exponent != 0 {
+        if exponent & 1 == 1 {
+            ret = ret.checked_mul(square)?;
+        }
+        exponent >>= 1;
+        if exponent != 0 {
+            square = square.checked_mul(square)?;
+        }
+    }
+    Some(ret)
+}
+
+/// Convert a float to the decimal number with the shortest digits that
+/// reads back as this float, rounded to `MAX_SCALE` digits after the
+/// decimal point.
+pub fn float_to_decimal(f: f64) -> Result<Decimal, MathParseErrors> {
+    Decimal::from_str(&f.to_string()).map_err(|_| DecimalOutOfRange(f.to_string()))
+}
+
+/* --------------------------------- Solving -------------------------------- */
+
+/// Value computed while solving. `None` means that the value is a float,
+/// which then spreads to the result of all the operations using it.
+type Dec = Option<Decimal>;
+
+/// The result of a comparison, 1 when it is true and 0 otherwise.
+fn from_bool(b: bool) -> Decimal {
+    Decimal::from(i64::from(b))
+}
+
+/// Reads a name, transforming it with the map if needed. Numbers written
+/// with a decimal point are decimal numbers while numbers in scientific
+/// notation and constants are floats.
+fn read_name(name: &str, map: &dyn Fn(&str) -> Option<String>, options: &MathParseOptions) -> Result<Dec, MathParseErrors> {
+    if let Some(new_name) = map(name) {
+        let parsed = crate::MathParse::parse_with_options(&new_name, options)?;
+        return decimal_solve(&parsed.internal.to_rpn(), &|_| None, options);
+    }
+    match constant_or_number(name, options)? {
+        Number::Int(i) => Ok(Some(Decimal::from(i))),
+        Number::Float(_) => {
+            let digits = name.chars()
+                .filter(|c| *c != '_' && !(options.apostrophe_separators && *c == '\''))
+                .collect::<String>();
+            if !digits.chars().all(|c| c.is_ascii_digit() || c == '.') {
+                return Ok(None);
+            }
+            match Decimal::from_str(&digits) {
+                Ok(num) if digits.split_once('.').is_none_or(|(_, frac)| frac.len() <= MAX_SCALE as usize) => Ok(Some(num)),
+                _ => Err(DecimalOutOfRange(name.to_string())),
+            }
+        },
+    }
+}
+
+/// Apply an operation with no decimal version as it is done on `Number`s,
+/// when its operands are integers that fit in `i64`s and its result is an
+/// integer.
+fn with_numbers(nums: &[Decimal], compute: impl FnOnce(&[Number]) -> Result<Number, MathParseErrors>) -> Result<Dec, MathParseErrors> {
+    let Some(nums) = nums.iter().map(|num| to_i64(*num).map(Number::Int)).collect::<Option<Vec<_>>>() else {
+        return Ok(None);
+    };
+    Ok(match compute(&nums)? {
+        Number::Int(i) => Some(Decimal::from(i)),
+        Number::Float(_) => None,
+    })
+}
+
+fn compute_unary(num: Dec, op: UnaryOp, options: &MathParseOptions) -> Result<Dec, MathParseErrors> {
+    let Some(num) = num else {
+        return Ok(None);
+    };
+    let ret = match op {
+        UnaryOp::Minus      => Some(-num),
+        UnaryOp::Plus       => Some(num),
+        UnaryOp::Percent    => div(num, Decimal::from(100)),
+        UnaryOp::LogicalNot => Some(from_bool(num.is_zero())),
+        op => return with_numbers(&[num], |nums| compute_unary_with(nums[0], op, options)),
+    };
+    ret.map(Some).ok_or_else(|| DecimalOutOfRange(format!("{op}{num}")))
+}
+
+fn compute_binary(num_1: Dec, num_2: Dec, op: BinaryOp, options: &MathParseOptions) -> Result<Dec, MathParseErrors> {
+    if num_2.is_some_and(|num| num.is_zero()) && matches!(op, Division | IntegerDivision | Reminder) {
+        return Err(UnexpectedZero);
+    }
+    let (Some(num_1), Some(num_2)) = (num_1, num_2) else {
+        return Ok(None);
+    };
+    let ret = match op {
+        Addition        => num_1.checked_add(num_2),
+        Subtraction     => num_1.checked_sub(num_2),
+        Multiplication  => num_1.checked_mul(num_2),
+        Division        => div(num_1, num_2),
+        IntegerDivision => integer_div(num_1, num_2, options.floor_division),
+        Reminder        => rem(num_1, num_2, options.euclidean_remainder),
+        Exponent        => {
+            // Only integer powers are exact.
+            let Some(exponent) = to_i64(num_2) else {
+                return Ok(None);
+            };
+            if num_1.is_zero() && exponent < 0 {
+                return Err(UnexpectedZero);
+            }
+            let power = u32::try_from(exponent.unsigned_abs()).ok().and_then(|e| pow(num_1, e));
+            if exponent < 0 {
+                power.and_then(|p| div(Decimal::ONE, p))
+            } else {
+                power
+            }
+        },
+        LessThan        => Some(from_bool(num_1 < num_2)),
+        LessOrEqual     => Some(from_bool(num_1 <= num_2)),
+        GreaterThan     => Some(from_bool(num_1 > num_2)),
+        GreaterOrEqual  => Some(from_bool(num_1 >= num_2)),
+        Equal           => Some(from_bool(num_1 == num_2)),
+        NotEqual        => Some(from_bool(num_1 != num_2)),
+        LogicalAnd      => Some(from_bool(!num_1.is_zero() && !num_2.is_zero())),
+        LogicalOr       => Some(from_bool(!num_1.is_zero() || !num_2.is_zero())),
+        ShiftLeft | ShiftRight | BitwiseAnd | BitwiseOr | BitwiseXor => {
+            return with_numbers(&[num_1, num_2], |nums| compute_binary_with(nums[0], nums[1], op, options));
+        },
+    };
+    ret.map(Some).ok_or_else(|| DecimalOutOfRange(format!("{num_1} {op} {num_2}")))
+}
+
+fn compute_call(_name: &str, _args: Vec<Dec>) -> Result<Dec, MathParseErrors> {
+    Ok(None)
+}
+
+fn decimal_solve(rpn_actions: &[RPN], map: &dyn Fn(&str) -> Option<String>, options: &MathParseOptions) -> Result<Dec, MathParseErrors> {
+    // As for exact fractions, the variables bound by binder functions can't
+    // be read as numbers.
+    if rpn_actions.iter().any(|action| matches!(action, RPN::Call(_, _))) {
+        return Ok(None);
+    }
+    let compute_name = | name: &str | -> Result<Dec, MathParseErrors> {
+        read_name(name, map, options)
+    };
+    let compute_unary = | num: Dec, op: UnaryOp | -> Result<Dec, MathParseErrors> {
+        compute_unary(num, op, options)
+    };
+    let compute_binary = | num_1: Dec, num_2: Dec, op: BinaryOp | -> Result<Dec, MathParseErrors> {
+        compute_binary(num_1, num_2, op, options)
+    };
+
+    exec_rpn(rpn_actions, &Box::new(compute_name), &compute_unary, &compute_binary, &compute_call)
+}
+
+/// Solve a list of RPN actions with decimal numbers. The result is `None`
+/// when a float is used.
+pub fn math_solve_decimal(rpn_actions: &[RPN], map: &dyn Fn(&str) -> Option<String>, options: &MathParseOptions) -> Result<Option<Decimal>, MathParseErrors> {
+    decimal_solve(rpn_actions, map, options)
+}
+
+/* --------------------------------- Testing -------------------------------- */
+
+#[test]
+fn test_decimal() {
+    let dec = |s: &str| Decimal::from_str(s).unwrap();
+    assert_eq!(to_i64(dec("2.00")), Some(2));
+    assert_eq!(to_i64(dec("1.5")), None);
+    assert_eq!(to_i64(dec("-1.5")), None);
+    assert_eq!(to_i64(dec("10000000000000000000")), None);
+    assert_eq!(div(dec("10"), dec("4")).map(|d| d.to_string()), Some("2.5".to_string()));
+    assert_eq!(integer_div(dec("-7.5"), dec("2"), false), Some(dec("-3")));
+    assert_eq!(integer_div(dec("-7.5"), dec("2"), true), Some(dec("-4")));
+    assert_eq!(integer_div(dec("-8"), dec("2"), true), Some(dec("-4")));
+    assert_eq!(rem(dec("-7.5"), dec("2"), false), Some(dec("-1.5")));
+    assert_eq!(rem(dec("-7.5"), dec("-2"), true), Some(dec("0.5")));
+    assert_eq!(rem(Decimal::MIN, dec("-1"), false), Some(Decimal::ZERO));
+    // The quotient is exact even when rounding the division would give the
+    // next integer.
+    assert_eq!(integer_div(dec("7922816251426433759354395033.5"), dec("1"), false), Some(dec("7922816251426433759354395033")));
+    assert_eq!(integer_div(Decimal::MIN, dec("-1"), false), Some(Decimal::MAX));
+    assert_eq!(pow(dec("1.1"), 2), Some(dec("1.21")));
+    assert_eq!(pow(dec("10"), 29), None);
+    assert_eq!(float_to_decimal(0.1 + 0.2).map(|d| d.to_string()), Ok("0.30000000000000004".to_string()));
+    assert_eq!(float_to_decimal(1e-300).map(|d| d.to_string()), Ok("0.0000000000000000000000000000".to_string()));
+    assert_eq!(float_to_decimal(1e300), Err(DecimalOutOfRange(1e300.to_string())));
+    assert_eq!(float_to_decimal(f64::NAN), Err(DecimalOutOfRange("NaN".to_string())));
+}
+
+#[test]
+fn test_solve_decimal() {
+    let solve = |expression: &str| crate::MathParse::parse(expression)?.solve_decimal(None).map(|num| num.to_string());
+    assert_eq!(solve("0.1 + 0.2"), Ok("0.3".to_string()));
+    assert_eq!(solve("0.10 + 0.20"), Ok("0.30".to_string()));
+    assert_eq!(solve("19.99 * 3"), Ok("59.97".to_string()));
+    assert_eq!(solve("1.10 * 1.10"), Ok("1.2100".to_string()));
+    assert_eq!(solve("100 * 7.5%"), Ok("7.500".to_string()));
+    assert_eq!(solve("1 / 3"), Ok("0.3333333333333333333333333333".to_string()));
+    assert_eq!(solve("10 / 4"), Ok("2.5".to_string()));
+    assert_eq!(solve("2 / 3 * 3"), Ok("2.0000000000000000000000000001".to_string()));
+    assert_eq!(solve("-7.5 // 2"), Ok("-3".to_string()));
+    assert_eq!(solve("-7.5 % 2"), Ok("-1.5".to_string()));
+    assert_eq!(solve("1.1 ** 2"), Ok("1.21".to_string()));
+    assert_eq!(solve("2 ** -2"), Ok("0.25".to_string()));
+    assert_eq!(solve("0.1 * 3 == 0.3"), Ok("1".to_string()));
+    assert_eq!(solve("0x0F | 0.0"), Ok("15".to_string()));
+    assert_eq!(solve("1_000.50 - 0.5"), Ok("1000.00".to_string()));
+    assert_eq!(solve("100000000000000000000.5 + 1"), Ok("100000000000000000001.5".to_string()));
+    assert_eq!(solve("0.1 + 1e-1"), Ok("0.2".to_string()));
+    assert_eq!(solve("0.1 + 2e-1"), Ok("0.30000000000000004".to_string()));
+    assert_eq!(solve("sqrt(0.25)"), Ok("0.5".to_string()));
+    assert_eq!(solve("1.5 / 0"), Err(UnexpectedZero));
+    assert_eq!(solve("1.5 % 0.0"), Err(UnexpectedZero));
+    assert_eq!(solve("0.0 ** -1"), Err(UnexpectedZero));
+    assert_eq!(solve("0.00000000000000000000000000001"), Err(DecimalOutOfRange("0.00000000000000000000000000001".to_string())));
+    assert_eq!(solve("10000000000000000000 ** 3"), Err(DecimalOutOfRange("10000000000000000000 ** 3".to_string())));
+    assert!(matches!(solve("1e300 * 10"), Err(DecimalOutOfRange(_))));
+
+    // Results with more than 28 digits after the decimal point, or more
+    // digits than fit in the 96 bits of the mantissa, are rounded half to
+    // even.
+    assert_eq!(solve("0.0000000000000000000000000001 * 0.5"), Ok("0.0000000000000000000000000000".to_string()));
+    assert_eq!(solve("0.0000000000000000000000000003 * 0.5"), Ok("0.0000000000000000000000000002".to_string()));
+    assert_eq!(solve("0.0000000000000000000000000005 / 2"), Ok("0.0000000000000000000000000002".to_string()));
+    assert_eq!(solve("0.0000000000000000000000000003 / 2"), Ok("0.0000000000000000000000000002".to_string()));
+    assert_eq!(solve("2 / 3"), Ok("0.6666666666666666666666666667".to_string()));
+    assert_eq!(solve("100000000000000000000 / 3"), Ok("33333333333333333333.333333333".to_string()));
+    assert_eq!(solve("79228162514264337593543950334 + 0.5"), Ok("79228162514264337593543950334".to_string()));
+    assert_eq!(solve("7922816251426433759354395033.5 + 0.25"), Ok("7922816251426433759354395034".to_string()));
+    assert_eq!(solve("79228162514264337593543950335"), Ok("79228162514264337593543950335".to_string()));
+    assert_eq!(solve("79228162514264337593543950335 + 0.5"), Err(DecimalOutOfRange("79228162514264337593543950335 + 0.5".to_string())));
+    assert_eq!(solve("-79228162514264337593543950335 - 1"), Err(DecimalOutOfRange("-79228162514264337593543950335 - 1".to_string())));
+    assert_eq!(solve("79228162514264337593543950336"), Err(DecimalOutOfRange("79228162514264337593543950336".to_string())));
+
+    let options = MathParseOptions {
+        decimal_comma: true,
+        floor_division: true,
+        euclidean_remainder: true,
+        ..MathParseOptions::default()
+    };
+    let solve = |expression: &str| crate::MathParse::parse_with_options(expression, &options)?.solve_decimal(None).map(|num| num.to_string());
+    assert_eq!(solve("0,1 + 0,2"), Ok("0.3".to_string()));
+    assert_eq!(solve("-7,5 // 2"), Ok("-4".to_string()));
+    assert_eq!(solve("-7,5 % 2"), Ok("0.5".to_string()));
+
+    let variables = std::collections::HashMap::from([
+        ("a".to_string(), "0.1".to_string()),
+    ]);
+    assert_eq!(crate::MathParse::parse("a * 3").unwrap().solve_decimal(Some(&variables)).map(|num| num.to_string()), Ok("0.3".to_string()));
+}
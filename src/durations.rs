@@ -0,0 +1,179 @@
+use crate::MathParseErrors;
+use crate::MathParseErrors::*;
+use crate::RPN;
+use crate::MathParseOptions;
+use crate::BinaryOp;
+use crate::UnaryOp;
+use crate::BinaryOp::*;
+use crate::rpn_stack_manipulation::*;
+use crate::solve::{Number, number_from_string, constant_value, compute_unary, compute_binary_with};
+
+/* -------------------------------- Durations ------------------------------- */
+
+/// Units that can be written after a number to make a duration, with their
+/// length in milliseconds.
+const DURATION_UNITS: [(&str, i64); 5] = [
+    ("ms", 1),
+    ("s",  1_000),
+    ("m",  60_000),
+    ("h",  3_600_000),
+    ("d",  86_400_000),
+];
+
+/// A number with the exponent of its time dimension. Durations have an
+/// exponent of 1 and are in milliseconds, plain numbers have an exponent of 0.
+#[derive(Debug, PartialEq, Copy, Clone)]
+struct Timed {
+    value: Number,
+    time: i8,
+}
+
+impl Timed {
+    fn plain(value: Number) -> Self {
+        Timed{value, time: 0}
+    }
+
+    fn duration(value: Number) -> Self {
+        Timed{value, time: 1}
+    }
+}
+
+/// Reads a literal in the form `<number><unit>` or a plain number.
+fn timed_from_string(s: &str, options: &MathParseOptions) -> Result<Timed, MathParseErrors> {
+    if let Ok(num) = number_from_string(s, options) {
+        return Ok(Timed::plain(num));
+    }
+    for (unit, factor) in &DURATION_UNITS {
+        if let Some(number) = s.strip_suffix(unit) {
+            if let Ok(num) = number_from_string(number, options) {
+                return Ok(Timed::duration((num * Number::Int(*factor)).simplified()));
+            }
+        }
+    }
+    Err(InvalidNumber(s.to_string()))
+}
+
+/// Reads a name, transforming it with the map if needed.
+fn read_name(name: &str, map: &dyn Fn(&str) -> Option<String>, options: &MathParseOptions) -> Result<Timed, MathParseErrors> {
+    if let Some(new_name) = map(name) {
+        let parsed = crate::MathParse::parse_with_options(&new_name, options)?;
+        timed_solve(&parsed.internal.to_rpn(), &|_| None, options)
+    } else if let Some(num) = constant_value(name, options) {
+        Ok(Timed::plain(num))
+    } else {
+        timed_from_string(name, options)
+    }
+}
+
+/// Make two numbers usable together in an addition. In lenient mode, plain
+/// numbers used with durations are seconds.
+fn make_compatible(num_1: Timed, num_2: Timed, options: &MathParseOptions) -> Result<(Timed, Timed), MathParseErrors> {
+    let as_seconds = |num: Timed| Timed::duration(num.value * Number::Int(1_000));
+    match (num_1.time, num_2.time) {
+        (x, y) if x == y => Ok((num_1, num_2)),
+        (0, 1) if options.lenient_durations => Ok((as_seconds(num_1), num_2)),
+        (1, 0) if options.lenient_durations => Ok((num_1, as_seconds(num_2))),
+        _ => Err(IncompatibleUnits),
+    }
+}
+
+fn timed_compute_unary(num: Timed, op: UnaryOp) -> Result<Timed, MathParseErrors> {
+    if matches!(op, UnaryOp::Not | UnaryOp::Sqrt) && num.time != 0 {
+        return Err(IncompatibleUnits);
+    }
+    let time = if op == UnaryOp::LogicalNot { 0 } else { num.time };
+    Ok(Timed{value: compute_unary(num.value, op)?, time})
+}
+
+fn timed_compute_binary(num_1: Timed, num_2: Timed, op: BinaryOp, options: &MathParseOptions) -> Result<Timed, MathParseErrors> {
+    let (num_1, num_2, time) = match op {
+        Addition | Subtraction | Reminder => {
+            let (num_1, num_2) = make_compatible(num_1, num_2, options)?;
+            (num_1, num_2, num_1.time)
+        },
+        LessThan | LessOrEqual | GreaterThan | GreaterOrEqual | Equal | NotEqual => {
+            let (num_1, num_2) = make_compatible(num_1, num_2, options)?;
+            (num_1, num_2, 0)
+        },
+        LogicalAnd | LogicalOr => (num_1, num_2, 0),
+        Multiplication => (num_1, num_2, num_1.time + num_2.time),
+        Division | IntegerDivision => (num_1, num_2, num_1.time - num_2.time),
+        Exponent | ShiftLeft | ShiftRight | BitwiseAnd | BitwiseOr | BitwiseXor => {
+            if num_1.time != 0 || num_2.time != 0 {
+                return Err(IncompatibleUnits);
+            }
+            (num_1, num_2, 0)
+        },
+    };
+    Ok(Timed{value: compute_binary_with(num_1.value, num_2.value, op, options)?, time})
+}
+
+fn compute_call(name: &str, _args: Vec<Timed>) -> Result<Timed, MathParseErrors> {
+    Err(UnknownFunction(name.to_string()))
+}
+
+fn timed_solve(rpn_actions: &[RPN], map: &dyn Fn(&str) -> Option<String>, options: &MathParseOptions) -> Result<Timed, MathParseErrors> {
+    let compute_name = | name: &str | -> Result<Timed, MathParseErrors> {
+        read_name(name, map, options)
+    };
+    let compute_binary = | num_1: Timed, num_2: Timed, op: BinaryOp | -> Result<Timed, MathParseErrors> {
+        timed_compute_binary(num_1, num_2, op, options)
+    };
+
+    exec_rpn(rpn_actions, &Box::new(compute_name), &timed_compute_unary, &compute_binary, &compute_call)
+}
+
+/// Solve a list of RPN actions where numbers can be followed by duration
+/// units.
+pub fn math_solve_duration(rpn_actions: &[RPN], map: &dyn Fn(&str) -> Option<String>, options: &MathParseOptions) -> Result<(Number, i8), MathParseErrors> {
+    let timed = timed_solve(rpn_actions, map, options)?;
+    Ok((timed.value.simplified(), timed.time))
+}
+
+/* --------------------------------- Testing -------------------------------- */
+
+#[cfg(test)]
+fn solve(expression: &str, lenient_durations: bool) -> Result<(Number, i8), MathParseErrors> {
+    let options = MathParseOptions {
+        lenient_durations,
+        ..MathParseOptions::default()
+    };
+    crate::MathParse::parse_with_options(expression, &options)?.solve_duration(None)
+}
+
+#[test]
+fn test_read_durations() {
+    let options = MathParseOptions::default();
+    assert_eq!(timed_from_string("3", &options),    Ok(Timed::plain(Number::Int(3))));
+    assert_eq!(timed_from_string("250ms", &options), Ok(Timed::duration(Number::Int(250))));
+    assert_eq!(timed_from_string("5m", &options),   Ok(Timed::duration(Number::Int(300_000))));
+    assert_eq!(timed_from_string("1.5s", &options), Ok(Timed::duration(Number::Int(1_500))));
+    assert_eq!(timed_from_string("2d", &options),   Ok(Timed::duration(Number::Int(172_800_000))));
+    assert_eq!(timed_from_string("0.0001s", &options), Ok(Timed::duration(Number::Float(0.1))));
+    assert_eq!(timed_from_string("3w", &options),   Err(InvalidNumber("3w".to_string())));
+    assert_eq!(timed_from_string("s", &options),    Err(InvalidNumber("s".to_string())));
+}
+
+#[test]
+fn test_solve_duration() {
+    assert_eq!(solve("5m + 30s", false), Ok((Number::Int(330_000), 1)));
+    assert_eq!(solve("2h / 15m", false), Ok((Number::Int(8), 0)));
+    assert_eq!(solve("1h - 90m", false), Ok((Number::Int(-1_800_000), 1)));
+    assert_eq!(solve("3 * 20s", false), Ok((Number::Int(60_000), 1)));
+    assert_eq!(solve("1m / 4", false), Ok((Number::Int(15_000), 1)));
+    assert_eq!(solve("5m + 30", false), Err(IncompatibleUnits));
+    assert_eq!(solve("5m + 30", true), Ok((Number::Int(330_000), 1)));
+    assert_eq!(solve("30 - 10s", true), Ok((Number::Int(20_000), 1)));
+    assert_eq!(solve("1 + 2", false), Ok((Number::Int(3), 0)));
+    assert_eq!(solve("5m & 1", true), Err(IncompatibleUnits));
+    assert_eq!(solve("5m ** 2", false), Err(IncompatibleUnits));
+    assert_eq!(solve("2 ** 10", false), Ok((Number::Int(1024), 0)));
+    assert_eq!(solve("~5m", false), Err(IncompatibleUnits));
+    assert_eq!(solve("!5m", false), Ok((Number::Int(0), 0)));
+
+    let variables = std::collections::HashMap::from([
+        ("timeout".to_string(), "1m + 30s".to_string()),
+    ]);
+    let parsed = crate::MathParse::parse("timeout * 2").unwrap();
+    assert_eq!(parsed.solve_duration(Some(&variables)), Ok((Number::Int(180_000), 1)));
+}
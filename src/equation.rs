@@ -0,0 +1,179 @@
+use crate::MathParse;
+use crate::MathParseErrors;
+use crate::MathParseErrors::*;
+use crate::RPN;
+use crate::BinaryOp::*;
+use crate::UnaryOp;
+use crate::solve::{Number, math_solve, compute_unary, compute_binary};
+use std::collections::HashMap;
+
+/* -------------------------------- Equations ------------------------------- */
+
+/// An equation made of two expressions separated by `=`, as given by
+/// `MathParse::parse_equation`.
+pub struct Equation {
+    pub(crate) lhs: MathParse,
+    pub(crate) rhs: MathParse,
+}
+
+impl Equation {
+    /// Solve the equation for the variable `var`, the other variables being
+    /// read from the map. Only linear equations where the variable is used
+    /// exactly once can be solved: the variable is isolated by undoing, from
+    /// the top of the expression down to the variable, the additions,
+    /// subtractions, multiplications, divisions, and negations applied to it.
+    /// If the variable is used in any other way, or if it is not used exactly
+    /// once, a `CannotIsolateVariable` error is returned. An equation with no
+    /// solution because of a zero, such as `x/0 = 4` or `0*x = 4`, gives an
+    /// `UnexpectedZero` error. As when solving expressions, a result that is
+    /// a whole number is given as an integer.
+    ///
+    /// ```
+    /// use math_parse::{MathParse, Number};
+    /// use math_parse::MathParseErrors::*;
+    ///
+    /// let equation = MathParse::parse_equation("2*x + 3 = 11").unwrap();
+    /// assert_eq!(equation.solve_for("x", None), Ok(Number::Int(4)));
+    /// let equation = MathParse::parse_equation("x*x = 4").unwrap();
+    /// assert_eq!(equation.solve_for("x", None), Err(CannotIsolateVariable("x".to_string())));
+    /// ```
+    pub fn solve_for(&self, var: &str, map: Option<&HashMap<String, String>>) -> Result<Number, MathParseErrors> {
+        let map_function = |s: &str| -> Option<String> {
+            match map {
+                None => None,
+                Some(x) => x.get(s).cloned(),
+            }
+        };
+        let options = &self.lhs.options;
+        let (lhs, rhs) = (self.lhs.internal.to_rpn(), self.rhs.internal.to_rpn());
+        let (expression, other_side) = match (uses(&lhs, var), uses(&rhs, var)) {
+            (1, 0) => (&lhs, &rhs),
+            (0, 1) => (&rhs, &lhs),
+            _ => {
+                return Err(CannotIsolateVariable(var.to_string()));
+            },
+        };
+
+        let starts = operand_starts(expression)?;
+        let mut value = math_solve(other_side, &map_function, options)?;
+        let mut end = expression.len();
+        loop {
+            // The operation at the top of `expression[..end]` is undone on
+            // `value`, then only the operand using the variable is kept.
+            match &expression[end - 1] {
+                RPN::Name(_) => {
+                    return Ok(value.simplified());
+                },
+                RPN::Unary(op @ (UnaryOp::Minus | UnaryOp::Plus)) => {
+                    value = compute_unary(value, *op)?;
+                    end -= 1;
+                },
+                RPN::Unary(UnaryOp::Percent) => {
+                    value = compute_binary(value, Number::Int(100), Multiplication)?;
+                    end -= 1;
+                },
+                RPN::Binary(op) => {
+                    let second = starts[end - 2]..(end - 1);
+                    let first = starts[second.start - 1]..second.start;
+                    let var_in_first = uses(&expression[first.clone()], var) == 1;
+                    let (var_operand, other_operand) = if var_in_first {
+                        (first, second)
+                    } else {
+                        (second, first)
+                    };
+                    let other = math_solve(&expression[other_operand], &map_function, options)?;
+                    value = match (op, var_in_first) {
+                        (Addition, _)           => compute_binary(value, other, Subtraction)?,
+                        (Subtraction, true)     => compute_binary(value, other, Addition)?,
+                        (Subtraction, false)    => compute_binary(other, value, Subtraction)?,
+                        (Multiplication, _)     => compute_binary(value, other, Division)?,
+                        // `x/0` has no value, so no `x` can solve the
+                        // equation.
+                        (Division, true) if other.is_zero() => {
+                            return Err(UnexpectedZero);
+                        },
+                        (Division, true)        => compute_binary(value, other, Multiplication)?,
+                        (Division, false)       => compute_binary(other, value, Division)?,
+                        _ => {
+                            return Err(CannotIsolateVariable(var.to_string()));
+                        },
+                    };
+                    end = var_operand.end;
+                },
+                RPN::Unary(_) | RPN::Call(_, _) => {
+                    return Err(CannotIsolateVariable(var.to_string()));
+                },
+            }
+        }
+    }
+}
+
+/// Number of times a name is used in RPN actions.
+fn uses(rpn_actions: &[RPN], name: &str) -> usize {
+    rpn_actions.iter()
+        .filter(|action| matches!(action, RPN::Name(x) if x == name))
+        .count()
+}
+
+/// For each RPN action, the index of the first action of the operand it ends,
+/// so that `rpn_actions[starts[i]..=i]` is a whole expression.
+fn operand_starts(rpn_actions: &[RPN]) -> Result<Vec<usize>, MathParseErrors> {
+    let mut starts = Vec::with_capacity(rpn_actions.len());
+    let mut stack = Vec::new();
+    for (index, action) in rpn_actions.iter().enumerate() {
+        let start = match action {
+            RPN::Name(_) => index,
+            RPN::Unary(_) => stack.pop().ok_or(UnbalancedStack)?,
+            RPN::Binary(_) => {
+                stack.pop().ok_or(UnbalancedStack)?;
+                stack.pop().ok_or(UnbalancedStack)?
+            },
+            RPN::Call(_, 0) => index,
+            RPN::Call(_, arity) => {
+                if stack.len() < *arity {
+                    return Err(UnbalancedStack);
+                }
+                stack.split_off(stack.len() - arity)[0]
+            },
+        };
+        stack.push(start);
+        starts.push(start);
+    }
+    Ok(starts)
+}
+
+/* --------------------------------- Testing -------------------------------- */
+
+#[test]
+fn test_solve_for() {
+    let solve = |equation: &str, var: &str| MathParse::parse_equation(equation)?.solve_for(var, None);
+    assert_eq!(solve("2*x + 3 = 11", "x"), Ok(Number::Int(4)));
+    assert_eq!(solve("11 = 3 + x*2", "x"), Ok(Number::Int(4)));
+    assert_eq!(solve("(y-32)*5/9 = 100", "y"), Ok(Number::Int(212)));
+    assert_eq!(solve("10 - x = 4", "x"), Ok(Number::Int(6)));
+    assert_eq!(solve("3 / x = 2", "x"), Ok(Number::Float(1.5)));
+    assert_eq!(solve("(x + 1)% = 0.5", "x"), Ok(Number::Int(49)));
+    assert_eq!(solve("-(x/4) = 2", "x"), Ok(Number::Int(-8)));
+    assert_eq!(solve("x = sum(i, 1, 4, i)", "x"), Ok(Number::Int(10)));
+    assert_eq!(solve("0*x + 1 = 5", "x"), Err(UnexpectedZero));
+    assert_eq!(solve("3 / x = 0", "x"), Err(UnexpectedZero));
+    assert_eq!(solve("x/0 = 4", "x"), Err(UnexpectedZero));
+    assert_eq!(solve("x/0 = 0", "x"), Err(UnexpectedZero));
+    assert_eq!(solve("(x + 1)/(2 - 2) = 4", "x"), Err(UnexpectedZero));
+    assert_eq!(solve("0*x = 4", "x"), Err(UnexpectedZero));
+    assert_eq!(solve("x*x = 4", "x"), Err(CannotIsolateVariable("x".to_string())));
+    assert_eq!(solve("x = x + 1", "x"), Err(CannotIsolateVariable("x".to_string())));
+    assert_eq!(solve("y = 1", "x"), Err(CannotIsolateVariable("x".to_string())));
+    assert_eq!(solve("x % 3 = 1", "x"), Err(CannotIsolateVariable("x".to_string())));
+    assert_eq!(solve("!x = 1", "x"), Err(CannotIsolateVariable("x".to_string())));
+    assert_eq!(solve("x + y = 1", "x"), Err(InvalidNumber("y".to_string())));
+    assert_eq!(solve("x = 1 = 2", "x"), Err(MisplacedOperator('=')));
+    assert_eq!(solve("x + 1", "x"), Err(MisplacedOperator('=')));
+
+    let variables = HashMap::from([
+        ("a".to_string(), "3".to_string()),
+        ("x".to_string(), "1000".to_string()),
+    ]);
+    let equation = MathParse::parse_equation("a*x - a = 2*a").unwrap();
+    assert_eq!(equation.solve_for("x", Some(&variables)), Ok(Number::Int(3)));
+}
@@ -0,0 +1,279 @@
+use crate::MathParseErrors;
+use crate::MathParseErrors::*;
+use crate::RPN;
+use crate::MathParseOptions;
+use crate::BinaryOp;
+use crate::UnaryOp;
+use crate::BinaryOp::*;
+use crate::rpn_stack_manipulation::*;
+use crate::solve::{Number, constant_or_number};
+
+/* --------------------------------- Rationals ------------------------------ */
+
+/// Exact result of an expression, as given by `MathParse::solve_exact`.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum ExactResult {
+    /// The result is an integer.
+    Int(i64),
+    /// The result is the fraction of the numerator by the denominator. The
+    /// fraction is in lowest terms and the denominator is always greater than
+    /// 1, the sign being on the numerator.
+    Ratio(i64, i64),
+    /// The result can't be computed exactly, because a float, an unsupported
+    /// operator, or a function is used, or because a value does not fit in an
+    /// `i64`.
+    Inexact,
+}
+
+/// A fraction in lowest terms with a positive denominator.
+#[derive(Debug, PartialEq, Copy, Clone)]
+struct Rational {
+    num: i64,
+    den: i64,
+}
+
+/// Value computed while solving. `None` means that the value can't be known
+/// exactly, which then spreads to the result of all the operations using it.
+type Exact = Option<Rational>;
+
+impl Rational {
+    /// Make a fraction in lowest terms, or `None` if it does not fit in
+    /// `i64`s.
+    fn new(num: i128, den: i128) -> Exact {
+        let divisor = gcd(num, den) * den.signum();
+        Some(Rational{
+            num: i64::try_from(num / divisor).ok()?,
+            den: i64::try_from(den / divisor).ok()?,
+        })
+    }
+
+    fn int(i: i64) -> Self {
+        Rational{num: i, den: 1}
+    }
+
+    fn is_zero(self) -> bool {
+        self.num == 0
+    }
+
+    /// The integer part of the fraction, rounded toward 0 as the integer
+    /// division of `Number`.
+    fn trunc(self) -> Exact {
+        Rational::new(i128::from(self.num) / i128::from(self.den), 1)
+    }
+
+    /// The integer part of the fraction, rounded down as the integer
+    /// division with the `floor_division` option.
+    fn floor(self) -> Exact {
+        Rational::new(i128::from(self.num).div_euclid(i128::from(self.den)), 1)
+    }
+}
+
+/// Greatest common divisor of two numbers, at least 1 so it can be used to
+/// divide.
+fn gcd(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.unsigned_abs(), b.unsigned_abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a.max(1) as i128
+}
+
+fn add(a: Rational, b: Rational) -> Exact {
+    let (a_num, a_den) = (i128::from(a.num), i128::from(a.den));
+    let (b_num, b_den) = (i128::from(b.num), i128::from(b.den));
+    Rational::new(a_num.checked_mul(b_den)?.checked_add(b_num.checked_mul(a_den)?)?, a_den.checked_mul(b_den)?)
+}
+
+fn neg(a: Rational) -> Exact {
+    Rational::new(-i128::from(a.num), i128::from(a.den))
+}
+
+fn mul(a: Rational, b: Rational) -> Exact {
+    Rational::new(i128::from(a.num) * i128::from(b.num), i128::from(a.den) * i128::from(b.den))
+}
+
+fn div(a: Rational, b: Rational) -> Result<Exact, MathParseErrors> {
+    if b.is_zero() {
+        return Err(UnexpectedZero);
+    }
+    Ok(Rational::new(i128::from(a.num) * i128::from(b.den), i128::from(a.den) * i128::from(b.num)))
+}
+
+/// Raise a fraction to an integer power. Other powers are not exact.
+fn pow(a: Rational, b: Rational) -> Result<Exact, MathParseErrors> {
+    if b.den != 1 {
+        return Ok(None);
+    }
+    if a.is_zero() && b.num < 0 {
+        return Err(UnexpectedZero);
+    }
+    let Ok(exponent) = u32::try_from(b.num.unsigned_abs()) else {
+        return Ok(None);
+    };
+    let (Some(num), Some(den)) = (i128::from(a.num).checked_pow(exponent), i128::from(a.den).checked_pow(exponent)) else {
+        return Ok(None);
+    };
+    Ok(if b.num < 0 {
+        Rational::new(den, num)
+    } else {
+        Rational::new(num, den)
+    })
+}
+
+/* --------------------------------- Solving -------------------------------- */
+
+/// Reads a name, transforming it with the map if needed.
+fn read_name(name: &str, map: &dyn Fn(&str) -> Option<String>, options: &MathParseOptions) -> Result<Exact, MathParseErrors> {
+    if let Some(new_name) = map(name) {
+        let parsed = crate::MathParse::parse_with_options(&new_name, options)?;
+        exact_solve(&parsed.internal.to_rpn(), &|_| None, options)
+    } else {
+        Ok(match constant_or_number(name, options)? {
+            Number::Int(i) => Some(Rational::int(i)),
+            Number::Float(_) => None,
+        })
+    }
+}
+
+fn compute_unary(num: Exact, op: UnaryOp) -> Result<Exact, MathParseErrors> {
+    Ok(match (num, op) {
+        (Some(num), UnaryOp::Minus) => neg(num),
+        (num, UnaryOp::Plus) => num,
+        (Some(num), UnaryOp::Percent) => div(num, Rational::int(100))?,
+        (Some(num), UnaryOp::LogicalNot) => Some(Rational::int(i64::from(num.is_zero()))),
+        _ => None,
+    })
+}
+
+fn compute_binary(num_1: Exact, num_2: Exact, op: BinaryOp) -> Result<Exact, MathParseErrors> {
+    if num_2.is_some_and(Rational::is_zero) && matches!(op, Division | IntegerDivision | Reminder) {
+        return Err(UnexpectedZero);
+    }
+    let (Some(num_1), Some(num_2)) = (num_1, num_2) else {
+        return Ok(None);
+    };
+    Ok(match op {
+        Addition        => add(num_1, num_2),
+        Subtraction     => neg(num_2).and_then(|x| add(num_1, x)),
+        Multiplication  => mul(num_1, num_2),
+        Division        => div(num_1, num_2)?,
+        IntegerDivision => div(num_1, num_2)?.and_then(Rational::trunc),
+        Reminder        => {
+            let quotient = div(num_1, num_2)?.and_then(Rational::trunc);
+            quotient.and_then(|q| mul(q, num_2)).and_then(|x| add(num_1, neg(x)?))
+        },
+        Exponent        => pow(num_1, num_2)?,
+        LessThan | LessOrEqual | GreaterThan | GreaterOrEqual | Equal | NotEqual => {
+            // The numbers compare as the sign of their difference compares
+            // to 0.
+            let sign = neg(num_2).and_then(|x| add(num_1, x)).map(|x| x.num.signum());
+            match sign.map(|sign| crate::solve::compute_binary(Number::Int(sign), Number::Int(0), op)) {
+                Some(Ok(Number::Int(result))) => Some(Rational::int(result)),
+                _ => None,
+            }
+        },
+        LogicalAnd      => Some(Rational::int(i64::from(!num_1.is_zero() && !num_2.is_zero()))),
+        LogicalOr       => Some(Rational::int(i64::from(!num_1.is_zero() || !num_2.is_zero()))),
+        ShiftLeft | ShiftRight | BitwiseAnd | BitwiseOr | BitwiseXor => None,
+    })
+}
+
+fn compute_call(_name: &str, _args: Vec<Exact>) -> Result<Exact, MathParseErrors> {
+    Ok(None)
+}
+
+fn exact_solve(rpn_actions: &[RPN], map: &dyn Fn(&str) -> Option<String>, options: &MathParseOptions) -> Result<Exact, MathParseErrors> {
+    // The variables bound by binder functions can't be read as numbers, so
+    // the arguments of functions must not be solved.
+    if rpn_actions.iter().any(|action| matches!(action, RPN::Call(_, _))) {
+        return Ok(None);
+    }
+    let compute_name = | name: &str | -> Result<Exact, MathParseErrors> {
+        read_name(name, map, options)
+    };
+    let compute_binary = | num_1: Exact, num_2: Exact, op: BinaryOp | -> Result<Exact, MathParseErrors> {
+        if op == IntegerDivision && options.floor_division {
+            return Ok(compute_binary(num_1, num_2, Division)?.and_then(Rational::floor));
+        }
+        let ret = compute_binary(num_1, num_2, op)?;
+        match (ret, num_2) {
+            (Some(r), Some(divisor)) if op == Reminder && options.euclidean_remainder && r.num < 0 => {
+                Ok(add(r, Rational{num: divisor.num.abs(), den: divisor.den}))
+            },
+            _ => Ok(ret),
+        }
+    };
+
+    exec_rpn(rpn_actions, &Box::new(compute_name), &compute_unary, &compute_binary, &compute_call)
+}
+
+/// Solve a list of RPN actions with exact fractions.
+pub fn math_solve_exact(rpn_actions: &[RPN], map: &dyn Fn(&str) -> Option<String>, options: &MathParseOptions) -> Result<ExactResult, MathParseErrors> {
+    Ok(match exact_solve(rpn_actions, map, options)? {
+        Some(Rational{num, den: 1}) => ExactResult::Int(num),
+        Some(Rational{num, den}) => ExactResult::Ratio(num, den),
+        None => ExactResult::Inexact,
+    })
+}
+
+/* --------------------------------- Testing -------------------------------- */
+
+#[test]
+fn test_solve_exact() {
+    use ExactResult::*;
+    let solve = |expression: &str| crate::MathParse::parse(expression)?.solve_exact(None);
+    assert_eq!(solve("1/3 + 1/6"), Ok(Ratio(1, 2)));
+    assert_eq!(solve("10/5"), Ok(Int(2)));
+    assert_eq!(solve("1.5+1"), Ok(Inexact));
+    assert_eq!(solve("1/3*3"), Ok(Int(1)));
+    assert_eq!(solve("15% + 1/4"), Ok(Ratio(2, 5)));
+    assert_eq!(solve("-4/6"), Ok(Ratio(-2, 3)));
+    assert_eq!(solve("4/-6"), Ok(Ratio(-2, 3)));
+    assert_eq!(solve("(7/2) // (1/3)"), Ok(Int(10)));
+    assert_eq!(solve("-7 // 2"), Ok(Int(-3)));
+    assert_eq!(solve("(7/2) % (1/3)"), Ok(Ratio(1, 6)));
+    assert_eq!(solve("-7 % 2"), Ok(Int(-1)));
+    assert_eq!(solve("1 / (2 - 2)"), Err(UnexpectedZero));
+    assert_eq!(solve("1.5 % 0"), Err(UnexpectedZero));
+    assert_eq!(solve("(2/3) ** 3"), Ok(Ratio(8, 27)));
+    assert_eq!(solve("(2/3) ** -2"), Ok(Ratio(9, 4)));
+    assert_eq!(solve("2 ** 0.5"), Ok(Inexact));
+    assert_eq!(solve("10 ** 30"), Ok(Inexact));
+    assert_eq!(solve("0 ** -2"), Err(UnexpectedZero));
+    assert_eq!(solve("6 & 3"), Ok(Inexact));
+    assert_eq!(solve("sum(i, 1, 3, i)"), Ok(Inexact));
+    assert_eq!(solve("9223372036854775807 + 1"), Ok(Inexact));
+    assert_eq!(solve("9223372036854775807 * 2 / 4"), Ok(Inexact));
+    assert_eq!(solve("1 / 9223372036854775807 / 2"), Ok(Inexact));
+    assert_eq!(solve("1 + x"), Err(InvalidNumber("x".to_string())));
+
+    let variables = std::collections::HashMap::from([
+        ("third".to_string(), "1/3".to_string()),
+        ("half".to_string(), "0.5".to_string()),
+    ]);
+    let solve = |expression: &str| crate::MathParse::parse(expression)?.solve_exact(Some(&variables));
+    assert_eq!(solve("third * 2"), Ok(Ratio(2, 3)));
+    assert_eq!(solve("third + half"), Ok(Inexact));
+
+    let options = crate::MathParseOptions {
+        euclidean_remainder: true,
+        ..crate::MathParseOptions::default()
+    };
+    let solve = |expression: &str| crate::MathParse::parse_with_options(expression, &options)?.solve_exact(None);
+    assert_eq!(solve("-7 % 2"), Ok(Int(1)));
+    assert_eq!(solve("-7 % (-2)"), Ok(Int(1)));
+    assert_eq!(solve("(-7/2) % (1/3)"), Ok(Ratio(1, 6)));
+    assert_eq!(solve("-7 % 0"), Err(UnexpectedZero));
+
+    let options = crate::MathParseOptions {
+        floor_division: true,
+        ..crate::MathParseOptions::default()
+    };
+    let solve = |expression: &str| crate::MathParse::parse_with_options(expression, &options)?.solve_exact(None);
+    assert_eq!(solve("-7 // 2"), Ok(Int(-4)));
+    assert_eq!(solve("7 // (-2)"), Ok(Int(-4)));
+    assert_eq!(solve("-7 // (-2)"), Ok(Int(3)));
+    assert_eq!(solve("(7/2) // (1/3)"), Ok(Int(10)));
+    assert_eq!(solve("(-7/2) // (1/3)"), Ok(Int(-11)));
+    assert_eq!(solve("-7 // 0"), Err(UnexpectedZero));
+}
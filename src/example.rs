@@ -7,17 +7,13 @@ fn main() {
 
     let parsed = math_parse::MathParse::parse(&args);
     let solved = match parsed {
-        Ok(x)  => x.solve_auto(None),
+        Ok(x)  => x.solve_value(None),
         Err(x) => Err(x),
     };
 
     match solved {
-        Ok(Ok(i)) => {
-            println!("{i}");
-            std::process::exit(0);
-        },
-        Ok(Err(f)) => {
-            println!("{f}");
+        Ok(value) => {
+            println!("{value}");
             std::process::exit(0);
         },
         Err(x) => {
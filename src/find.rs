@@ -0,0 +1,198 @@
+use crate::MathParse;
+use crate::MathParseOptions;
+use crate::BinaryOp;
+use crate::solve::number_from_string;
+use crate::tokenize::{is_math_char, math_token_with_positions, MathValue};
+use std::ops::Range;
+
+/* ------------------------- Expressions in free text ------------------------ */
+
+/// Maximum length in bytes of the expressions found, so that the time spent
+/// on a text is linear in its length.
+const MAX_EXPRESSION_LEN: usize = 128;
+
+/// Look for math expressions inside of free text, such as `3*4+2` in
+/// `we need 3*4+2 units by Friday`, and return the byte range of each of them
+/// in the text along with the parsed expression.
+///
+/// The following heuristics are used to find expressions:
+///
+/// * Expressions are made only of ASCII digits, `.`, whitespace, and
+///   operators other than `,`. Named variables, hexadecimal numbers, and
+///   character literals are thus never part of found expressions.
+/// * Each number must be a single word, so `1 2 + 3` is not an expression.
+/// * Expressions must parse successfully and use at least one binary
+///   operator.
+/// * Expressions must use at least one binary operator other than `-` or
+///   contain parenthesis. This prevents dates such as `2024-01-02` or ranges
+///   such as `10-20` from being found, at the cost of missing subtractions
+///   such as `10-3`.
+/// * The text is scanned from left to right and the longest expression
+///   starting at each position is kept, so found expressions never overlap.
+///   Leading and trailing whitespace is not part of the expressions, and
+///   expressions end with a digit or a parenthesis so that the dot ending a
+///   sentence is not part of them.
+/// * Expressions are at most 128 bytes long. A longer expression is found
+///   as several shorter ones.
+///
+/// ```
+/// use math_parse::find_expressions;
+///
+/// let text = "we need 3*4+2 units by Friday";
+/// let found = find_expressions(text);
+/// assert_eq!(found.len(), 1);
+/// assert_eq!(&text[found[0].0.clone()], "3*4+2");
+/// assert_eq!(found[0].1.solve_int(None), Ok(14));
+/// ```
+pub fn find_expressions(text: &str) -> Vec<(Range<usize>, MathParse)> {
+    let mut ret = vec![];
+    let mut run_start = None;
+    for (index, c) in text.char_indices().chain(std::iter::once((text.len(), 'a'))) {
+        if is_expression_char(c) {
+            run_start.get_or_insert(index);
+        } else if let Some(start) = run_start.take() {
+            find_in_run(text, start..index, &mut ret);
+        }
+    }
+    ret
+}
+
+/// Find the expressions in a part of the text made only of characters that
+/// can be in expressions.
+fn find_in_run(text: &str, run: Range<usize>, found: &mut Vec<(Range<usize>, MathParse)>) {
+    let mut start = run.start;
+    while start < run.end {
+        let candidate_start = text[start..run.end].trim_start();
+        start = run.end - candidate_start.len();
+        let mut limit = run.end.min(start + MAX_EXPRESSION_LEN);
+        while !text.is_char_boundary(limit) {
+            limit -= 1;
+        }
+        let longest = candidate_ends(&text[start..limit]).into_iter()
+            .find_map(|end| read_expression(&text[start..start+end]).map(|parsed| (start+end, parsed)));
+        match longest {
+            Some((end, parsed)) => {
+                found.push((start..end, parsed));
+                start = end;
+            },
+            None => {
+                start += text[start..].chars().next().map_or(1, char::len_utf8);
+            },
+        }
+    }
+}
+
+/// Return the ends of the candidate expressions at the start of the text,
+/// from the longest to the shortest. The candidates that can't be
+/// expressions as they have unbalanced parenthesis, or as they contain a
+/// number following an other number such as `1 2`, are left out so that
+/// they are not parsed.
+fn candidate_ends(s: &str) -> Vec<usize> {
+    let is_number_char = |c: char| c.is_ascii_digit() || c == '.';
+    let mut ends = vec![];
+    let mut depth = 0_usize;
+    let mut after_number = false;
+    let mut spaced = false;
+    for (index, c) in s.char_indices() {
+        if c == '(' {
+            depth += 1;
+        } else if c == ')' {
+            match depth.checked_sub(1) {
+                Some(new_depth) => depth = new_depth,
+                None => break,
+            }
+        }
+        if c.is_whitespace() {
+            spaced = after_number;
+        } else if is_number_char(c) {
+            if spaced {
+                break;
+            }
+            after_number = true;
+        } else {
+            after_number = false;
+            spaced = false;
+        }
+        if depth == 0 && (c.is_ascii_digit() || c == ')') {
+            ends.push(index + c.len_utf8());
+        }
+    }
+    ends.reverse();
+    ends
+}
+
+/// Parse a candidate expression if it matches the heuristics of
+/// `find_expressions`.
+fn read_expression(candidate: &str) -> Option<MathParse> {
+    let starts_well = candidate.starts_with(|c: char| c.is_ascii_digit() || "(.+-−!~".contains(c));
+    let ends_well = candidate.ends_with(|c: char| c.is_ascii_digit() || c == ')');
+    if !starts_well || !ends_well {
+        return None;
+    }
+    let options = MathParseOptions::default();
+    let (tokens, _) = math_token_with_positions(candidate);
+    for token in &tokens {
+        if let MathValue::Name(name) = token {
+            let name = name.trim();
+            if name.contains(char::is_whitespace) || number_from_string(name, &options).is_err() {
+                return None;
+            }
+        }
+    }
+    let parsed = MathParse::parse(candidate).ok()?;
+    let (_, binary) = parsed.operators();
+    let not_only_subtraction = binary.iter().any(|op| *op != BinaryOp::Subtraction);
+    if binary.is_empty() || !(not_only_subtraction || candidate.contains('(')) {
+        return None;
+    }
+    Some(parsed)
+}
+
+/// Return true if the character can be part of an expression.
+fn is_expression_char(c: char) -> bool {
+    c.is_ascii_digit() || c == '.' || c.is_whitespace() || (is_math_char(c) && c != ',')
+}
+
+/* --------------------------------- Testing -------------------------------- */
+
+#[cfg(test)]
+fn found_strings(text: &str) -> Vec<&str> {
+    find_expressions(text).into_iter().map(|(range, _)| &text[range]).collect()
+}
+
+#[test]
+fn test_find_expressions() {
+    assert_eq!(found_strings("we need 3*4+2 units by Friday"), vec!["3*4+2"]);
+    assert_eq!(found_strings("see you on 2024-01-02, or 2024-01-03"), Vec::<&str>::new());
+    assert_eq!(found_strings("first 2 + 2, then (10 - 4) / 3 done."), vec!["2 + 2", "(10 - 4) / 3"]);
+    assert_eq!(found_strings("x+1 is not 1 2 + 3, but 5 * 2."), vec!["2 + 3", "5 * 2"]);
+    assert_eq!(found_strings("nothing (here) 12 -"), Vec::<&str>::new());
+    assert_eq!(found_strings("(1+2 is unbalanced"), vec!["1+2"]);
+    assert_eq!(found_strings("3×4 is 12"), vec!["3×4"]);
+    let found = find_expressions("it costs (3+4)*2 euros");
+    assert_eq!(found[0].0, 9..16);
+    assert_eq!(found[0].1.solve_int(None), Ok(14));
+
+    assert_eq!(candidate_ends("1 + 2 3"), vec![5, 1]);
+    assert_eq!(candidate_ends("(1+2)*3) + 4"), vec![7, 5]);
+    assert_eq!(candidate_ends("(1) 2 + 3"), vec![9, 5, 3]);
+}
+
+#[test]
+fn test_find_expressions_long_runs() {
+    let numbers = (0..2000).map(|i| i.to_string()).collect::<Vec<_>>().join(" ");
+    let text = format!("{numbers} then 1 + 1");
+    assert_eq!(found_strings(&text), vec!["1 + 1"]);
+
+    // Found expressions are cut at 128 bytes.
+    let sum = vec!["1"; 1000].join("+");
+    let found = find_expressions(&sum);
+    assert!(found.len() > 1);
+    assert!(found.iter().all(|(range, _)| range.len() <= MAX_EXPRESSION_LEN));
+    assert_eq!(found.iter().map(|(_, parsed)| parsed.solve_int(None).unwrap()).sum::<i64>(), 1000);
+
+    let unbalanced = "(1+".repeat(2000);
+    assert_eq!(found_strings(&unbalanced), Vec::<&str>::new());
+    let invalid = "1*/".repeat(500);
+    assert_eq!(found_strings(&invalid), Vec::<&str>::new());
+}
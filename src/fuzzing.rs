@@ -0,0 +1,178 @@
+use crate::Tree;
+use crate::OpKind;
+
+/* --------------------------- Generation of trees -------------------------- */
+
+/// Names used for the variables in generated trees.
+const VARIABLES: [&str; 3] = ["x", "y", "z"];
+
+/// Reads the bytes given by a fuzzer one by one, giving 0 once they are all
+/// used.
+struct Bytes<'a>(std::slice::Iter<'a, u8>);
+
+impl Bytes<'_> {
+    fn next(&mut self) -> u8 {
+        self.0.next().copied().unwrap_or(0)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        (0..8).fold(0, |acc, _| (acc << 8) | u64::from(self.next()))
+    }
+}
+
+/// A node of a generated tree, in prefix order.
+enum Node {
+    Leaf(String),
+    Op(OpKind),
+}
+
+/// Generate a leaf: an integer, a float, or a variable.
+fn generate_leaf(bytes: &mut Bytes) -> String {
+    match bytes.next() % 4 {
+        0 | 1 => bytes.next().to_string(),
+        2 => (bytes.next_u64() >> 1).to_string(),
+        _ => match bytes.next() % 2 {
+            0 => format!("{}.{}", bytes.next(), bytes.next() % 10),
+            _ => VARIABLES[usize::from(bytes.next()) % VARIABLES.len()].to_string(),
+        },
+    }
+}
+
+impl Tree {
+    /// Generate a tree from the bytes given by a fuzzer. The tree is at most
+    /// `max_depth` operators deep and only uses the operators in
+    /// `allowed_ops`. Its leaves are non-negative integers, floats, and the
+    /// variables `x`, `y`, and `z`. The same bytes always give the same tree
+    /// and once all the bytes are used, the remaining leaves are all `0`.
+    ///
+    /// Unary operators are never directly applied to an other unary operator
+    /// as chains of them are simplified when parsing. Thus, the expression
+    /// written by `Tree::to_expression_string` is parsed back to the same
+    /// tree, unless the unary `+`, which is removed when parsing, is allowed.
+    ///
+    /// ```
+    /// use math_parse::{MathParse, Tree, OpKind, BinaryOp, UnaryOp};
+    ///
+    /// let ops = [OpKind::Binary(BinaryOp::Addition), OpKind::Unary(UnaryOp::Minus)];
+    /// let tree = Tree::arbitrary_with(&[3, 1, 1, 0, 7, 1, 1, 2, 1, 0, 1], 4, &ops);
+    /// let parsed = MathParse::parse(&tree.to_expression_string()).unwrap();
+    /// assert_eq!(parsed.to_tree(), Ok(tree));
+    /// ```
+    pub fn arbitrary_with(data: &[u8], max_depth: usize, allowed_ops: &[OpKind]) -> Tree {
+        let mut bytes = Bytes(data.iter());
+
+        // Each node still to generate is described by its depth and by
+        // whether it is the operand of an unary operator.
+        let mut to_generate = vec![(0, false)];
+        let mut nodes = Vec::new();
+        while let Some((depth, under_unary)) = to_generate.pop() {
+            let op = if depth < max_depth && !allowed_ops.is_empty() && bytes.next() % 4 != 0 {
+                match allowed_ops[usize::from(bytes.next()) % allowed_ops.len()] {
+                    OpKind::Unary(_) if under_unary => None,
+                    op => Some(op),
+                }
+            } else {
+                None
+            };
+            match op {
+                Some(OpKind::Unary(op)) => {
+                    to_generate.push((depth + 1, true));
+                    nodes.push(Node::Op(OpKind::Unary(op)));
+                },
+                Some(OpKind::Binary(op)) => {
+                    to_generate.push((depth + 1, false));
+                    to_generate.push((depth + 1, false));
+                    nodes.push(Node::Op(OpKind::Binary(op)));
+                },
+                None => {
+                    nodes.push(Node::Leaf(generate_leaf(&mut bytes)));
+                },
+            }
+        }
+
+        // Build the tree from the end, the first operand of each operator is
+        // on top of the stack. As every operator got its operands, the stack
+        // is never missing any element.
+        let mut stack = Vec::new();
+        let zero = || Tree::Name("0".to_string());
+        for node in nodes.into_iter().rev() {
+            let tree = match node {
+                Node::Leaf(name) => Tree::Name(name),
+                Node::Op(OpKind::Unary(op)) => {
+                    Tree::Unary(op, Box::new(stack.pop().unwrap_or_else(zero)))
+                },
+                Node::Op(OpKind::Binary(op)) => {
+                    let first = stack.pop().unwrap_or_else(zero);
+                    let second = stack.pop().unwrap_or_else(zero);
+                    Tree::Binary(op, Box::new(first), Box::new(second))
+                },
+            };
+            stack.push(tree);
+        }
+        stack.pop().unwrap_or_else(zero)
+    }
+}
+
+/* --------------------------------- Testing -------------------------------- */
+
+/// Generate pseudo-random bytes to feed to `Tree::arbitrary_with`.
+#[cfg(test)]
+fn random_bytes(seed: u64, len: usize) -> Vec<u8> {
+    let mut state = seed.wrapping_mul(0x9E3779B97F4A7C15) | 1;
+    (0..len).map(|_| {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        (state >> 56) as u8
+    }).collect()
+}
+
+#[cfg(test)]
+fn all_ops() -> Vec<OpKind> {
+    crate::supported_operators().into_iter().map(|info| info.op).collect()
+}
+
+#[test]
+fn test_generate_tree() {
+    assert_eq!(Tree::arbitrary_with(&[], 10, &all_ops()), Tree::Name("0".to_string()));
+    assert_eq!(Tree::arbitrary_with(&[1, 2, 3], 0, &all_ops()), Tree::Name("2".to_string()));
+    for seed in 0..20 {
+        let data = random_bytes(seed, 64);
+        assert_eq!(Tree::arbitrary_with(&data, 6, &all_ops()), Tree::arbitrary_with(&data, 6, &all_ops()));
+    }
+}
+
+#[test]
+fn test_expression_round_trip() {
+    let ops: Vec<OpKind> = all_ops().into_iter()
+        .filter(|op| *op != OpKind::Unary(crate::UnaryOp::Plus))
+        .collect();
+    for seed in 0..500 {
+        let tree = Tree::arbitrary_with(&random_bytes(seed, 256), 8, &ops);
+        let expression = tree.to_expression_string();
+        let parsed = crate::MathParse::parse(&expression).map(|x| x.to_tree());
+        assert_eq!(parsed, Ok(Ok(tree)), "{expression}");
+    }
+}
+
+#[test]
+fn test_bitwise_never_panics() {
+    use crate::BinaryOp::*;
+    let ops = [
+        OpKind::Binary(ShiftLeft), OpKind::Binary(ShiftRight), OpKind::Binary(BitwiseAnd),
+        OpKind::Binary(BitwiseOr), OpKind::Binary(BitwiseXor), OpKind::Unary(crate::UnaryOp::Not),
+    ];
+    let variables = std::collections::HashMap::from([
+        ("x".to_string(), "-1".to_string()),
+        ("y".to_string(), "63".to_string()),
+        ("z".to_string(), "64".to_string()),
+    ]);
+    for seed in 0..2000 {
+        let tree = Tree::arbitrary_with(&random_bytes(seed, 64), 4, &ops);
+        let expression = tree.to_expression_string();
+        let result = std::panic::catch_unwind(|| {
+            let _ = crate::MathParse::parse(&expression).map(|x| x.solve_number(Some(&variables)));
+        });
+        assert!(result.is_ok(), "Solving {expression} panicked.");
+    }
+}
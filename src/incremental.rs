@@ -0,0 +1,210 @@
+use crate::MathParse;
+use crate::MathParseOptions;
+use crate::PositionedError;
+use crate::tokenize::*;
+use crate::tokenize::MathValue::*;
+use crate::parse::parse_token_list;
+use std::ops::Range;
+
+/* ---------------------------- Incremental parser -------------------------- */
+
+/// Parser for expressions that are edited a little at a time, such as in a
+/// formula editor. The tokens of the expression are kept so that, after an
+/// edit, only the tokens around it are read again.
+///
+/// For now, the tokens are then parsed again as a whole.
+///
+/// ```
+/// use math_parse::IncrementalParser;
+///
+/// let mut parser = IncrementalParser::new("2 * (3 + 4)");
+/// assert_eq!(parser.parsed().unwrap().solve_int(None), Ok(14));
+/// parser.apply_edit(5..6, "10").unwrap();
+/// assert_eq!(parser.text(), "2 * (10 + 4)");
+/// assert_eq!(parser.parsed().unwrap().solve_int(None), Ok(28));
+/// assert!(parser.apply_edit(0..1, "").is_err());
+/// ```
+pub struct IncrementalParser {
+    text: String,
+    options: MathParseOptions,
+    /// Byte range of each token of the text, the operators made of several
+    /// characters being kept as separate tokens.
+    spans: Vec<Range<usize>>,
+    parsed: Result<MathParse, PositionedError>,
+}
+
+impl IncrementalParser {
+    /// Parse an expression in infix notation that is going to be edited.
+    pub fn new(expression: &str) -> Self {
+        Self::with_options(expression, &MathParseOptions::default())
+    }
+
+    /// Parse an expression in infix notation with custom options.
+    pub fn with_options(expression: &str, options: &MathParseOptions) -> Self {
+        let text = expression.to_string();
+        let spans = token_spans(&text, 0);
+        let parsed = parse_spans(&text, &spans, options);
+        IncrementalParser{text, options: options.clone(), spans, parsed}
+    }
+
+    /// The current text of the expression.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// The expression parsed from the current text, or the error that
+    /// prevents it from being parsed.
+    pub fn parsed(&self) -> Result<&MathParse, &PositionedError> {
+        self.parsed.as_ref()
+    }
+
+    /// Replace the given byte range of the text and parse it again, returning
+    /// the error of the new parse if any. Only the tokens between the last
+    /// single character operator before the edit and the first one after it
+    /// are read again, unless there is a character literal between them, in
+    /// which case the whole text is read again.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of the text or not on character boundaries,
+    /// as `String::replace_range` does.
+    pub fn apply_edit(&mut self, range: Range<usize>, replacement: &str) -> Result<(), PositionedError> {
+        let (tokens, window) = self.edit_window(&range);
+        self.text.replace_range(range.clone(), replacement);
+        let new_window = window.start..(window.end - range.len() + replacement.len());
+
+        if self.text[new_window.clone()].contains('\'') {
+            // An unclosed character literal can run past the window.
+            self.spans = token_spans(&self.text, 0);
+        } else {
+            for span in &mut self.spans[tokens.end..] {
+                *span = (span.start - window.end + new_window.end)..(span.end - window.end + new_window.end);
+            }
+            let new_spans = token_spans(&self.text[new_window.clone()], new_window.start);
+            self.spans.splice(tokens, new_spans);
+        }
+
+        self.parsed = parse_spans(&self.text, &self.spans, &self.options);
+        match &self.parsed {
+            Ok(_) => Ok(()),
+            Err(e) => Err(e.clone()),
+        }
+    }
+
+    /// Find the tokens that must be read again after an edit of the given
+    /// range and the byte range they cover. They are bounded by tokens that
+    /// are single characters operators, after which tokenizing always starts
+    /// in the same state. The signs are not used as bounds as they can be
+    /// part of numbers in scientific notation.
+    fn edit_window(&self, range: &Range<usize>) -> (Range<usize>, Range<usize>) {
+        let is_bound = |span: &Range<usize>| {
+            let mut chars = self.text[span.clone()].chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => is_math_char(c) && c != '+' && c != '-',
+                _ => false,
+            }
+        };
+
+        let before = self.spans.partition_point(|span| span.end <= range.start);
+        let first = self.spans[..before].iter().rposition(is_bound).map_or(0, |i| i + 1);
+        let after = self.spans.partition_point(|span| span.start < range.end).max(first);
+        let last = self.spans[after..].iter().position(is_bound).map_or(self.spans.len(), |i| i + after);
+
+        let start = if first == 0 { 0 } else { self.spans[first - 1].end };
+        let end = if last == self.spans.len() { self.text.len() } else { self.spans[last].start };
+        (first..last, start..end)
+    }
+}
+
+/// Byte ranges of the tokens of a text, offset by the given position.
+fn token_spans(text: &str, offset: usize) -> Vec<Range<usize>> {
+    let (tokens, positions) = math_token_base(text);
+    tokens.iter().zip(positions)
+        .filter_map(|(token, position)| match token {
+            Name(name) => Some((offset + position)..(offset + position + name.len())),
+            Operator(c) => Some((offset + position)..(offset + position + c.len_utf8())),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Parse the tokens of a text from their spans.
+fn parse_spans(text: &str, spans: &[Range<usize>], options: &MathParseOptions) -> Result<MathParse, PositionedError> {
+    let mut tokens = Vec::with_capacity(spans.len() + 1);
+    let mut positions = Vec::with_capacity(spans.len() + 1);
+    for span in spans {
+        let token = &text[span.clone()];
+        let mut chars = token.chars();
+        tokens.push(match (chars.next(), chars.next()) {
+            (Some(c), None) if is_math_char(c) => Operator(c),
+            _ => Name(token),
+        });
+        positions.push(span.start);
+    }
+    tokens.push(TrailingError);
+    positions.push(text.len());
+    combine_complex_tokens(&mut tokens, &positions);
+    let (tokens, positions) = split_word_operators(tokens, positions);
+    check_token_names(&tokens, &positions, options, &[])?;
+    Ok(MathParse::from_parsed_lines(parse_token_list(tokens, options)?, text, options)?)
+}
+
+/* --------------------------------- Testing -------------------------------- */
+
+#[cfg(test)]
+fn assert_same_as_full_parse(parser: &IncrementalParser) {
+    let text = parser.text();
+    assert_eq!(parser.spans, token_spans(text, 0), "{text}");
+    let incremental = parser.parsed().map(|x| x.to_rpn()).map_err(|e| e.error.clone());
+    let full = MathParse::parse(text).map(|x| x.to_rpn());
+    assert_eq!(incremental, full, "{text}");
+}
+
+#[test]
+fn test_incremental_typing() {
+    let expression = "(a1 + 2.5e-3*b) // 3 << 1 - '+' × sum(i, 1, 3, i*y) + 1e+5 ÷ d mod not x";
+    let mut parser = IncrementalParser::new("");
+    for (index, c) in expression.char_indices() {
+        let _ = parser.apply_edit(index..index, &c.to_string());
+        assert_same_as_full_parse(&parser);
+    }
+    assert!(parser.parsed().is_ok());
+
+    let mut parser = IncrementalParser::new("x");
+    for _ in 0..50 {
+        let _ = parser.apply_edit(1..1, "*(1+x)");
+        assert_same_as_full_parse(&parser);
+    }
+}
+
+#[test]
+fn test_incremental_edits() {
+    let piece = "(a1 + 2.5e-3*b) // 3 << 1 - '+' × sum(i, 1, 3, i*y) + 1e+5 ÷ d";
+    let expression = vec![piece; 100].join(" + ");
+    let replacements = ["", "+", "-", "*", "/", "<", ">", "(", ")", "'", " ", "e", "1", "1e", "x", "÷", "$", ","];
+    let mut parser = IncrementalParser::new(&expression);
+    assert_same_as_full_parse(&parser);
+    assert!(parser.parsed().is_ok());
+
+    let mut state: u64 = 0x2545F4914F6CDD1D;
+    let mut random = |max: usize| {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        (state % max as u64) as usize
+    };
+    for _ in 0..500 {
+        let text = parser.text();
+        let mut start = random(text.len() + 1);
+        while !text.is_char_boundary(start) {
+            start -= 1;
+        }
+        let mut end = (start + random(6)).min(text.len());
+        while !text.is_char_boundary(end) {
+            end += 1;
+        }
+        let replacement = replacements[random(replacements.len())];
+        let _ = parser.apply_edit(start..end, replacement);
+        assert_same_as_full_parse(&parser);
+    }
+}
@@ -0,0 +1,81 @@
+use crate::RPN;
+use crate::BinaryOp;
+use crate::UnaryOp;
+use std::collections::HashMap;
+
+/* ------------------------------ Interned names ---------------------------- */
+
+/// RPN action where the names are replaced by their index in a symbol table.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+enum Action {
+    Name(u32),
+    Unary(UnaryOp),
+    Binary(BinaryOp),
+    Call(u32, u32),
+}
+
+/// RPN actions where each name is only stored once, in a symbol table, the
+/// actions keeping its index. Generated expressions can use the same
+/// variables hundreds of times, which would otherwise each hold their own
+/// copy of the name.
+///
+/// The symbols are in the order of their first use, so the same RPN actions
+/// always give the same symbol table and interned actions can be compared
+/// and hashed directly.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct InternedRPN {
+    symbols: Vec<String>,
+    actions: Vec<Action>,
+}
+
+impl InternedRPN {
+    /// Intern the names of RPN actions.
+    pub fn new(rpn_actions: Vec<RPN>) -> Self {
+        let mut symbols = vec![];
+        let mut indexes = HashMap::<String, u32>::new();
+        let mut intern = |name: String| -> u32 {
+            *indexes.entry(name).or_insert_with_key(|name| {
+                symbols.push(name.clone());
+                symbols.len() as u32 - 1
+            })
+        };
+        let actions = rpn_actions.into_iter()
+            .map(|action| match action {
+                RPN::Name(name)        => Action::Name(intern(name)),
+                RPN::Unary(op)         => Action::Unary(op),
+                RPN::Binary(op)        => Action::Binary(op),
+                RPN::Call(name, arity) => Action::Call(intern(name), arity as u32),
+            })
+            .collect();
+        InternedRPN{symbols, actions}
+    }
+
+    /// The names used by the actions, numbers and function names included,
+    /// each one once.
+    pub fn symbols(&self) -> &[String] {
+        &self.symbols
+    }
+
+    /// Write the actions back with their names.
+    pub fn to_rpn(&self) -> Vec<RPN> {
+        self.actions.iter()
+            .map(|action| match action {
+                Action::Name(index)        => RPN::Name(self.symbols[*index as usize].clone()),
+                Action::Unary(op)          => RPN::Unary(*op),
+                Action::Binary(op)         => RPN::Binary(*op),
+                Action::Call(index, arity) => RPN::Call(self.symbols[*index as usize].clone(), *arity as usize),
+            })
+            .collect()
+    }
+}
+
+/* --------------------------------- Testing -------------------------------- */
+
+#[test]
+fn test_interned_names() {
+    let rpn = crate::MathParse::parse("x*x + f(x, 3) - 3 + y").unwrap().to_rpn().unwrap();
+    let interned = InternedRPN::new(rpn.clone());
+    assert_eq!(interned.symbols(), ["x", "3", "f", "y"]);
+    assert_eq!(interned.to_rpn(), rpn);
+    assert_eq!(interned, InternedRPN::new(rpn));
+}
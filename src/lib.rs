@@ -5,13 +5,60 @@ mod parse_rpn;
 mod tokenize;
 mod solve;
 mod parse;
+mod cache;
+mod pool;
+mod find;
+mod rename;
+mod prefix;
+mod exact;
+mod equation;
+mod incremental;
+mod session;
+mod spans;
 mod tree;
 mod rpn;
+mod intern;
+mod system;
+mod portable;
+#[cfg(feature = "units")]
+mod units;
+#[cfg(feature = "durations")]
+mod durations;
+#[cfg(feature = "fuzzing")]
+mod fuzzing;
+#[cfg(feature = "serde")]
+mod analysis;
+#[cfg(feature = "serde")]
+mod serialization;
+#[cfg(feature = "bigint")]
+mod bigint;
+#[cfg(feature = "decimal")]
+mod decimal;
 
 use solve::*;
 use parse::math_parse;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
 use number_conversion::*;
+pub use solve::Number;
+pub use cache::{ExprCache, CacheStats};
+pub use pool::{ExprPool, ExprId};
+pub use find::find_expressions;
+pub use system::solve_system;
+pub use rename::RenameReport;
+pub use exact::ExactResult;
+pub use equation::Equation;
+pub use incremental::IncrementalParser;
+pub use session::{EvalSession, EvalStatus};
+pub use spans::SpanMap;
+#[cfg(feature = "units")]
+pub use units::Dimensions;
+#[cfg(feature = "serde")]
+pub use analysis::ANALYSIS_SCHEMA;
+#[cfg(feature = "bigint")]
+pub use bigint::BigInt;
+#[cfg(feature = "decimal")]
+pub use rust_decimal::Decimal;
 
 /* --------------------------------- Parsing -------------------------------- */
 
@@ -20,7 +67,11 @@ use number_conversion::*;
 pub struct MathParse {
     // Internal representation of parsed math is the RPN one. Might or might
     // not change in the future.
-    internal: Vec<RPN>
+    internal: intern::InternedRPN,
+    options: MathParseOptions,
+    // Expression in infix notation the RPN actions come from, if any. Used
+    // to find where each element of the expression is written.
+    source: Option<String>,
 }
 
 impl MathParse {
@@ -29,10 +80,125 @@ impl MathParse {
     /// ```
     /// math_parse::MathParse::parse("3 + 4").unwrap();
     /// ```
+    ///
+    /// The expression can also be a list of comma-separated expressions, such
+    /// as `1+2, 3*4` or `(1+2, 3*4)`. The comma has a lower precedence than
+    /// any operator and it is only a list separator at the top level of the
+    /// expression or inside a single pair of parenthesis surrounding the whole
+    /// expression. A comma anywhere else, as in `(1, 2) + 3`, is an error.
+    /// Lists can only be solved with [`MathParse::solve_list_float`] or
+    /// converted with [`MathParse::to_trees`]; solving them as a single value
+    /// gives an `UnbalancedStack` error.
     pub fn parse(expression: &str) -> Result<Self, MathParseErrors> {
-        let parsed_tree = math_parse(expression)?;
-        let internal = rpn::parse_rpn(&parsed_tree)?;
-        Ok(MathParse{internal})
+        Self::parse_with_options(expression, &MathParseOptions::default())
+    }
+
+    /// Parse a math expression in infix notation with custom options. The
+    /// options are kept with the parsed expression and used when solving it.
+    ///
+    /// ```
+    /// use math_parse::{MathParse, MathParseOptions};
+    ///
+    /// let options = MathParseOptions {
+    ///     byte_size_suffixes: true,
+    ///     ..MathParseOptions::default()
+    /// };
+    /// assert_eq!(
+    ///     MathParse::parse_with_options("4GiB / 512KiB", &options).unwrap().solve_int(None),
+    ///     Ok(8192));
+    /// ```
+    pub fn parse_with_options(expression: &str, options: &MathParseOptions) -> Result<Self, MathParseErrors> {
+        let rewritten = match &options.token_rewriter {
+            Some(rewriter) => tokenize::rewrite_words(expression, &*rewriter.0)?,
+            None => None,
+        };
+        let expression = rewritten.as_deref().unwrap_or(expression);
+        let swapped = options.decimal_comma.then(|| tokenize::swap_decimal_comma(expression));
+        let expression = swapped.as_deref().unwrap_or(expression);
+        Self::from_parsed_lines(math_parse(expression, options)?, expression, options)
+    }
+
+    /// Make the RPN actions of parsed lines of infix math, read from the
+    /// given expression.
+    fn from_parsed_lines(lines: Vec<Vec<tokenize::MathValue>>, expression: &str, options: &MathParseOptions) -> Result<Self, MathParseErrors> {
+        let mut internal = vec![];
+        for parsed_tree in lines {
+            internal.append(&mut rpn::parse_rpn(&parsed_tree)?);
+        }
+        solve::check_builtin_calls(&internal)?;
+        Ok(MathParse{internal: intern::InternedRPN::new(internal), options: options.clone(), source: Some(expression.to_string())})
+    }
+
+    /// Parse a math expression that is still being typed, such as for a live
+    /// preview of its result. A binary operator at the end of the expression
+    /// is ignored and the parenthesis that are still open are closed at the
+    /// end of the expression. The fixes done are returned along with the
+    /// parsed expression. Expressions that are invalid in any other way, such
+    /// as `1+*2`, are still errors.
+    ///
+    /// ```
+    /// use math_parse::{MathParse, LenientFix};
+    ///
+    /// let (parsed, fixes) = MathParse::parse_lenient("(3*").unwrap();
+    /// assert_eq!(parsed.solve_int(None), Ok(3));
+    /// assert_eq!(fixes, vec![LenientFix::IgnoredTrailingOperator(2..3), LenientFix::ClosedParenthesis(0)]);
+    /// ```
+    pub fn parse_lenient(expression: &str) -> Result<(Self, Vec<LenientFix>), MathParseErrors> {
+        Self::parse_lenient_with_options(expression, &MathParseOptions::default())
+    }
+
+    /// Parse a math expression that is still being typed with custom options.
+    pub fn parse_lenient_with_options(expression: &str, options: &MathParseOptions) -> Result<(Self, Vec<LenientFix>), MathParseErrors> {
+        let (fixed, fixes) = parse::lenient_fix(expression);
+        Ok((Self::parse_with_options(&fixed, options)?, fixes))
+    }
+
+    /// Parse a math expression in infix notation and also return warnings
+    /// about its content. The warnings are given even if the expression can't
+    /// be parsed.
+    ///
+    /// ```
+    /// use math_parse::{MathParse, MathParseWarning};
+    ///
+    /// let (parsed, warnings) = MathParse::parse_with_warnings("2^8");
+    /// assert!(parsed.is_ok());
+    /// assert_eq!(warnings, vec![MathParseWarning::ConfusingOperator('^', 1..2)]);
+    /// ```
+    pub fn parse_with_warnings(expression: &str) -> (Result<Self, MathParseErrors>, Vec<MathParseWarning>) {
+        (Self::parse(expression), parse::parse_warnings(expression))
+    }
+
+    /// Parse an equation made of two expressions in infix notation separated
+    /// by a single `=`, such as `2*x + 3 = 11`. If there is no `=` or more
+    /// than one, a `MisplacedOperator('=')` error is returned. The `=` of
+    /// comparison operators such as `==` or `<=` are not separators.
+    ///
+    /// ```
+    /// use math_parse::{MathParse, Number};
+    ///
+    /// let equation = MathParse::parse_equation("(y-32)*5/9 = 100").unwrap();
+    /// assert_eq!(equation.solve_for("y", None), Ok(Number::Int(212)));
+    /// ```
+    pub fn parse_equation(expression: &str) -> Result<Equation, MathParseErrors> {
+        Self::parse_equation_with_options(expression, &MathParseOptions::default())
+    }
+
+    /// Parse an equation in infix notation with custom options.
+    pub fn parse_equation_with_options(expression: &str, options: &MathParseOptions) -> Result<Equation, MathParseErrors> {
+        let bytes = expression.as_bytes();
+        let mut separators = (0..bytes.len()).filter(|&i| {
+            bytes[i] == b'='
+                && !matches!(i.checked_sub(1).map(|j| bytes[j]), Some(b'=' | b'<' | b'>' | b'!'))
+                && bytes.get(i + 1) != Some(&b'=')
+        });
+        let (Some(separator), None) = (separators.next(), separators.next()) else {
+            return Err(MisplacedOperator('='));
+        };
+        let (lhs, rhs) = (&expression[..separator], &expression[separator + 1..]);
+        Ok(Equation {
+            lhs: Self::parse_with_options(lhs, options)?,
+            rhs: Self::parse_with_options(rhs, options)?,
+        })
     }
 
     /// Parse a math expression in postfix notation (RPN).
@@ -40,57 +206,669 @@ impl MathParse {
     /// ```
     /// math_parse::MathParse::parse_rpn("3 4 +").unwrap();
     /// ```
+    ///
+    /// Elements can be separated by whitespace or by commas, and a `#` starts
+    /// a comment that ends with the line. An expression with no elements,
+    /// such as one made only of comments, gives an `UnbalancedStack` error.
+    ///
+    /// ```
+    /// let program = "3, 4, +  # add the basics
+    ///                2 *      # and double them";
+    /// assert_eq!(math_parse::MathParse::parse_rpn(program).unwrap().solve_int(None), Ok(14));
+    /// ```
     pub fn parse_rpn(expression: &str) -> Result<Self, MathParseErrors> {
-        let internal = parse_rpn::parse_rpn(expression)?;
-        Ok(MathParse{internal})
+        Self::parse_rpn_with_options(expression, &MathParseOptions::default())
+    }
+
+    /// Parse a math expression in postfix notation (RPN) with custom options.
+    pub fn parse_rpn_with_options(expression: &str, options: &MathParseOptions) -> Result<Self, MathParseErrors> {
+        let internal = parse_rpn::parse_rpn(expression, options)?;
+        Ok(MathParse{internal: intern::InternedRPN::new(internal), options: options.clone(), source: None})
+    }
+
+    /// Parse a math expression in prefix notation (Polish notation). The
+    /// operators are the same as in RPN, except for the unary operators which
+    /// are written `neg` for `-`, `not` for `~`, and `pos` for the unary `+`,
+    /// so variables can't have these names. As in infix notation, `not` is
+    /// the bitwise not and `!` is the logical not. Function calls are written
+    /// as `name@arity`, such as `sum@4 i 1 10 * i i`.
+    ///
+    /// ```
+    /// use math_parse::MathParse;
+    ///
+    /// assert_eq!(MathParse::parse_prefix("* + 2 3 neg 4").unwrap().solve_int(None), Ok(-20));
+    /// ```
+    pub fn parse_prefix(expression: &str) -> Result<Self, MathParseErrors> {
+        Self::parse_prefix_with_options(expression, &MathParseOptions::default())
+    }
+
+    /// Parse a math expression in prefix notation with custom options.
+    pub fn parse_prefix_with_options(expression: &str, options: &MathParseOptions) -> Result<Self, MathParseErrors> {
+        let internal = prefix::parse_prefix(expression, options)?;
+        Ok(MathParse{internal: intern::InternedRPN::new(internal), options: options.clone(), source: None})
+    }
+}
+
+/// Parsed expressions are equal when they have the same structure, that is
+/// the same RPN actions. The way the expressions were written does not
+/// matter: `1+2` and `(1 + 2)` are equal, but `1+2+3` and `1+(2+3)` are not
+/// as the additions are not applied in the same order. The options used to
+/// parse the expressions are not compared either. As hashing is done on the
+/// same RPN actions, parsed expressions can be used as keys of hash maps.
+///
+/// ```
+/// use math_parse::MathParse;
+/// assert!(MathParse::parse("1 + 2").unwrap() == MathParse::parse("(1+2)").unwrap());
+/// assert!(MathParse::parse("1+2").unwrap() == MathParse::parse_rpn("1 2 +").unwrap());
+/// assert!(MathParse::parse("1+2+3").unwrap() != MathParse::parse("1+(2+3)").unwrap());
+/// ```
+impl PartialEq for MathParse {
+    fn eq(&self, other: &Self) -> bool {
+        self.internal == other.internal
+    }
+}
+
+impl Eq for MathParse {}
+
+impl std::hash::Hash for MathParse {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.internal.hash(state);
+    }
+}
+
+/* --------------------------------- Options -------------------------------- */
+
+/// Options used to change the way math expressions are read. Unless stated
+/// otherwise, all options are disabled by default.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MathParseOptions {
+    /// Accept byte-size suffixes after numbers. The decimal suffixes `B`, `KB`,
+    /// `MB`, `GB`, and `TB` are powers of 1000 while the binary suffixes
+    /// `KiB`, `MiB`, `GiB`, and `TiB` are powers of 1024. Integers followed by
+    /// a suffix stay integers. The suffixes are case-sensitive, so `kb` or
+    /// `mib` are not recognized. Numbers that are valid without a suffix are
+    /// read as they are, so `0x1B` is still 27.
+    pub byte_size_suffixes: bool,
+
+    /// Accept SI suffixes after numbers, so that `4k` is 4000. The decimal
+    /// suffixes `k`, `M`, `G`, and `T` are powers of 1000 while the binary
+    /// suffixes `Ki`, `Mi`, `Gi`, and `Ti` are powers of 1024. Integers
+    /// followed by a suffix stay integers. Without this option, names such as
+    /// `4k` are not valid numbers and can only be named variables. Numbers
+    /// that are valid without a suffix are read as they are, so `36#1k` is
+    /// still 56.
+    pub si_suffixes: bool,
+
+    /// Accept apostrophes between the digits of numbers, as in `1'000'000`
+    /// or `0xFFFF'FFFF`. Like the underscores, which are always accepted,
+    /// each apostrophe must be between two digits.
+    pub apostrophe_separators: bool,
+
+    /// Give an `IntegerOverflow` error when the result of an addition, a
+    /// subtraction, a multiplication, a negation, or an integer division of
+    /// integers does not fit in an `i64`. By default, the result is then
+    /// computed as a float, as done for the powers.
+    pub integer_overflow_errors: bool,
+
+    /// Divide by a float zero as done by IEEE 754 floats, so that `1/0.0` is
+    /// infinity and `0/0.0` is NaN, instead of giving an `UnexpectedZero`
+    /// error. Dividing by the integer 0, as in `1/0`, is still an error.
+    pub infinite_float_division: bool,
+
+    /// Round the results of the integer divisions down, as Python does,
+    /// instead of toward 0, so that `-7 // 2` is -4 instead of -3. Paired
+    /// with `euclidean_remainder`, this gives `a == b * (a // b) + a % b`
+    /// for positive divisors.
+    pub floor_division: bool,
+
+    /// Maximum number of operations that can be performed when solving an
+    /// expression. Each read number, each operation, and each function call
+    /// count as one operation. When the limit is reached, solving fails with
+    /// `TooManyOperations`. There is no limit when set to `None`.
+    pub max_operations: Option<usize>,
+
+    /// Accept any character that is not an operator in names. By default,
+    /// names can only be made of letters, digits, `_`, and `.`, which covers
+    /// variable names and numbers. Any other character in a name, such as
+    /// `$` or a control character, gives an `InvalidCharacter` error.
+    /// Character literals such as `'$'` can always hold any character.
+    pub permissive_identifiers: bool,
+
+    /// Source of the random numbers used by the `rand` and `randint`
+    /// functions. When there is no source, calling these functions gives a
+    /// `NoRandomSource` error.
+    pub random_source: Option<RandomSource>,
+
+    /// Accept expressions in Reverse Polish notation that leave more than one
+    /// number on the stack, such as `1 2 3 +`. They can only be solved with
+    /// `MathParse::solve_stack`.
+    pub multiple_rpn_results: bool,
+
+    /// Use compensated summation for the chains of additions and
+    /// subtractions of floats, such as `0.1 + 0.1 + 0.1`, so that their
+    /// rounding errors do not accumulate. This makes solving a bit slower.
+    pub accurate_float_sums: bool,
+
+    /// Compute the float operations that depend on the math library of the
+    /// platform, which are the powers and remainders of floats and the
+    /// functions such as `sin` or `ln`, with a portable implementation made
+    /// of basic operations only. Their results are then the same bits on
+    /// every platform, such as x86_64 and wasm32, but they can be a few units
+    /// in the last place away from the default ones. The other operations and
+    /// `sqrt` are correctly rounded, so they always give the same bits.
+    pub deterministic_floats: bool,
+
+    /// Make `%` give the remainder of the Euclidean division, which is never
+    /// negative, so that `-7 % 3` is 2 instead of -1. This applies to floats
+    /// too, but the integer division `//` is not changed.
+    pub euclidean_remainder: bool,
+
+    /// Accept angles written in degrees, minutes, and seconds, such as
+    /// `12°30'45"`, the minutes and seconds being optional. They are read as
+    /// a float number of degrees, so `12°30'` is 12.5. As there is no unit of
+    /// angle, they are always plain numbers of degrees, even when given to
+    /// functions that take radians.
+    pub dms_angles: bool,
+
+    /// Read the word `of` after a percentage as a multiplication, so that
+    /// `15% of 240` is 36. The value before the `%` is multiplied by the
+    /// value after `of` and divided by 100, as if they were in parenthesis.
+    /// `of` can then not be used as a variable name and any other use of it
+    /// is a `MisplacedWord` error. This only applies to infix notation.
+    pub percent_of: bool,
+
+    /// Don't read the names `pi`, `e`, and `tau` as the constants π, e, and
+    /// τ. The named variables of the map always shadow the constants, but
+    /// with this option, a name such as `pi` that is not in the map is an
+    /// `InvalidNumber` error.
+    pub disable_constants: bool,
+
+    /// Function called on each word of the names of an expression before it
+    /// is parsed, which can rewrite the word into another name or into an
+    /// operator. This only applies to infix notation.
+    pub token_rewriter: Option<TokenRewriter>,
+
+    /// Read the `,` as the decimal separator, as in `3,14`, and the `;` as
+    /// the separator of lists and function arguments, as in `max(1,5; 2)`.
+    /// This only applies to infix notation.
+    pub decimal_comma: bool,
+
+    /// Used by `MathParse::solve_duration`, allow adding or subtracting
+    /// durations and plain numbers, the plain numbers being in seconds.
+    #[cfg(feature = "durations")]
+    pub lenient_durations: bool,
+}
+
+/// Source of random numbers used by the `rand` and `randint` functions. It
+/// wraps a function that must return a float in the range `[0, 1)` each time
+/// it is called. Clones of a source share the same function.
+///
+/// ```
+/// use math_parse::{MathParse, MathParseOptions, RandomSource};
+///
+/// let mut next = 0.0;
+/// let options = MathParseOptions {
+///     random_source: Some(RandomSource::new(move || {next += 0.25; next})),
+///     ..MathParseOptions::default()
+/// };
+/// let parsed = MathParse::parse_with_options("10 + rand()*4", &options).unwrap();
+/// assert_eq!(parsed.solve_int(None), Ok(11));
+/// assert_eq!(parsed.solve_int(None), Ok(12));
+/// ```
+#[derive(Clone)]
+pub struct RandomSource(Arc<Mutex<dyn FnMut() -> f64 + Send>>);
+
+impl RandomSource {
+    /// Make a random source from a function returning floats in `[0, 1)`.
+    pub fn new(source: impl FnMut() -> f64 + Send + 'static) -> Self {
+        RandomSource(Arc::new(Mutex::new(source)))
+    }
+
+    /// Get the next random number.
+    fn next(&self) -> f64 {
+        let mut source = match self.0.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        source()
+    }
+}
+
+impl fmt::Debug for RandomSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "RandomSource")
+    }
+}
+
+impl PartialEq for RandomSource {
+    /// Two random sources are equal if they share the same function.
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// Rewriter of the words of the names of an expression in infix notation,
+/// used to read small languages built on top of math expressions. It wraps a
+/// function called on each word of the names, the words being separated by
+/// operators or whitespace, that returns the new text of the word or `None`
+/// to keep it. The new text must be a single name or operator, otherwise
+/// parsing gives an `InvalidRewrite` error. As only whole words are
+/// rewritten, `DIVIDEND` is left alone by a rewriter of `DIV`. Character
+/// literals are never rewritten.
+///
+/// The expression is rewritten before being parsed, so the positions in
+/// errors and spans are the ones in the rewritten expression. A rewritten
+/// operator is separated from its neighbours by spaces, so that it is not
+/// combined with them into another operator. Clones of a rewriter share the
+/// same function.
+///
+/// ```
+/// use math_parse::{MathParse, MathParseOptions, TokenRewriter};
+///
+/// let options = MathParseOptions {
+///     token_rewriter: Some(TokenRewriter::new(|word| match word {
+///         "DIV" => Some("//".to_string()),
+///         "MOD" => Some("%".to_string()),
+///         _ => None,
+///     })),
+///     ..MathParseOptions::default()
+/// };
+/// assert_eq!(MathParse::parse_with_options("10 DIV 3", &options).unwrap().solve_int(None), Ok(3));
+/// assert_eq!(MathParse::parse_with_options("10 MOD 3", &options).unwrap().solve_int(None), Ok(1));
+/// ```
+#[derive(Clone)]
+pub struct TokenRewriter(Arc<RewriteFunction>);
+
+/// Function wrapped by a `TokenRewriter`.
+type RewriteFunction = dyn Fn(&str) -> Option<String> + Send + Sync;
+
+impl TokenRewriter {
+    /// Make a token rewriter from a function returning the new text of a
+    /// word, or `None` to keep it.
+    pub fn new(rewriter: impl Fn(&str) -> Option<String> + Send + Sync + 'static) -> Self {
+        TokenRewriter(Arc::new(rewriter))
+    }
+}
+
+impl fmt::Debug for TokenRewriter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "TokenRewriter")
+    }
+}
+
+impl PartialEq for TokenRewriter {
+    /// Two token rewriters are equal if they share the same function.
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
     }
 }
 
 /* --------------------------------- Solving -------------------------------- */
 
+/// Functions given by the user to `MathParse::solve_with_functions`, by name.
+/// Each function takes the solved arguments of the call and returns its
+/// result or an error.
+pub type UserFunctions = HashMap<String, Box<dyn Fn(&[Number]) -> Result<Number, MathParseErrors>>>;
+
+/// Result of solving an expression, either an integer or a float.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum SolvedValue {
+    Int(i64),
+    Float(f64),
+}
+
+impl fmt::Display for SolvedValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SolvedValue::Int(i)   => write!(f, "{i}"),
+            SolvedValue::Float(x) => write!(f, "{x}"),
+        }
+    }
+}
+
+impl SolvedValue {
+    /// Convert the value to an integer, failing if it is a float.
+    fn to_int(self) -> Result<i64, MathParseErrors> {
+        match self {
+            SolvedValue::Int(i)   => Ok(i),
+            SolvedValue::Float(f) => f_to_i_strict(f),
+        }
+    }
+
+    /// Convert the value to an unsigned integer, failing if it is negative
+    /// or if it is a float that is not an integer.
+    fn to_uint(self) -> Result<u64, MathParseErrors> {
+        match self {
+            SolvedValue::Int(i) => u64::try_from(i).map_err(|_| UnexpectedNegative),
+            SolvedValue::Float(f) if f < 0.0 => Err(UnexpectedNegative),
+            // 18446744073709551616 is 2 ** 64, the first float too big for
+            // an u64.
+            SolvedValue::Float(f) if f.fract() == 0.0 && f < 18_446_744_073_709_551_616.0 => Ok(f as u64),
+            SolvedValue::Float(f) => Err(ReturnFloatExpectedInt(f)),
+        }
+    }
+
+    /// Convert the value to a float.
+    fn to_float(self) -> f64 {
+        match self {
+            SolvedValue::Int(i)   => i as f64,
+            SolvedValue::Float(f) => f,
+        }
+    }
+}
+
+/// A format specification used by `MathParse::solve_to_string`.
+struct FormatSpec {
+    /// Add the prefix of the radix, as with `{:#x}`.
+    alternate: bool,
+    /// Number of decimals, as with `{:.2}`.
+    precision: Option<usize>,
+    /// The formatting trait to use, `' '` for `Display`.
+    kind: char,
+}
+
+impl FormatSpec {
+    /// Read a format specification such as `{:#x}` or `{:.2}`.
+    fn parse(spec: &str) -> Result<Self, MathParseErrors> {
+        let invalid = || InvalidFormatSpec(spec.to_string());
+        let inside = spec.strip_prefix('{')
+            .and_then(|x| x.strip_suffix('}'))
+            .ok_or_else(invalid)?;
+        if inside.is_empty() {
+            return Ok(FormatSpec{alternate: false, precision: None, kind: ' '});
+        }
+        let mut rest = inside.strip_prefix(':').ok_or_else(invalid)?;
+        let alternate = if let Some(x) = rest.strip_prefix('#') {
+            rest = x;
+            true
+        } else {
+            false
+        };
+        let precision = if let Some(x) = rest.strip_prefix('.') {
+            let digits_end = x.find(|c: char| !c.is_ascii_digit()).unwrap_or(x.len());
+            rest = &x[digits_end..];
+            Some(x[..digits_end].parse::<usize>().map_err(|_| invalid())?)
+        } else {
+            None
+        };
+        let mut chars = rest.chars();
+        let kind = match (chars.next(), chars.next()) {
+            (None, None) => ' ',
+            (Some(c), None) => c,
+            _ => {
+                return Err(invalid());
+            },
+        };
+        let valid = match kind {
+            ' ' | 'e' | 'E' => !alternate,
+            'x' | 'X' | 'o' | 'b' => precision.is_none(),
+            _ => false,
+        };
+        if valid {
+            Ok(FormatSpec{alternate, precision, kind})
+        } else {
+            Err(invalid())
+        }
+    }
+
+    /// Format a solved value.
+    fn apply(&self, value: SolvedValue) -> Result<String, MathParseErrors> {
+        let ret = match (self.kind, self.precision) {
+            (' ', None)            => value.to_string(),
+            (' ', Some(precision)) => format!("{:.precision$}", value.to_float()),
+            ('e', None)            => format!("{:e}", value.to_float()),
+            ('e', Some(precision)) => format!("{:.precision$e}", value.to_float()),
+            ('E', None)            => format!("{:E}", value.to_float()),
+            ('E', Some(precision)) => format!("{:.precision$E}", value.to_float()),
+            ('x', _) if self.alternate => format!("{:#x}", value.to_int()?),
+            ('x', _)               => format!("{:x}", value.to_int()?),
+            ('X', _) if self.alternate => format!("{:#X}", value.to_int()?),
+            ('X', _)               => format!("{:X}", value.to_int()?),
+            ('o', _) if self.alternate => format!("{:#o}", value.to_int()?),
+            ('o', _)               => format!("{:o}", value.to_int()?),
+            ('b', _) if self.alternate => format!("{:#b}", value.to_int()?),
+            ('b', _)               => format!("{:b}", value.to_int()?),
+            (x, _) => {
+                return Err(MathParseInternalBug(format!("Format kind {x} should have been rejected when reading the format specification.")));
+            },
+        };
+        Ok(ret)
+    }
+}
+
+impl From<Number> for SolvedValue {
+    fn from(num: Number) -> Self {
+        match num {
+            Number::Int(i)   => SolvedValue::Int(i),
+            Number::Float(f) => SolvedValue::Float(f),
+        }
+    }
+}
+
+impl From<i64> for SolvedValue {
+    fn from(i: i64) -> Self {
+        SolvedValue::Int(i)
+    }
+}
+
+impl From<f64> for SolvedValue {
+    fn from(f: f64) -> Self {
+        SolvedValue::Float(f)
+    }
+}
+
 impl MathParse {
     /// Does all the computation from a string with a line of math to the final
     /// resulting number. If the result can be an int, return it as
-    /// `Ok(Ok(int))`. If it can only be a float, return it as `Ok(Err(floar))`.
-    /// If it can't be solved, return `Err(error)`.
+    /// `Ok(SolvedValue::Int(int))`. If it can only be a float, return it as
+    /// `Ok(SolvedValue::Float(float))`. If it can't be solved, return
+    /// `Err(error)`.
     ///
     /// ```
-    /// use math_parse::MathParse;
+    /// use math_parse::{MathParse, SolvedValue};
     /// use math_parse::MathParseErrors::*;
     ///
     /// assert_eq!(
-    ///     MathParse::parse("3 + 8.0").unwrap().solve_auto(None),
-    ///     Ok(Ok(11)));
+    ///     MathParse::parse("3 + 8.0").unwrap().solve_value(None),
+    ///     Ok(SolvedValue::Int(11)));
     /// assert_eq!(
-    ///     MathParse::parse("3 - 8.5").unwrap().solve_auto(None),
-    ///     Ok(Err(-5.5)));
+    ///     MathParse::parse("3 - 8.5").unwrap().solve_value(None),
+    ///     Ok(SolvedValue::Float(-5.5)));
     /// assert_eq!(
-    ///     MathParse::parse("34 + bcd").unwrap().solve_auto(None),
+    ///     MathParse::parse("34 + bcd").unwrap().solve_value(None),
     ///     Err(InvalidNumber("bcd".to_string())));
     /// ```
     ///
     /// A optional map of variable name can be taken as argument.
-    pub fn solve_auto(&self, map: Option<&HashMap<String, String>>) -> Result<Result<i64, f64>, MathParseErrors> {
+    pub fn solve_value(&self, map: Option<&HashMap<String, String>>) -> Result<SolvedValue, MathParseErrors> {
         let map_function = |s: &str| -> Option<String> {
             match map {
                 None => None,
-                Some(x) => match x.get(s) {
-                    Some(x) => Some(x.clone()),
-                    None => None,
-                },
+                Some(x) => x.get(s).cloned(),
             }
         };
 
-        match math_solve(&self.internal, &map_function) {
-            Err(err)             => Err(err),
-            Ok(Number::Int(i))   => Ok(Ok(i)),
-            Ok(Number::Float(f)) => Ok(
-                if let Ok(i) = f_to_i_strict(f) {
-                    Ok(i)
-                } else {
-                    Err(f)
-                }
-            ),
+        match math_solve(&self.internal.to_rpn(), &map_function, &self.options)?.simplified() {
+            Number::Int(i)   => Ok(SolvedValue::Int(i)),
+            Number::Float(f) => Ok(SolvedValue::Float(f)),
+        }
+    }
+
+    /// Same as `solve_value` but the functions of `functions` can also be
+    /// called by the expression, such as to give it functions of a domain.
+    /// Builtin functions are called instead of user functions with the same
+    /// name, and calling a function found in neither gives an
+    /// `UnknownFunction` error. The arguments are solved before the function
+    /// is called. User functions can only be called from expressions parsed
+    /// from infix notation, as parsing RPN checks that only builtin functions
+    /// are called.
+    ///
+    /// ```
+    /// use math_parse::{MathParse, MathParseErrors, Number, SolvedValue, UserFunctions};
+    ///
+    /// let mut functions = UserFunctions::new();
+    /// functions.insert("lerp".to_string(), Box::new(|args: &[Number]| match args {
+    ///     [a, b, t] => Ok(Number::Float(a.as_f64() + (b.as_f64() - a.as_f64()) * t.as_f64())),
+    ///     _ => Err(MathParseErrors::InvalidFunctionCall("lerp".to_string())),
+    /// }));
+    /// let parsed = MathParse::parse("lerp(10, 20, 0.25) * 2").unwrap();
+    /// assert_eq!(parsed.solve_with_functions(None, &functions), Ok(SolvedValue::Int(25)));
+    /// assert_eq!(parsed.solve_value(None), Err(MathParseErrors::UnknownFunction("lerp".to_string())));
+    /// ```
+    pub fn solve_with_functions(&self, map: Option<&HashMap<String, String>>, functions: &UserFunctions) -> Result<SolvedValue, MathParseErrors> {
+        let map_function = |s: &str| -> Option<String> {
+            match map {
+                None => None,
+                Some(x) => x.get(s).cloned(),
+            }
+        };
+
+        let mut solver = Solver::with_functions(&map_function, &self.options, functions);
+        Ok(solver.solve_expression(&self.internal.to_rpn())?.simplified().into())
+    }
+
+    /// Same as `solve_value` but a float result within `tolerance` of an
+    /// integer is given as this integer. A tolerance of 0 gives the same
+    /// result as `solve_value`. Floats above 2^53, which can't tell apart
+    /// neighbouring integers, are kept as floats whatever the tolerance.
+    ///
+    /// ```
+    /// use math_parse::{MathParse, SolvedValue};
+    ///
+    /// let parsed = MathParse::parse("(0.1+0.2)*10").unwrap();
+    /// assert_eq!(parsed.solve_value(None), Ok(SolvedValue::Float(3.0000000000000004)));
+    /// assert_eq!(parsed.solve_value_with_tolerance(None, 1e-9), Ok(SolvedValue::Int(3)));
+    /// ```
+    ///
+    /// A optional map of variable name can be taken as argument.
+    pub fn solve_value_with_tolerance(&self, map: Option<&HashMap<String, String>>, tolerance: f64) -> Result<SolvedValue, MathParseErrors> {
+        match self.solve_value(map)? {
+            SolvedValue::Float(f) => match f_to_i_tolerant(f, tolerance) {
+                Ok(i) => Ok(SolvedValue::Int(i)),
+                Err(_) => Ok(SolvedValue::Float(f)),
+            },
+            value => Ok(value),
+        }
+    }
+
+    /// Same as `solve_int` but a float result within `tolerance` of an
+    /// integer is accepted and given as this integer. A tolerance of 0 gives
+    /// the same result as `solve_int`.
+    pub fn solve_int_with_tolerance(&self, variable_map: Option<&HashMap<String, String>>, tolerance: f64) -> Result<i64, MathParseErrors> {
+        self.solve_value_with_tolerance(variable_map, tolerance)?.to_int()
+    }
+
+    /// Same as `solve_value` but also return the warnings raised while
+    /// solving. The warnings don't change the result.
+    ///
+    /// ```
+    /// use math_parse::{MathParse, MathParseWarning, SolvedValue, BinaryOp};
+    ///
+    /// assert_eq!(
+    ///     MathParse::parse("3 + 0.5").unwrap().solve_value_with_warnings(None),
+    ///     (Ok(SolvedValue::Float(3.5)), vec![MathParseWarning::MixedNumberTypes(BinaryOp::Addition)]));
+    /// ```
+    pub fn solve_value_with_warnings(&self, map: Option<&HashMap<String, String>>) -> (Result<SolvedValue, MathParseErrors>, Vec<MathParseWarning>) {
+        let map_function = |s: &str| -> Option<String> {
+            match map {
+                None => None,
+                Some(x) => x.get(s).cloned(),
+            }
+        };
+
+        let (result, warnings) = math_solve_with_warnings(&self.internal.to_rpn(), &map_function, &self.options);
+        (result.map(|num| num.simplified().into()), warnings)
+    }
+
+    /// Same as `solve_int` but also return the warnings raised while solving.
+    pub fn solve_int_with_warnings(&self, variable_map: Option<&HashMap<String, String>>) -> (Result<i64, MathParseErrors>, Vec<MathParseWarning>) {
+        let (result, warnings) = self.solve_value_with_warnings(variable_map);
+        (result.and_then(SolvedValue::to_int), warnings)
+    }
+
+    /// Same as `solve_float` but also return the warnings raised while solving.
+    pub fn solve_float_with_warnings(&self, variable_map: Option<&HashMap<String, String>>) -> (Result<f64, MathParseErrors>, Vec<MathParseWarning>) {
+        let (result, mut warnings) = self.solve_value_with_warnings(variable_map);
+        if let Ok(SolvedValue::Int(i)) = result {
+            if !i_fits_in_f(i) {
+                warnings.push(MathParseWarning::PrecisionLoss(i));
+            }
+        }
+        (result.map(SolvedValue::to_float), warnings)
+    }
+
+    /// Same as `solve_value` but the map must not have entries that are not
+    /// used, such as the ones with a typo in their name. After solving, the
+    /// names of the map that were never read give an `UnusedVariables`
+    /// error. A name only used in the value of a conditional expression that
+    /// is not chosen, such as `b` in `1 ? a : b`, is not read.
+    ///
+    /// A name of the map that shadows a builtin constant, such as `pi`, gives
+    /// a `ShadowedConstant` error before solving.
+    ///
+    /// ```
+    /// use math_parse::{MathParse, SolvedValue};
+    /// use math_parse::MathParseErrors::*;
+    ///
+    /// let map = std::collections::HashMap::from([
+    ///     ("price".to_string(), "10".to_string()),
+    ///     ("Rate".to_string(), "0.2".to_string()),
+    /// ]);
+    /// let parsed = MathParse::parse("price * 2").unwrap();
+    /// assert_eq!(parsed.solve_value(Some(&map)), Ok(SolvedValue::Int(20)));
+    /// assert_eq!(parsed.solve_value_strict(Some(&map)), Err(UnusedVariables(vec!["Rate".to_string()])));
+    /// ```
+    pub fn solve_value_strict(&self, map: Option<&HashMap<String, String>>) -> Result<SolvedValue, MathParseErrors> {
+        let Some(map) = map else {
+            return self.solve_value(None);
+        };
+        let mut shadowing: Vec<&String> = map.keys().filter(|name| constant_value(name, &self.options).is_some()).collect();
+        shadowing.sort_unstable();
+        if let Some(name) = shadowing.first() {
+            return Err(ShadowedConstant(name.to_string()));
+        }
+
+        let read = std::cell::RefCell::new(HashSet::<String>::new());
+        let map_function = |s: &str| -> Option<String> {
+            let value = map.get(s).cloned();
+            if value.is_some() {
+                read.borrow_mut().insert(s.to_string());
+            }
+            value
+        };
+        let result = math_solve(&self.internal.to_rpn(), &map_function, &self.options)?.simplified();
+
+        let read = read.into_inner();
+        let mut unused: Vec<String> = map.keys().filter(|name| !read.contains(*name)).cloned().collect();
+        if !unused.is_empty() {
+            unused.sort_unstable();
+            return Err(UnusedVariables(unused));
+        }
+        Ok(result.into())
+    }
+
+    /// Does all the computation from a string with a line of math to the final
+    /// resulting number. If the result can be an int, return it as
+    /// `Ok(Ok(int))`. If it can only be a float, return it as `Ok(Err(floar))`.
+    /// If it can't be solved, return `Err(error)`.
+    ///
+    /// ```
+    /// # #![allow(deprecated)]
+    /// use math_parse::MathParse;
+    ///
+    /// assert_eq!(
+    ///     MathParse::parse("3 - 8.5").unwrap().solve_auto(None),
+    ///     Ok(Err(-5.5)));
+    /// ```
+    ///
+    /// A optional map of variable name can be taken as argument. To see
+    /// floats close to integers, such as `0.1*30`, as integers, use
+    /// `solve_value_with_tolerance`.
+    #[deprecated(since = "1.1.0", note = "use `solve_value` instead")]
+    pub fn solve_auto(&self, map: Option<&HashMap<String, String>>) -> Result<Result<i64, f64>, MathParseErrors> {
+        match self.solve_value(map)? {
+            SolvedValue::Int(i)   => Ok(Ok(i)),
+            SolvedValue::Float(f) => Ok(Err(f)),
         }
     }
 
@@ -122,42 +900,666 @@ impl MathParse {
     /// assert_eq!(result, 10);
     /// ```
     pub fn solve_int(&self, variable_map: Option<&HashMap<String, String>>) -> Result<i64, MathParseErrors> {
-        match self.solve_auto(variable_map)? {
-            Ok(i)  => Ok(i),
-            Err(f) => Ok(f_to_i_strict(f)?),
+        self.solve_value(variable_map)?.to_int()
+    }
+
+    /// Same as `solve_int` but the result is given as an `u64`, so it can be
+    /// used as a size or an offset. A negative result gives an
+    /// `UnexpectedNegative` error. A float result is accepted if it is a
+    /// non-negative integer, even above `i64::MAX`.
+    ///
+    /// ```
+    /// use math_parse::MathParse;
+    /// use math_parse::MathParseErrors::*;
+    ///
+    /// assert_eq!(MathParse::parse("4 * 1024").unwrap().solve_uint(None), Ok(4096));
+    /// assert_eq!(MathParse::parse("2 ** 63").unwrap().solve_uint(None), Ok(1 << 63));
+    /// assert_eq!(MathParse::parse("2 - 3").unwrap().solve_uint(None), Err(UnexpectedNegative));
+    /// ```
+    ///
+    /// Hex, octal, binary, and other literals not in base 10 are always
+    /// signed `i64`s in two's complement, so the ones above
+    /// `0x7FFFFFFFFFFFFFFF` are negative. As the result would depend on
+    /// this, an expression using such a literal, directly or through its
+    /// named variables, gives an `UnexpectedNegative` error even when its
+    /// result is positive, as for `0xFFFFFFFFFFFFFFFF // 2`. `solve_bits`
+    /// with a width of 64 gives the bits of such expressions.
+    ///
+    /// ```
+    /// use math_parse::MathParse;
+    /// use math_parse::MathParseErrors::*;
+    ///
+    /// let parsed = MathParse::parse("0xFFFFFFFFFFFFFFFF").unwrap();
+    /// assert_eq!(parsed.solve_uint(None), Err(UnexpectedNegative));
+    /// assert_eq!(parsed.solve_bits(None, 64), Ok(u64::MAX));
+    /// ```
+    ///
+    /// A optional map of variable name can be taken as argument.
+    pub fn solve_uint(&self, variable_map: Option<&HashMap<String, String>>) -> Result<u64, MathParseErrors> {
+        if self.uses_negative_radix_literal(variable_map) {
+            return Err(UnexpectedNegative);
+        }
+        self.solve_value(variable_map)?.to_uint()
+    }
+
+    /// Tell if the expression, or the value of one of its named variables,
+    /// uses a literal not in base 10 that is negative in two's complement.
+    fn uses_negative_radix_literal(&self, variable_map: Option<&HashMap<String, String>>) -> bool {
+        self.internal.to_rpn().iter().any(|action| {
+            let RPN::Name(name) = action else {
+                return false;
+            };
+            match variable_map.and_then(|map| map.get(name)) {
+                // The values of named variables are solved without the map.
+                Some(value) => MathParse::parse_with_options(value, &self.options)
+                    .is_ok_and(|parsed| parsed.uses_negative_radix_literal(None)),
+                None => solve::is_radix_literal(name) && matches!(number_from_string(name, &self.options), Ok(Number::Int(i)) if i < 0),
+            }
+        })
+    }
+
+    /// Solve the expression and return the bits of the integer result as an
+    /// unsigned number of `width` bits, which can be 8, 16, 32, or 64. The
+    /// result is truncated to its lowest `width` bits, so negative results
+    /// are seen in two's complement. Any other width gives an
+    /// `InvalidBitWidth` error and a float result gives a
+    /// `ReturnFloatExpectedInt` error.
+    ///
+    /// ```
+    /// use math_parse::MathParse;
+    ///
+    /// assert_eq!(MathParse::parse("~0").unwrap().solve_bits(None, 64), Ok(0xFFFFFFFFFFFFFFFF));
+    /// assert_eq!(MathParse::parse("-1").unwrap().solve_bits(None, 16), Ok(0xFFFF));
+    /// assert_eq!(MathParse::parse("0x1234").unwrap().solve_bits(None, 8), Ok(0x34));
+    /// ```
+    ///
+    /// A optional map of variable name can be taken as argument.
+    pub fn solve_bits(&self, variable_map: Option<&HashMap<String, String>>, width: u8) -> Result<u64, MathParseErrors> {
+        let mask = match width {
+            8 | 16 | 32 => (1 << width) - 1,
+            64 => u64::MAX,
+            _ => {
+                return Err(InvalidBitWidth(width));
+            },
+        };
+        let bits = self.solve_number(variable_map)?.to_u64_bits()?;
+        Ok(bits & mask)
+    }
+
+    /// Does all the computation from a string with a line of math to the final
+    /// resulting number. The result is returned as a `Ok(f64)`.
+    /// If it can't be solved, return `Err(error)`.
+    ///
+    /// ```
+    /// use math_parse::MathParse;
+    /// use math_parse::MathParseErrors::*;
+    ///
+    /// assert_eq!(
+    ///     MathParse::parse("3 + 8").unwrap().solve_float(None),
+    ///     Ok(11.0));
+    /// assert_eq!(
+    ///     MathParse::parse("3 - 8.5").unwrap().solve_float(None),
+    ///     Ok(-5.5));
+    /// ```
+    ///
+    /// A optional map of variable name can be taken as argument.
+    pub fn solve_float(&self, variable_map: Option<&HashMap<String, String>>) -> Result<f64, MathParseErrors> {
+        Ok(self.solve_value(variable_map)?.to_float())
+    }
+
+    /// Solve the expression and format the result with a format
+    /// specification similar to the ones of Rust's `format!`. The available
+    /// specifications are:
+    ///
+    /// * `{}`: the default format of the result.
+    /// * `{:.N}`: a float with `N` decimals.
+    /// * `{:e}`, `{:E}`, `{:.Ne}`, and `{:.NE}`: scientific notation,
+    ///   optionally with `N` decimals.
+    /// * `{:x}`, `{:X}`, `{:o}`, and `{:b}`: an integer in hexadecimal, octal,
+    ///   or binary. A `#` can be added before the letter to add the `0x`,
+    ///   `0o`, or `0b` prefix, as in `{:#x}`. A float result gives a
+    ///   `ReturnFloatExpectedInt` error.
+    ///
+    /// Any other specification gives an `InvalidFormatSpec` error.
+    ///
+    /// ```
+    /// use math_parse::MathParse;
+    /// use math_parse::MathParseErrors::*;
+    ///
+    /// let parsed = MathParse::parse("200 + 55").unwrap();
+    /// assert_eq!(parsed.solve_to_string(None, "{}"), Ok("255".to_string()));
+    /// assert_eq!(parsed.solve_to_string(None, "{:#x}"), Ok("0xff".to_string()));
+    /// assert_eq!(parsed.solve_to_string(None, "{:.2}"), Ok("255.00".to_string()));
+    /// assert_eq!(
+    ///     MathParse::parse("1 / 8").unwrap().solve_to_string(None, "{:x}"),
+    ///     Err(ReturnFloatExpectedInt(0.125)));
+    /// ```
+    ///
+    /// A optional map of variable name can be taken as argument.
+    pub fn solve_to_string(&self, variable_map: Option<&HashMap<String, String>>, spec: &str) -> Result<String, MathParseErrors> {
+        let format = FormatSpec::parse(spec)?;
+        format.apply(self.solve_value(variable_map)?)
+    }
+
+    /// Solve a list of comma-separated expressions and return the result of
+    /// each of them as a float. A single expression gives a list with a single
+    /// element.
+    ///
+    /// ```
+    /// use math_parse::MathParse;
+    ///
+    /// assert_eq!(
+    ///     MathParse::parse("(1+2, 3*4, 5/2)").unwrap().solve_list_float(None),
+    ///     Ok(vec![3.0, 12.0, 2.5]));
+    /// assert_eq!(
+    ///     MathParse::parse("7").unwrap().solve_list_float(None),
+    ///     Ok(vec![7.0]));
+    /// ```
+    ///
+    /// A optional map of variable name can be taken as argument.
+    pub fn solve_list_float(&self, variable_map: Option<&HashMap<String, String>>) -> Result<Vec<f64>, MathParseErrors> {
+        let map_function = |s: &str| -> Option<String> {
+            match variable_map {
+                None => None,
+                Some(x) => x.get(s).cloned(),
+            }
+        };
+        let results = math_solve_list(&self.internal.to_rpn(), &map_function, &self.options)?;
+        if results.is_empty() {
+            return Err(EmptyLine);
+        }
+        Ok(results.into_iter().map(Number::as_f64).collect())
+    }
+
+    /// Solve the expression and return all the numbers left on the stack,
+    /// from the bottom to the top. This is meant for expressions in Reverse
+    /// Polish notation parsed with the `multiple_rpn_results` option, which
+    /// can leave several numbers on the stack. Expressions in infix notation
+    /// give a number for each element of their list, so a single one for
+    /// expressions that are not lists.
+    ///
+    /// ```
+    /// use math_parse::{MathParse, MathParseOptions, Number};
+    ///
+    /// let options = MathParseOptions {
+    ///     multiple_rpn_results: true,
+    ///     ..MathParseOptions::default()
+    /// };
+    /// let parsed = MathParse::parse_rpn_with_options("1 2 3 +", &options).unwrap();
+    /// assert_eq!(parsed.solve_stack(None), Ok(vec![Number::Int(1), Number::Int(5)]));
+    /// ```
+    ///
+    /// A optional map of variable name can be taken as argument.
+    pub fn solve_stack(&self, variable_map: Option<&HashMap<String, String>>) -> Result<Vec<Number>, MathParseErrors> {
+        let map_function = |s: &str| -> Option<String> {
+            match variable_map {
+                None => None,
+                Some(x) => x.get(s).cloned(),
+            }
+        };
+        let results = math_solve_list(&self.internal.to_rpn(), &map_function, &self.options)?;
+        Ok(results.into_iter().map(Number::simplified).collect())
+    }
+
+    /// Solve the expression with exact fractions instead of floats, so that
+    /// `1/3*3` is exactly 1. This works when the expression only uses integers
+    /// and the operators `+`, `-`, `*`, `/`, `//`, and `%`. When a float,
+    /// another operator, or a function is used, or when a numerator or a
+    /// denominator does not fit in an `i64`, the result is
+    /// `ExactResult::Inexact`.
+    ///
+    /// ```
+    /// use math_parse::{MathParse, ExactResult};
+    ///
+    /// assert_eq!(MathParse::parse("1/3 + 1/6").unwrap().solve_exact(None), Ok(ExactResult::Ratio(1, 2)));
+    /// assert_eq!(MathParse::parse("10/5").unwrap().solve_exact(None), Ok(ExactResult::Int(2)));
+    /// assert_eq!(MathParse::parse("1.5+1").unwrap().solve_exact(None), Ok(ExactResult::Inexact));
+    /// ```
+    ///
+    /// A optional map of variable name can be taken as argument.
+    pub fn solve_exact(&self, variable_map: Option<&HashMap<String, String>>) -> Result<ExactResult, MathParseErrors> {
+        let map_function = |s: &str| -> Option<String> {
+            match variable_map {
+                None => None,
+                Some(x) => x.get(s).cloned(),
+            }
+        };
+        exact::math_solve_exact(&self.internal.to_rpn(), &map_function, &self.options)
+    }
+
+    /// Solve the expression with integers of arbitrary size, so that results
+    /// such as `2**200` don't overflow. The operations on integers are
+    /// exact as long as their results have at most 16384 bits, the shifts and
+    /// the bitwise operators still working on 64 bits. The functions `min`,
+    /// `max`, `abs`, `clamp`, `gcd`, `lcm`, `sum`, and `product` are exact
+    /// too, while the other functions need arguments that fit in an `i64`.
+    /// When a float or a bigger integer is used, the expression is solved as
+    /// with `solve_int`. The result is the `BigInt` of the num-bigint crate.
+    ///
+    /// ```
+    /// use math_parse::MathParse;
+    ///
+    /// let parsed = MathParse::parse("2**100 + 1").unwrap();
+    /// assert_eq!(parsed.solve_big(None).unwrap().to_string(), "1267650600228229401496703205377");
+    /// assert!(parsed.solve_int(None).is_err());
+    /// let factorial = MathParse::parse("product(i, 1, 25, i)").unwrap();
+    /// assert_eq!(factorial.solve_big(None).unwrap().to_string(), "15511210043330985984000000");
+    /// ```
+    ///
+    /// A optional map of variable name can be taken as argument.
+    #[cfg(feature = "bigint")]
+    pub fn solve_big(&self, variable_map: Option<&HashMap<String, String>>) -> Result<BigInt, MathParseErrors> {
+        let map_function = |s: &str| -> Option<String> {
+            match variable_map {
+                None => None,
+                Some(x) => x.get(s).cloned(),
+            }
+        };
+        match bigint::math_solve_big(&self.internal.to_rpn(), &map_function, &self.options)? {
+            Some(num) => Ok(num),
+            None => self.solve_int(variable_map).map(BigInt::from),
         }
     }
 
-    /// Does all the computation from a string with a line of math to the final
-    /// resulting number. The result is returned as a `Ok(f64)`.
-    /// If it can't be solved, return `Err(error)`.
+    /// Solve the expression with `rust_decimal` decimal numbers instead of
+    /// floats, so that `0.1 + 0.2` is exactly 0.3. Numbers written with a
+    /// decimal point and integers are decimal numbers, which have a 96 bits
+    /// mantissa and up to 28 digits after the decimal point. Results with
+    /// more digits are rounded half to even. When a number in
+    /// scientific notation, a constant, or a function is used, the
+    /// expression is solved with floats and the result is converted to a
+    /// decimal number. A result that does not fit gives a
+    /// `DecimalOutOfRange` error.
+    ///
+    /// ```
+    /// use math_parse::MathParse;
+    ///
+    /// let parsed = MathParse::parse("0.1 + 0.2").unwrap();
+    /// assert_eq!(parsed.solve_decimal(None).unwrap().to_string(), "0.3");
+    /// assert_eq!(parsed.solve_float(None), Ok(0.30000000000000004));
+    /// ```
+    ///
+    /// A optional map of variable name can be taken as argument.
+    #[cfg(feature = "decimal")]
+    pub fn solve_decimal(&self, variable_map: Option<&HashMap<String, String>>) -> Result<Decimal, MathParseErrors> {
+        let map_function = |s: &str| -> Option<String> {
+            match variable_map {
+                None => None,
+                Some(x) => x.get(s).cloned(),
+            }
+        };
+        match decimal::math_solve_decimal(&self.internal.to_rpn(), &map_function, &self.options)? {
+            Some(num) => Ok(num),
+            None => decimal::float_to_decimal(self.solve_float(variable_map)?),
+        }
+    }
+
+    /// Solve many expressions using the same map of named variables. Each
+    /// named variable is solved at most once for the whole batch and
+    /// expressions present more than once are only solved once. The results
+    /// are in the same order as the expressions and an expression that fails
+    /// does not prevent the others to be solved.
+    ///
+    /// ```
+    /// use math_parse::{MathParse, Number};
+    ///
+    /// let variables = std::collections::HashMap::from([
+    ///     ("a".to_string(), "3*3".to_string()),
+    /// ]);
+    /// let results = MathParse::solve_many(["a+1", "2*", "a/2"], Some(&variables));
+    /// assert_eq!(results[0], Ok(Number::Int(10)));
+    /// assert!(results[1].is_err());
+    /// assert_eq!(results[2], Ok(Number::Float(4.5)));
+    /// ```
+    pub fn solve_many<'a>(expressions: impl IntoIterator<Item = &'a str>, variable_map: Option<&HashMap<String, String>>) -> Vec<Result<Number, MathParseErrors>> {
+        Self::solve_many_with_options(expressions, variable_map, &MathParseOptions::default())
+    }
+
+    /// Solve many expressions as `solve_many` does, with custom options used
+    /// both to parse and to solve them.
+    ///
+    /// ```
+    /// use math_parse::{MathParse, MathParseOptions, Number};
+    ///
+    /// let options = MathParseOptions{decimal_comma: true, ..Default::default()};
+    /// let results = MathParse::solve_many_with_options(["1,5 * 2", "3"], None, &options);
+    /// assert_eq!(results, vec![Ok(Number::Int(3)), Ok(Number::Int(3))]);
+    /// ```
+    pub fn solve_many_with_options<'a>(expressions: impl IntoIterator<Item = &'a str>, variable_map: Option<&HashMap<String, String>>, options: &MathParseOptions) -> Vec<Result<Number, MathParseErrors>> {
+        let map_function = |s: &str| -> Option<String> {
+            match variable_map {
+                None => None,
+                Some(x) => x.get(s).cloned(),
+            }
+        };
+        let mut solver = Solver::new(&map_function, options);
+        let mut solved = HashMap::<&str, Result<Number, MathParseErrors>>::new();
+
+        let mut ret = vec![];
+        for expression in expressions {
+            if let Some(result) = solved.get(expression) {
+                ret.push(result.clone());
+                continue;
+            }
+            let result = match Self::parse_with_options(expression, options) {
+                Ok(parsed) => solver.solve_expression(&parsed.internal.to_rpn()).map(Number::simplified),
+                Err(err) => Err(err),
+            };
+            solved.insert(expression, result.clone());
+            ret.push(result);
+        }
+        ret
+    }
+
+    /// Solve the expression for each of the given values of the variable
+    /// `var` and return the results as floats, as `solve_float` would. The
+    /// other named variables are read from the map and solved only once for
+    /// all the values, which makes this much faster than calling
+    /// `solve_float` for each value. `var` shadows any variable with the same
+    /// name in the map.
+    ///
+    /// Values of `var` that are integers are used as integers, as if they
+    /// were written without a decimal point in the expression, so `x & 1`
+    /// works for `x = 3.0`.
+    ///
+    /// If solving fails for any value, such as `1/x` for `x = 0`, no result
+    /// is returned. Instead, a `FailedAtValue` error holds the index of the
+    /// first failing value and its error. The other named variables are
+    /// solved before any value, so their errors, such as a variable missing
+    /// from the map, are returned as they are, even when the variable is only
+    /// used in a branch of a conditional that is never taken.
+    ///
+    /// ```
+    /// use math_parse::MathParse;
+    /// use math_parse::MathParseErrors::*;
+    ///
+    /// let variables = std::collections::HashMap::from([
+    ///     ("a".to_string(), "2".to_string()),
+    /// ]);
+    /// let parsed = MathParse::parse("a*x + 1").unwrap();
+    /// assert_eq!(parsed.solve_float_over("x", &[0.0, 0.5, 3.0], Some(&variables)), Ok(vec![1.0, 2.0, 7.0]));
+    /// assert_eq!(MathParse::parse("1/x").unwrap().solve_float_over("x", &[1.0, 0.0], None), Err(FailedAtValue(1, Box::new(UnexpectedZero))));
+    /// ```
+    pub fn solve_float_over(&self, var: &str, values: &[f64], other_vars: Option<&HashMap<String, String>>) -> Result<Vec<f64>, MathParseErrors> {
+        let map_function = |s: &str| -> Option<String> {
+            match other_vars {
+                None => None,
+                Some(x) => x.get(s).cloned(),
+            }
+        };
+        let mut solver = Solver::new(&map_function, &self.options);
+        let rpn_actions = self.internal.to_rpn();
+        let mut other_names = solve::free_variables(&rpn_actions, &self.options);
+        other_names.retain(|name| name != var);
+        solver.solve_variables(&other_names)?;
+        values.iter()
+            .enumerate()
+            .map(|(index, value)| {
+                let value = Number::Float(*value).simplified();
+                solver.solve_with_binding(&rpn_actions, var, value)
+                    .map(|result| result.simplified().as_f64())
+                    .map_err(|err| FailedAtValue(index, Box::new(err)))
+            })
+            .collect()
+    }
+
+    /// Check whether the expression and `other` probably always give the
+    /// same result, by solving both of them with `trials` pseudo-random
+    /// values given to the named variables of either one. The values are
+    /// between -10 and 10, and one point out of two uses integers so that
+    /// integer-only operations are checked too. The same seed always gives
+    /// the same values.
+    ///
+    /// This is a probabilistic check: `false` means that a point where the
+    /// results differ was found, but `true` only means that none was found.
+    /// Results are compared with a relative tolerance of 1e-9, so that
+    /// rounding errors don't make formulas differ.
+    ///
+    /// A point where either expression can't be solved, such as `1/x` for
+    /// `x = 0`, is skipped and replaced by another one. If more than four
+    /// points per trial are skipped, the error of the last skipped point is
+    /// returned.
+    ///
+    /// ```
+    /// use math_parse::MathParse;
+    ///
+    /// let old = MathParse::parse("(a+b)*2").unwrap();
+    /// assert_eq!(old.probably_equivalent(&MathParse::parse("2*a+2*b").unwrap(), 20, 1), Ok(true));
+    /// assert_eq!(old.probably_equivalent(&MathParse::parse("2*a+b").unwrap(), 20, 1), Ok(false));
+    /// ```
+    pub fn probably_equivalent(&self, other: &MathParse, trials: u32, seed: u64) -> Result<bool, MathParseErrors> {
+        let mut variables = solve::free_variables(&self.internal.to_rpn(), &self.options);
+        for name in solve::free_variables(&other.internal.to_rpn(), &other.options) {
+            if !variables.contains(&name) {
+                variables.push(name);
+            }
+        }
+
+        let mut state = seed.wrapping_mul(0x9E3779B97F4A7C15) | 1;
+        let mut random = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state >> 11) as f64 / (1u64 << 53) as f64 * 20.0 - 10.0
+        };
+
+        let mut skips_left = trials.saturating_mul(4);
+        let mut trial = 0;
+        let mut use_integers = false;
+        while trial < trials {
+            use_integers = !use_integers;
+            let map: HashMap<String, String> = variables.iter()
+                .map(|name| {
+                    let value = random();
+                    let value = if use_integers { value.round() } else { value };
+                    (name.clone(), value.to_string())
+                })
+                .collect();
+            let results = self.solve_number(Some(&map))
+                .and_then(|x| Ok((x, other.solve_number(Some(&map))?)));
+            match results {
+                Ok((x, y)) => {
+                    let (x, y) = (x.as_f64(), y.as_f64());
+                    let close = x == y || (x - y).abs() <= 1e-9 * x.abs().max(y.abs()).max(1.0);
+                    if !close {
+                        return Ok(false);
+                    }
+                    trial += 1;
+                },
+                Err(err) if skips_left == 0 => return Err(err),
+                Err(_) => skips_left -= 1,
+            }
+        }
+        Ok(true)
+    }
+
+    /// Return true if the expression uses no named variables and no random
+    /// numbers, so that it always has the same result. The variables bound by
+    /// `sum` and `product` are not named variables, nor are the constants
+    /// such as `pi` unless the `disable_constants` option is set.
+    ///
+    /// ```
+    /// use math_parse::MathParse;
+    ///
+    /// assert_eq!(MathParse::parse("60*60*24").unwrap().is_constant(), true);
+    /// assert_eq!(MathParse::parse("x*1.21").unwrap().is_constant(), false);
+    /// ```
+    pub fn is_constant(&self) -> bool {
+        solve::is_constant(&self.internal.to_rpn(), &self.options)
+    }
+
+    /// Solve the expression if it is constant, as told by `is_constant`.
+    /// `None` is returned if it is not constant, while a constant expression
+    /// that can't be solved, such as `1/0`, gives its error.
+    ///
+    /// ```
+    /// use math_parse::{MathParse, Number};
+    /// use math_parse::MathParseErrors::*;
+    ///
+    /// assert_eq!(MathParse::parse("60*60*24").unwrap().as_constant(), Some(Ok(Number::Int(86400))));
+    /// assert_eq!(MathParse::parse("1/0").unwrap().as_constant(), Some(Err(UnexpectedZero)));
+    /// assert_eq!(MathParse::parse("x*1.21").unwrap().as_constant(), None);
+    /// ```
+    pub fn as_constant(&self) -> Option<Result<Number, MathParseErrors>> {
+        if self.is_constant() {
+            Some(self.solve_number(None))
+        } else {
+            None
+        }
+    }
+
+    /// List the number literals of the expression, in the order they are
+    /// used when solving it. They are read as they are when solving, so
+    /// `0x10` gives 16, and named variables are left out.
+    ///
+    /// ```
+    /// use math_parse::{MathParse, Number};
+    ///
+    /// let literals = MathParse::parse("0x10 + rate*2.5 - 3").unwrap().literals();
+    /// assert_eq!(literals, vec![Number::Int(16), Number::Float(2.5), Number::Int(3)]);
+    /// ```
+    pub fn literals(&self) -> Vec<Number> {
+        self.literals_with_spans().into_iter().map(|(num, _)| num).collect()
+    }
+
+    /// List the number literals of the expression, as `literals` does, along
+    /// with the byte range of the expression they are read from. The ranges
+    /// are only known for expressions parsed from infix notation.
+    ///
+    /// ```
+    /// use math_parse::{MathParse, Number};
+    ///
+    /// let literals = MathParse::parse("x*2 + 1.5").unwrap().literals_with_spans();
+    /// assert_eq!(literals, vec![(Number::Int(2), Some(2..3)), (Number::Float(1.5), Some(6..9))]);
+    /// ```
+    pub fn literals_with_spans(&self) -> Vec<(Number, Option<std::ops::Range<usize>>)> {
+        let spans = match &self.source {
+            Some(source) => spans::name_spans(source, &self.internal.to_rpn()).ok(),
+            None => None,
+        };
+        self.internal.to_rpn().into_iter()
+            .filter_map(|action| match action {
+                RPN::Name(name) => Some(name),
+                _ => None,
+            })
+            .enumerate()
+            .filter_map(|(index, name)| {
+                let num = solve::number_from_string(&name, &self.options).ok()?;
+                Some((num, spans.as_ref().and_then(|spans| spans.get(index).cloned())))
+            })
+            .collect()
+    }
+
+    /// List the names used by the expression, each one once, in the order of
+    /// their first use. The names are kept once in the parsed expression
+    /// however many times they are used. Number literals and the names of
+    /// called functions are names too.
+    ///
+    /// ```
+    /// use math_parse::MathParse;
+    ///
+    /// let parsed = MathParse::parse("x*x + x*3 + max(x, y)").unwrap();
+    /// assert_eq!(parsed.names(), ["x", "3", "y", "max"]);
+    /// ```
+    pub fn names(&self) -> &[String] {
+        self.internal.symbols()
+    }
+
+    /// Start solving the expression in a session that can be run a few steps
+    /// at a time, such as to spread the work of a large expression over
+    /// several frames of an application. The expression and the map of
+    /// variables are copied in the session.
+    ///
+    /// ```
+    /// use math_parse::{MathParse, EvalStatus, Number};
+    ///
+    /// let mut session = MathParse::parse("(1 + 2) * 3").unwrap().start_eval(None);
+    /// assert_eq!(session.run(2), EvalStatus::Paused);
+    /// assert_eq!(session.run(100), EvalStatus::Done(Ok(Number::Int(9))));
+    /// ```
+    pub fn start_eval(&self, map: Option<&HashMap<String, String>>) -> EvalSession {
+        EvalSession::new(&self.internal.to_rpn(), map, &self.options)
+    }
+
+    /// Same as `solve_value` but the result is given as a `Number`, which
+    /// can be used in further operations with the operators of Rust.
+    ///
+    /// ```
+    /// use math_parse::{MathParse, Number};
+    ///
+    /// let results: Vec<Number> = ["1 + 2", "1 / 4"].iter()
+    ///     .map(|expression| MathParse::parse(expression).unwrap().solve_number(None).unwrap())
+    ///     .collect();
+    /// assert_eq!(results, vec![Number::Int(3), Number::Float(0.25)]);
+    /// assert_eq!(results[0] + results[1], Number::Float(3.25));
+    /// assert_eq!(i64::try_from(results[0]), Ok(3));
+    /// ```
+    ///
+    /// A optional map of variable name can be taken as argument.
+    pub fn solve_number(&self, variable_map: Option<&HashMap<String, String>>) -> Result<solve::Number, MathParseErrors> {
+        Ok(match self.solve_value(variable_map)? {
+            SolvedValue::Int(i)   => solve::Number::Int(i),
+            SolvedValue::Float(f) => solve::Number::Float(f),
+        })
+    }
+
+    /// Solve an expression where numbers can be followed by units, such as
+    /// `3m + 20cm`. The result is given in SI base units (meters, seconds, and
+    /// kilograms) along with its dimensions. Adding or subtracting quantities
+    /// of different dimensions is an error.
     ///
     /// ```
-    /// use math_parse::MathParse;
-    /// use math_parse::MathParseErrors::*;
+    /// use math_parse::{MathParse, Dimensions};
     ///
     /// assert_eq!(
-    ///     MathParse::parse("3 + 8").unwrap().solve_float(None),
-    ///     Ok(11.0));
-    /// assert_eq!(
-    ///     MathParse::parse("3 - 8.5").unwrap().solve_float(None),
-    ///     Ok(-5.5));
+    ///     MathParse::parse("10m / 2s").unwrap().solve_quantity(None),
+    ///     Ok((5.0, Dimensions{length: 1, time: -1, mass: 0})));
     /// ```
     ///
-    /// A optional map of variable name can be taken as argument.
-    pub fn solve_float(&self, variable_map: Option<&HashMap<String, String>>) -> Result<f64, MathParseErrors> {
-        match self.solve_auto(variable_map)? {
-            Ok(i)  => Ok(i as f64),
-            Err(f) => Ok(f),
-        }
+    /// Available units are `m`, `s`, `g` (with SI prefixes from `n` to `G`),
+    /// `min`, and `h`. A optional map of variable name can be taken as
+    /// argument.
+    #[cfg(feature = "units")]
+    pub fn solve_quantity(&self, variable_map: Option<&HashMap<String, String>>) -> Result<(f64, Dimensions), MathParseErrors> {
+        let map_function = |s: &str| -> Option<String> {
+            match variable_map {
+                None => None,
+                Some(x) => x.get(s).cloned(),
+            }
+        };
+        units::math_solve_quantity(&self.internal.to_rpn(), &map_function, &self.options)
     }
 
-    /// Solve the result as a number, for internal use.
-    fn solve_number(&self, variable_map: Option<&HashMap<String, String>>) -> Result<solve::Number, MathParseErrors> {
-        Ok(match self.solve_auto(variable_map)? {
-            Ok(i)  => solve::Number::Int(i),
-            Err(f) => solve::Number::Float(f),
-        })
+    /// Solve an expression where numbers can be followed by the duration
+    /// units `ms`, `s`, `m`, `h`, and `d`, such as `5m + 30s`. The result is
+    /// given along with the exponent of its time dimension: durations have an
+    /// exponent of 1 and are given in milliseconds, while plain numbers, such
+    /// as the ratio of two durations, have an exponent of 0.
+    ///
+    /// ```
+    /// use math_parse::{MathParse, Number};
+    ///
+    /// assert_eq!(
+    ///     MathParse::parse("5m + 30s").unwrap().solve_duration(None),
+    ///     Ok((Number::Int(330_000), 1)));
+    /// assert_eq!(
+    ///     MathParse::parse("2h / 15m").unwrap().solve_duration(None),
+    ///     Ok((Number::Int(8), 0)));
+    /// ```
+    ///
+    /// Adding or subtracting a duration and a plain number gives an
+    /// `IncompatibleUnits` error, unless the `lenient_durations` option is
+    /// set, in which case the plain number is in seconds. A optional map of
+    /// variable name can be taken as argument.
+    #[cfg(feature = "durations")]
+    pub fn solve_duration(&self, variable_map: Option<&HashMap<String, String>>) -> Result<(Number, i8), MathParseErrors> {
+        let map_function = |s: &str| -> Option<String> {
+            match variable_map {
+                None => None,
+                Some(x) => x.get(s).cloned(),
+            }
+        };
+        durations::math_solve_duration(&self.internal.to_rpn(), &map_function, &self.options)
     }
 }
 
@@ -176,11 +1578,90 @@ pub fn contains_math_char(s: &str) -> bool {
     tokenize::contains_math_char(s)
 }
 
+/// Parse an expression and solve it to an integer in a single call, as
+/// `MathParse::parse` followed by `solve_int` would.
+///
+/// ```
+/// use math_parse::eval_int;
+/// assert_eq!(eval_int("3*4+1"), Ok(13));
+/// assert!(eval_int("3*").is_err());
+/// ```
+pub fn eval_int(expression: &str) -> Result<i64, MathParseErrors> {
+    MathParse::parse(expression)?.solve_int(None)
+}
+
+/// Parse an expression and solve it to a float in a single call, as
+/// `MathParse::parse` followed by `solve_float` would.
+///
+/// ```
+/// use math_parse::eval_float;
+/// assert_eq!(eval_float("5/8+6"), Ok(6.625));
+/// ```
+pub fn eval_float(expression: &str) -> Result<f64, MathParseErrors> {
+    MathParse::parse(expression)?.solve_float(None)
+}
+
+/// Same as `eval_int` but the named variables are read from the map.
+///
+/// ```
+/// use math_parse::eval_int_with;
+/// let variables = std::collections::HashMap::from([
+///     ("a".to_string(), "41".to_string()),
+/// ]);
+/// assert_eq!(eval_int_with("a+1", &variables), Ok(42));
+/// ```
+pub fn eval_int_with(expression: &str, variable_map: &HashMap<String, String>) -> Result<i64, MathParseErrors> {
+    MathParse::parse(expression)?.solve_int(Some(variable_map))
+}
+
+/// Same as `eval_float` but the named variables are read from the map.
+pub fn eval_float_with(expression: &str, variable_map: &HashMap<String, String>) -> Result<f64, MathParseErrors> {
+    MathParse::parse(expression)?.solve_float(Some(variable_map))
+}
+
+/// Read a number literal, such as `12`, `0x1F`, `0b101`, `1.5e-3`, or `'a'`,
+/// the same way numbers are read when solving expressions. Whitespace around
+/// the literal is ignored.
+///
+/// ```
+/// use math_parse::{parse_number_literal, Number, MathParseErrors};
+/// assert_eq!(parse_number_literal("0x1F"), Ok(Number::Int(31)));
+/// assert_eq!(parse_number_literal(" 2.5 "), Ok(Number::Float(2.5)));
+/// assert_eq!(parse_number_literal("1+2"), Err(MathParseErrors::InvalidNumber("1+2".to_string())));
+/// ```
+pub fn parse_number_literal(s: &str) -> Result<Number, MathParseErrors> {
+    use tokenize::MathValue;
+    let options = MathParseOptions::default();
+    let (tokens, _) = tokenize::math_token_checked(s, &options, &[])?;
+    match tokens.as_slice() {
+        [MathValue::Name(name), MathValue::TrailingError] => number_from_string(name.trim(), &options),
+        [MathValue::TrailingError] => Err(EmptyLine),
+        _ => Err(InvalidNumber(s.to_string())),
+    }
+}
+
+/// Return true if the string is a number literal that can be read by
+/// [`parse_number_literal`].
+///
+/// ```
+/// use math_parse::is_number_literal;
+/// assert_eq!(is_number_literal("0x1F"), true);
+/// assert_eq!(is_number_literal("x"), false);
+/// ```
+pub fn is_number_literal(s: &str) -> bool {
+    parse_number_literal(s).is_ok()
+}
+
 /* --------------------------------- Errors --------------------------------- */
 
+/// Operator suggested by `MathParseErrors::BadOperatorHint`. Named so that
+/// serde does not read it as borrowed from the deserialized data.
+type OperatorHint = &'static str;
+
 /// Type used to represent any errors that can happen in the parsing of a math
 /// expression.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MathParseErrors {
     /// A parenthesis was opened but never closed.
     UnclosedParenthesis,
@@ -203,16 +1684,19 @@ pub enum MathParseErrors {
     TrailingOperator,
 
     /// A float could not be converted to an int.
-    IntConversion(f64),
+    IntConversion(#[cfg_attr(feature = "serde", serde(with = "serialization::float"))] f64),
 
     /// A binary operation have been tried on a float.
-    BinaryOpOnFloat(f64, char),
+    BinaryOpOnFloat(#[cfg_attr(feature = "serde", serde(with = "serialization::float"))] f64, char),
+
+    /// A bitwise function, such as `popcount`, have been called with a float.
+    FunctionOnFloat(#[cfg_attr(feature = "serde", serde(with = "serialization::float"))] f64, String),
 
     /// We wanted to return an int but we got a float instead.
-    ReturnFloatExpectedInt(f64),
+    ReturnFloatExpectedInt(#[cfg_attr(feature = "serde", serde(with = "serialization::float"))] f64),
 
     /// A given operator was invalid, but we can suggest an other instead.
-    BadOperatorHint(char, &'static str),
+    BadOperatorHint(char, #[cfg_attr(feature = "serde", serde(with = "serialization::operator_hint"))] OperatorHint),
 
     /// There was an unwanted zero.
     UnexpectedZero,
@@ -226,6 +1710,93 @@ pub enum MathParseErrors {
     /// The number of elements on the RPN stack is not valid.
     UnbalancedStack,
 
+    /// Two quantities with different units were added or subtracted.
+    IncompatibleUnits,
+
+    /// A function that does not exist was called.
+    UnknownFunction(String),
+
+    /// A function was called with the wrong number of arguments or with
+    /// arguments of the wrong kind.
+    InvalidFunctionCall(String),
+
+    /// Solving the expression needed more operations than the limit set in the
+    /// options.
+    TooManyOperations(usize),
+
+    /// A string could not be read as an operator.
+    UnknownOperator(String),
+
+    /// An expression looks like a number but is not written correctly. The
+    /// kind of error tells what is wrong with it.
+    MalformedNumber(String, NumberErrorKind),
+
+    /// A character that can't be used in names. The `usize` is the byte
+    /// position of the character in the expression.
+    InvalidCharacter(char, usize),
+
+    /// A format specification given to `MathParse::solve_to_string` is not
+    /// valid.
+    InvalidFormatSpec(String),
+
+    /// The `rand` or `randint` function was called but there is no random
+    /// source in the options.
+    NoRandomSource,
+
+    /// The number of bits asked to `MathParse::solve_bits` is not 8, 16, 32,
+    /// or 64.
+    InvalidBitWidth(u8),
+
+    /// The variable given to `Equation::solve_for` can't be isolated because
+    /// the equation is not linear or does not use it exactly once.
+    CannotIsolateVariable(String),
+
+    /// A word that has a special meaning is not where it should be, such as
+    /// `of` when the `percent_of` option is set.
+    MisplacedWord(String),
+
+    /// The argument given to the function is out of its domain, such as
+    /// `asin(2)`.
+    DomainError(String),
+
+    /// A formula given to `solve_system` uses itself, directly or through
+    /// other formulas.
+    CyclicDefinition(String),
+
+    /// Names of the map given to `MathParse::solve_value_strict` that were
+    /// not used when solving the expression.
+    UnusedVariables(Vec<String>),
+
+    /// A name of the map given to `MathParse::solve_value_strict` is also
+    /// the name of a builtin constant.
+    ShadowedConstant(String),
+
+    /// The token rewriter of the options rewrote a word, given first, into
+    /// something that is not a single name or operator, given second.
+    InvalidRewrite(String, String),
+
+    /// The integer result of the function is too big to be stored in an
+    /// `i64`, such as the result of `lcm` for large arguments.
+    FunctionOverflow(String),
+
+    /// The result of an operation on two integers, given with the symbol of
+    /// the operation, does not fit in an `i64`. This is only raised with the
+    /// `integer_overflow_errors` option, as the result is a float otherwise.
+    IntegerOverflow(i64, char, i64),
+
+    /// A number does not fit in a decimal number or has too many digits
+    /// after the decimal point, or the result of an operation on decimal
+    /// numbers does not fit in one.
+    DecimalOutOfRange(String),
+
+    /// The exponent of a unit of a quantity does not fit in an `i8`, as in
+    /// `1m ** 200`.
+    DimensionOverflow,
+
+    /// Solving with `MathParse::solve_float_over` failed for the value at
+    /// the given index of the slice of values, with the given error.
+    FailedAtValue(usize, Box<MathParseErrors>),
+
     /// This error should never be raised and should be reported to the
     /// library's maintainer.
     MathParseInternalBug(String),
@@ -247,35 +1818,424 @@ impl fmt::Display for MathParseErrors {
             TrailingOperator => write!(f, "An operator is the last element of a line of math."),
             IntConversion(fp) => write!(f, "The floating point number {fp} could not be converted to an int which is needed."),
             BinaryOpOnFloat(fp, c) => write!(f, "The bitwise operation `{c}` is being performed on the floating point number `{fp}`."),
+            FunctionOnFloat(fp, s) => write!(f, "The bitwise function `{s}` is being called with the floating point number `{fp}`."),
             ReturnFloatExpectedInt(fp) => write!(f, "An integer was wanted but the floating point number `{fp}` was returned instead."),
             BadOperatorHint(c, s) => write!(f, "The operator '{c}' is invalid. Did you meant '{s}'?"),
             UnexpectedZero => write!(f, "There is a 0 in an operation where it is invalid such as a division or a remainder."),
             UnexpectedNegative => write!(f, "There is a negative number in an operation where it is invalid such as a logical shift."),
             InvalidRPNOperator(c) => write!(f, "The operators {c} is not valid when parsing RPN expressions."),
             UnbalancedStack => write!(f, "The RPN stack does not contains a valid number of elements. There is too much or not enough operators."),
+            IncompatibleUnits => write!(f, "Two quantities with incompatible units are used together."),
+            UnknownFunction(s) => write!(f, "The function `{s}` does not exist."),
+            InvalidFunctionCall(s) => write!(f, "The function `{s}` is called with invalid arguments."),
+            TooManyOperations(n) => write!(f, "Solving the expression needs more than {n} operations."),
+            UnknownOperator(s) => write!(f, "`{s}` is not a valid operator."),
+            MalformedNumber(s, kind) => write!(f, "The number `{s}` is malformed: {kind}."),
+            InvalidCharacter(c, p) if c.is_control() || tokenize::is_invisible(*c) => {
+                write!(f, "The character U+{:04X} at position {p} can't be used in a name.", *c as u32)
+            },
+            InvalidCharacter(c, p) => write!(f, "The character `{}` at position {p} can't be used in a name.", c.escape_debug()),
+            InvalidFormatSpec(s) => write!(f, "`{s}` is not a valid format specification."),
+            NoRandomSource => write!(f, "Random numbers are used but no random source is given in the options."),
+            InvalidBitWidth(w) => write!(f, "Results can't be given on {w} bits, only 8, 16, 32, or 64 bits can be used."),
+            CannotIsolateVariable(s) => write!(f, "The variable `{s}` can't be isolated in the equation."),
+            MisplacedWord(s) => write!(f, "The word `{s}` is not where it should be."),
+            DomainError(s) => write!(f, "The argument of `{s}` is out of its domain."),
+            CyclicDefinition(s) => write!(f, "The definition of `{s}` depends on itself."),
+            UnusedVariables(names) => write!(f, "The named variables `{}` are not used.", names.join("`, `")),
+            ShadowedConstant(s) => write!(f, "The named variable `{s}` shadows a builtin constant."),
+            FunctionOverflow(s) => write!(f, "The result of `{s}` is too big to be an integer."),
+            IntegerOverflow(a, op, b) => write!(f, "The result of `{a} {op} {b}` is too big to be an integer."),
+            DecimalOutOfRange(s) => write!(f, "`{s}` can't be represented as a decimal number."),
+            DimensionOverflow => write!(f, "The exponent of a unit is not between -128 and 127."),
+            FailedAtValue(i, err) => write!(f, "Solving for the value at index {i} failed: {err}"),
+            InvalidRewrite(word, rewrite) => write!(f, "The word `{word}` is rewritten as `{rewrite}`, which is not a single name or operator."),
             MathParseInternalBug(s) => write!(f, "There is a bug in the math-parse library. The error message is the following:\n{s}\nPlease, report it with the input given to the library to the developer of math-parse over here: https://github.com/Arkaeriit/math-parse"),
         }
     }
 }
 
+/// Reasons for a number to be malformed.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NumberErrorKind {
+    /// A number in scientific notation has no digits after its exponent
+    /// marker, such as `2e`.
+    MissingExponentDigits,
+
+    /// An angle in degrees, minutes, and seconds is not written as `12°30'45"`
+    /// or its minutes or seconds are not lower than 60.
+    MalformedAngle,
+
+    /// A number has more than one decimal dot, such as `1.2.3`.
+    MultipleDots,
+
+    /// A number contains a character that can't be in it, with its byte
+    /// position in the number, such as the `a` of `12a`.
+    UnexpectedChar(char, usize),
+}
+
+impl NumberErrorKind {
+    /// Stable identifier of the kind of error.
+    pub fn code(&self) -> &'static str {
+        match self {
+            NumberErrorKind::MissingExponentDigits => "missing_exponent_digits",
+            NumberErrorKind::MalformedAngle => "malformed_angle",
+            NumberErrorKind::MultipleDots => "multiple_dots",
+            NumberErrorKind::UnexpectedChar(_, _) => "unexpected_char",
+        }
+    }
+}
+
+impl fmt::Display for NumberErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NumberErrorKind::MissingExponentDigits => write!(f, "scientific notation needs digits after the exponent, as in `2e3`"),
+            NumberErrorKind::MalformedAngle => write!(f, "angles are written as `12°30'45\"`, with minutes and seconds lower than 60"),
+            NumberErrorKind::MultipleDots => write!(f, "a number can only have one decimal dot"),
+            NumberErrorKind::UnexpectedChar(c, p) => write!(f, "the character `{c}` at position {p} can't be in a number"),
+        }
+    }
+}
+
+impl MathParseErrors {
+    /// Split the error into a stable identifier and its dynamic arguments, as
+    /// strings. This is meant to be used to translate error messages, the
+    /// `Display` implementation giving the English ones.
+    ///
+    /// ```
+    /// use math_parse::MathParseErrors::*;
+    ///
+    /// assert_eq!(
+    ///     BinaryOpOnFloat(2.5, '&').message_parts(),
+    ///     ("binary_op_on_float", vec!["2.5".to_string(), "&".to_string()]));
+    /// ```
+    pub fn message_parts(&self) -> (&'static str, Vec<String>) {
+        match self {
+            UnclosedParenthesis => ("unclosed_parenthesis", vec![]),
+            UnopenedParenthesis => ("unopened_parenthesis", vec![]),
+            EmptyLine => ("empty_line", vec![]),
+            InvalidNumber(s) => ("invalid_number", vec![s.clone()]),
+            MisplacedOperator(c) => ("misplaced_operator", vec![c.to_string()]),
+            TrailingOperator => ("trailing_operator", vec![]),
+            IntConversion(fp) => ("int_conversion", vec![fp.to_string()]),
+            BinaryOpOnFloat(fp, c) => ("binary_op_on_float", vec![fp.to_string(), c.to_string()]),
+            FunctionOnFloat(fp, s) => ("function_on_float", vec![fp.to_string(), s.clone()]),
+            ReturnFloatExpectedInt(fp) => ("return_float_expected_int", vec![fp.to_string()]),
+            BadOperatorHint(c, s) => ("bad_operator_hint", vec![c.to_string(), s.to_string()]),
+            UnexpectedZero => ("unexpected_zero", vec![]),
+            UnexpectedNegative => ("unexpected_negative", vec![]),
+            InvalidRPNOperator(c) => ("invalid_rpn_operator", vec![c.to_string()]),
+            UnbalancedStack => ("unbalanced_stack", vec![]),
+            IncompatibleUnits => ("incompatible_units", vec![]),
+            UnknownFunction(s) => ("unknown_function", vec![s.clone()]),
+            InvalidFunctionCall(s) => ("invalid_function_call", vec![s.clone()]),
+            TooManyOperations(n) => ("too_many_operations", vec![n.to_string()]),
+            UnknownOperator(s) => ("unknown_operator", vec![s.clone()]),
+            MalformedNumber(s, kind) => ("malformed_number", vec![s.clone(), kind.code().to_string()]),
+            InvalidCharacter(c, p) => ("invalid_character", vec![c.to_string(), p.to_string()]),
+            InvalidFormatSpec(s) => ("invalid_format_spec", vec![s.clone()]),
+            NoRandomSource => ("no_random_source", vec![]),
+            InvalidBitWidth(w) => ("invalid_bit_width", vec![w.to_string()]),
+            CannotIsolateVariable(s) => ("cannot_isolate_variable", vec![s.clone()]),
+            MisplacedWord(s) => ("misplaced_word", vec![s.clone()]),
+            DomainError(s) => ("domain_error", vec![s.clone()]),
+            CyclicDefinition(s) => ("cyclic_definition", vec![s.clone()]),
+            UnusedVariables(names) => ("unused_variables", names.clone()),
+            ShadowedConstant(s) => ("shadowed_constant", vec![s.clone()]),
+            FunctionOverflow(s) => ("function_overflow", vec![s.clone()]),
+            IntegerOverflow(a, op, b) => ("integer_overflow", vec![a.to_string(), op.to_string(), b.to_string()]),
+            DecimalOutOfRange(s) => ("decimal_out_of_range", vec![s.clone()]),
+            DimensionOverflow => ("dimension_overflow", vec![]),
+            FailedAtValue(i, err) => {
+                let (code, args) = err.message_parts();
+                ("failed_at_value", [i.to_string(), code.to_string()].into_iter().chain(args).collect())
+            },
+            InvalidRewrite(word, rewrite) => ("invalid_rewrite", vec![word.clone(), rewrite.clone()]),
+            MathParseInternalBug(s) => ("math_parse_internal_bug", vec![s.clone()]),
+        }
+    }
+}
+
+/// An error along with the byte range of the expression it is about, when it
+/// is known.
+#[derive(Debug, PartialEq, Clone)]
+pub struct PositionedError {
+    pub error: MathParseErrors,
+    pub span: Option<std::ops::Range<usize>>,
+}
+
+impl From<MathParseErrors> for PositionedError {
+    fn from(error: MathParseErrors) -> Self {
+        let span = match error {
+            InvalidCharacter(c, p) => Some(p..p+c.len_utf8()),
+            _ => None,
+        };
+        PositionedError{error, span}
+    }
+}
+
+impl fmt::Display for PositionedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.error)
+    }
+}
+
+/* -------------------------------- Warnings -------------------------------- */
+
+/// Situations that are not errors but might not be what was intended. They
+/// can be obtained with `MathParse::parse_with_warnings` and the
+/// `solve_*_with_warnings` methods.
+#[derive(Debug, PartialEq, Clone)]
+pub enum MathParseWarning {
+    /// An integer and a float are used together in a binary operation, the
+    /// integer being converted to a float.
+    MixedNumberTypes(BinaryOp),
+
+    /// An integer too big to be exactly represented as a float was converted
+    /// to a float.
+    PrecisionLoss(i64),
+
+    /// An operator that is often mistaken for another one is used, such as `^`
+    /// which is a bitwise xor and not a power. The range is the position of
+    /// the operator in the expression, in bytes.
+    ConfusingOperator(char, std::ops::Range<usize>),
+
+    /// A named variable of the map is used instead of the builtin constant
+    /// with the same name, such as `pi`.
+    ShadowedConstant(String),
+}
+
+impl fmt::Display for MathParseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MathParseWarning::MixedNumberTypes(op) => write!(f, "An integer is converted to a float to be used with `{op}`."),
+            MathParseWarning::PrecisionLoss(i) => write!(f, "The integer {i} can't be exactly represented as a float."),
+            MathParseWarning::ConfusingOperator('^', span) => write!(f, "The operator `^` at position {} is a bitwise xor, not a power.", span.start),
+            MathParseWarning::ConfusingOperator(c, span) => write!(f, "The operator `{c}` at position {} might not do what is expected.", span.start),
+            MathParseWarning::ShadowedConstant(s) => write!(f, "The named variable `{s}` is used instead of the builtin constant."),
+        }
+    }
+}
+
+/// Fixes done by `MathParse::parse_lenient` to make an expression that is
+/// being typed complete. Positions are in bytes.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum LenientFix {
+    /// A binary operator at the given range, at the end of the expression,
+    /// was ignored.
+    IgnoredTrailingOperator(std::ops::Range<usize>),
+
+    /// The parenthesis opened at the given position was not closed, it was
+    /// closed at the end of the expression.
+    ClosedParenthesis(usize),
+}
+
+impl fmt::Display for LenientFix {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LenientFix::IgnoredTrailingOperator(span) => write!(f, "The operator at position {} at the end of the expression is ignored.", span.start),
+            LenientFix::ClosedParenthesis(p) => write!(f, "The parenthesis at position {p} is closed at the end of the expression."),
+        }
+    }
+}
+
 /* ------------------------------- Operations ------------------------------- */
 
+/// An unary or a binary operator.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum OpKind {
+    Unary(UnaryOp),
+    Binary(BinaryOp),
+}
+
+/// Description of an operator, as given by `supported_operators`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct OperatorInfo {
+    pub op: OpKind,
+    /// The ways to write the operator in an expression, the first one being
+    /// its canonical ASCII spelling.
+    pub symbols: Vec<&'static str>,
+    /// Operators with a higher precedence are applied first.
+    pub precedence: u8,
+    /// A short description of the operation.
+    pub description: &'static str,
+}
+
+/// Every operator with the ways to write it, its precedence, and its
+/// description. This is the table used to read operators when parsing. The
+/// operators made of two characters, such as `//`, are tokenized as the
+/// single character that is also in their symbols, such as `⟌`.
+const OPERATORS: [(OpKind, &[&str], u8, &str); 26] = [
+    (OpKind::Unary(Not),               &["~"],                     12, "Bitwise not."),
+    (OpKind::Unary(LogicalNot),        &["!"],                     12, "1 if the value is 0, 0 otherwise."),
+    (OpKind::Unary(Minus),             &["-", "−"],                12, "Negation."),
+    (OpKind::Unary(Plus),              &["+"],                     12, "No effect."),
+    (OpKind::Unary(Percent),           &["%"],                     12, "Percentage, written after the value to divide by 100."),
+    (OpKind::Unary(Sqrt),              &["√"],                     12, "Square root."),
+    (OpKind::Binary(Exponent),         &["**", "↑"],               11, "Exponentiation, applied from right to left."),
+    (OpKind::Binary(Multiplication),   &["*", "×", "·"],           10, "Multiplication."),
+    (OpKind::Binary(Division),         &["/", "∕", "⁄", "÷"],      10, "Division."),
+    (OpKind::Binary(IntegerDivision),  &["//", "⟌"],               10, "Integer division, rounded toward 0."),
+    (OpKind::Binary(Reminder),         &["%"],                     10, "Remainder of the integer division."),
+    (OpKind::Binary(Addition),         &["+"],                     9, "Addition."),
+    (OpKind::Binary(Subtraction),      &["-", "−"],                9, "Subtraction."),
+    (OpKind::Binary(ShiftLeft),        &["<<", "≪"],               8, "Logical shift to the left."),
+    (OpKind::Binary(ShiftRight),       &[">>", "≫"],               8, "Arithmetic shift to the right, which keeps the sign."),
+    (OpKind::Binary(LessThan),         &["<"],                     7, "1 if the left hand side is less than the right hand side, 0 otherwise."),
+    (OpKind::Binary(LessOrEqual),      &["<=", "≤"],               7, "1 if the left hand side is less than or equal to the right hand side, 0 otherwise."),
+    (OpKind::Binary(GreaterThan),      &[">"],                     7, "1 if the left hand side is greater than the right hand side, 0 otherwise."),
+    (OpKind::Binary(GreaterOrEqual),   &[">=", "≥"],               7, "1 if the left hand side is greater than or equal to the right hand side, 0 otherwise."),
+    (OpKind::Binary(Equal),            &["==", "⩵"],               6, "1 if both sides are equal, 0 otherwise."),
+    (OpKind::Binary(NotEqual),         &["!=", "≠"],               6, "1 if both sides are different, 0 otherwise."),
+    (OpKind::Binary(BitwiseAnd),       &["&"],                     5, "Bitwise and."),
+    (OpKind::Binary(BitwiseXor),       &["^", "⊕"],                4, "Bitwise xor."),
+    (OpKind::Binary(BitwiseOr),        &["|"],                     3, "Bitwise or."),
+    (OpKind::Binary(LogicalAnd),       &["&&", "∧"],               2, "1 if both sides are not 0, 0 otherwise."),
+    (OpKind::Binary(LogicalOr),        &["||", "∨"],               1, "1 if any side is not 0, 0 otherwise."),
+];
+
+/// Lowest and highest precedence of the binary operators.
+pub(crate) const BINARY_PRECEDENCES: std::ops::RangeInclusive<u8> = 1..=11;
+
+/// Find the unary or binary operator written with the given symbol.
+fn find_operator(symbol: &str, binary: bool) -> Option<OpKind> {
+    OPERATORS.iter()
+        .find(|(op, symbols, _, _)| matches!(op, OpKind::Binary(_)) == binary && symbols.contains(&symbol))
+        .map(|(op, _, _, _)| *op)
+}
+
+/// Return the precedence of an operator from the table of operators.
+fn operator_precedence(op: OpKind) -> u8 {
+    OPERATORS.iter()
+        .find(|(entry_op, _, _, _)| *entry_op == op)
+        .map_or(0, |(_, _, precedence, _)| *precedence)
+}
+
+/// Return the precedence of the binary operator tokenized as the given
+/// character. The characters that are not valid operators but that are
+/// hinted as the start of one, such as `<`, have the precedence of the
+/// hinted operator so that the error can be reported. As this is used a lot
+/// while parsing, the precedence of each character is taken from the table
+/// of operators only once.
+pub(crate) fn binary_char_precedence(c: char) -> Option<u8> {
+    static PRECEDENCES: std::sync::OnceLock<Vec<(char, u8)>> = std::sync::OnceLock::new();
+    let precedences = PRECEDENCES.get_or_init(|| {
+        let mut ret = vec![('=', Equal.precedence())];
+        for (op, symbols, precedence, _) in &OPERATORS {
+            if let OpKind::Binary(_) = op {
+                for symbol in symbols.iter() {
+                    let mut chars = symbol.chars();
+                    if let (Some(c), None) = (chars.next(), chars.next()) {
+                        ret.push((c, *precedence));
+                    }
+                }
+            }
+        }
+        ret
+    });
+    precedences.iter()
+        .find(|(op_char, _)| *op_char == c)
+        .map(|(_, precedence)| *precedence)
+}
+
+/// Return the description of every supported operator, to build help
+/// screens. The unary operators come first.
+///
+/// ```
+/// use math_parse::{supported_operators, OpKind, BinaryOp};
+///
+/// let operators = supported_operators();
+/// let addition = operators.iter().find(|info| info.op == OpKind::Binary(BinaryOp::Addition)).unwrap();
+/// assert_eq!(addition.symbols, vec!["+"]);
+/// assert!(addition.precedence < BinaryOp::Multiplication.precedence());
+/// ```
+pub fn supported_operators() -> Vec<OperatorInfo> {
+    OPERATORS.iter()
+        .map(|(op, symbols, precedence, description)| OperatorInfo{
+            op: *op,
+            symbols: symbols.to_vec(),
+            precedence: *precedence,
+            description,
+        })
+        .collect()
+}
+
 /// Available unary operations.
-#[derive(Debug, PartialEq, Copy, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
 pub enum UnaryOp {
+    /// Bitwise complement, only on integers.
     Not,
+    /// 1 for 0 and 0 for any other value, floats included.
+    LogicalNot,
     Minus,
-    Plus
+    Plus,
+    /// Written after its operand, as in `50%`.
+    Percent,
+    Sqrt,
 }
 use crate::UnaryOp::*;
 
 impl UnaryOp {
     fn from_char(c: char) -> Result<Self, MathParseErrors> {
-        match c {
-            '!' => Ok(Not),
-            '-' => Ok(Minus),
-            '+' => Ok(Plus),
-            x   => Err(MathParseInternalBug(format!("{x} is not a valid unary operator."))),
+        match find_operator(c.encode_utf8(&mut [0; 4]), false) {
+            Some(OpKind::Unary(op)) => Ok(op),
+            _ => Err(UnknownOperator(c.to_string())),
+        }
+    }
+
+    /// Return the precedence of the operator. Operators with a higher
+    /// precedence are applied first.
+    pub fn precedence(self) -> u8 {
+        operator_precedence(OpKind::Unary(self))
+    }
+
+    /// Apply the operation on a number, exactly as it is done when solving an
+    /// expression.
+    ///
+    /// ```
+    /// use math_parse::{UnaryOp, Number};
+    ///
+    /// assert_eq!(UnaryOp::Minus.apply(Number::Int(3)), Ok(Number::Int(-3)));
+    /// assert!(UnaryOp::Not.apply(Number::Float(1.5)).is_err());
+    /// assert_eq!(UnaryOp::LogicalNot.apply(Number::Float(1.5)), Ok(Number::Int(0)));
+    /// ```
+    pub fn apply(self, value: Number) -> Result<Number, MathParseErrors> {
+        solve::compute_unary(value, self)
+    }
+
+    /// Return the canonical ASCII spelling of the operator.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Not        => "~",
+            LogicalNot => "!",
+            Minus      => "-",
+            Plus       => "+",
+            Percent    => "%",
+            Sqrt       => "√",
+        }
+    }
+}
+
+impl std::str::FromStr for UnaryOp {
+    type Err = MathParseErrors;
+
+    /// Reads an unary operator. Both the ASCII and the Unicode forms used in
+    /// expressions are accepted, as well as `not`.
+    ///
+    /// ```
+    /// use math_parse::UnaryOp;
+    ///
+    /// assert_eq!("not".parse::<UnaryOp>(), Ok(UnaryOp::Not));
+    /// assert_eq!("−".parse::<UnaryOp>(), Ok(UnaryOp::Minus));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "not" {
+            return Ok(Not);
+        }
+        match find_operator(s, false) {
+            Some(OpKind::Unary(op)) => Ok(op),
+            _ => Err(UnknownOperator(s.to_string())),
         }
     }
 }
@@ -283,16 +2243,20 @@ impl UnaryOp {
 impl fmt::Display for UnaryOp {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            Not   => write!(f, "!"),
-            Minus => write!(f, "-"),
-            Plus  => write!(f, "+"),
+            Not        => write!(f, "~"),
+            LogicalNot => write!(f, "!"),
+            Minus      => write!(f, "-"),
+            Plus       => write!(f, "+"),
+            Percent    => write!(f, "%"),
+            Sqrt       => write!(f, "√"),
         }
     }
 }
 
 /// Available binary operations.
-#[derive(Debug, PartialEq, Copy, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
 pub enum BinaryOp {
+    Exponent,
     Multiplication,
     Division,
     IntegerDivision,
@@ -301,29 +2265,105 @@ pub enum BinaryOp {
     Subtraction,
     ShiftLeft,
     ShiftRight,
+    LessThan,
+    LessOrEqual,
+    GreaterThan,
+    GreaterOrEqual,
+    Equal,
+    NotEqual,
     BitwiseAnd,
     BitwiseOr,
     BitwiseXor,
+    LogicalAnd,
+    LogicalOr,
 }
 use crate::BinaryOp::*;
 
 impl BinaryOp {
     fn from_char(c: char) -> Result<Self, MathParseErrors> {
-        match c {
-            '*' | '×' | '·'       => Ok(Multiplication),
-            '/' | '∕' | '⁄' | '÷' => Ok(Division),
-            '+'                   => Ok(Addition),
-            '-' | '−'             => Ok(Subtraction),
-            '%'                   => Ok(Reminder),
-            '⟌'                   => Ok(IntegerDivision),
-            '|'                   => Ok(BitwiseOr),
-            '&'                   => Ok(BitwiseAnd),
-            '^'                   => Ok(BitwiseXor),
-            '≪'                   => Ok(ShiftLeft),
-            '≫'                   => Ok(ShiftRight),
-            '<'                   => Err(BadOperatorHint('<', "<<")),
-            '>'                   => Err(BadOperatorHint('>', ">>")),
-            x                     => Err(MathParseInternalBug(format!("{x} is not a valid operator."))),
+        match (c, find_operator(c.encode_utf8(&mut [0; 4]), true)) {
+            (_, Some(OpKind::Binary(op))) => Ok(op),
+            ('=', _) => Err(BadOperatorHint('=', "==")),
+            (x, _)   => Err(UnknownOperator(x.to_string())),
+        }
+    }
+
+    /// Return the precedence of the operator. Operators with a higher
+    /// precedence are applied first, and operators with the same precedence
+    /// are applied from left to right, except for the right associative ones.
+    pub fn precedence(self) -> u8 {
+        operator_precedence(OpKind::Binary(self))
+    }
+
+    /// Return true if the operator is applied from right to left when it is
+    /// chained, as the exponentiation, so that `2**3**2` is `2**(3**2)`.
+    pub fn is_right_associative(self) -> bool {
+        self == Exponent
+    }
+
+    /// Apply the operation on two numbers, exactly as it is done when solving
+    /// an expression. This includes the errors, such as dividing by zero or
+    /// using bitwise operations on floats.
+    ///
+    /// ```
+    /// use math_parse::{BinaryOp, Number};
+    /// use math_parse::MathParseErrors::*;
+    ///
+    /// assert_eq!(BinaryOp::Division.apply(Number::Int(3), Number::Int(2)), Ok(Number::Float(1.5)));
+    /// assert_eq!(BinaryOp::Division.apply(Number::Int(3), Number::Int(0)), Err(UnexpectedZero));
+    /// ```
+    pub fn apply(self, lhs: Number, rhs: Number) -> Result<Number, MathParseErrors> {
+        solve::compute_binary(lhs, rhs, self)
+    }
+
+    /// Return the canonical ASCII spelling of the operator. Unlike the
+    /// `Display` implementation, the bitwise xor is shown as `^`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Exponent        => "**",
+            Multiplication  => "*",
+            Division        => "/",
+            IntegerDivision => "//",
+            Reminder        => "%",
+            Addition        => "+",
+            Subtraction     => "-",
+            ShiftLeft       => "<<",
+            ShiftRight      => ">>",
+            LessThan        => "<",
+            LessOrEqual     => "<=",
+            GreaterThan     => ">",
+            GreaterOrEqual  => ">=",
+            Equal           => "==",
+            NotEqual        => "!=",
+            BitwiseAnd      => "&",
+            BitwiseOr       => "|",
+            BitwiseXor      => "^",
+            LogicalAnd      => "&&",
+            LogicalOr       => "||",
+        }
+    }
+}
+
+impl std::str::FromStr for BinaryOp {
+    type Err = MathParseErrors;
+
+    /// Reads a binary operator. Both the ASCII and the Unicode forms used in
+    /// expressions are accepted, as well as the forms used by `Display`.
+    ///
+    /// ```
+    /// use math_parse::BinaryOp;
+    ///
+    /// assert_eq!("<<".parse::<BinaryOp>(), Ok(BinaryOp::ShiftLeft));
+    /// assert_eq!("÷".parse::<BinaryOp>(), Ok(BinaryOp::Division));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(OpKind::Binary(op)) = find_operator(s, true) {
+            return Ok(op);
+        }
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => BinaryOp::from_char(c),
+            _ => Err(UnknownOperator(s.to_string())),
         }
     }
 }
@@ -331,6 +2371,7 @@ impl BinaryOp {
 impl fmt::Display for BinaryOp {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
+            Exponent        => write!(f, "**"),
             Multiplication  => write!(f, "*"),
             Division        => write!(f, "/"),
             IntegerDivision => write!(f, "//"),
@@ -339,9 +2380,17 @@ impl fmt::Display for BinaryOp {
             Subtraction     => write!(f, "-"),
             ShiftLeft       => write!(f, "<<"),
             ShiftRight      => write!(f, ">>"),
+            LessThan        => write!(f, "<"),
+            LessOrEqual     => write!(f, "<="),
+            GreaterThan     => write!(f, ">"),
+            GreaterOrEqual  => write!(f, ">="),
+            Equal           => write!(f, "=="),
+            NotEqual        => write!(f, "!="),
             BitwiseAnd      => write!(f, "&"),
             BitwiseOr       => write!(f, "|"),
-            BitwiseXor      => write!(f, "⊕"), // Not ^ in order not to mistake it for exponentiation.
+            BitwiseXor      => write!(f, "⊕"), // Not ^ in order not to mistake it for exponentiation. It can be parsed back.
+            LogicalAnd      => write!(f, "&&"),
+            LogicalOr       => write!(f, "||"),
         }
     }
 }
@@ -350,19 +2399,26 @@ impl fmt::Display for BinaryOp {
 
 /// Elements that make a list of RPN instruction extracted from a math
 /// expression.
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub enum RPN {
     Name(String),
     Unary(UnaryOp),
     Binary(BinaryOp),
+    /// A function call with its name and its number of arguments.
+    Call(String, usize),
 }
 
 impl fmt::Display for RPN {
+    /// Show an action as it is written in Reverse Polish notation, so that
+    /// the actions can be parsed back by `MathParse::parse_rpn`. Function
+    /// calls are shown as `name@arity`, such as `sum@4`, and the unary
+    /// operators as the words `neg`, `not`, and `pos`.
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            RPN::Name(x)   => write!(f, "{x}"),
-            RPN::Unary(x)  => write!(f, "{x}"),
-            RPN::Binary(x) => write!(f, "{x}"),
+            RPN::Name(x)    => write!(f, "{x}"),
+            RPN::Unary(x)   => write!(f, "{}", parse_rpn::unary_word(*x)),
+            RPN::Binary(x)  => write!(f, "{x}"),
+            RPN::Call(x, n) => write!(f, "{x}@{n}"),
         }
     }
 }
@@ -379,15 +2435,116 @@ impl MathParse {
     /// use math_parse::MathParse;
     ///
     /// assert_eq!(
-    ///     MathParse::parse("3-4+(-5)").unwrap().to_rpn(),
-    ///     Ok(vec![Name("3".to_string()), Name("4".to_string()), Binary(Subtraction), Name("5".to_string()), Unary(Minus), Binary(Addition)]));
+    ///     MathParse::parse("3-4+(-5)").unwrap().to_rpn(),
+    ///     Ok(vec![Name("3".to_string()), Name("4".to_string()), Binary(Subtraction), Name("5".to_string()), Unary(Minus), Binary(Addition)]));
+    /// ```
+    pub fn to_rpn(&self) -> Result<Vec<RPN>, MathParseErrors> {
+        Ok(self.internal.to_rpn())
+    }
+
+    /// Return the set of unary operations and the set of binary operations
+    /// used in the expression.
+    ///
+    /// Example:
+    /// ```
+    /// use math_parse::BinaryOp::*;
+    /// use math_parse::MathParse;
+    /// use std::collections::HashSet;
+    ///
+    /// let (unary, binary) = MathParse::parse("a*(b+c) >> 2").unwrap().operators();
+    /// assert!(unary.is_empty());
+    /// assert_eq!(binary, HashSet::from([Multiplication, Addition, ShiftRight]));
+    /// ```
+    pub fn operators(&self) -> (HashSet<UnaryOp>, HashSet<BinaryOp>) {
+        let mut unary = HashSet::new();
+        let mut binary = HashSet::new();
+        for step in self.internal.to_rpn() {
+            match step {
+                RPN::Unary(op)  => {unary.insert(op);},
+                RPN::Binary(op) => {binary.insert(op);},
+                RPN::Name(_) | RPN::Call(_, _) => {},
+            }
+        }
+        (unary, binary)
+    }
+
+    /// Return the binary operation applied last when solving the
+    /// expression, which is the root of its tree. `None` is returned when
+    /// the root is a value, an unary operation, or a function call.
+    ///
+    /// ```
+    /// use math_parse::{MathParse, BinaryOp};
+    ///
+    /// assert_eq!(MathParse::parse("a*(b+c)").unwrap().precedence_root(), Some(BinaryOp::Multiplication));
+    /// assert_eq!(MathParse::parse("-(b+c)").unwrap().precedence_root(), None);
+    /// ```
+    pub fn precedence_root(&self) -> Option<BinaryOp> {
+        match self.internal.to_rpn().last() {
+            Some(RPN::Binary(op)) => Some(*op),
+            _ => None,
+        }
+    }
+
+    /// Return true if the expression must be put in parenthesis to be used
+    /// as an operand of the `parent` operation, on the given side, without
+    /// changing its meaning. For example, `a+b` needs them to be multiplied,
+    /// but `a*b` doesn't need them to be added. As the additions, the
+    /// multiplications, and the bitwise and logical operations are
+    /// associative, `a*b` doesn't need them either to be the right operand
+    /// of a multiplication, even if `c*(a*b)` is solved in a different order
+    /// than `c*a*b`. Conditional expressions such
+    /// as `c ? a : b` always need them, and as they are stored as calls to
+    /// `if`, so does `if(c, a, b)`.
+    ///
+    /// ```
+    /// use math_parse::{MathParse, BinaryOp, OperandSide};
+    ///
+    /// let parsed = MathParse::parse("a-b").unwrap();
+    /// assert_eq!(parsed.needs_parens_under(BinaryOp::Subtraction, OperandSide::Left), false);
+    /// assert_eq!(parsed.needs_parens_under(BinaryOp::Subtraction, OperandSide::Right), true);
+    /// ```
+    pub fn needs_parens_under(&self, parent: BinaryOp, side: OperandSide) -> bool {
+        match self.internal.to_rpn().last() {
+            Some(RPN::Binary(op)) => match op.precedence().cmp(&parent.precedence()) {
+                std::cmp::Ordering::Less => true,
+                std::cmp::Ordering::Greater => false,
+                std::cmp::Ordering::Equal if side == OperandSide::Right && *op == parent
+                    && matches!(parent, Addition | Multiplication | BitwiseAnd | BitwiseOr | BitwiseXor | LogicalAnd | LogicalOr) => false,
+                std::cmp::Ordering::Equal => (side == OperandSide::Left) == parent.is_right_associative(),
+            },
+            Some(RPN::Call(name, 3)) => name == solve::CONDITIONAL_FUNCTION,
+            _ => false,
+        }
+    }
+}
+
+/// Side of a binary operation an operand is on.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum OperandSide {
+    Left,
+    Right,
+}
+
+impl MathParse {
+    /// Write the expression in prefix notation, in the format read by
+    /// `MathParse::parse_prefix`. Lists of expressions can't be written in
+    /// prefix notation and give an `UnbalancedStack` error.
+    ///
+    /// Example:
+    /// ```
+    /// use math_parse::MathParse;
+    ///
+    /// assert_eq!(
+    ///     MathParse::parse("(2+3)*x").unwrap().to_prefix_string(),
+    ///     Ok("* + 2 3 x".to_string()));
     /// ```
-    pub fn to_rpn(&self) -> Result<Vec<RPN>, MathParseErrors> {
-        Ok(self.internal.clone())
+    pub fn to_prefix_string(&self) -> Result<String, MathParseErrors> {
+        prefix::rpn_to_prefix(&self.internal.to_rpn())
     }
 }
 
-/// Shows a representation of an expression formatted into RPN.
+/// Shows a representation of an expression formatted into RPN, which can be
+/// parsed back with `MathParse::parse_rpn`.
 ///
 /// Example:
 /// ```
@@ -399,12 +2556,12 @@ impl MathParse {
 /// ```
 pub fn rpn_slice_to_string(rpn: &[RPN]) -> String {
     let mut ret = String::new();
-    if rpn.len() == 0 {
+    if rpn.is_empty() {
         return ret;
     }
     ret.push_str(&format!("{}", rpn[0]));
-    for i in 1..rpn.len() {
-        ret.push_str(&format!(" {}", rpn[i]));
+    for action in &rpn[1..] {
+        ret.push_str(&format!(" {action}"));
     }
     ret
 }
@@ -412,11 +2569,15 @@ pub fn rpn_slice_to_string(rpn: &[RPN]) -> String {
 /* ------------------------------ Tree notation ----------------------------- */
 
 /// Parsed element showed in a tree in infix notation. 
-#[derive(Debug, PartialEq, Clone)]
+///
+/// Like the rest of the crate, the implementations of `Clone`, `PartialEq`,
+/// `Debug`, and `Drop` are iterative, so deep trees can be used without
+/// overflowing the stack.
 pub enum Tree {
     Name(String),
     Unary(UnaryOp, Box<Tree>),
     Binary(BinaryOp, Box<Tree>, Box<Tree>),
+    Call(String, Vec<Tree>),
 }
 
 
@@ -441,43 +2602,202 @@ impl MathParse {
     ///             Box::new(Name("5".to_string())))))));
     /// ```
     pub fn to_tree(&self) -> Result<Tree, MathParseErrors> {
-        tree::parse_to_tree(&self.internal)
+        tree::parse_to_tree(&self.internal.to_rpn())
     }
-}
 
-impl fmt::Display for Tree {
-    /// Show a tree as an infix expression.
+    /// Convert the expression into a tree, as `to_tree` does, along with the
+    /// byte range of the expression each node of the tree is read from. The
+    /// spans are only known for expressions parsed from infix notation, the
+    /// `SpanMap` is empty for the others.
     ///
     /// Example:
     /// ```
-    /// use math_parse::*;
-    /// assert_eq!(
-    ///     format!("{}", MathParse::parse("(2+3)*2/5").unwrap().to_tree().unwrap()),
-    ///     "(((2 + 3) * 2) / 5)".to_string());
+    /// use math_parse::MathParse;
+    ///
+    /// let (tree, spans) = MathParse::parse("(a+1)*b").unwrap().to_tree_with_spans().unwrap();
+    /// assert_eq!(format!("{tree}"), "((a + 1) * b)");
+    /// assert_eq!(spans.get(&[]), Some(0..7));
+    /// assert_eq!(spans.get(&[0]), Some(0..5)); // (a+1)
+    /// assert_eq!(spans.get(&[0, 1]), Some(3..4)); // 1
     /// ```
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        enum TreeFmt {
+    pub fn to_tree_with_spans(&self) -> Result<(Tree, SpanMap), MathParseErrors> {
+        let tree = self.to_tree()?;
+        let spans = match &self.source {
+            Some(source) => spans::tree_spans(source, &self.internal.to_rpn())?,
+            None => SpanMap::empty(),
+        };
+        Ok((tree, spans))
+    }
+
+    /// Convert a list of comma-separated expressions into a tree for each
+    /// element of the list.
+    ///
+    /// Example:
+    /// ```
+    /// use math_parse::MathParse;
+    ///
+    /// let trees = MathParse::parse("1+2, 3").unwrap().to_trees().unwrap();
+    /// assert_eq!(trees.len(), 2);
+    /// assert_eq!(format!("{}", trees[0]), "(1 + 2)");
+    /// ```
+    pub fn to_trees(&self) -> Result<Vec<Tree>, MathParseErrors> {
+        tree::parse_to_trees(&self.internal.to_rpn())
+    }
+
+    /// Describe everything about the expression in a single JSON object,
+    /// such as for an editor. Only available with the `serde` feature. The
+    /// fields of the object are:
+    ///
+    /// * `schema`: the version of the schema of the object, `ANALYSIS_SCHEMA`.
+    ///   New fields can be added without changing it, but it is increased
+    ///   when a field is removed, renamed, or changes meaning.
+    /// * `rpn`: the RPN actions, as strings that `parse_rpn` can read.
+    /// * `trees`: the tree of each expression of the list, a single one for
+    ///   most expressions. A node is `{"name": n}`, `{"unary": op, "operand":
+    ///   x}`, `{"binary": op, "lhs": x, "rhs": y}`, or `{"call": f, "args":
+    ///   [...]}`, the operators being their symbol.
+    /// * `variables`: the named variables, in the order of their first use.
+    /// * `literals`: the number literals, as `{"value": v, "span": [start,
+    ///   end]}`.
+    /// * `operators`: the symbols of the operators used, as `{"unary": [...],
+    ///   "binary": [...]}`.
+    /// * `warnings`: the warnings found when parsing, as `{"kind": k,
+    ///   "message": m, "span": [start, end]}`.
+    ///
+    /// The spans, in bytes, and the warnings are only known for expressions
+    /// parsed from infix notation. The fields holding them are left out
+    /// otherwise, as is the span of a warning not tied to a position.
+    ///
+    /// ```
+    /// use math_parse::MathParse;
+    ///
+    /// let analysis = MathParse::parse("(a+2)*b").unwrap().to_analysis_json().unwrap();
+    /// assert_eq!(analysis["schema"], 1);
+    /// assert_eq!(analysis["variables"], serde_json::json!(["a", "b"]));
+    /// assert_eq!(analysis["literals"][0]["span"], serde_json::json!([3, 4]));
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn to_analysis_json(&self) -> Result<serde_json::Value, MathParseErrors> {
+        analysis::analysis_json(self)
+    }
+
+    /// Return a copy of the expression where the named variables are renamed
+    /// according to the mapping, along with a report of the renaming. Only
+    /// whole names are renamed, so renaming `rate` does not change
+    /// `rated_power`, and numbers and function names are never renamed.
+    /// Renaming a variable to the name of an other variable is allowed but
+    /// reported as a collision.
+    ///
+    /// Example:
+    /// ```
+    /// use math_parse::MathParse;
+    /// use std::collections::HashMap;
+    ///
+    /// let mapping = HashMap::from([("rate".to_string(), "interest".to_string())]);
+    /// let (renamed, report) = MathParse::parse("rate * rated_power").unwrap().rename_variables(&mapping);
+    /// assert_eq!(format!("{}", renamed.to_tree().unwrap()), "(interest * rated_power)");
+    /// assert_eq!(report.replacements, 1);
+    /// assert!(report.collisions.is_empty());
+    /// ```
+    pub fn rename_variables(&self, mapping: &HashMap<String, String>) -> (MathParse, RenameReport) {
+        let (internal, report) = rename::rename_rpn(&self.internal.to_rpn(), mapping, &self.options);
+        (MathParse{internal: intern::InternedRPN::new(internal), options: self.options.clone(), source: None}, report)
+    }
+}
+
+impl Tree {
+    /// Return a copy of the tree where the named variables are renamed
+    /// according to the mapping, along with a report of the renaming. This
+    /// works as `MathParse::rename_variables`.
+    pub fn rename_variables(&self, mapping: &HashMap<String, String>) -> (Tree, RenameReport) {
+        rename::rename_tree(self, mapping)
+    }
+
+    /// Write the tree as an infix expression that can be parsed back. Unlike
+    /// the `Display` implementation, the operators are written with their
+    /// canonical ASCII spelling, so the bitwise xor is written `^`.
+    ///
+    /// ```
+    /// use math_parse::MathParse;
+    ///
+    /// let tree = MathParse::parse("a ^ -(b + 1)").unwrap().to_tree().unwrap();
+    /// assert_eq!(tree.to_expression_string(), "(a ^ -(b + 1))");
+    /// assert_eq!(MathParse::parse(&tree.to_expression_string()).unwrap().to_tree(), Ok(tree));
+    /// ```
+    pub fn to_expression_string(&self) -> String {
+        let mut ret = String::new();
+        match self.write_infix(&mut ret, true) {
+            Ok(()) => ret,
+            Err(_) => String::new(), // Writing in a `String` never fails.
+        }
+    }
+
+    /// Write the tree in infix notation. If `parseable` is true, the binary
+    /// operators are written with their ASCII spelling.
+    fn write_infix(&self, f: &mut impl fmt::Write, parseable: bool) -> fmt::Result {
+        enum TreeFmt<'a> {
             S(String),
-            T(Tree),
+            T(&'a Tree),
         } use TreeFmt::*;
         use Tree::*;
 
-        let mut to_format = vec![T(self.clone())];
-        while to_format.len() != 0 {
+        let mut to_format = vec![T(self)];
+        while !to_format.is_empty() {
             match to_format.pop().unwrap() {
                 T(Name(s)) => {
                     write!(f, "{s}")?;
                 },
+                T(Unary(Percent, next)) => {
+                    // A percentage of an other unary operation is in
+                    // parenthesis as `-a%` is `-(a%)`.
+                    to_format.push(S("%".to_string()));
+                    if let Unary(_, _) = **next {
+                        write!(f, "(")?;
+                        to_format.push(S(")".to_string()));
+                    }
+                    to_format.push(T(next));
+                },
                 T(Unary(op, next)) => {
-                    to_format.push(T(*next));
+                    to_format.push(T(next));
                     write!(f, "{op}")?;
                 },
                 T(Binary(op, next_1, next_2)) => {
                     write!(f, "(")?;
                     to_format.push(S(")".to_string()));
-                    to_format.push(T(*next_2));
-                    to_format.push(S(format!(" {op} ")));
-                    to_format.push(T(*next_1));
+                    if let (Reminder, Unary(Minus | Plus, _)) = (op, &**next_2) {
+                        // Otherwise, `a % -b` would be the percentage of `a`
+                        // minus `b`.
+                        to_format.push(S(")".to_string()));
+                        to_format.push(T(next_2));
+                        to_format.push(S("(".to_string()));
+                    } else {
+                        to_format.push(T(next_2));
+                    }
+                    if parseable {
+                        to_format.push(S(format!(" {} ", op.as_str())));
+                    } else {
+                        to_format.push(S(format!(" {op} ")));
+                    }
+                    to_format.push(T(next_1));
+                },
+                T(Call(name, args)) if name == solve::CONDITIONAL_FUNCTION && args.len() == 3 => {
+                    write!(f, "(")?;
+                    to_format.push(S(")".to_string()));
+                    to_format.push(T(&args[2]));
+                    to_format.push(S(" : ".to_string()));
+                    to_format.push(T(&args[1]));
+                    to_format.push(S(" ? ".to_string()));
+                    to_format.push(T(&args[0]));
+                },
+                T(Call(name, args)) => {
+                    write!(f, "{name}(")?;
+                    to_format.push(S(")".to_string()));
+                    for (i, arg) in args.iter().enumerate().rev() {
+                        to_format.push(T(arg));
+                        if i != 0 {
+                            to_format.push(S(", ".to_string()));
+                        }
+                    }
                 },
                 S(s) => {
                     write!(f, "{s}")?;
@@ -488,6 +2808,23 @@ impl fmt::Display for Tree {
     }
 }
 
+impl fmt::Display for Tree {
+    /// Show a tree as an infix expression, which can be parsed back. The
+    /// bitwise xor is shown as `⊕`, use `to_expression_string` to get `^`
+    /// instead.
+    ///
+    /// Example:
+    /// ```
+    /// use math_parse::*;
+    /// assert_eq!(
+    ///     format!("{}", MathParse::parse("(2+3)*2/5").unwrap().to_tree().unwrap()),
+    ///     "(((2 + 3) * 2) / 5)".to_string());
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write_infix(f, false)
+    }
+}
+
 /* --------------------------------- Testing -------------------------------- */
 
 #[cfg(test)]
@@ -495,7 +2832,7 @@ fn name_r(s: &str) -> RPN {
     RPN::Name(s.to_string())
 }
 #[cfg(test)]
-fn name_p(s: &str) -> tokenize::MathValue {
+fn name_p(s: &str) -> tokenize::MathValue<'_> {
     tokenize::MathValue::Name(s)
 }
 #[cfg(test)]
@@ -555,12 +2892,17 @@ fn test_math_compute() {
     compute_int("((3+3)·b+8)*(a-1)", ((3+3)*b+8)*(a-1));
     compute_int("0", 0);
     compute_int("-a + b − c", -a + b - c);
-    compute_int("-−-+++-a", ----a);
+    compute_int("-−-+++-a", -(-(-(-a))));
+    #[allow(clippy::identity_op)]
     compute_int("3%8+99", 3%8+99);
     compute_int("10.0//3.0", 10/3);
-    compute_int("!-4", !-4);
+    compute_int("~-4", !-4);
     compute_int("((3+4)*(8+(4-1)))-(43+8//2+1)", ((3+4) * (8+(4-1))) - (43+8/2+1));
     compute_int("((0xFF&0xF)|0x10)^0x3", ((0xFF & 0xF) | 0x10) ^ 0x3);
+    compute_int("0b11110000 & 0xF0 | 0b1", 0b11110000 & 0xF0 | 0b1);
+    compute_int("1_000 * 0xFF_FF", 1_000 * 0xFF_FF);
+    compute_int("2#1010 + 36#Z", 0b1010 + 35);
+    compute_int("0xFFFFFFFFFFFFFFFF & 0xF", 15);
     compute_int("(10<<5)>>(2<<1)", (10 << 5) >> (2 << 1));
 
     compute_float("4×9/4", 4.0*9.0/4.0);
@@ -605,8 +2947,92 @@ fn test_api() {
     assert_eq!(math_solve_float("3+3"    ), Ok(6.0));
     assert_eq!(math_solve_float("3.0+3.0"), Ok(6.0));
 
-    assert_eq!(contains_math_char("ab+cd"), true);
-    assert_eq!(contains_math_char("abcd"), false);
+    assert!(contains_math_char("ab+cd"));
+    assert!(!contains_math_char("abcd"));
+    assert!(contains_math_char("√2"));
+}
+
+#[test]
+fn test_probably_equivalent() {
+    let check = |a: &str, b: &str| MathParse::parse(a)?.probably_equivalent(&MathParse::parse(b)?, 50, 7);
+    assert_eq!(check("(a+b)*2", "2*a+2*b"), Ok(true));
+    assert_eq!(check("(a+b)*2", "2*a+b"), Ok(false));
+    assert_eq!(check("(x*x - 1)/(x - 1)", "x + 1"), Ok(true));
+    assert_eq!(check("x/x", "1"), Ok(true));
+    assert_eq!(check("x & 1", "x % 2"), Ok(false));
+    assert_eq!(check("sum(i, 1, n, i)", "n*(n+1)/2"), Ok(false));
+    assert_eq!(check("sum(i, 1, 3, i) * pi", "6*pi"), Ok(true));
+    assert_eq!(check("1/(x - x)", "1/(y - y)"), Err(MathParseErrors::UnexpectedZero));
+    assert_eq!(check("a", "b"), Ok(false));
+    assert_eq!(check("a", "b").unwrap(), check("a", "b").unwrap());
+    let parsed = MathParse::parse("a*b - c").unwrap();
+    assert_eq!(parsed.probably_equivalent(&MathParse::parse("b*a - c").unwrap(), 0, 3), Ok(true));
+}
+
+#[test]
+fn test_decimal_comma() {
+    let options = MathParseOptions {
+        decimal_comma: true,
+        ..MathParseOptions::default()
+    };
+    let parse = |expression: &str| MathParse::parse_with_options(expression, &options);
+    assert_eq!(parse("2,5 * 2").unwrap().solve_float(None), Ok(5.0));
+    assert_eq!(parse("max(1,5; 2,5) + ,5").unwrap().solve_float(None), Ok(3.0));
+    assert_eq!(parse("1,5; 2").unwrap().solve_list_float(None), Ok(vec![1.5, 2.0]));
+    assert_eq!(parse("',' - ';'").unwrap().solve_int(None), Ok(',' as i64 - ';' as i64));
+    assert_eq!(parse("1,2,3").unwrap().solve_float(None), Err(MalformedNumber("1.2.3".to_string(), NumberErrorKind::MultipleDots)));
+
+    // Without the option, the comma separates lists.
+    assert_eq!(MathParse::parse("1,5").unwrap().solve_list_float(None), Ok(vec![1.0, 5.0]));
+    assert_eq!(MathParse::parse("1;5").map(|_| ()), Err(InvalidCharacter(';', 1)));
+}
+
+#[test]
+fn test_token_rewriter() {
+    let options = MathParseOptions {
+        token_rewriter: Some(TokenRewriter::new(|word| match word {
+            "DIV" => Some("//".to_string()),
+            "mod" => Some("%".to_string()),
+            "MUL" => Some("* 2".to_string()),
+            _ => None,
+        })),
+        ..MathParseOptions::default()
+    };
+    let variables = HashMap::from([
+        ("DIVIDEND".to_string(), "20".to_string()),
+    ]);
+    let solve = |expression: &str| MathParse::parse_with_options(expression, &options)?.solve_int(Some(&variables));
+    assert_eq!(solve("10 DIV 3"), Ok(3));
+    assert_eq!(solve("DIVIDEND+1"), Ok(21));
+    assert_eq!(solve("DIVIDEND DIV 3 + 17 mod 5"), Ok(8));
+    assert_eq!(solve("2 MUL 3"), Err(InvalidRewrite("MUL".to_string(), "* 2".to_string())));
+    assert!(MathParse::parse_with_options("10 DIV 3", &options).unwrap() == MathParse::parse("10 // 3").unwrap());
+    assert_eq!(MathParse::parse("10 DIV 3").unwrap().solve_int(None), Err(MalformedNumber("10DIV3".to_string(), NumberErrorKind::UnexpectedChar('D', 2))));
+}
+
+#[test]
+fn test_user_functions() {
+    let mut functions = UserFunctions::new();
+    functions.insert("lerp".to_string(), Box::new(|args: &[Number]| match args {
+        [a, b, t] => Ok(Number::Float(a.as_f64() + (b.as_f64() - a.as_f64()) * t.as_f64())),
+        _ => Err(InvalidFunctionCall("lerp".to_string())),
+    }));
+    functions.insert("double".to_string(), Box::new(|args: &[Number]| Ok(Number::Int(2 * args[0].as_i64_strict()?))));
+    functions.insert("min".to_string(), Box::new(|_: &[Number]| Ok(Number::Int(-1000))));
+    functions.insert("fail".to_string(), Box::new(|_: &[Number]| Err(DomainError("fail".to_string()))));
+    let variables = HashMap::from([
+        ("x".to_string(), "0.5".to_string()),
+    ]);
+    let solve = |expression: &str| MathParse::parse(expression)?.solve_with_functions(Some(&variables), &functions);
+    assert_eq!(solve("lerp(0, 10, x)"), Ok(SolvedValue::Int(5)));
+    assert_eq!(solve("double(double(3)) + 1"), Ok(SolvedValue::Int(13)));
+    assert_eq!(solve("sum(i, 1, 3, double(i))"), Ok(SolvedValue::Int(12)));
+    assert_eq!(solve("min(4, 7)"), Ok(SolvedValue::Int(4)));
+    assert_eq!(solve("lerp(1, 2)"), Err(InvalidFunctionCall("lerp".to_string())));
+    assert_eq!(solve("fail(1)"), Err(DomainError("fail".to_string())));
+    assert_eq!(solve("nope(1)"), Err(UnknownFunction("nope".to_string())));
+    assert_eq!(solve("0 ? fail(1) : 3"), Ok(SolvedValue::Int(3)));
+    assert_eq!(MathParse::parse("double(4)").unwrap().solve_value(None), Err(UnknownFunction("double".to_string())));
 }
 
 #[test]
@@ -624,8 +3050,8 @@ fn test_bitwise_on_float() {
 
 #[test]
 fn test_operator_hints() {
-    assert_eq!(math_solve_int("3876<4"), Err(BadOperatorHint('<', "<<")));
-    assert_eq!(math_solve_int("3876>4"), Err(BadOperatorHint('>', ">>")));
+    assert_eq!(math_solve_int("3876=4"), Err(BadOperatorHint('=', "==")));
+    assert_eq!(math_solve_int("1 <<= 4"), Err(MisplacedOperator('=')));
 }
 
 #[test]
@@ -647,6 +3073,24 @@ fn test_parse_rpn() {
     assert_eq!(solve_rpn("3 4 2 + *"), Ok(18));
     assert_eq!(solve_rpn("3 (4 + 3) 2 + *"), Err(InvalidRPNOperator('(')));
     assert_eq!(solve_rpn("3 2 + *"), Err(UnbalancedStack));
+    assert_eq!(solve_rpn("+5 3 -"), Ok(2));
+    assert_eq!(solve_rpn("5 3 +"), Ok(8));
+    assert_eq!(solve_rpn("5 3 ++2 *"), Ok(16));
+    assert_eq!(solve_rpn("5, 3, - # comment"), Ok(2));
+    assert_eq!(solve_rpn("# only a comment"), Err(UnbalancedStack));
+
+    let options = MathParseOptions {
+        multiple_rpn_results: true,
+        ..MathParseOptions::default()
+    };
+    let program = MathParse::parse_rpn_with_options("1 2 3 +", &options).unwrap();
+    assert_eq!(program.solve_stack(None), Ok(vec![Number::Int(1), Number::Int(5)]));
+    assert_eq!(program.solve_int(None), Err(UnbalancedStack));
+    assert_eq!(program.solve_float(None), Err(UnbalancedStack));
+    assert_eq!(MathParse::parse_rpn("1 2 3 +").map(|_| ()), Err(UnbalancedStack));
+    assert_eq!(MathParse::parse_rpn_with_options("x 2 / 0.5", &options).unwrap().solve_stack(None),
+        Err(InvalidNumber("x".to_string())));
+    assert_eq!(MathParse::parse("1 + 2").unwrap().solve_stack(None), Ok(vec![Number::Int(3)]));
 }
 
 #[test]
@@ -660,6 +3104,968 @@ fn test_misc_errors() {
     assert_eq!(compute("1 - (1*3)", None), Ok(Number::Int(-2)));
     assert_eq!(compute("1+-1", None), Ok(Number::Int(0)));
     assert_eq!(compute("1 + - 1", None), Ok(Number::Int(0)));
+    assert_eq!(compute("7 // -2", None), Ok(Number::Int(-3)));
+    assert_eq!(compute("1 << -2", None), Err(UnexpectedNegative));
+    assert_eq!(compute("8 >> --2", None), Ok(Number::Int(2)));
+    assert_eq!(compute("1 << * 2", None), Err(MisplacedOperator('*')));
+}
+
+#[test]
+fn test_lists() {
+    let solve_list = |expression: &str| MathParse::parse(expression)?.solve_list_float(None);
+    assert_eq!(solve_list("1+2, 3*4"), Ok(vec![3.0, 12.0]));
+    assert_eq!(solve_list("(1+2, 3*4)"), Ok(vec![3.0, 12.0]));
+    assert_eq!(solve_list("(1), (2), 3"), Ok(vec![1.0, 2.0, 3.0]));
+    assert_eq!(solve_list("2*(1+2), -4"), Ok(vec![6.0, -4.0]));
+    assert_eq!(solve_list("8"), Ok(vec![8.0]));
+    assert_eq!(solve_list("(1, 2) + 3"), Err(MisplacedOperator(',')));
+    assert_eq!(solve_list("1, ,2"), Err(EmptyLine));
+    assert_eq!(solve_list("()"), Err(EmptyLine));
+    assert_eq!(solve_list(","), Err(EmptyLine));
+    assert_eq!(MathParse::parse("1, 2").unwrap().solve_int(None), Err(UnbalancedStack));
+    assert_eq!(MathParse::parse("1, 2*a").unwrap().to_trees(),
+        Ok(vec![name_t("1"), Tree::Binary(BinaryOp::Multiplication, Box::new(name_t("2")), Box::new(name_t("a")))]));
+}
+
+#[test]
+fn test_function_calls() {
+    let rpn = MathParse::parse("sum(i, 1, 10, i*i) + 2").unwrap().to_rpn().unwrap();
+    assert_eq!(rpn_slice_to_string(&rpn), "i 1 10 i i * sum@4 2 +");
+    assert_eq!(MathParse::parse_rpn(&rpn_slice_to_string(&rpn)).unwrap().solve_int(None), Ok(387));
+    assert_eq!(format!("{}", MathParse::parse("2*sum(i, 1, 10, (i+1))").unwrap().to_tree().unwrap()),
+        "(2 * sum(i, 1, 10, (i + 1)))");
+    assert_eq!(MathParse::parse("f()").unwrap().to_tree(), Ok(Tree::Call("f".to_string(), vec![])));
+    assert_eq!(MathParse::parse("f(g(1), (2, 3))").map(|_| ()), Err(MisplacedOperator(',')));
+    assert_eq!(MathParse::parse("f(1, 2").map(|_| ()), Err(UnclosedParenthesis));
+    assert_eq!(MathParse::parse("(sum(i, 1, 3, i), product(i, 1, 3, i))").unwrap().solve_list_float(None), Ok(vec![6.0, 6.0]));
+}
+
+#[test]
+fn test_solved_value() {
+    assert_eq!(MathParse::parse("2.5 * 2").unwrap().solve_value(None), Ok(SolvedValue::Int(5)));
+    assert_eq!(MathParse::parse("1 / 4").unwrap().solve_value(None), Ok(SolvedValue::Float(0.25)));
+    assert_eq!(SolvedValue::from(3), SolvedValue::Int(3));
+    assert_eq!(SolvedValue::from(3.0), SolvedValue::Float(3.0));
+    assert_ne!(SolvedValue::Int(3), SolvedValue::Float(3.0));
+    assert_eq!(format!("{}", SolvedValue::Int(-4)), "-4");
+    assert_eq!(format!("{}", SolvedValue::Float(0.25)), "0.25");
+}
+
+#[test]
+fn test_solve_with_tolerance() {
+    let value = |expression: &str, tolerance: f64| MathParse::parse(expression).unwrap().solve_value_with_tolerance(None, tolerance);
+    let int = |expression: &str, tolerance: f64| MathParse::parse(expression).unwrap().solve_int_with_tolerance(None, tolerance);
+    assert_eq!(value("(0.1+0.2)*10", 1e-9), Ok(SolvedValue::Int(3)));
+    assert_eq!(int("(0.1+0.2)*10", 1e-9), Ok(3));
+    assert_eq!(int("0.1*30", 1e-9), Ok(3));
+    assert_eq!(int("-(0.1+0.2)*10", 1e-9), Ok(-3));
+    assert_eq!(int("1 - 1e-10", 1e-9), Ok(1));
+    assert_eq!(int("-2 - 1e-10", 1e-9), Ok(-2));
+    assert_eq!(value("3 + 1e-8", 1e-9), Ok(SolvedValue::Float(3.00000001)));
+    assert_eq!(value("5 / 2", 1e-9), Ok(SolvedValue::Float(2.5)));
+    assert_eq!(value("5 / 2", 0.4), Ok(SolvedValue::Float(2.5)));
+    assert_eq!(int("5 / 2", 1e-9), Err(ReturnFloatExpectedInt(2.5)));
+    assert_eq!(value("1e300", 0.5), Ok(SolvedValue::Float(1e300)));
+    assert_eq!(value("3 + 9e-7", 1e-6), Ok(SolvedValue::Int(3)));
+    assert_eq!(value("3 + 1.1e-6", 1e-6), Ok(SolvedValue::Float(3.0000011)));
+    assert_eq!(value("-3 - 9e-7", 1e-6), Ok(SolvedValue::Int(-3)));
+    assert_eq!(value("-3 - 1.1e-6", 1e-6), Ok(SolvedValue::Float(-3.0000011)));
+    assert_eq!(value("1125899906842624.25", 0.3), Ok(SolvedValue::Int(1125899906842624)));
+    assert_eq!(value("1125899906842624.25", 0.2), Ok(SolvedValue::Float(1125899906842624.0 + 0.25)));
+    assert_eq!(value("2 ** 60 + 0.5", 1.0), Ok(SolvedValue::Float(1152921504606846976.0)));
+    assert_eq!(value("-(2 ** 60) - 0.5", 1.0), Ok(SolvedValue::Float(-1152921504606846976.0)));
+    for expression in ["(0.1+0.2)*10", "5 / 2", "7 * 2", "1e300", "1 - 1e-10"] {
+        assert_eq!(value(expression, 0.0), MathParse::parse(expression).unwrap().solve_value(None), "{expression}");
+        assert_eq!(int(expression, 0.0), MathParse::parse(expression).unwrap().solve_int(None), "{expression}");
+        assert_eq!(value(expression, f64::NAN), MathParse::parse(expression).unwrap().solve_value(None), "{expression}");
+    }
+}
+
+#[test]
+fn test_parse_lenient() {
+    use LenientFix::*;
+    let lenient = |expression: &str| MathParse::parse_lenient(expression).map(|(parsed, fixes)| (parsed.solve_int(None), fixes));
+    assert_eq!(lenient("12+"), Ok((Ok(12), vec![IgnoredTrailingOperator(2..3)])));
+    assert_eq!(lenient("(3*"), Ok((Ok(3), vec![IgnoredTrailingOperator(2..3), ClosedParenthesis(0)])));
+    assert_eq!(lenient("((1+2"), Ok((Ok(3), vec![ClosedParenthesis(1), ClosedParenthesis(0)])));
+    assert_eq!(lenient("2*(1+(2"), Ok((Ok(6), vec![ClosedParenthesis(5), ClosedParenthesis(2)])));
+    assert_eq!(lenient("1 << 2 >>"), Ok((Ok(4), vec![IgnoredTrailingOperator(7..9)])));
+    assert_eq!(lenient("4*5"), Ok((Ok(20), vec![])));
+    assert_eq!(lenient("1+*2").err(), Some(MisplacedOperator('*')));
+    assert_eq!(lenient("1+2)").err(), Some(UnopenedParenthesis));
+    assert_eq!(lenient("(3*(").err(), Some(EmptyLine));
+    assert_eq!(lenient("12++").err(), Some(TrailingOperator));
+
+    assert_eq!(MathParse::parse("12+").err(), Some(EmptyLine));
+    assert_eq!(MathParse::parse("(3*").err(), Some(UnclosedParenthesis));
+    assert_eq!(MathParse::parse("((1+2").err(), Some(UnclosedParenthesis));
+}
+
+#[test]
+fn test_solve_float_over() {
+    let variables = HashMap::from([
+        ("a".to_string(), "0.5".to_string()),
+        ("x".to_string(), "1000".to_string()),
+    ]);
+    let parsed = MathParse::parse("a*x*x + 3*x - 2/(x+1.5) + sum(i, 1, 3, i*x) + (x & 1)").unwrap();
+    let values: Vec<f64> = (0..2000).map(|i| f64::from(i) * 3.0 - 10.0).collect();
+
+    let fast = parsed.solve_float_over("x", &values, Some(&variables)).unwrap();
+    let mut naive = vec![];
+    for value in &values {
+        let mut variables = variables.clone();
+        variables.insert("x".to_string(), value.to_string());
+        naive.push(parsed.solve_float(Some(&variables)).unwrap());
+    }
+    assert_eq!(fast, naive);
+
+    let parsed = MathParse::parse("1/x").unwrap();
+    assert_eq!(parsed.solve_float_over("x", &[4.0, 2.0], None), Ok(vec![0.25, 0.5]));
+    assert_eq!(parsed.solve_float_over("x", &[4.0, 0.0, 2.0, 0.0], None), Err(FailedAtValue(1, Box::new(UnexpectedZero))));
+    assert_eq!(parsed.solve_float_over("x", &[], None), Ok(vec![]));
+    assert_eq!(MathParse::parse("x & 3").unwrap().solve_float_over("x", &[6.0, 6.5], None), Err(FailedAtValue(1, Box::new(BinaryOpOnFloat(6.5, '&')))));
+    assert_eq!(MathParse::parse("x + y").unwrap().solve_float_over("x", &[1.0], None), Err(InvalidNumber("y".to_string())));
+    assert_eq!(MathParse::parse("x + y").unwrap().solve_float_over("x", &[], None), Err(InvalidNumber("y".to_string())));
+    let variables = HashMap::from([("y".to_string(), "1/0".to_string())]);
+    assert_eq!(MathParse::parse("x + y").unwrap().solve_float_over("x", &[1.0], Some(&variables)), Err(UnexpectedZero));
+    assert_eq!(MathParse::parse("sum(i, 1, x, i)").unwrap().solve_float_over("x", &[3.0], None), Ok(vec![6.0]));
+}
+
+#[test]
+#[ignore = "timing test, run it with `cargo test --release -- --ignored`"]
+fn test_solve_float_over_speed() {
+    let variables = HashMap::from([
+        ("a".to_string(), "sum(i, 1, 1000, i*i) / 1e9".to_string()),
+    ]);
+    let parsed = MathParse::parse("a*x*x + x").unwrap();
+    let values: Vec<f64> = (0..1000).map(f64::from).collect();
+
+    let start = std::time::Instant::now();
+    let fast = parsed.solve_float_over("x", &values, Some(&variables)).unwrap();
+    let fast_time = start.elapsed();
+    let start = std::time::Instant::now();
+    let mut naive = vec![];
+    for value in &values {
+        let mut variables = variables.clone();
+        variables.insert("x".to_string(), value.to_string());
+        naive.push(parsed.solve_float(Some(&variables)).unwrap());
+    }
+    let naive_time = start.elapsed();
+    assert_eq!(fast, naive);
+    assert!(fast_time * 10 < naive_time, "{fast_time:?} for solve_float_over and {naive_time:?} for solve_float");
+}
+
+#[test]
+fn test_solve_many() {
+    let variables = HashMap::from([
+        ("width".to_string(), "4".to_string()),
+        ("height".to_string(), "width/8".to_string()),
+    ]);
+    let results = MathParse::solve_many(["1+2", "width*2", "3/", "width*2", "height", "toto", "7/2"], Some(&variables));
+    assert_eq!(results, vec![
+        Ok(Number::Int(3)),
+        Ok(Number::Int(8)),
+        Err(EmptyLine),
+        Ok(Number::Int(8)),
+        Err(InvalidNumber("width".to_string())),
+        Err(InvalidNumber("toto".to_string())),
+        Ok(Number::Float(3.5)),
+    ]);
+    assert_eq!(MathParse::solve_many(Vec::<&str>::new(), None), vec![]);
+
+    let options = MathParseOptions{max_operations: Some(3), ..Default::default()};
+    let results = MathParse::solve_many_with_options(["1+2", "1+2+3+4"], None, &options);
+    assert_eq!(results, vec![Ok(Number::Int(3)), Err(TooManyOperations(3))]);
+}
+
+#[test]
+fn test_operations_apply() {
+    let numbers = [Number::Int(7), Number::Int(-2), Number::Int(0), Number::Int(40), Number::Float(2.5), Number::Float(0.0), Number::Float(-3.0)];
+    let to_name = |n: Number| match n {
+        Number::Int(i)   => RPN::Name(format!("{i}")),
+        Number::Float(f) => RPN::Name(format!("{f:?}")),
+    };
+    let solve = |rpn: Vec<RPN>| math_solve(&rpn, &|_| None, &MathParseOptions::default());
+    for op in [Multiplication, Division, IntegerDivision, Reminder, Addition, Subtraction, ShiftLeft, ShiftRight, BitwiseAnd, BitwiseOr, BitwiseXor] {
+        for lhs in numbers {
+            for rhs in numbers {
+                assert_eq!(op.apply(lhs, rhs), solve(vec![to_name(lhs), to_name(rhs), RPN::Binary(op)]), "{lhs:?} {op} {rhs:?}");
+            }
+        }
+    }
+    for op in [Not, LogicalNot, Minus, Plus] {
+        for value in numbers {
+            assert_eq!(op.apply(value), solve(vec![to_name(value), RPN::Unary(op)]), "{op} {value:?}");
+        }
+    }
+    assert_eq!(BitwiseAnd.apply(Number::Float(2.5), Number::Int(1)), Err(BinaryOpOnFloat(2.5, '&')));
+    assert_eq!(ShiftLeft.apply(Number::Int(1), Number::Int(-2)), Err(UnexpectedNegative));
+    assert_eq!(Reminder.apply(Number::Int(1), Number::Int(0)), Err(UnexpectedZero));
+    assert_eq!(Not.apply(Number::Float(0.5)), Err(BinaryOpOnFloat(0.5, '~')));
+    assert_eq!(LogicalNot.apply(Number::Float(0.5)), Ok(Number::Int(0)));
+}
+
+#[test]
+fn test_operators_from_str() {
+    use std::str::FromStr;
+    for op in [Multiplication, Division, IntegerDivision, Reminder, Addition, Subtraction, ShiftLeft, ShiftRight, BitwiseAnd, BitwiseOr, BitwiseXor] {
+        assert_eq!(BinaryOp::from_str(op.as_str()), Ok(op));
+        assert_eq!(BinaryOp::from_str(&op.to_string()), Ok(op));
+    }
+    for op in [Not, LogicalNot, Minus, Plus] {
+        assert_eq!(UnaryOp::from_str(op.as_str()), Ok(op));
+    }
+    assert_eq!(BinaryOp::from_str("×"), Ok(Multiplication));
+    assert_eq!(BinaryOp::from_str("·"), Ok(Multiplication));
+    assert_eq!(BinaryOp::from_str("⁄"), Ok(Division));
+    assert_eq!(BinaryOp::from_str("⟌"), Ok(IntegerDivision));
+    assert_eq!(BinaryOp::from_str("−"), Ok(Subtraction));
+    assert_eq!(BinaryOp::from_str("≪"), Ok(ShiftLeft));
+    assert_eq!(BinaryOp::from_str("≫"), Ok(ShiftRight));
+    assert_eq!(BinaryOp::from_str("<"), Ok(LessThan));
+    assert_eq!(BinaryOp::from_str("≠"), Ok(NotEqual));
+    assert_eq!(BinaryOp::from_str("="), Err(BadOperatorHint('=', "==")));
+    assert_eq!(BinaryOp::from_str("**"), Ok(Exponent));
+    assert_eq!(BinaryOp::from_str("***"), Err(UnknownOperator("***".to_string())));
+    assert_eq!(BinaryOp::from_str("a"), Err(UnknownOperator("a".to_string())));
+    assert_eq!(BinaryOp::from_str(""), Err(UnknownOperator("".to_string())));
+    assert_eq!(UnaryOp::from_str("~"), Ok(Not));
+    assert_eq!(UnaryOp::from_str("!"), Ok(LogicalNot));
+    assert_eq!(UnaryOp::from_str("not"), Ok(Not));
+    assert_eq!(UnaryOp::from_str("*"), Err(UnknownOperator("*".to_string())));
+}
+
+#[test]
+fn test_supported_operators() {
+    let operators = supported_operators();
+    let all = [
+        OpKind::Unary(Not), OpKind::Unary(LogicalNot), OpKind::Unary(Minus), OpKind::Unary(Plus), OpKind::Unary(Percent), OpKind::Unary(Sqrt), OpKind::Binary(Exponent),
+        OpKind::Binary(Multiplication), OpKind::Binary(Division), OpKind::Binary(IntegerDivision),
+        OpKind::Binary(Reminder), OpKind::Binary(Addition), OpKind::Binary(Subtraction),
+        OpKind::Binary(ShiftLeft), OpKind::Binary(ShiftRight), OpKind::Binary(LessThan),
+        OpKind::Binary(LessOrEqual), OpKind::Binary(GreaterThan), OpKind::Binary(GreaterOrEqual),
+        OpKind::Binary(Equal), OpKind::Binary(NotEqual), OpKind::Binary(BitwiseAnd),
+        OpKind::Binary(BitwiseOr), OpKind::Binary(BitwiseXor), OpKind::Binary(LogicalAnd),
+        OpKind::Binary(LogicalOr),
+    ];
+    assert_eq!(operators.len(), all.len());
+    for op in all {
+        assert_eq!(operators.iter().filter(|info| info.op == op).count(), 1, "{op:?}");
+    }
+
+    // The symbols can be read back and the precedences match the parser.
+    for info in &operators {
+        for symbol in &info.symbols {
+            match info.op {
+                OpKind::Unary(op) => assert_eq!(symbol.parse::<UnaryOp>(), Ok(op)),
+                OpKind::Binary(op) => assert_eq!(symbol.parse::<BinaryOp>(), Ok(op)),
+            }
+        }
+        if let OpKind::Unary(op @ (Not | LogicalNot | Minus)) = info.op {
+            let rpn = MathParse::parse(&format!("{}a * b", info.symbols[0])).unwrap().to_rpn().unwrap();
+            assert_eq!(rpn[1], RPN::Unary(op), "{op:?}");
+        }
+    }
+    let binary = operators.iter().filter_map(|info| match info.op {
+        OpKind::Binary(op) => Some((op, info.precedence)),
+        _ => None,
+    }).collect::<Vec<_>>();
+    for (op_1, precedence_1) in &binary {
+        assert!(BINARY_PRECEDENCES.contains(precedence_1));
+        for (op_2, precedence_2) in &binary {
+            let rpn = MathParse::parse(&format!("a {} b {} c", op_1.as_str(), op_2.as_str())).unwrap().to_rpn().unwrap();
+            let left_first = rpn[2] == RPN::Binary(*op_1);
+            let right_associative = op_1 == op_2 && op_1.is_right_associative();
+            assert_eq!(left_first, precedence_1 >= precedence_2 && !right_associative, "{op_1:?} {op_2:?}");
+        }
+    }
+}
+
+#[test]
+fn test_scientific_notation() {
+    let variables = HashMap::from([
+        ("e".to_string(), "2.5".to_string()),
+        ("e3".to_string(), "7".to_string()),
+        ("x".to_string(), "1".to_string()),
+    ]);
+    let solve = |expression: &str| compute(expression, Some(&variables));
+    assert_eq!(solve("2e3"), Ok(Number::Int(2000)));
+    assert_eq!(solve("2E+3 - 1"), Ok(Number::Int(1999)));
+    assert_eq!(solve("1.5e-3*2"), Ok(Number::Float(0.003)));
+    assert_eq!(solve("2e-3-1e-3"), Ok(Number::Float(0.001)));
+    assert_eq!(MathParse::parse("2.5e-3*4").unwrap().solve_float(None), Ok(0.01));
+    assert_eq!(solve("1_000e-3 + 1"), Ok(Number::Int(2)));
+    assert_eq!(rpn_slice_to_string(&MathParse::parse("x e-3").unwrap().to_rpn().unwrap()), "xe 3 -");
+    assert_eq!(solve("e+1"), Ok(Number::Float(3.5)));
+    assert_eq!(solve("2*e"), Ok(Number::Int(5)));
+    assert_eq!(solve("e3 * 2"), Ok(Number::Int(14)));
+    assert_eq!(solve("2e"), Err(MalformedNumber("2e".to_string(), NumberErrorKind::MissingExponentDigits)));
+    assert_eq!(solve("1e+x"), Err(MalformedNumber("1e".to_string(), NumberErrorKind::MissingExponentDigits)));
+    assert_eq!(solve("0x1e+1"), Ok(Number::Int(0x1F)));
+    assert_eq!(compute("e", None), Ok(Number::Float(std::f64::consts::E)));
+    assert_eq!(compute("e3", None), Err(InvalidNumber("e3".to_string())));
+    assert_eq!(MathParse::parse_rpn("1e-3 2e+3 *").unwrap().solve_float(None), Ok(2.0));
+}
+
+#[test]
+fn test_warnings() {
+    use MathParseWarning::*;
+    let (parsed, warnings) = MathParse::parse_with_warnings("(3 ^ 1) + '^' ^ 2");
+    assert_eq!(warnings, vec![ConfusingOperator('^', 3..4), ConfusingOperator('^', 14..15)]);
+    let parsed = parsed.unwrap();
+    assert_eq!(parsed.solve_int_with_warnings(None), (Ok(((3 ^ 1) + '^' as i64) ^ 2), vec![]));
+    assert_eq!(MathParse::parse_with_warnings("2 ^").1, vec![ConfusingOperator('^', 2..3)]);
+
+    let parsed = MathParse::parse("2 * 1.5 + 3 * 0.5").unwrap();
+    assert_eq!(parsed.solve_float_with_warnings(None), (Ok(4.5), vec![MixedNumberTypes(Multiplication)]));
+    assert_eq!(parsed.solve_int_with_warnings(None), (Err(ReturnFloatExpectedInt(4.5)), vec![MixedNumberTypes(Multiplication)]));
+    assert_eq!(parsed.solve_float(None), Ok(4.5));
+
+    let parsed = MathParse::parse("9007199254740993 / 1").unwrap();
+    assert_eq!(parsed.solve_value_with_warnings(None), (Ok(SolvedValue::Int(9007199254740992)), vec![PrecisionLoss(9007199254740993)]));
+    assert_eq!(parsed.solve_value(None), Ok(SolvedValue::Int(9007199254740992)));
+    let parsed = MathParse::parse("9007199254740993 + 0").unwrap();
+    assert_eq!(parsed.solve_float_with_warnings(None), (Ok(9007199254740992.0), vec![PrecisionLoss(9007199254740993)]));
+    assert_eq!(parsed.solve_int_with_warnings(None), (Ok(9007199254740993), vec![]));
+
+    let (parsed, warnings) = MathParse::parse_with_warnings("1 +* 2");
+    assert_eq!(parsed.map(|_| ()), Err(MisplacedOperator('*')));
+    assert_eq!(warnings, vec![]);
+    assert_eq!(MathParse::parse("1 / 0").unwrap().solve_value_with_warnings(None), (Err(UnexpectedZero), vec![]));
+
+    let variables = HashMap::from([
+        ("pi".to_string(), "3".to_string()),
+        ("e".to_string(), "2".to_string()),
+        ("r".to_string(), "2".to_string()),
+    ]);
+    let parsed = MathParse::parse("pi * r * r + pi").unwrap();
+    assert_eq!(parsed.solve_int_with_warnings(Some(&variables)), (Ok(15), vec![ShadowedConstant("pi".to_string())]));
+    assert_eq!(parsed.solve_int(Some(&variables)), Ok(15));
+    assert_eq!(MathParse::parse("tau * r").unwrap().solve_float_with_warnings(Some(&variables)), (Ok(std::f64::consts::TAU * 2.0), vec![MixedNumberTypes(Multiplication)]));
+    let options = MathParseOptions {
+        disable_constants: true,
+        ..MathParseOptions::default()
+    };
+    let parsed = MathParse::parse_with_options("pi * r", &options).unwrap();
+    assert_eq!(parsed.solve_int_with_warnings(Some(&variables)), (Ok(6), vec![]));
+    assert_eq!(format!("{}", ShadowedConstant("pi".to_string())), "The named variable `pi` is used instead of the builtin constant.");
+}
+
+#[test]
+fn test_identifier_policy() {
+    assert_eq!(MathParse::parse("a$#@b + 1").map(|_| ()), Err(InvalidCharacter('$', 1)));
+    assert_eq!(MathParse::parse_rpn("1 a\0 +").map(|_| ()), Err(InvalidCharacter('\0', 3)));
+    let variables = HashMap::from([("température".to_string(), "20".to_string())]);
+    assert_eq!(MathParse::parse("température+1").unwrap().solve_int(Some(&variables)), Ok(21));
+    assert_eq!(MathParse::parse_rpn("i 1 3 i sum@4").unwrap().solve_int(None), Ok(6));
+
+    let options = MathParseOptions {
+        permissive_identifiers: true,
+        ..MathParseOptions::default()
+    };
+    assert_eq!(MathParse::parse_with_options("a$#@b + 1", &options).unwrap().solve_int(None),
+        Err(InvalidNumber("a$#@b".to_string())));
+}
+
+#[test]
+fn test_invisible_characters() {
+    // The invisible characters of pasted text are ignored between tokens.
+    assert_eq!(MathParse::parse("\u{FEFF}3+4").unwrap().solve_int(None), Ok(7));
+    assert_eq!(MathParse::parse("2 *\u{200B} (3 \u{2060}+ 4)\u{FEFF}").unwrap().solve_int(None), Ok(14));
+    assert_eq!(MathParse::parse_rpn("\u{FEFF}1 2 +\u{200B}").unwrap().solve_int(None), Ok(3));
+
+    // Inside of names, they are errors that name the code point, as the
+    // name is not what it looks like. So are the control characters.
+    let error = MathParse::parse("1\u{200B}2 + 1").map(|_| ());
+    assert_eq!(error, Err(InvalidCharacter('\u{200B}', 1)));
+    assert_eq!(error.unwrap_err().to_string(), "The character U+200B at position 1 can't be used in a name.");
+    assert_eq!(InvalidCharacter('\u{7}', 0).to_string(), "The character U+0007 at position 0 can't be used in a name.");
+    assert_eq!(InvalidCharacter('$', 3).to_string(), "The character `$` at position 3 can't be used in a name.");
+}
+
+#[test]
+fn test_special_float_literals() {
+    let parsed = |expression: &str| MathParse::parse(expression).unwrap();
+    assert_eq!(parsed("inf").solve_value(None), Ok(SolvedValue::Float(f64::INFINITY)));
+    assert_eq!(parsed("-inf").solve_value(None), Ok(SolvedValue::Float(f64::NEG_INFINITY)));
+    assert_eq!(parsed("2 * inf - 1e308").solve_value(None), Ok(SolvedValue::Float(f64::INFINITY)));
+    assert!(matches!(parsed("nan").solve_value(None), Ok(SolvedValue::Float(x)) if x.is_nan()));
+    assert_eq!(parsed("inf").solve_int(None), Err(IntConversion(f64::INFINITY)));
+
+    // A variable of the map takes precedence over the literal.
+    let variables = HashMap::from([("inf".to_string(), "3".to_string())]);
+    assert_eq!(parsed("inf + 1").solve_value(Some(&variables)), Ok(SolvedValue::Int(4)));
+}
+
+#[test]
+fn test_number_literals() {
+    let corpus = ["12", "1.5", ".5", "5.", "0x1F", "0x", "0xG", "0b101", "0b", "0b12", "1e3", "1.5e-3", "1E+10", "2e", "e3", "1..2", "'a'",
+        "'ab'", "'\\n'", "abc", "a$", "", "  ", " 7 ", "inf", "NaN", "9223372036854775808"];
+    for s in corpus {
+        let solved = MathParse::parse(s).and_then(|x| x.solve_float(None));
+        assert_eq!(is_number_literal(s), solved.is_ok(), "{s}");
+    }
+    for expression in ["-12", "+12", "(3)", "1 + 2", "0x-5"] {
+        assert!(!is_number_literal(expression), "{expression}");
+    }
+    assert_eq!(parse_number_literal("1.5e-3"), Ok(Number::Float(1.5e-3)));
+    assert_eq!(parse_number_literal("'a'"), Ok(Number::Int(97)));
+    assert_eq!(parse_number_literal("2e"), Err(MalformedNumber("2e".to_string(), NumberErrorKind::MissingExponentDigits)));
+    assert_eq!(parse_number_literal(""), Err(EmptyLine));
+}
+
+#[test]
+fn test_solve_to_string() {
+    let format = |expression: &str, spec: &str| MathParse::parse(expression).unwrap().solve_to_string(None, spec);
+    let int = "300 - 45";
+    let float = "1234.5 / 10";
+    assert_eq!(format(int, "{}"), Ok("255".to_string()));
+    assert_eq!(format(float, "{}"), Ok("123.45".to_string()));
+    assert_eq!(format(int, "{:.2}"), Ok("255.00".to_string()));
+    assert_eq!(format(float, "{:.1}"), Ok("123.5".to_string()));
+    assert_eq!(format(float, "{:.0}"), Ok("123".to_string()));
+    assert_eq!(format(int, "{:x}"), Ok("ff".to_string()));
+    assert_eq!(format(int, "{:#X}"), Ok("0xFF".to_string()));
+    assert_eq!(format(int, "{:#b}"), Ok("0b11111111".to_string()));
+    assert_eq!(format(int, "{:o}"), Ok("377".to_string()));
+    assert_eq!(format(float, "{:x}"), Err(ReturnFloatExpectedInt(123.45)));
+    assert_eq!(format("4.0 * 4", "{:x}"), Ok("10".to_string()));
+    assert_eq!(format(int, "{:e}"), Ok("2.55e2".to_string()));
+    assert_eq!(format(float, "{:e}"), Ok("1.2345e2".to_string()));
+    assert_eq!(format(float, "{:.2E}"), Ok("1.23E2".to_string()));
+    for spec in ["", "{", "{:q}", "{:.x}", "{:#.2}", "{:.2x}", "{:xx}", "{0}", "{:#e}", "x{}"] {
+        assert_eq!(format(int, spec), Err(InvalidFormatSpec(spec.to_string())), "{spec}");
+    }
+}
+
+#[test]
+fn test_solve_uint() {
+    let uint = |expression: &str| MathParse::parse(expression).unwrap().solve_uint(None);
+    assert_eq!(uint("0"), Ok(0));
+    assert_eq!(uint("0x1000 + 16"), Ok(0x1010));
+    assert_eq!(uint("9223372036854775807"), Ok(i64::MAX as u64));
+    assert_eq!(uint("9223372036854775807 + 1"), Ok(1 << 63));
+    assert_eq!(uint("2 ** 64 - 2 ** 12"), Ok(u64::MAX - 0xFFF));
+    assert_eq!(uint("6.0 / 2"), Ok(3));
+    assert_eq!(uint("-0.0"), Ok(0));
+    assert_eq!(uint("-1"), Err(UnexpectedNegative));
+    assert_eq!(uint("-1e30"), Err(UnexpectedNegative));
+    assert_eq!(uint("0x7FFFFFFFFFFFFFFF"), Ok(i64::MAX as u64));
+    assert_eq!(uint("0x7FFFFFFFFFFFFFFF + 1"), Ok(1 << 63));
+    assert_eq!(uint("0o777 * 0b10 + 36#Z"), Ok(0o777 * 2 + 35));
+
+    // Literals above `0x7FFFFFFFFFFFFFFF` are negative, whatever the shape
+    // of the expression.
+    assert_eq!(uint("0xFFFFFFFFFFFFFFFF"), Err(UnexpectedNegative));
+    assert_eq!(uint("(0xFFFF_FFFF_FFFF_FFFF)"), Err(UnexpectedNegative));
+    assert_eq!(uint("0xFFFFFFFFFFFFFFFF + 0"), Err(UnexpectedNegative));
+    assert_eq!(uint("0xFFFFFFFFFFFFFFFF // 2"), Err(UnexpectedNegative));
+    assert_eq!(uint("0xFFFFFFFFFFFFFFFF & 0xF"), Err(UnexpectedNegative));
+    assert_eq!(uint("0x8000000000000000 - 1"), Err(UnexpectedNegative));
+    assert_eq!(uint("0b1111111111111111111111111111111111111111111111111111111111111110"), Err(UnexpectedNegative));
+    assert_eq!(uint("0o1777777777777777777777"), Err(UnexpectedNegative));
+    assert_eq!(MathParse::parse("0xFFFFFFFFFFFFFFFF // 2").unwrap().solve_bits(None, 64), Ok(0));
+    let variables = HashMap::from([
+        ("mask".to_string(), "0xFFFFFFFFFFFFFFFF & 0xFF".to_string()),
+        ("0xFFFFFFFFFFFFFFFF".to_string(), "1".to_string()),
+    ]);
+    let uint_with = |expression: &str| MathParse::parse(expression).unwrap().solve_uint(Some(&variables));
+    assert_eq!(uint_with("mask + 1"), Err(UnexpectedNegative));
+    assert_eq!(uint_with("0xFFFFFFFFFFFFFFFF + 1"), Ok(2));
+    assert_eq!(uint("0x10000000000000000"), Err(InvalidNumber("0x10000000000000000".to_string())));
+    assert_eq!(uint("1 / 2"), Err(ReturnFloatExpectedInt(0.5)));
+    assert_eq!(uint("2 ** 64"), Err(ReturnFloatExpectedInt(18446744073709551616.0)));
+    assert!(matches!(uint("inf"), Err(ReturnFloatExpectedInt(f)) if f.is_infinite()));
+    assert!(matches!(uint("nan"), Err(ReturnFloatExpectedInt(f)) if f.is_nan()));
+}
+
+#[test]
+fn test_solve_bits() {
+    let bits = |expression: &str, width: u8| MathParse::parse(expression).unwrap().solve_bits(None, width);
+    assert_eq!(bits("-1", 8), Ok(0xFF));
+    assert_eq!(bits("-1", 16), Ok(0xFFFF));
+    assert_eq!(bits("-1", 32), Ok(0xFFFFFFFF));
+    assert_eq!(bits("-1", 64), Ok(0xFFFFFFFFFFFFFFFF));
+    for width in [8, 16, 32, 64] {
+        assert_eq!(bits("0", width), Ok(0), "{width}");
+    }
+    assert_eq!(bits("0x1FF", 8), Ok(0xFF));
+    assert_eq!(bits("0x12345", 16), Ok(0x2345));
+    assert_eq!(bits("0x123456789", 32), Ok(0x23456789));
+    assert_eq!(bits("0x123456789", 64), Ok(0x123456789));
+    assert_eq!(bits("~0x0F & 0xFF", 64), Ok(0xF0));
+    assert_eq!(bits("2.0 * 8", 8), Ok(16));
+    assert_eq!(bits("1 / 2", 8), Err(ReturnFloatExpectedInt(0.5)));
+    assert_eq!(bits("1", 12), Err(InvalidBitWidth(12)));
+    assert_eq!(bits("1", 0), Err(InvalidBitWidth(0)));
+    assert_eq!(format!("{:#X}", bits("-1", 16).unwrap()), "0xFFFF");
+    assert_eq!(Number::Int(-2).to_u64_bits(), Ok(u64::MAX - 1));
+    assert_eq!(Number::Float(1.5).to_u64_bits(), Err(ReturnFloatExpectedInt(1.5)));
+}
+
+#[test]
+fn test_operators() {
+    use BinaryOp::*;
+    use UnaryOp::*;
+    let (unary, binary) = MathParse::parse("-a + ~(b - -c) * sum(i, 1, 3, i % 2)").unwrap().operators();
+    assert_eq!(unary, HashSet::from([Minus, Not]));
+    assert_eq!(binary, HashSet::from([Addition, Subtraction, Multiplication, Reminder]));
+    let (unary, binary) = MathParse::parse_rpn("3 4 ^").unwrap().operators();
+    assert!(unary.is_empty());
+    assert_eq!(binary, HashSet::from([BitwiseXor]));
+    let (unary, binary) = MathParse::parse("12").unwrap().operators();
+    assert!(unary.is_empty() && binary.is_empty());
+}
+
+#[test]
+fn test_exponent() {
+    assert_eq!(compute("2**3**2", None), Ok(Number::Int(512)));
+    assert_eq!(compute("(2**3)**2", None), Ok(Number::Int(64)));
+    assert_eq!(compute("2 ↑ 10", None), Ok(Number::Int(1024)));
+    assert_eq!(compute("3 * 2 ** 2", None), Ok(Number::Int(12)));
+    assert_eq!(compute("2 ** 2 * 3", None), Ok(Number::Int(12)));
+    assert_eq!(compute("-2 ** 2", None), Ok(Number::Int(4)));
+    assert_eq!(compute("-(2 ** 3)", None), Ok(Number::Int(-8)));
+    assert_eq!(compute("7 ** 0", None), Ok(Number::Int(1)));
+    assert_eq!(compute("2 ** -1", None), Ok(Number::Float(0.5)));
+    assert_eq!(compute("4 ** 0.5", None), Ok(Number::Int(2)));
+    assert_eq!(compute("2 ** 0.5", None), Ok(Number::Float(2.0_f64.sqrt())));
+    assert_eq!(compute("2.5 ** 2", None), Ok(Number::Float(6.25)));
+    assert_eq!(compute("2 ** 64", None), Ok(Number::Float(18446744073709551616.0)));
+    assert_eq!(compute("0 ** 0", None), Ok(Number::Int(1)));
+    assert_eq!(compute("0 ** -1", None), Err(UnexpectedZero));
+    assert_eq!(compute("2 * * 3", None), Err(MisplacedOperator('*')));
+    assert_eq!(math_solve_int("3 ** 4"), Ok(81));
+    assert_eq!(math_solve_float("9 ** 0.5"), Ok(3.0));
+    assert_eq!(MathParse::parse("2 ** 3").unwrap().solve_value(None), Ok(SolvedValue::Int(8)));
+
+    let parsed = MathParse::parse("a ** b ** c").unwrap();
+    assert_eq!(parsed.to_rpn(), Ok(vec![name_r("a"), name_r("b"), name_r("c"), RPN::Binary(Exponent), RPN::Binary(Exponent)]));
+    assert_eq!(format!("{}", parsed.to_tree().unwrap()), "(a ** (b ** c))");
+    assert_eq!(rpn_slice_to_string(&parsed.to_rpn().unwrap()), "a b c ** **");
+    assert_eq!(MathParse::parse_rpn("2 3 2 ** **").unwrap().solve_int(None), Ok(512));
+    assert_eq!(MathParse::parse_rpn("2 3 ↑").unwrap().solve_int(None), Ok(8));
+}
+
+#[test]
+fn test_literals() {
+    let literals = |expression: &str| MathParse::parse(expression).unwrap().literals();
+    assert_eq!(literals("a + b * c"), vec![]);
+    assert_eq!(literals("2 * x + 2 - y"), vec![Number::Int(2), Number::Int(2)]);
+    assert_eq!(literals("sum(i, 1, 10, i * 0.5) + 'a' + 1e3"),
+        vec![Number::Int(1), Number::Int(10), Number::Float(0.5), Number::Int(97), Number::Float(1000.0)]);
+    assert_eq!(MathParse::parse_rpn("3 x 0x1F + *").unwrap().literals(), vec![Number::Int(3), Number::Int(31)]);
+
+    let spans = MathParse::parse("(0x10 + rate) // f(-3, 2.5)").unwrap().literals_with_spans();
+    assert_eq!(spans, vec![(Number::Int(16), Some(1..5)), (Number::Int(3), Some(20..21)), (Number::Float(2.5), Some(23..26))]);
+    let spans = MathParse::parse("rand() * 4 + sum(k, 1, 2, k)").unwrap().literals_with_spans();
+    assert_eq!(spans, vec![(Number::Int(4), Some(9..10)), (Number::Int(1), Some(20..21)), (Number::Int(2), Some(23..24))]);
+    assert_eq!(MathParse::parse_rpn("1 2 +").unwrap().literals_with_spans(), vec![(Number::Int(1), None), (Number::Int(2), None)]);
+}
+
+#[test]
+fn test_operators_in_any_position() {
+    // Whatever the operator and where it is used, the error should be about
+    // the expression and never be an internal bug.
+    for c in crate::tokenize::MATH_CHARS {
+        let infix = [
+            format!("{c}"), format!("{c}1"), format!("1{c}"), format!("1 {c} 2"), format!("1 {c}{c} 2"),
+            format!("{c}{c}1"), format!("2{c}-1"), format!("({c}1)"), format!("(1{c})"),
+            format!("f({c})"), format!("f(1, {c})"), format!("f({c}, 1)"), format!("sum(i, 1, 2, {c})"),
+        ];
+        for expression in infix {
+            let results = match MathParse::parse(&expression) {
+                Ok(parsed) => vec![parsed.solve_number(None).map(|_| ()), parsed.to_rpn().map(|_| ()), parsed.to_tree().map(|_| ())],
+                Err(e) => vec![Err(e)],
+            };
+            for result in results {
+                assert!(!matches!(result, Err(MathParseInternalBug(_))), "{expression}: {result:?}");
+            }
+        }
+        for expression in [format!("{c}"), format!("1 {c}"), format!("1 2 {c}"), format!("1 2 {c}{c}"), format!("{c} 1 2")] {
+            let result = MathParse::parse_rpn(&expression).and_then(|parsed| parsed.solve_number(None));
+            assert!(!matches!(result, Err(MathParseInternalBug(_))), "{expression}: {result:?}");
+        }
+    }
+
+    assert_eq!(compute("(-)", None), Err(TrailingOperator));
+    assert_eq!(compute("(1+)", None), Err(TrailingOperator));
+    assert_eq!(compute("1 + (2 *)", None), Err(TrailingOperator));
+    assert_eq!(compute("sum(i, 1, 2, -)", None), Err(TrailingOperator));
+    assert_eq!(MathParse::parse("f(1+, 2)").map(|_| ()), Err(TrailingOperator));
+    assert_eq!(MathParse::parse("f(!, 2)").map(|_| ()), Err(TrailingOperator));
+    assert_eq!(MathParse::parse("f(1, 2)").map(|_| ()), Ok(()));
+    assert_eq!(MathParse::parse("f()").map(|_| ()), Ok(()));
+    assert_eq!(compute("1 ~ 2", None), Err(MisplacedOperator('~')));
+    assert_eq!(compute("3!", None), Err(MisplacedOperator('!')));
+    assert_eq!(MathParse::parse_rpn("1 2 ?").map(|_| ()), Err(InvalidRPNOperator('?')));
+    assert_eq!(MathParse::parse_rpn("1 2 (").map(|_| ()), Err(InvalidRPNOperator('(')));
+}
+
+#[test]
+fn test_comparisons() {
+    assert_eq!(compute("1 < 2", None), Ok(Number::Int(1)));
+    assert_eq!(compute("2 < 2", None), Ok(Number::Int(0)));
+    assert_eq!(compute("2 <= 2", None), Ok(Number::Int(1)));
+    assert_eq!(compute("3 > 2.5", None), Ok(Number::Int(1)));
+    assert_eq!(compute("2.5 >= 3", None), Ok(Number::Int(0)));
+    assert_eq!(compute("4 == 4.0", None), Ok(Number::Int(1)));
+    assert_eq!(compute("4 != 4.0", None), Ok(Number::Int(0)));
+    assert_eq!(compute("0.1 + 0.2 == 0.3", None), Ok(Number::Int(0)));
+    assert_eq!(compute("9007199254740993 > 9007199254740992", None), Ok(Number::Int(1)));
+    assert_eq!(compute("1 ≤ 2 ≥ 1", None), Ok(Number::Int(1)));
+    assert_eq!(compute("3 ⩵ 3 ≠ 0", None), Ok(Number::Int(1)));
+    assert_eq!(compute("nan == nan", None), Ok(Number::Int(0)));
+    assert_eq!(compute("nan != nan", None), Ok(Number::Int(1)));
+
+    // Comparisons come after the arithmetic and the shifts, but before the
+    // bitwise operators.
+    assert_eq!(compute("1 + 2 < 2 * 2", None), Ok(Number::Int(1)));
+    assert_eq!(compute("1 << 2 > 3", None), Ok(Number::Int(1)));
+    assert_eq!(compute("1 < 2 == 2 < 3", None), Ok(Number::Int(1)));
+    assert_eq!(compute("6 & 3 == 3", None), Ok(Number::Int(0)));
+    assert_eq!(compute("~1 == -2", None), Ok(Number::Int(1)));
+    assert_eq!(compute("1 = 1", None), Err(BadOperatorHint('=', "==")));
+    assert_eq!(compute("1 === 1", None), Err(MisplacedOperator('=')));
+    assert_eq!(compute("1 <== 1", None), Err(MisplacedOperator('=')));
+
+    let parsed = MathParse::parse("a <= b != c").unwrap();
+    assert_eq!(parsed.to_rpn(), Ok(vec![name_r("a"), name_r("b"), RPN::Binary(LessOrEqual), name_r("c"), RPN::Binary(NotEqual)]));
+    assert_eq!(format!("{}", parsed.to_tree().unwrap()), "((a <= b) != c)");
+    assert_eq!(rpn_slice_to_string(&parsed.to_rpn().unwrap()), "a b <= c !=");
+
+    let equation = MathParse::parse_equation("x + (1 <= 2) = 4").unwrap();
+    assert_eq!(equation.solve_for("x", None), Ok(Number::Int(3)));
+    assert_eq!(MathParse::parse_equation("x == 1").map(|_| ()), Err(MisplacedOperator('=')));
+    assert_eq!(MathParse::parse_equation("x != 1 = y >= 2").map(|_| ()), Ok(()));
+}
+
+#[test]
+fn test_logical_operators() {
+    assert_eq!(compute("1 && 2", None), Ok(Number::Int(1)));
+    assert_eq!(compute("1 && 0", None), Ok(Number::Int(0)));
+    assert_eq!(compute("0 || 0", None), Ok(Number::Int(0)));
+    assert_eq!(compute("0 || -3", None), Ok(Number::Int(1)));
+    assert_eq!(compute("1.5 && 0", None), Ok(Number::Int(0)));
+    assert_eq!(compute("0.5 || 0.0", None), Ok(Number::Int(1)));
+    assert_eq!(compute("1 ∧ 2 ∨ 0", None), Ok(Number::Int(1)));
+    assert_eq!(compute("nan && 1", None), Ok(Number::Int(1)));
+    assert_eq!(compute("0 && 1/0", None), Err(UnexpectedZero));
+
+    // `&&` and `||` come after every other operator, `&&` first.
+    assert_eq!(compute("1 || 1 && 0", None), Ok(Number::Int(1)));
+    assert_eq!(compute("(1 || 1) && 0", None), Ok(Number::Int(0)));
+    assert_eq!(compute("2 & 1 || 4 | 0", None), Ok(Number::Int(1)));
+    assert_eq!(compute("1 < 2 && 3 > 4", None), Ok(Number::Int(0)));
+    assert_eq!(compute("1 &&& 1", None), Err(MisplacedOperator('&')));
+    assert_eq!(compute("1 ||| 1", None), Err(MisplacedOperator('|')));
+
+    let parsed = MathParse::parse("a || b && c").unwrap();
+    assert_eq!(parsed.to_rpn(), Ok(vec![name_r("a"), name_r("b"), name_r("c"), RPN::Binary(LogicalAnd), RPN::Binary(LogicalOr)]));
+    assert_eq!(format!("{}", parsed.to_tree().unwrap()), "(a || (b && c))");
+    assert_eq!(rpn_slice_to_string(&parsed.to_rpn().unwrap()), "a b c && ||");
+    assert!(MathParse::parse_rpn("a b c && ||").unwrap() == parsed);
+    assert!(MathParse::parse_rpn("a b c ∧ ∨").unwrap() == parsed);
+
+    // `!` is the logical not, `~` stays the bitwise not.
+    assert_eq!(compute("!0", None), Ok(Number::Int(1)));
+    assert_eq!(compute("!5", None), Ok(Number::Int(0)));
+    assert_eq!(compute("!0.0", None), Ok(Number::Int(1)));
+    assert_eq!(compute("!2.5", None), Ok(Number::Int(0)));
+    assert_eq!(compute("!!7", None), Ok(Number::Int(1)));
+    assert_eq!(compute("~5", None), Ok(Number::Int(-6)));
+    assert_eq!(compute("~2.5", None), Err(BinaryOpOnFloat(2.5, '~')));
+    let parsed = MathParse::parse("!a + ~b").unwrap();
+    assert_eq!(parsed.to_rpn(), Ok(vec![name_r("a"), RPN::Unary(LogicalNot), name_r("b"), RPN::Unary(Not), RPN::Binary(Addition)]));
+    assert_eq!(format!("{}", parsed.to_tree().unwrap()), "(!a + ~b)");
+    assert_eq!(rpn_slice_to_string(&parsed.to_rpn().unwrap()), "a ! b not +");
+    assert!(MathParse::parse_rpn("a ! b not +").unwrap() == parsed);
+}
+
+#[test]
+fn test_needs_parens_under() {
+    let parsed = |expression: &str| MathParse::parse(expression).unwrap();
+    for side in [OperandSide::Left, OperandSide::Right] {
+        assert!(parsed("a+b").needs_parens_under(Multiplication, side));
+        assert!(!parsed("a*b").needs_parens_under(Multiplication, side));
+        assert!(!parsed("a*b").needs_parens_under(Addition, side));
+        assert!(!parsed("a").needs_parens_under(Exponent, side));
+        assert!(!parsed("-(a+b)").needs_parens_under(Exponent, side));
+        assert!(!parsed("max(a, b+1)").needs_parens_under(Exponent, side));
+        assert!(parsed("a ? b : c").needs_parens_under(LogicalOr, side));
+    }
+    assert!(!parsed("a-b").needs_parens_under(Subtraction, OperandSide::Left));
+    assert!(parsed("a-b").needs_parens_under(Subtraction, OperandSide::Right));
+    assert!(parsed("a**b").needs_parens_under(Exponent, OperandSide::Left));
+    assert!(!parsed("a**b").needs_parens_under(Exponent, OperandSide::Right));
+    assert_eq!(parsed("(a+b)").precedence_root(), Some(Addition));
+    assert_eq!(parsed("a < b").precedence_root(), Some(LessThan));
+    assert_eq!(parsed("!a").precedence_root(), None);
+    assert_eq!(parsed("sum(i, 1, 3, i+1)").precedence_root(), None);
+
+    // Splicing the expression without parenthesis only changes how it is
+    // parsed when they are needed, or when both operations are the same
+    // associative one.
+    let (_, binary) = parsed("a ** b * c / d // e % f + g - h << i >> j < k <= l > m >= n == o != p & q ^ r | s && t || u").operators();
+    for child in &binary {
+        let expression = parsed(&format!("a {} b", child.as_str()));
+        for parent in &binary {
+            let with_parens = parsed(&format!("(a {} b) {} c", child.as_str(), parent.as_str()));
+            let without = parsed(&format!("a {} b {} c", child.as_str(), parent.as_str()));
+            assert_eq!(expression.needs_parens_under(*parent, OperandSide::Left), with_parens != without, "{child:?} {parent:?}");
+            let with_parens = parsed(&format!("c {} (a {} b)", parent.as_str(), child.as_str()));
+            let without = parsed(&format!("c {} a {} b", parent.as_str(), child.as_str()));
+            let needed = expression.needs_parens_under(*parent, OperandSide::Right);
+            assert!(needed == (with_parens != without) || (child == parent && !needed), "{child:?} {parent:?}");
+        }
+    }
+}
+
+#[test]
+fn test_eval_functions() {
+    let variables = HashMap::from([("a".to_string(), "6".to_string())]);
+    for expression in ["3*4+1", "7/2", "a*a", "1/0", "3*", "2.5 & 1", "(1", "x"] {
+        let parsed = MathParse::parse(expression);
+        let parsed = parsed.as_ref().map_err(Clone::clone);
+        assert_eq!(eval_int(expression), parsed.clone().and_then(|p| p.solve_int(None)), "{expression}");
+        assert_eq!(eval_float(expression), parsed.clone().and_then(|p| p.solve_float(None)), "{expression}");
+        assert_eq!(eval_int_with(expression, &variables), parsed.clone().and_then(|p| p.solve_int(Some(&variables))), "{expression}");
+        assert_eq!(eval_float_with(expression, &variables), parsed.and_then(|p| p.solve_float(Some(&variables))), "{expression}");
+    }
+    assert_eq!(eval_int("3*4+1"), Ok(13));
+    assert_eq!(eval_float("7/2"), Ok(3.5));
+    assert_eq!(eval_int_with("a*a", &variables), Ok(36));
+    assert_eq!(eval_int("7/2"), Err(ReturnFloatExpectedInt(3.5)));
+    assert_eq!(eval_int("3*"), Err(EmptyLine));
+}
+
+#[test]
+fn test_implicit_multiplication() {
+    let variables = HashMap::from([("a".to_string(), "3".to_string())]);
+    assert_eq!(compute("2(3+4)", None), Ok(Number::Int(14)));
+    assert_eq!(compute("(1+1)(2+2)", None), Ok(Number::Int(8)));
+    assert_eq!(compute("(a+1)(a-1)", Some(&variables)), Ok(Number::Int(8)));
+    assert_eq!(compute("(a) a", Some(&variables)), Ok(Number::Int(9)));
+    assert_eq!(compute("2 (3) (4)", None), Ok(Number::Int(24)));
+    assert_eq!(compute("0x10(2)", None), Ok(Number::Int(32)));
+    assert_eq!(compute("-2(3)", None), Ok(Number::Int(-6)));
+    assert_eq!(compute("2(3)**2", None), Ok(Number::Int(18)));
+    assert_eq!(compute("sqrt(4)(3)", None), Ok(Number::Int(6)));
+
+    // Same precedence as an explicit `*`, applied from left to right.
+    assert_eq!(compute("6/2(1+2)", None), Ok(Number::Int(9)));
+    assert_eq!(compute("6/2(1+2)", None), compute("6/2*(1+2)", None));
+    assert_eq!(compute("1 + 2(3) - 1", None), Ok(Number::Int(6)));
+
+    // Names that can be function names are still function calls.
+    assert_eq!(compute("a(2)", Some(&variables)), Err(UnknownFunction("a".to_string())));
+    assert_eq!(compute("a*(2)", Some(&variables)), Ok(Number::Int(6)));
+    assert_eq!(parse_rpn("2(x)"), Ok(vec![name_r("2"), name_r("x"), RPN::Binary(Multiplication)]));
+    assert_eq!(MathParse::parse_rpn("2 (3) *").map(|_| ()), Err(InvalidRPNOperator('(')));
+}
+
+#[test]
+fn test_strict_solve() {
+    let map = HashMap::from([("rate".to_string(), "0.5".to_string()), ("Rate".to_string(), "2".to_string())]);
+    let parsed = MathParse::parse("rate * 10").unwrap();
+    assert_eq!(parsed.solve_value(Some(&map)), Ok(SolvedValue::Int(5)));
+    assert_eq!(parsed.solve_value_strict(Some(&map)), Err(UnusedVariables(vec!["Rate".to_string()])));
+    assert_eq!(MathParse::parse("rate * Rate").unwrap().solve_value_strict(Some(&map)), Ok(SolvedValue::Int(1)));
+    assert_eq!(MathParse::parse("1/0").unwrap().solve_value_strict(Some(&map)), Err(UnexpectedZero));
+    assert_eq!(MathParse::parse("2 + 2").unwrap().solve_value_strict(None), Ok(SolvedValue::Int(4)));
+    let unused = vec!["Rate".to_string(), "rate".to_string()];
+    assert_eq!(MathParse::parse("2 + 2").unwrap().solve_value_strict(Some(&map)), Err(UnusedVariables(unused)));
+
+    // Names bound by `sum` shadow the map, and only the chosen value of a
+    // condition is read.
+    let map = HashMap::from([("i".to_string(), "5".to_string()), ("n".to_string(), "3".to_string())]);
+    assert_eq!(MathParse::parse("sum(i, 1, n, i)").unwrap().solve_value_strict(Some(&map)), Err(UnusedVariables(vec!["i".to_string()])));
+    assert_eq!(MathParse::parse("1 ? n : i").unwrap().solve_value_strict(Some(&map)), Err(UnusedVariables(vec!["i".to_string()])));
+
+    let map = HashMap::from([("pi".to_string(), "3".to_string()), ("e".to_string(), "3".to_string())]);
+    let parsed = MathParse::parse("pi + e").unwrap();
+    assert_eq!(parsed.solve_value(Some(&map)), Ok(SolvedValue::Int(6)));
+    assert_eq!(parsed.solve_value_strict(Some(&map)), Err(ShadowedConstant("e".to_string())));
+    let options = MathParseOptions{disable_constants: true, ..MathParseOptions::default()};
+    let parsed = MathParse::parse_with_options("pi + e", &options).unwrap();
+    assert_eq!(parsed.solve_value_strict(Some(&map)), Ok(SolvedValue::Int(6)));
+}
+
+#[test]
+fn test_conditional_operator() {
+    let variables = HashMap::from([("x".to_string(), "5".to_string())]);
+    assert_eq!(compute("x > 3 ? 10 : 20", Some(&variables)), Ok(Number::Int(10)));
+    assert_eq!(compute("x < 3 ? 10 : 20", Some(&variables)), Ok(Number::Int(20)));
+    assert_eq!(compute("x ? 1.5 : 2", Some(&variables)), Ok(Number::Float(1.5)));
+    assert_eq!(compute("1 + 1 ? 2 * 3 : 4 || 0", None), Ok(Number::Int(6)));
+    assert_eq!(compute("(0 ? 1 : 2) + 1", None), Ok(Number::Int(3)));
+    assert_eq!(compute("if(0, 1, 2)", None), Ok(Number::Int(2)));
+    assert_eq!(compute("max(0 ? 1 : 2, 1)", None), Ok(Number::Int(2)));
+
+    // Nested conditions are right associative.
+    assert_eq!(compute("0 ? 1 : 0 ? 2 : 3", None), Ok(Number::Int(3)));
+    assert_eq!(compute("1 ? 0 ? 1 : 2 : 3", None), Ok(Number::Int(2)));
+    assert_eq!(compute("(1 ? 0 : 1) ? 2 : 3", None), Ok(Number::Int(3)));
+    assert_eq!(compute("sum(i, 1, 3, i) > 5 ? 1 : 0 ? 2 : 3", None), Ok(Number::Int(1)));
+
+    // Only the chosen value is solved.
+    assert_eq!(compute("0 ? 1/0 : 2", None), Ok(Number::Int(2)));
+    assert_eq!(compute("1 ? 1/0 : 2", None), Err(UnexpectedZero));
+    let mut session = MathParse::parse("(1 ? 0 : 1) ? 1/0 : sum(i, 1, 3, i ? i : 1/0)").unwrap().start_eval(None);
+    assert_eq!(session.run(1000), EvalStatus::Done(Ok(Number::Int(6))));
+
+    assert_eq!(compute("1 ? 2", None), Err(MisplacedOperator('?')));
+    assert_eq!(compute("1 : 2", None), Err(MisplacedOperator(':')));
+    assert_eq!(compute("1 ? 2 : 3 : 4", None), Err(MisplacedOperator(':')));
+    assert_eq!(compute("1 ? : 3", None), Err(MisplacedOperator(':')));
+    assert_eq!(compute("(1 ? 2) : 3", None), Err(MisplacedOperator('?')));
+    assert_eq!(compute("if(1, 2)", None), Err(InvalidFunctionCall("if".to_string())));
+
+    let parsed = MathParse::parse("a ? b : c ? d : e").unwrap();
+    let expected = vec![name_r("a"), name_r("b"), name_r("c"), name_r("d"), name_r("e"), RPN::Call("if".to_string(), 3), RPN::Call("if".to_string(), 3)];
+    assert_eq!(parsed.to_rpn(), Ok(expected));
+    assert_eq!(format!("{}", parsed.to_tree().unwrap()), "(a ? b : (c ? d : e))");
+    assert_eq!(rpn_slice_to_string(&parsed.to_rpn().unwrap()), "a b c d e if@3 if@3");
+    assert!(MathParse::parse_rpn("a b c d e if@3 if@3").unwrap() == parsed);
+    let tree = MathParse::parse("(a ? b : c) ? d + 1 : e").unwrap().to_tree().unwrap();
+    assert_eq!(tree.to_expression_string(), "((a ? b : c) ? (d + 1) : e)");
+    assert_eq!(MathParse::parse(&tree.to_expression_string()).unwrap().to_tree(), Ok(tree));
+}
+
+#[test]
+// The random source of the options can change, but it is not hashed.
+#[allow(clippy::mutable_key_type)]
+fn test_parsed_as_key() {
+    use std::hash::{BuildHasher, RandomState};
+    let hasher = RandomState::new();
+    let spaced = MathParse::parse("1 + 2").unwrap();
+    let packed = MathParse::parse("1+2").unwrap();
+    assert!(spaced == packed);
+    assert_eq!(hasher.hash_one(&spaced), hasher.hash_one(&packed));
+
+    let mut results = HashMap::<MathParse, f64>::new();
+    results.insert(spaced, 3.0);
+    assert_eq!(results.get(&packed), Some(&3.0));
+    assert_eq!(results.get(&MathParse::parse("((1)) + (2)").unwrap()), Some(&3.0));
+    assert_eq!(results.get(&MathParse::parse_rpn("1 2 +").unwrap()), Some(&3.0));
+    assert_eq!(results.get(&MathParse::parse("2 + 1").unwrap()), None);
+    assert_eq!(results.get(&MathParse::parse("1 + 2 + 0").unwrap()), None);
+    results.insert(MathParse::parse("1+2").unwrap(), 4.0);
+    assert_eq!(results.len(), 1);
+}
+
+#[test]
+fn test_interned_names() {
+    // `||` has the lowest precedence, so the long chain is parsed quickly.
+    let expression = vec!["x"; 10000].join(" || ") + " * x";
+    let parsed = MathParse::parse(&expression).unwrap();
+    assert_eq!(parsed.names(), ["x"]);
+
+    let rpn = parsed.to_rpn().unwrap();
+    assert_eq!(rpn.len(), 20001);
+    assert_eq!(rpn[..4], [name_r("x"), name_r("x"), RPN::Binary(LogicalOr), name_r("x")]);
+    assert_eq!(rpn[19997..], [name_r("x"), name_r("x"), RPN::Binary(Multiplication), RPN::Binary(LogicalOr)]);
+    let variables = HashMap::from([("x".to_string(), "5".to_string())]);
+    assert_eq!(parsed.solve_int(Some(&variables)), Ok(1));
+    assert_eq!(format!("{}", parsed.to_tree().unwrap()).matches('x').count(), 10001);
+    assert!(MathParse::parse_rpn(&rpn_slice_to_string(&rpn)).unwrap() == parsed);
+}
+
+#[test]
+fn test_unary_chains() {
+    let solve = |expression: &str| MathParse::parse(expression)?.solve_value(None);
+    for x in ["0", "5", "-7", "2.5", "4611686018427387904"] {
+        for chain in ["--", "---", "!!", "!!!", "!!!!", "-!-", "-+!+!-", "+-+-+", "!-!", "~!~", "~~", "~~~", "-~!~-", "-−-+++-"] {
+            let reduced = format!("{chain}({x})");
+            let expanded = chain.chars().rev().fold(x.to_string(), |acc, op| format!("{op}({acc})"));
+            assert_eq!(solve(&reduced), solve(&expanded), "{reduced}");
+        }
+    }
+    let tree = |expression: &str| format!("{}", MathParse::parse(expression).unwrap().to_tree().unwrap());
+    assert_eq!(tree("-−-+++-a"), "a");
+    assert_eq!(tree("+-+--a * !!!b"), "(-a * !b)");
+    assert_eq!(tree("!!!!!!x"), "!!x");
+    assert_eq!(tree("~~~~~x"), "~x");
+    assert_eq!(tree(&format!("{}a", "-".repeat(10_001))), "-a");
+    assert_eq!(tree(&format!("{}{}x", "-+".repeat(5_000), "!".repeat(5_000))), "!!x");
+}
+
+#[test]
+fn test_errors_transport() {
+    let errors = vec![
+        InvalidNumber("x".to_string()),
+        BinaryOpOnFloat(f64::NAN, '&'),
+        BadOperatorHint('<', "<<"),
+        MalformedNumber("2e".to_string(), NumberErrorKind::MissingExponentDigits),
+        InvalidCharacter('$', 3),
+    ];
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let worker_errors = errors.clone();
+    std::thread::spawn(move || {
+        for error in worker_errors {
+            sender.send(error).unwrap();
+        }
+    }).join().unwrap();
+    let received: Vec<MathParseErrors> = receiver.iter().collect();
+    assert_eq!(received.len(), errors.len());
+    for (error, received) in errors.iter().zip(&received) {
+        assert_eq!(error.message_parts(), received.message_parts());
+    }
+}
+
+#[test]
+fn test_message_parts() {
+    let no_args: Vec<String> = vec![];
+    assert_eq!(UnclosedParenthesis.message_parts(), ("unclosed_parenthesis", no_args.clone()));
+    assert_eq!(UnopenedParenthesis.message_parts(), ("unopened_parenthesis", no_args.clone()));
+    assert_eq!(EmptyLine.message_parts(), ("empty_line", no_args.clone()));
+    assert_eq!(InvalidNumber("abc".to_string()).message_parts(), ("invalid_number", vec!["abc".to_string()]));
+    assert_eq!(MisplacedOperator('*').message_parts(), ("misplaced_operator", vec!["*".to_string()]));
+    assert_eq!(TrailingOperator.message_parts(), ("trailing_operator", no_args.clone()));
+    assert_eq!(IntConversion(1e300).message_parts(), ("int_conversion", vec![1e300.to_string()]));
+    assert_eq!(BinaryOpOnFloat(2.5, '|').message_parts(), ("binary_op_on_float", vec!["2.5".to_string(), "|".to_string()]));
+    assert_eq!(ReturnFloatExpectedInt(-5.5).message_parts(), ("return_float_expected_int", vec!["-5.5".to_string()]));
+    assert_eq!(BadOperatorHint('<', "<<").message_parts(), ("bad_operator_hint", vec!["<".to_string(), "<<".to_string()]));
+    assert_eq!(UnexpectedZero.message_parts(), ("unexpected_zero", no_args.clone()));
+    assert_eq!(UnexpectedNegative.message_parts(), ("unexpected_negative", no_args.clone()));
+    assert_eq!(InvalidRPNOperator('(').message_parts(), ("invalid_rpn_operator", vec!["(".to_string()]));
+    assert_eq!(UnbalancedStack.message_parts(), ("unbalanced_stack", no_args.clone()));
+    assert_eq!(IncompatibleUnits.message_parts(), ("incompatible_units", no_args.clone()));
+    assert_eq!(UnknownFunction("f".to_string()).message_parts(), ("unknown_function", vec!["f".to_string()]));
+    assert_eq!(InvalidFunctionCall("sum".to_string()).message_parts(), ("invalid_function_call", vec!["sum".to_string()]));
+    assert_eq!(TooManyOperations(10).message_parts(), ("too_many_operations", vec!["10".to_string()]));
+    assert_eq!(UnknownOperator("**".to_string()).message_parts(), ("unknown_operator", vec!["**".to_string()]));
+    assert_eq!(MalformedNumber("2e".to_string(), NumberErrorKind::MissingExponentDigits).message_parts(),
+        ("malformed_number", vec!["2e".to_string(), "missing_exponent_digits".to_string()]));
+    assert_eq!(MalformedNumber("12°61'".to_string(), NumberErrorKind::MalformedAngle).message_parts(),
+        ("malformed_number", vec!["12°61'".to_string(), "malformed_angle".to_string()]));
+    assert_eq!(MalformedNumber("1.2.3".to_string(), NumberErrorKind::MultipleDots).message_parts(),
+        ("malformed_number", vec!["1.2.3".to_string(), "multiple_dots".to_string()]));
+    assert_eq!(MalformedNumber("12a".to_string(), NumberErrorKind::UnexpectedChar('a', 2)).message_parts(),
+        ("malformed_number", vec!["12a".to_string(), "unexpected_char".to_string()]));
+    assert_eq!(format!("{}", MalformedNumber("12a".to_string(), NumberErrorKind::UnexpectedChar('a', 2))),
+        "The number `12a` is malformed: the character `a` at position 2 can't be in a number.");
+    assert_eq!(InvalidCharacter('$', 3).message_parts(), ("invalid_character", vec!["$".to_string(), "3".to_string()]));
+    assert_eq!(InvalidFormatSpec("{:q}".to_string()).message_parts(), ("invalid_format_spec", vec!["{:q}".to_string()]));
+    assert_eq!(NoRandomSource.message_parts(), ("no_random_source", no_args.clone()));
+    assert_eq!(InvalidBitWidth(12).message_parts(), ("invalid_bit_width", vec!["12".to_string()]));
+    assert_eq!(CannotIsolateVariable("x".to_string()).message_parts(), ("cannot_isolate_variable", vec!["x".to_string()]));
+    assert_eq!(MisplacedWord("of".to_string()).message_parts(), ("misplaced_word", vec!["of".to_string()]));
+    assert_eq!(DomainError("asin".to_string()).message_parts(), ("domain_error", vec!["asin".to_string()]));
+    assert_eq!(CyclicDefinition("total".to_string()).message_parts(), ("cyclic_definition", vec!["total".to_string()]));
+    let unused = vec!["a".to_string(), "b".to_string()];
+    assert_eq!(UnusedVariables(unused.clone()).message_parts(), ("unused_variables", unused.clone()));
+    assert_eq!(format!("{}", UnusedVariables(unused)), "The named variables `a`, `b` are not used.");
+    assert_eq!(ShadowedConstant("pi".to_string()).message_parts(), ("shadowed_constant", vec!["pi".to_string()]));
+    assert_eq!(InvalidRewrite("DIV".to_string(), "/ 2".to_string()).message_parts(), ("invalid_rewrite", vec!["DIV".to_string(), "/ 2".to_string()]));
+    assert_eq!(FunctionOverflow("lcm".to_string()).message_parts(), ("function_overflow", vec!["lcm".to_string()]));
+    assert_eq!(IntegerOverflow(i64::MAX, '+', 1).message_parts(), ("integer_overflow", vec![i64::MAX.to_string(), "+".to_string(), "1".to_string()]));
+    assert_eq!(DecimalOutOfRange("1e300".to_string()).message_parts(), ("decimal_out_of_range", vec!["1e300".to_string()]));
+    assert_eq!(DimensionOverflow.message_parts(), ("dimension_overflow", no_args.clone()));
+    assert_eq!(FailedAtValue(2, Box::new(UnexpectedZero)).message_parts(), ("failed_at_value", vec!["2".to_string(), "unexpected_zero".to_string()]));
+    assert_eq!(format!("{}", FailedAtValue(2, Box::new(UnexpectedZero))), "Solving for the value at index 2 failed: There is a 0 in an operation where it is invalid such as a division or a remainder.");
+    assert_eq!(FunctionOnFloat(1.5, "rotl".to_string()).message_parts(), ("function_on_float", vec!["1.5".to_string(), "rotl".to_string()]));
+    assert_eq!(MathParseInternalBug("oops".to_string()).message_parts(), ("math_parse_internal_bug", vec!["oops".to_string()]));
 }
 
 #[test]
@@ -675,3 +4081,4 @@ fn test_readme_example() {
         format!("{parsed}").as_str(),
         "(((2 + 3) * 2) / 5)".to_string());
 }
+
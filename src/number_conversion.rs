@@ -15,7 +15,7 @@ pub fn u_to_i(u: usize) -> Result<isize, MathParseErrors> {
     if let Ok(i) = TryInto::<isize>::try_into(u) {
         Ok(i)
     } else {
-        return Err(MathParseInternalBug(format!("{u} should be made as isize.")));
+        Err(MathParseInternalBug(format!("{u} should be made as isize.")))
     }
 }
 
@@ -27,9 +27,7 @@ pub fn f_to_i(f: f64) -> Result<i64, MathParseErrors> {
     }
     let f = f.round();
 
-    if f > INTEGRAL_LIMIT {
-        Err(IntConversion(f))
-    } else if f < -1.0 * INTEGRAL_LIMIT {
+    if !(-INTEGRAL_LIMIT..=INTEGRAL_LIMIT).contains(&f) {
         Err(IntConversion(f))
     } else {
         Ok(f as i64)
@@ -47,6 +45,27 @@ pub fn f_to_i_strict(f: f64) -> Result<i64, MathParseErrors> {
     }
 }
 
+/// Convert a float to an integer if it is within `tolerance` of it. With a
+/// tolerance of 0, or a tolerance that is not a positive number, this is the
+/// same as `f_to_i_strict`.
+pub fn f_to_i_tolerant(f: f64, tolerance: f64) -> Result<i64, MathParseErrors> {
+    if tolerance.is_nan() || tolerance <= 0.0 {
+        return f_to_i_strict(f);
+    }
+    let rounded = f.round();
+    if (f - rounded).abs() <= tolerance {
+        f_to_i(rounded)
+    } else {
+        Err(ReturnFloatExpectedInt(f))
+    }
+}
+
+/// Return true if the integer is small enough for all integers of its
+/// magnitude to be exactly represented as floats.
+pub fn i_fits_in_f(i: i64) -> bool {
+    i.unsigned_abs() <= INTEGRAL_LIMIT as u64
+}
+
 /// Convert an integer to a float
 pub fn i_to_f(i: i64) -> f64 {
     i as f64
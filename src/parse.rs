@@ -1,9 +1,19 @@
 use crate::tokenize::MathValue::*;
+#[cfg(test)]
 use crate::tokenize::math_token;
+use crate::tokenize::math_token_with_positions;
+use crate::tokenize::math_token_checked;
+use crate::tokenize::split_word_operators;
+use crate::MathParseOptions;
+use crate::MathParseWarning;
+use crate::LenientFix;
+use crate::BinaryOp;
 use crate::tokenize::MathValue;
 use crate::number_conversion::*;
 use crate::MathParseErrors::*;
 use crate::MathParseErrors;
+use crate::{BINARY_PRECEDENCES, binary_char_precedence};
+use crate::solve::CONDITIONAL_FUNCTION;
 
 /// Parse a line of `MathValue` and make it into a tree of operations.
 /// The root of the tree will be kept as the first element of the vector.
@@ -43,59 +53,6 @@ use crate::MathParseErrors;
 //                               
 fn math_parse_tokens(line: &mut [MathValue]) -> Result<(), MathParseErrors> {
 
-    /// Parse unary operators this must be done before any other steps of the
-    /// parsing as the next steps will move around the elements used to
-    /// determine which operators are unary.
-    fn unary_parse(line: &mut [MathValue]) -> Result<(), MathParseErrors> {
-        let mut previous_operator = true;
-        for i in 0..line.len() {
-            match &line[i] {
-                Operator('+') => {
-                    if previous_operator {
-                        let _ = std::mem::replace(&mut line[i], UnaryOperation('+', 1));
-                    }
-                    previous_operator = true;
-                },
-                Operator('-') | Operator('−') => {
-                    if previous_operator {
-                        let _ = std::mem::replace(&mut line[i], UnaryOperation('-', 1));
-                    }
-                    previous_operator = true;
-                },
-                Operator('!') | Operator('~') => {
-                    if previous_operator {
-                        let _ = std::mem::replace(&mut line[i], UnaryOperation('!', 1));
-                    }
-                    previous_operator = true;
-                },
-                Operator('(') => {
-                    previous_operator = true;
-                },
-                Operator(')') => {
-                    previous_operator = false;
-                },
-                Operator(x) => {
-                    if previous_operator {
-                        return Err(MisplacedOperator(*x));
-                    }
-                    previous_operator = true;
-                },
-                Name(_) => {
-                    previous_operator = false;
-                },
-                ParenOpen(_) => {
-                    previous_operator = false;
-                },
-                TrailingError => {},
-                x => {
-                    return Err(MathParseInternalBug(format!("{x:?} should not have been present in unary_parse.")));
-                },
-            }
-        }
-        Ok(())
-    }
-
-
     /// Transform content in parenthesis into a root element.
     fn paren_parse(line: &mut [MathValue]) -> Result<(), MathParseErrors> {
 
@@ -115,6 +72,10 @@ fn math_parse_tokens(line: &mut [MathValue]) -> Result<(), MathParseErrors> {
                         return Err(UnopenedParenthesis);
                     };
                     let size_between_paren = i - paren_open_index - 1;
+                    if paren_open_index > 0 && matches!(line[paren_open_index-1], Name(name) if is_function_name(name)) {
+                        call_parse(line, paren_open_index, i)?;
+                        return Ok(true);
+                    }
                     let (before_used, used_slice_and_end) = line.split_at_mut(paren_open_index+1);
                     let (used_slice, after_used) = used_slice_and_end.split_at_mut(size_between_paren);
                     all_but_paren_parse(used_slice)?;
@@ -126,13 +87,52 @@ fn math_parse_tokens(line: &mut [MathValue]) -> Result<(), MathParseErrors> {
                 }
                 i += 1;
             }
-            if let Some(_) = maybe_paren_open_index {
+            if maybe_paren_open_index.is_some() {
                 Err(UnclosedParenthesis)
             } else {
                 Ok(false)
             }
         }
 
+        /// Parse a function call whose arguments are between the given
+        /// parenthesis. The name of the function is just before the opening
+        /// parenthesis.
+        fn call_parse(line: &mut [MathValue], paren_open_index: usize, paren_close_index: usize) -> Result<(), MathParseErrors> {
+            let name = if let Name(name) = line[paren_open_index-1] {
+                name
+            } else {
+                return Err(MathParseInternalBug(format!("{:?} should have been a function name.", line[paren_open_index-1])));
+            };
+
+            // Looking for the commas separating the arguments, skipping over
+            // already parsed parenthesis.
+            let mut separators = vec![paren_close_index];
+            let mut index = paren_close_index;
+            while index > paren_open_index + 1 {
+                index -= 1;
+                match line[index] {
+                    Operator(',') => separators.push(index),
+                    ParenClose(size) => index -= size,
+                    _ => {},
+                }
+            }
+            separators.push(paren_open_index);
+            separators.reverse();
+
+            let mut offsets = vec![];
+            if paren_close_index - paren_open_index > 1 {
+                for bounds in separators.windows(2) {
+                    all_but_paren_parse(&mut line[bounds[0]+1..bounds[1]])?;
+                    offsets.push(u_to_i(bounds[0] + 1 - (paren_open_index - 1))?);
+                }
+            }
+
+            line[paren_open_index-1] = FunctionCall(name, offsets);
+            line[paren_open_index] = ParenOpen(1);
+            line[paren_close_index] = ParenClose(paren_close_index - paren_open_index); // Jumping back to the opening parenthesis as there might be no arguments.
+            Ok(())
+        }
+
         while single_paren_parse(line)? {}
         Ok(())
     }
@@ -152,26 +152,35 @@ fn math_parse_tokens(line: &mut [MathValue]) -> Result<(), MathParseErrors> {
         enum ParseSteps {
             /// We want to solve a range. In it we will find an operator and
             /// split each sides in `BlockSolving` and the middle in
-            /// `OperatorReading`.
-            BlockSolving(IndexRange),
+            /// `OperatorReading`. The range comes with the lowest precedence its
+            /// operators can have, which is 0 if it can contain conditional
+            /// expressions, so that the search for the operator can stop early.
+            BlockSolving(IndexRange, u8),
 
             /// An operator, with it we only need to move around the operator so
             /// that it ends up on the front.
             OperatorReading{range: IndexRange, index: usize},
+
+            /// A conditional expression, with the index of its `?` and `:`. It
+            /// is made into a call to `if` at the front of the range.
+            ConditionalReading{range: IndexRange, question: usize, colon: usize},
         } use ParseSteps::*;
 
         /// Processes all the tasks in the given stack until it's empty.
         fn solve_tasks(line: &mut [MathValue], tasks_stack: &mut Vec<ParseSteps>) -> Result<(), MathParseErrors> {
-            while tasks_stack.len() != 0 {
+            while !tasks_stack.is_empty() {
                 match tasks_stack.pop() {
                     Some(OperatorReading{range, index}) => {
                         make_op(line, &range, index)?;
                     },
-                    Some(BlockSolving(range)) => {
-                        solve_block(line, &range, tasks_stack)?;
+                    Some(ConditionalReading{range, question, colon}) => {
+                        make_conditional(line, &range, question, colon)?;
+                    },
+                    Some(BlockSolving(range, lowest_possible)) => {
+                        solve_block(line, &range, tasks_stack, lowest_possible)?;
                     },
                     None => {
-                        return Err(MathParseInternalBug(format!("Error, the stack is empty in solve_tasks.")));
+                        return Err(MathParseInternalBug("Error, the stack is empty in solve_tasks.".to_string()));
                     },
                 }
             }
@@ -179,20 +188,20 @@ fn math_parse_tokens(line: &mut [MathValue]) -> Result<(), MathParseErrors> {
         }
 
         /// To solve a bloc, search for all operators and process them.
-        fn solve_block(line: &mut [MathValue], range: &IndexRange, tasks_stack: &mut Vec<ParseSteps>) -> Result<(), MathParseErrors> {
-            if parse_op(line, &['|'], range, tasks_stack)? { return Ok(()); }
-            if parse_op(line, &['^'], range, tasks_stack)? { return Ok(()); }
-            if parse_op(line, &['&'], range, tasks_stack)? { return Ok(()); }
-            if parse_op(line, &['≪', '≫', '<', '>'], range, tasks_stack)? { return Ok(()); }
-            if parse_op(line, &['+', '-', '−'], range, tasks_stack)? { return Ok(()); }
-            parse_op(line, &['/', '∕', '⁄', '÷', '*', '×', '·', '%', '⟌'], range, tasks_stack)?;
+        fn solve_block(line: &mut [MathValue], range: &IndexRange, tasks_stack: &mut Vec<ParseSteps>, lowest_possible: u8) -> Result<(), MathParseErrors> {
+            parse_op(line, range, tasks_stack, lowest_possible)?;
             Ok(())
         }
 
-        /// Parse a line of math from left to right, if any operator from the
-        /// list if found, calls `make_tasks_from_op` on it.
-        /// Handles the special cases of 1 or 2 elements in the line.
-        fn parse_op(line: &mut [MathValue], ops: &[char], range: &IndexRange, tasks_stack: &mut Vec<ParseSteps>) -> Result<bool, MathParseErrors> {
+        /// Parse a line of math from right to left, looking for the last
+        /// operator with the lowest precedence, and calls `make_tasks_from_op`
+        /// on it. Conditional expressions have an even lower precedence, so
+        /// the first `?` and its `:` are used first if there are any, which
+        /// makes them right associative. The search stops at the first
+        /// operator with the lowest possible precedence of the range, as no
+        /// operator can come after it. Handles the special cases of 1 or 2
+        /// elements in the line.
+        fn parse_op(line: &mut [MathValue], range: &IndexRange, tasks_stack: &mut Vec<ParseSteps>, lowest_possible: u8) -> Result<bool, MathParseErrors> {
             match range.to - range.from {
                 0 => Err(EmptyLine),
                 1 => match line[range.from] {
@@ -200,14 +209,25 @@ fn math_parse_tokens(line: &mut [MathValue]) -> Result<(), MathParseErrors> {
                     _ => Ok(true),
                 },
                 _ => {
+                    let mut lowest: Option<(u8, usize)> = None; // Precedence and index of the operator to process.
+                    let mut conditionals = vec![]; // Index of the `?` and `:`, from right to left.
                     let mut index = range.to - 2;
-                    while index >= range.from+1 {
+                    while index > range.from {
                         match line[index] {
+                            Operator(c @ ('?' | ':')) => {
+                                conditionals.push((c, index));
+                                index -= 1;
+                            },
                             Operator(c) => {
-                                for op in ops {
-                                    if c == *op {
-                                        make_tasks_from_op(range, index, tasks_stack);
-                                        return Ok(true);
+                                if let Some(precedence) = binary_char_precedence(c) {
+                                    // Among operators with the same precedence, the last one is
+                                    // applied last, unless they are right associative.
+                                    let right_associative = || BinaryOp::from_char(c).is_ok_and(BinaryOp::is_right_associative);
+                                    if lowest.is_none_or(|(lowest, _)| precedence < lowest || (precedence == lowest && right_associative())) {
+                                        lowest = Some((precedence, index));
+                                        if precedence == lowest_possible && !right_associative() {
+                                            break;
+                                        }
                                     }
                                 }
                                 index -= 1;
@@ -220,15 +240,27 @@ fn math_parse_tokens(line: &mut [MathValue]) -> Result<(), MathParseErrors> {
                             }
                         }
                     }
-                    Ok(false)
+                    if !conditionals.is_empty() {
+                        let (question, colon) = matching_conditional(conditionals.into_iter().rev())?;
+                        make_tasks_from_conditional(range, question, colon, tasks_stack);
+                        return Ok(true);
+                    }
+                    match lowest {
+                        Some((precedence, index)) => {
+                            make_tasks_from_op(range, index, precedence, tasks_stack);
+                            Ok(true)
+                        },
+                        None => Ok(false),
+                    }
                 },
             }
         }
 
         /// When an operation index, cut the two parts of the equation that need
         /// solving and add them to the task stack. Also add the operation
-        /// management to it.
-        fn make_tasks_from_op(range: &IndexRange, operator_index: usize, tasks_stack: &mut Vec<ParseSteps>) {
+        /// management to it. No operator of the two parts has a lower
+        /// precedence than the one of the operation.
+        fn make_tasks_from_op(range: &IndexRange, operator_index: usize, precedence: u8, tasks_stack: &mut Vec<ParseSteps>) {
             let part1 = IndexRange{
                 from: range.from,
                 to: operator_index,
@@ -238,8 +270,63 @@ fn math_parse_tokens(line: &mut [MathValue]) -> Result<(), MathParseErrors> {
                 to: range.to,
             };
             tasks_stack.push(OperatorReading{range: range.clone(), index: operator_index});
-            tasks_stack.push(BlockSolving(part1));
-            tasks_stack.push(BlockSolving(part2));
+            tasks_stack.push(BlockSolving(part1, precedence));
+            tasks_stack.push(BlockSolving(part2, precedence));
+        }
+
+        /// Find the index of the first `?` and of its `:` among the `?` and
+        /// `:` of a range, given from left to right.
+        fn matching_conditional(mut conditionals: impl Iterator<Item = (char, usize)>) -> Result<(usize, usize), MathParseErrors> {
+            let question = match conditionals.next() {
+                Some(('?', index)) => index,
+                Some((c, _)) => return Err(MisplacedOperator(c)),
+                None => return Err(MathParseInternalBug("Missing conditional operator.".to_string())),
+            };
+            let mut depth = 0;
+            for (c, index) in conditionals {
+                match c {
+                    '?' => depth += 1,
+                    _ if depth == 0 => return Ok((question, index)),
+                    _ => depth -= 1,
+                }
+            }
+            Err(MisplacedOperator('?'))
+        }
+
+        /// Add the tasks to parse a conditional expression, its three parts
+        /// being solved first.
+        fn make_tasks_from_conditional(range: &IndexRange, question: usize, colon: usize, tasks_stack: &mut Vec<ParseSteps>) {
+            tasks_stack.push(ConditionalReading{range: range.clone(), question, colon});
+            tasks_stack.push(BlockSolving(IndexRange{from: range.from, to: question}, 0));
+            tasks_stack.push(BlockSolving(IndexRange{from: question+1, to: colon}, 0));
+            tasks_stack.push(BlockSolving(IndexRange{from: colon+1, to: range.to}, 0));
+        }
+
+        /// Move the first element of a range, which is the root of the part
+        /// starting there, to the given offset from the start of the range.
+        fn move_header<'a>(line: &mut [MathValue<'a>], range: &IndexRange, root: MathValue<'a>, offset: isize) {
+            let header = std::mem::replace(&mut line[range.from], root);
+            let header = match header {
+                ParenOpen(inside_offset) => ParenOpen(inside_offset - offset),
+                Operation(c, offset_1, offset_2) => Operation(c, offset_1 - offset, offset_2 - offset),
+                UnaryOperation(c, inside_offset) => UnaryOperation(c, inside_offset - offset),
+                FunctionCall(name, offsets) => FunctionCall(name, offsets.iter().map(|x| x - offset).collect()),
+                x => x,
+            };
+            line[range.from + offset as usize] = header;
+        }
+
+        /// To solve a `ConditionalReading` task, make the condition into a
+        /// call to `if` put at the front of the range, the condition being
+        /// moved in place of the `?`. The `:` is removed as the tokens of a
+        /// parenthesis can be scanned again with the range around it.
+        fn make_conditional(line: &mut [MathValue], range: &IndexRange, question: usize, colon: usize) -> Result<(), MathParseErrors> {
+            let question_offset = u_to_i(question - range.from)?;
+            let colon_offset = u_to_i(colon - range.from)?;
+            let call = FunctionCall(CONDITIONAL_FUNCTION, vec![question_offset, question_offset + 1, colon_offset + 1]);
+            move_header(line, range, call, question_offset);
+            line[colon] = ParenOpen(1);
+            Ok(())
         }
 
         /// To solve a `OperatorReading` task, replace the operator into an
@@ -252,34 +339,433 @@ fn math_parse_tokens(line: &mut [MathValue]) -> Result<(), MathParseErrors> {
             };
             let operator_offset = u_to_i(operator_index - range.from)?;
             let operation = Operation(op, operator_offset, operator_offset+1);
-            let part_1_header = std::mem::replace(&mut line[range.from], operation);
-            let part_1_header = match part_1_header {
-                ParenOpen(inside_offset) => ParenOpen(inside_offset - operator_offset),
-                Operation(c, offset_1, offset_2) => Operation(c, offset_1 - operator_offset, offset_2 - operator_offset),
-                UnaryOperation(c, offset) => UnaryOperation(c, offset - operator_offset),
-                x => x,
-            };
-            let _ = std::mem::replace(&mut line[operator_index], part_1_header);
+            move_header(line, range, operation, operator_offset);
             Ok(())
         }
 
-        let mut tasks_stack = vec![BlockSolving(IndexRange{from:0, to:line.len()})];
+        let has_conditionals = line.iter().any(|x| matches!(x, Operator('?' | ':')));
+        let lowest_possible = if has_conditionals { 0 } else { *BINARY_PRECEDENCES.start() };
+        let mut tasks_stack = vec![BlockSolving(IndexRange{from:0, to:line.len()}, lowest_possible)];
         solve_tasks(line, &mut tasks_stack)
     }
 
     unary_parse(line)?;
+    move_percentages(line)?;
     paren_parse(line)?;
     all_but_paren_parse(line)?;
     Ok(())
 }
 
-/// Tokenize and then parse a math expression.
-pub fn math_parse<'a>(expression: &'a str) -> Result<Vec<MathValue<'a>>, MathParseErrors> {
-    let mut tokens = math_token(expression);
-    math_parse_tokens(&mut tokens)?;
+/// Parse unary operators this must be done before any other steps of the
+/// parsing as the next steps will move around the elements used to
+/// determine which operators are unary.
+pub fn unary_parse(line: &mut [MathValue]) -> Result<(), MathParseErrors> {
+    let mut previous_operator = true;
+    let mut previous_open = true; // True after an opening parenthesis or a comma, where a list or an argument starts.
+    let mut previous_function_name = false;
+    let mut paren_are_calls = Vec::<bool>::new(); // For each opened parenthesis, true if it is a function call.
+    for index in 0..line.len() {
+        // A `%` after a value is a percentage when no value follows it.
+        let percent = !previous_operator && line[index] == Operator('%') && match line.get(index + 1) {
+            None | Some(TrailingError) | Some(Operator(')' | ',' | '?' | ':')) => true,
+            Some(Operator(c)) => binary_char_precedence(*c).is_some(),
+            _ => false,
+        };
+        let token = &mut line[index];
+        let function_name = matches!(token, Name(name) if is_function_name(name));
+        match token {
+            Operator('%') if percent => {
+                // Moved in front of its operand by `move_percentages`.
+                *token = UnaryOperation('%', 1);
+            },
+            Operator('+') => {
+                if previous_operator {
+                    *token = UnaryOperation('+', 1);
+                }
+                previous_operator = true;
+            },
+            Operator('-') | Operator('−') => {
+                if previous_operator {
+                    *token = UnaryOperation('-', 1);
+                }
+                previous_operator = true;
+            },
+            Operator(c @ ('!' | '~')) => {
+                if !previous_operator {
+                    return Err(MisplacedOperator(*c));
+                }
+                *token = UnaryOperation(*c, 1);
+            },
+            Operator('√') => {
+                if !previous_operator {
+                    return Err(MisplacedOperator('√'));
+                }
+                *token = UnaryOperation('√', 1);
+            },
+            Operator('(') => {
+                paren_are_calls.push(previous_function_name);
+                previous_operator = true;
+            },
+            Operator(',') => {
+                if paren_are_calls.last() != Some(&true) {
+                    return Err(MisplacedOperator(','));
+                }
+                if previous_operator && !previous_open {
+                    return Err(TrailingOperator);
+                }
+                previous_operator = true;
+            },
+            Operator(')') => {
+                if previous_operator && !previous_open {
+                    return Err(TrailingOperator);
+                }
+                paren_are_calls.pop();
+                previous_operator = false;
+            },
+            Operator(x) => {
+                if previous_operator {
+                    return Err(MisplacedOperator(*x));
+                }
+                previous_operator = true;
+            },
+            Name(_) => {
+                previous_operator = false;
+            },
+            ParenOpen(_) => {
+                // Second character of an operator such as `//`, which
+                // was already read with the first one.
+                continue;
+            },
+            TrailingError => {},
+            x => {
+                return Err(MathParseInternalBug(format!("{x:?} should not have been present in unary_parse.")));
+            },
+        }
+        previous_open = matches!(token, Operator('(' | ','));
+        previous_function_name = function_name;
+    }
+    collapse_unary_chains(line);
+    Ok(())
+}
+
+/// Move the percentages found by `unary_parse` in front of their operand, as
+/// the other unary operators, so that `(a+1)%` is read as `%(a+1)`.
+fn move_percentages(line: &mut [MathValue]) -> Result<(), MathParseErrors> {
+    for index in 0..line.len() {
+        if line[index] != UnaryOperation('%', 1) {
+            continue;
+        }
+        let start = index.checked_sub(1)
+            .and_then(|end| value_start(line, end))
+            .ok_or(MathParseInternalBug("Percentage without an operand.".to_string()))?;
+        line[start..=index].rotate_right(1);
+    }
+    Ok(())
+}
+
+/// Reduce the chains of unary operators that follow each other. The `+`
+/// are removed and pairs of `-` cancel. Pairs of `~` also cancel, but two
+/// of them are kept if there was any so that using `~` on a float is
+/// still an error. In the same way, `!!` is kept as it turns any value into
+/// 0 or 1, but a third `!` cancels with one of them. The removed operators
+/// are replaced by `ParenOpen(1)` pointing to the next token.
+fn collapse_unary_chains(line: &mut [MathValue]) {
+    let mut index = 0;
+    while index < line.len() {
+        let chain_end = index + line[index..].iter().take_while(|x| matches!(x, UnaryOperation(_, 1))).count();
+        if chain_end == index {
+            index += 1;
+            continue;
+        }
+        let mut reduced = Vec::<char>::new();
+        for token in &line[index..chain_end] {
+            match token {
+                UnaryOperation('+', _) => {},
+                UnaryOperation(op, _) => reduced.push(*op),
+                _ => {},
+            }
+            if reduced.ends_with(&['-', '-']) || reduced.ends_with(&['~', '~', '~']) || reduced.ends_with(&['!', '!', '!']) {
+                reduced.truncate(reduced.len() - 2);
+            }
+        }
+        let reduced_start = chain_end - reduced.len();
+        for (i, token) in line[index..chain_end].iter_mut().enumerate() {
+            *token = match i.checked_sub(reduced_start - index) {
+                Some(reduced_index) => UnaryOperation(reduced[reduced_index], 1),
+                None => ParenOpen(1),
+            };
+        }
+        index = chain_end;
+    }
+}
+
+/// Make an expression that is being typed complete, as done by
+/// `MathParse::parse_lenient`. A binary operator at the end of the expression
+/// is removed and the parenthesis that are still open are closed.
+pub fn lenient_fix(expression: &str) -> (String, Vec<LenientFix>) {
+    let (tokens, positions) = math_token_with_positions(expression);
+    // The second character of operators such as `//` is skipped.
+    let read: Vec<(&MathValue, usize)> = tokens.iter().zip(positions)
+        .filter(|(token, _)| !matches!(token, ParenOpen(_) | TrailingError))
+        .collect();
+
+    let mut fixes = vec![];
+    let mut fixed = expression.to_string();
+    let mut end = read.len();
+    if let [.., (Name(_) | Operator(')'), _), (Operator(c), position)] = read[..] {
+        // A `%` after a value is a percentage and not a trailing operator.
+        if BinaryOp::from_char(*c).is_ok() && *c != '%' {
+            fixes.push(LenientFix::IgnoredTrailingOperator(position..expression.trim_end().len()));
+            fixed.truncate(position);
+            end -= 1;
+        }
+    }
+
+    let mut open_parens = vec![];
+    for (token, position) in &read[..end] {
+        match token {
+            Operator('(') => open_parens.push(*position),
+            Operator(')') => {
+                open_parens.pop();
+            },
+            _ => {},
+        }
+    }
+    for position in open_parens.into_iter().rev() {
+        fixes.push(LenientFix::ClosedParenthesis(position));
+        fixed.push(')');
+    }
+    (fixed, fixes)
+}
+
+/// Look for the operators of the expression that deserve a warning.
+pub fn parse_warnings(expression: &str) -> Vec<MathParseWarning> {
+    let (tokens, positions) = math_token_with_positions(expression);
+    let mut ret = vec![];
+    for (token, position) in tokens.iter().zip(positions) {
+        if let Operator('^') = token {
+            ret.push(MathParseWarning::ConfusingOperator('^', position..position+1));
+        }
+    }
+    ret
+}
+
+/// Return true if the name can be the name of a function if it is followed by
+/// parenthesis.
+pub fn is_function_name(name: &str) -> bool {
+    let name = name.trim();
+    match name.chars().next() {
+        Some(c) if c.is_alphabetic() || c == '_' => name.chars().all(|c| c.is_alphanumeric() || c == '_'),
+        _ => false,
+    }
+}
+
+/// Tokenize and then parse a math expression. As the expression can be a list
+/// of comma-separated expressions, a parsed line is returned for each element
+/// of the list.
+pub fn math_parse<'a>(expression: &'a str, options: &MathParseOptions) -> Result<Vec<Vec<MathValue<'a>>>, MathParseErrors> {
+    let (tokens, positions) = math_token_checked(expression, options, &[])?;
+    let (tokens, _) = split_word_operators(tokens, positions);
+    parse_token_list(tokens, options)
+}
+
+/// Parse already tokenized and checked math expression, as `math_parse` does.
+pub fn parse_token_list<'a>(tokens: Vec<MathValue<'a>>, options: &MathParseOptions) -> Result<Vec<Vec<MathValue<'a>>>, MathParseErrors> {
+    let tokens = if options.percent_of {
+        rewrite_percent_of(tokens)?
+    } else {
+        tokens
+    };
+    let tokens = insert_implicit_multiplications(tokens);
+    let mut ret = split_list(&tokens);
+    for line in &mut ret {
+        math_parse_tokens(line)?;
+    }
+    Ok(ret)
+}
+
+/// Insert the multiplications implied by a value directly followed by a
+/// parenthesis, or by a parenthesis followed by a value, such as in `2(3+4)`
+/// or `(a+1)(a-1)`. A name that can be a function name followed by a
+/// parenthesis is a function call and not a multiplication.
+fn insert_implicit_multiplications(tokens: Vec<MathValue>) -> Vec<MathValue> {
+    let mut ret = Vec::with_capacity(tokens.len());
+    for token in tokens {
+        let implied = match (ret.last(), &token) {
+            (Some(Name(name)), Operator('(')) => !is_function_name(name),
+            (Some(Operator(')')), Operator('(') | Name(_)) => true,
+            _ => false,
+        };
+        if implied {
+            ret.push(Operator('*'));
+        }
+        ret.push(token);
+    }
+    ret
+}
+
+/// Index of the first token of the value ending at the given token, which
+/// is either a name or a closing parenthesis, possibly of a function call.
+fn value_start(tokens: &[MathValue], end: usize) -> Option<usize> {
+    match tokens[end] {
+        Name(_) => Some(end),
+        Operator(')') => {
+            let mut depth = 0;
+            let mut index = end;
+            loop {
+                match tokens[index] {
+                    Operator(')') => depth += 1,
+                    Operator('(') => depth -= 1,
+                    _ => {},
+                }
+                if depth == 0 {
+                    break;
+                }
+                index = index.checked_sub(1)?;
+            }
+            match index.checked_sub(1).map(|i| &tokens[i]) {
+                Some(Name(name)) if is_function_name(name) => Some(index - 1),
+                _ => Some(index),
+            }
+        },
+        _ => None,
+    }
+}
+
+/// Rewrite the percentages of values, such as `15% of 240`, as the
+/// multiplication `(15 * 240 / 100)`. The word `of` must follow a `%` placed
+/// after a value, otherwise it is a `MisplacedWord` error.
+fn rewrite_percent_of(mut tokens: Vec<MathValue>) -> Result<Vec<MathValue>, MathParseErrors> {
+    let misplaced = || MisplacedWord("of".to_string());
+
+    /// Index of the token after the value starting at the given token, unary
+    /// operators included.
+    fn value_end(tokens: &[MathValue], start: usize) -> Option<usize> {
+        let mut index = start;
+        while matches!(tokens.get(index)?, Operator('+' | '-' | '−' | '!' | '~')) {
+            index += 1;
+        }
+        match tokens.get(index)? {
+            Name(name) if is_function_name(name) && tokens.get(index + 1) == Some(&Operator('(')) => index += 1,
+            Name(_) => return Some(index + 1),
+            Operator('(') => {},
+            _ => return None,
+        }
+        let mut depth = 0;
+        loop {
+            match tokens.get(index)? {
+                Operator('(') => depth += 1,
+                Operator(')') => depth -= 1,
+                _ => {},
+            }
+            index += 1;
+            if depth == 0 {
+                return Some(index);
+            }
+        }
+    }
+
+    let mut index = 0;
+    while index < tokens.len() {
+        let Name(name) = tokens[index] else {
+            index += 1;
+            continue;
+        };
+        if !name.split_whitespace().any(|word| word == "of") {
+            index += 1;
+            continue;
+        }
+        let rest = name.trim_start().strip_prefix("of")
+            .filter(|rest| rest.is_empty() || rest.starts_with(char::is_whitespace))
+            .filter(|rest| !rest.split_whitespace().any(|word| word == "of"))
+            .ok_or_else(misplaced)?;
+        if index < 2 || tokens[index - 1] != Operator('%') {
+            return Err(misplaced());
+        }
+        let start = value_start(&tokens, index - 2).ok_or_else(misplaced)?;
+        tokens[index - 1] = Operator('*');
+        if rest.trim().is_empty() {
+            tokens.remove(index);
+        } else {
+            tokens[index] = Name(rest);
+        }
+        let end = value_end(&tokens, index).ok_or_else(misplaced)?;
+        tokens.splice(end..end, [Operator('/'), Name("100"), Operator(')')]);
+        tokens.insert(start, Operator('('));
+        index = end + 4;
+    }
     Ok(tokens)
 }
 
+/// Split a line of tokens on the commas used as list separators. Commas are
+/// list separators when they are not inside of parenthesis, or when the whole
+/// line is inside of a single group of parenthesis. Each returned line ends
+/// with a `TrailingError`.
+fn split_list<'a>(tokens: &[MathValue<'a>]) -> Vec<Vec<MathValue<'a>>> {
+    /// Return the index of the parenthesis closing the one at the start of the
+    /// tokens if any.
+    fn first_group_end(tokens: &[MathValue]) -> Option<usize> {
+        if tokens.first() != Some(&Operator('(')) {
+            return None;
+        }
+        let mut depth = 0;
+        for (index, token) in tokens.iter().enumerate() {
+            match token {
+                Operator('(') => depth += 1,
+                Operator(')') => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(index);
+                    }
+                },
+                _ => {},
+            }
+        }
+        None
+    }
+
+    /// Split the tokens on the commas at the given parenthesis depth.
+    fn split_at_depth<'a>(tokens: &[MathValue<'a>], split_depth: isize) -> Vec<Vec<MathValue<'a>>> {
+        let mut ret = vec![vec![]];
+        let mut depth = 0; // Can go negative on unopened parenthesis, reported when parsing.
+        for token in tokens {
+            match token {
+                Operator('(') => depth += 1,
+                Operator(')') => depth -= 1,
+                Operator(',') if depth == split_depth => {
+                    ret.push(vec![]);
+                    continue;
+                },
+                _ => {},
+            }
+            if let Some(last) = ret.last_mut() {
+                last.push(token.clone());
+            }
+        }
+        for line in &mut ret {
+            line.push(TrailingError);
+        }
+        ret
+    }
+
+    let content = &tokens[..tokens.len()-1]; // Removing the trailing error.
+    let top_level = split_at_depth(content, 0);
+    if top_level.len() > 1 {
+        return top_level;
+    }
+    match first_group_end(content) {
+        Some(end) if end == content.len()-1 => {
+            let inside = split_at_depth(&content[1..end], 0);
+            if inside.len() > 1 {
+                inside
+            } else {
+                top_level
+            }
+        },
+        _ => top_level,
+    }
+}
+
 
 /* --------------------------------- Testing -------------------------------- */
 
@@ -291,7 +777,7 @@ fn test_math_parse() {
     let math_line = "+88+89";
     let mut tokens = math_token(math_line);
     math_parse_tokens(&mut tokens).unwrap();
-    assert_eq!(tokens, vec![Operation('+', 2, 3), name_p("88"), UnaryOperation('+', -1), name_p("89"), TrailingError]);
+    assert_eq!(tokens, vec![Operation('+', 2, 3), name_p("88"), ParenOpen(-1), name_p("89"), TrailingError]);
 
     let math_line = "-1*2+-3*4";
     let mut tokens = math_token(math_line);
@@ -322,5 +808,153 @@ fn test_math_parse() {
     assert_eq!(math_parse_tokens(&mut math_token("33+*23")), Err(MisplacedOperator('*')));
     assert_eq!(math_parse_tokens(&mut math_token("*2")), Err(MisplacedOperator('*')));
     assert_eq!(math_parse_tokens(&mut math_token("2/")), Err(EmptyLine));
+    assert_eq!(math_parse_tokens(&mut math_token("2,3")), Err(MisplacedOperator(',')));
 }
 
+#[test]
+fn test_split_list() {
+    assert_eq!(split_list(&math_token("1+2")), vec![vec![name_p("1"), Operator('+'), name_p("2"), TrailingError]]);
+    assert_eq!(split_list(&math_token("1,2")), vec![vec![name_p("1"), TrailingError], vec![name_p("2"), TrailingError]]);
+    assert_eq!(split_list(&math_token("(1,2)")), vec![vec![name_p("1"), TrailingError], vec![name_p("2"), TrailingError]]);
+    assert_eq!(split_list(&math_token("(1),(2)")).len(), 2);
+    assert_eq!(split_list(&math_token("(1,2)+1")).len(), 1);
+    assert_eq!(split_list(&math_token("((1,2))")).len(), 1);
+    assert_eq!(math_parse("(1,2)+3", &MathParseOptions::default()), Err(MisplacedOperator(',')));
+    assert_eq!(math_parse("1,,2", &MathParseOptions::default()), Err(EmptyLine));
+    assert_eq!(math_parse("()", &MathParseOptions::default()), Err(EmptyLine));
+}
+
+#[test]
+fn test_collapse_unary_chains() {
+    let mut tokens = math_token("-+-!!!-+!a");
+    math_parse_tokens(&mut tokens).unwrap();
+    assert_eq!(tokens, vec![ParenOpen(1), ParenOpen(1), ParenOpen(1), ParenOpen(1), ParenOpen(1), ParenOpen(1),
+        UnaryOperation('!', 1), UnaryOperation('-', 1), UnaryOperation('!', 1), name_p("a"), TrailingError]);
+
+    let mut tokens = math_token("!!!!x");
+    math_parse_tokens(&mut tokens).unwrap();
+    assert_eq!(tokens, vec![ParenOpen(1), ParenOpen(1), UnaryOperation('!', 1), UnaryOperation('!', 1), name_p("x"), TrailingError]);
+
+    let mut tokens = math_token("~~~!!x");
+    math_parse_tokens(&mut tokens).unwrap();
+    assert_eq!(tokens, vec![ParenOpen(1), ParenOpen(1), UnaryOperation('~', 1), UnaryOperation('!', 1), UnaryOperation('!', 1), name_p("x"), TrailingError]);
+}
+
+#[test]
+fn test_lenient_fix() {
+    use LenientFix::*;
+    assert_eq!(lenient_fix("12+"), ("12".to_string(), vec![IgnoredTrailingOperator(2..3)]));
+    assert_eq!(lenient_fix("(3* "), ("(3)".to_string(), vec![IgnoredTrailingOperator(2..3), ClosedParenthesis(0)]));
+    assert_eq!(lenient_fix("((1+2"), ("((1+2))".to_string(), vec![ClosedParenthesis(1), ClosedParenthesis(0)]));
+    assert_eq!(lenient_fix("f(1) //"), ("f(1) ".to_string(), vec![IgnoredTrailingOperator(5..7)]));
+    assert_eq!(lenient_fix("1 + -"), ("1 + -".to_string(), vec![]));
+    assert_eq!(lenient_fix("1 + (2)"), ("1 + (2)".to_string(), vec![]));
+    assert_eq!(lenient_fix("1 + 2%"), ("1 + 2%".to_string(), vec![]));
+}
+
+#[test]
+fn test_percent_of() {
+    let options = MathParseOptions {
+        percent_of: true,
+        ..MathParseOptions::default()
+    };
+    let solve = |expression: &str| crate::MathParse::parse_with_options(expression, &options)?.solve_number(None);
+    assert_eq!(solve("15% of 240"), Ok(crate::Number::Int(36)));
+    assert_eq!(solve("7% of 300"), Ok(crate::Number::Int(21)));
+    assert_eq!(solve("2 * 10 % of (100 + 50) - 1"), Ok(crate::Number::Int(29)));
+    assert_eq!(solve("-5% of -200"), Ok(crate::Number::Int(10)));
+    assert_eq!(solve("(2 + 3)%of sum(i, 1, 4, i)"), Ok(crate::Number::Float(0.5)));
+    assert_eq!(solve("60 // 50% of 40"), Ok(crate::Number::Int(3)));
+    assert_eq!(solve("7 % 4"), Ok(crate::Number::Int(3)));
+    let variables = std::collections::HashMap::from([
+        ("rate".to_string(), "12.5".to_string()),
+        ("price".to_string(), "80".to_string()),
+    ]);
+    let discount = crate::MathParse::parse_with_options("price - rate% of price", &options).unwrap();
+    assert_eq!(discount.solve_number(Some(&variables)), Ok(crate::Number::Int(70)));
+    assert_eq!(solve("15 of 240"), Err(MisplacedWord("of".to_string())));
+    assert_eq!(solve("15% of of 240"), Err(MisplacedWord("of".to_string())));
+    assert_eq!(solve("of 240"), Err(MisplacedWord("of".to_string())));
+    assert_eq!(solve("15 + % of 240"), Err(MisplacedWord("of".to_string())));
+    assert_eq!(solve("15% of"), Err(MisplacedWord("of".to_string())));
+    assert_eq!(solve("15% of *"), Err(MisplacedWord("of".to_string())));
+    assert_eq!(solve("offset + 1"), Err(InvalidNumber("offset".to_string())));
+}
+
+#[test]
+fn test_word_operators() {
+    use crate::MathParse;
+    let variables = std::collections::HashMap::from([
+        ("android".to_string(), "6".to_string()),
+        ("modulus".to_string(), "4".to_string()),
+        ("notes".to_string(), "1".to_string()),
+    ]);
+    let solve = |expression: &str| MathParse::parse(expression)?.solve_int(Some(&variables));
+    assert_eq!(solve("3 and 5"), Ok(1));
+    assert_eq!(solve("3 or 5"), Ok(7));
+    assert_eq!(solve("3 xor 5"), Ok(6));
+    assert_eq!(solve("10 mod 3"), Ok(1));
+    assert_eq!(solve("1 shl 4"), Ok(16));
+    assert_eq!(solve("256 shr 4"), Ok(16));
+    assert_eq!(solve("not 0"), Ok(-1));
+    assert_eq!(solve("not 0"), solve("~0"));
+    assert_eq!(solve("!0"), Ok(1));
+    assert_eq!(solve("5 and not 1"), Ok(4));
+    assert_eq!(solve("not (1 or 2)"), Ok(-4));
+    assert_eq!(solve("(1 + 2) mod 2"), Ok(1));
+    assert_eq!(solve("10 mod -3 and 7"), Ok(1));
+    assert_eq!(solve("android and modulus"), Ok(4));
+    assert_eq!(solve("modulus mod android"), Ok(4));
+    assert_eq!(solve("not notes"), Ok(-2));
+    assert_eq!(solve("1 000 and 1 023"), Ok(1000));
+    assert!(MathParse::parse("3 and 5").unwrap() == MathParse::parse("3 & 5").unwrap());
+    assert!(MathParse::parse("not 1 shl 2").unwrap() == MathParse::parse("~1 << 2").unwrap());
+
+    // Words that are not where an operator could be are left in names.
+    assert!(solve("and 3").is_err());
+    assert!(solve("3 not 5").is_err());
+    assert!(solve("3 and").is_err());
+    assert_eq!(MathParse::parse_rpn("3 5 &").unwrap().solve_int(None), Ok(1));
+    assert_eq!(MathParse::parse_rpn("5 not").unwrap().solve_int(None), Ok(-6));
+}
+
+#[test]
+fn test_percentages() {
+    use crate::Number::*;
+    let solve = |expression: &str| crate::MathParse::parse(expression)?.solve_number(None);
+    assert_eq!(solve("50% + 0.25"), Ok(Float(0.75)));
+    assert_eq!(solve("50%"), Ok(Float(0.5)));
+    assert_eq!(solve("200 * 15%"), Ok(Int(30)));
+    assert_eq!(solve("(1 + 2)% * 100"), Ok(Int(3)));
+    assert_eq!(solve("max(10%, 20%)"), Ok(Float(0.2)));
+    assert_eq!(solve("2 ** 50%"), Ok(Float(2.0_f64.sqrt())));
+    assert_eq!(solve("-50%"), Ok(Float(-0.5)));
+    assert_eq!(solve("10%%"), Ok(Float(0.001)));
+    assert_eq!(solve("1 ? 5% : 2"), Ok(Float(0.05)));
+
+    // A value after the `%` makes it a remainder, whatever the spacing.
+    assert_eq!(solve("7 % 3"), Ok(Int(1)));
+    assert_eq!(solve("5 % 2"), Ok(Int(1)));
+    assert_eq!(solve("5% 2"), Ok(Int(1)));
+    assert_eq!(solve("5 %2"), Ok(Int(1)));
+    assert_eq!(solve("7 % (-3)"), Ok(Int(1)));
+    assert_eq!(solve("50% % 3"), Ok(Float(0.5)));
+
+    // A sign written directly before its operand is a part of the value
+    // after the `%`, while a sign followed by whitespace is an addition or
+    // a subtraction.
+    assert_eq!(solve("7 % -3"), Ok(Int(1)));
+    assert_eq!(solve("7 %-3"), Ok(Int(1)));
+    assert_eq!(solve("-7 % -3"), Ok(Int(-1)));
+    assert_eq!(solve("7 % +3"), Ok(Int(1)));
+    assert_eq!(solve("7 mod -3"), Ok(Int(1)));
+    assert_eq!(solve("50% - 0.25"), Ok(Float(0.25)));
+    assert_eq!(solve("7 % - 3"), Ok(Float(-2.93)));
+    assert_eq!(solve("50% * 3"), Ok(Float(1.5)));
+    assert_eq!(solve("% 3"), Err(MisplacedOperator('%')));
+
+    let mut tokens = math_token("(a)%");
+    unary_parse(&mut tokens).unwrap();
+    move_percentages(&mut tokens).unwrap();
+    assert_eq!(tokens, vec![UnaryOperation('%', 1), Operator('('), name_p("a"), Operator(')'), TrailingError]);
+}
@@ -1,24 +1,55 @@
 use crate::tokenize::MathValue::*;
-use crate::tokenize::math_token;
+use crate::tokenize::math_token_checked;
+use crate::tokenize::{char_literal_len, is_math_char, is_radix_prefix};
 use crate::tokenize::MathValue;
 use crate::MathParseErrors::*;
 use crate::MathParseErrors;
 use crate::BinaryOp;
+use crate::UnaryOp;
 use crate::RPN::*;
 use crate::RPN;
+use crate::MathParseOptions;
 
-pub fn parse_rpn(expression: &str) -> Result<Vec<RPN>, MathParseErrors> {
-    let tokens = math_token(expression);
-    let rpn = rpn_parse_tokens(&tokens)?;
-    check_rpn_valid(&rpn)?;
+/// Words used for the unary operators in postfix and prefix notation, as
+/// their symbols are also the ones of binary operators. The `√`, `!`, and
+/// `~` are only used for unary operators, so they are kept. The first word of
+/// an operator is the one used to write it.
+const UNARY_WORDS: [(&str, UnaryOp); 7] = [
+    ("neg", UnaryOp::Minus),
+    ("not", UnaryOp::Not),
+    ("~", UnaryOp::Not),
+    ("!", UnaryOp::LogicalNot),
+    ("pos", UnaryOp::Plus),
+    ("pct", UnaryOp::Percent),
+    ("√", UnaryOp::Sqrt),
+];
+
+pub fn unary_word(op: UnaryOp) -> &'static str {
+    UNARY_WORDS.iter()
+        .find(|(_, word_op)| *word_op == op)
+        .map_or("", |(word, _)| word)
+}
+
+pub fn parse_rpn(expression: &str, options: &MathParseOptions) -> Result<Vec<RPN>, MathParseErrors> {
+    let rpn = read_rpn_elements(expression, options)?;
+    check_rpn_valid(&rpn, options)?;
     Ok(rpn)
 }
 
+/// Read each element of an expression in postfix or prefix notation, without
+/// checking that they make a valid expression.
+pub fn read_rpn_elements(expression: &str, options: &MathParseOptions) -> Result<Vec<RPN>, MathParseErrors> {
+    let expression = blank_comments_and_separators(expression);
+    let (tokens, positions) = math_token_checked(&expression, options, &['@'])?;
+    let tokens = remove_leading_plus(tokens, &positions);
+    rpn_parse_tokens(&tokens)
+}
+
 fn rpn_parse_tokens(tokens: &[MathValue]) -> Result<Vec<RPN>, MathParseErrors> {
     let mut ret = Vec::new();
     for token in tokens {
         let parsed_values = match token {
-            MathValue::Name(x) => parse_rpn_name(*x),
+            MathValue::Name(x) => parse_rpn_name(x),
             Operator(x) => parse_rpn_operator(*x)?,
             TrailingError => vec![],
             ParenOpen(1) => vec![], // This one can be put there when parsing complex tokens
@@ -34,10 +65,13 @@ fn rpn_parse_tokens(tokens: &[MathValue]) -> Result<Vec<RPN>, MathParseErrors> {
 }
 
 fn parse_rpn_operator(c: char) -> Result<Vec<RPN>, MathParseErrors> {
+    if let Some((_, op)) = UNARY_WORDS.iter().find(|(word, _)| word.chars().eq([c])) {
+        return Ok(vec![Unary(*op)]);
+    }
     let op = match BinaryOp::from_char(c) {
-        Ok(x)                        => Ok(x),
-        Err(MathParseInternalBug(_)) => Err(InvalidRPNOperator(c)),
-        x                            => x
+        Ok(x)                    => Ok(x),
+        Err(UnknownOperator(_))  => Err(InvalidRPNOperator(c)),
+        x                        => x
     };
     match op {
         Ok(x)  => Ok(vec![Binary(x)]),
@@ -46,27 +80,132 @@ fn parse_rpn_operator(c: char) -> Result<Vec<RPN>, MathParseErrors> {
 }
 
 fn parse_rpn_name(names: &str) -> Vec<RPN> {
-    split_words(names).iter().map(|x| RPN::Name(x.clone())).collect::<Vec<RPN>>()
+    if names.starts_with('\'') {
+        return vec![RPN::Name(names.to_string())];
+    }
+    split_words(names).iter().map(|x| parse_rpn_word(x)).collect::<Vec<RPN>>()
 }
 
-fn check_rpn_valid(rpn: &[RPN]) -> Result<(), MathParseErrors> {
-    fn map_to_one(_: &str) -> Option<String> {
-        Some("1".to_string())
+/// Reads a single word. Function calls are written as `name@arity`, but the
+/// arity can be omitted for builtin functions.
+fn parse_rpn_word(word: &str) -> RPN {
+    if let Some((name, arity)) = word.rsplit_once('@') {
+        if let Ok(arity) = arity.parse::<usize>() {
+            if !name.is_empty() {
+                return Call(name.to_string(), arity);
+            }
+        }
+    }
+    if let Some(arity) = crate::solve::builtin_arity(word) {
+        return Call(word.to_string(), arity);
     }
+    if let Some((_, op)) = UNARY_WORDS.iter().find(|(unary_word, _)| *unary_word == word) {
+        return Unary(*op);
+    }
+    RPN::Name(word.to_string())
+}
 
-    match crate::solve::math_solve(rpn, &map_to_one) {
-        Ok(_) => Ok(()),
-        Err(x) => Err(x),
+/// Check that RPN actions make a valid expression: each operator and function
+/// call must have enough operands, the functions must be builtin functions
+/// called with the right number of arguments, and a single number must be
+/// left on the stack, unless the options allow several. The expression is
+/// not solved, so expressions that are valid but can't be solved, such as
+/// `1 0 /`, are accepted.
+pub fn check_rpn_valid(rpn: &[RPN], options: &MathParseOptions) -> Result<(), MathParseErrors> {
+    let mut stack_size = 0;
+    for action in rpn {
+        let needed = match action {
+            RPN::Name(_) => 0,
+            Unary(_) => 1,
+            Binary(_) => 2,
+            Call(name, arity) => {
+                match crate::solve::builtin_accepts(name, *arity) {
+                    Some(true) => {},
+                    Some(false) => return Err(InvalidFunctionCall(name.clone())),
+                    None => return Err(UnknownFunction(name.clone())),
+                }
+                *arity
+            },
+        };
+        if stack_size < needed {
+            return Err(UnbalancedStack);
+        }
+        stack_size = stack_size - needed + 1;
+    }
+    match stack_size {
+        1 => {},
+        2.. if options.multiple_rpn_results => {},
+        _ => return Err(UnbalancedStack),
     }
+
+    for (call_index, starts) in crate::solve::lazy_calls(rpn)?.into_iter().flatten() {
+        match &rpn[call_index] {
+            Call(name, _) if crate::solve::binder_function(name).is_some() => {
+                crate::solve::binder_variable(rpn, name, &starts)?;
+            },
+            _ => {},
+        }
+    }
+    Ok(())
 }
 
 /* ---------------------------------- Utils --------------------------------- */
 
+/// Replace the comments, from a `#` to the end of the line, and the commas
+/// used as separators with spaces. A `#` right after the radix of a number,
+/// as in `2#1010`, is not a comment. Character literals are kept as they are.
+/// Each removed character is replaced by as many spaces as its length in
+/// bytes so that the position of the other characters does not change.
+fn blank_comments_and_separators(s: &str) -> String {
+    let mut ret = String::with_capacity(s.len());
+    let mut literal_end = 0; // Index of the end of the last character literal.
+    let mut in_comment = false;
+    let mut previous = ' ';
+    for (index, c) in s.char_indices() {
+        if index < literal_end {
+            ret.push(c);
+        } else if c == '\n' {
+            in_comment = false;
+            ret.push(c);
+        } else if in_comment || (c == '#' && !is_radix_prefix(word_before(s, index))) || c == ',' {
+            in_comment |= c == '#';
+            ret.extend(std::iter::repeat_n(' ', c.len_utf8()));
+        } else {
+            if c == '\'' && (previous.is_whitespace() || is_math_char(previous)) {
+                literal_end = index + char_literal_len(&s[index..]);
+            }
+            ret.push(c);
+        }
+        previous = c;
+    }
+    ret
+}
+
+/// Return the part of the word that is before the given index.
+fn word_before(s: &str, index: usize) -> &str {
+    s[..index].rsplit(|c: char| c.is_whitespace() || c == ',' || is_math_char(c)).next().unwrap_or("")
+}
+
+/// Remove the `+` placed right before a number, such as in `+5`, as they are
+/// the sign of the number and not additions.
+fn remove_leading_plus<'a>(tokens: Vec<MathValue<'a>>, positions: &[usize]) -> Vec<MathValue<'a>> {
+    let mut ret = Vec::with_capacity(tokens.len());
+    for (index, token) in tokens.iter().enumerate() {
+        let sign = *token == Operator('+')
+            && positions[index+1] == positions[index] + 1
+            && matches!(tokens[index+1], MathValue::Name(name) if name.starts_with(|c: char| c.is_ascii_digit()));
+        if !sign {
+            ret.push(token.clone());
+        }
+    }
+    ret
+}
+
 /// From a str, return an iterator to each words.
 fn split_words(s: &str) -> Vec<String> {
     let uniform_whitespace = s.replace(&['\t', ' ', '\n', '\r', ' '][..], " ");
     uniform_whitespace.split(" ")
-        .filter(|x| *x != "")
+        .filter(|x| !x.is_empty())
         .map(|x| x.to_string())
         .collect::<Vec<String>>()
 }
@@ -82,35 +221,143 @@ fn test_parse_rpn() {
 
     assert_eq!(
         parse_rpn("88
-            99	+"),
+            99	+", &MathParseOptions::default()),
         Ok(vec![name_r("88"), name_r("99"), Binary(Addition)]));
 
     assert_eq!(
-        parse_rpn("88 77 + 33 11 + *"),
+        parse_rpn("88 77 + 33 11 + *", &MathParseOptions::default()),
         Ok(vec![name_r("88"), name_r("77"), Binary(Addition), name_r("33"), name_r("11"), Binary(Addition), Binary(Multiplication)]));
 
     assert_eq!(
-        parse_rpn("(88 77 +)"),
+        parse_rpn("(88 77 +)", &MathParseOptions::default()),
         Err(InvalidRPNOperator('(')));
 
     assert_eq!(
-        parse_rpn("8 7 + +"),
+        parse_rpn("8 7 + +", &MathParseOptions::default()),
         Err(UnbalancedStack));
 
     assert_eq!(
-        parse_rpn("+"),
+        parse_rpn("+", &MathParseOptions::default()),
         Err(UnbalancedStack));
 
     assert_eq!(
-        parse_rpn("8 7"),
+        parse_rpn("8 7", &MathParseOptions::default()),
         Err(UnbalancedStack));
 
     assert_eq!(
-        parse_rpn("6 1 >>"),
+        parse_rpn("6 1 >>", &MathParseOptions::default()),
         Ok(vec![name_r("6"), name_r("1"), Binary(ShiftRight)]));
 
     assert_eq!(
-        parse_rpn("6 1 >"),
-        Err(BadOperatorHint('>', ">>")));
+        parse_rpn("6 1 >", &MathParseOptions::default()),
+        Ok(vec![name_r("6"), name_r("1"), Binary(GreaterThan)]));
+
+    assert_eq!(
+        parse_rpn("6 1 <= 2 !=", &MathParseOptions::default()),
+        Ok(vec![name_r("6"), name_r("1"), Binary(LessOrEqual), name_r("2"), Binary(NotEqual)]));
+
+    assert_eq!(
+        parse_rpn("1 0.5 && 0 ||", &MathParseOptions::default()),
+        Ok(vec![name_r("1"), name_r("0.5"), Binary(LogicalAnd), name_r("0"), Binary(LogicalOr)]));
+
+    assert_eq!(
+        parse_rpn("6 1 =", &MathParseOptions::default()),
+        Err(BadOperatorHint('=', "==")));
+
+    assert_eq!(
+        parse_rpn("i 1 10 i i * sum", &MathParseOptions::default()),
+        Ok(vec![name_r("i"), name_r("1"), name_r("10"), name_r("i"), name_r("i"), Binary(Multiplication), Call("sum".to_string(), 4)]));
+
+    assert_eq!(
+        parse_rpn("k 1 5 k product@4", &MathParseOptions::default()),
+        Ok(vec![name_r("k"), name_r("1"), name_r("5"), name_r("k"), Call("product".to_string(), 4)]));
+
+    assert_eq!(
+        parse_rpn("1 2 f@2", &MathParseOptions::default()),
+        Err(UnknownFunction("f".to_string())));
+
+    assert_eq!(
+        parse_rpn("+5 3 -", &MathParseOptions::default()),
+        Ok(vec![name_r("5"), name_r("3"), Binary(Subtraction)]));
+
+    assert_eq!(
+        parse_rpn("5 +3 +", &MathParseOptions::default()),
+        Ok(vec![name_r("5"), name_r("3"), Binary(Addition)]));
+
+    assert_eq!(
+        parse_rpn("5 3 + +", &MathParseOptions::default()),
+        Err(UnbalancedStack));
+
+    assert_eq!(
+        parse_rpn("3 4 +  # add the basics
+                   2 *    # double, it's easy + fast
+                   # 2 +", &MathParseOptions::default()),
+        Ok(vec![name_r("3"), name_r("4"), Binary(Addition), name_r("2"), Binary(Multiplication)]));
+
+    assert_eq!(
+        parse_rpn("# nothing to see", &MathParseOptions::default()),
+        Err(UnbalancedStack));
+
+    assert_eq!(
+        parse_rpn("1, 2,3,+,+", &MathParseOptions::default()),
+        Ok(vec![name_r("1"), name_r("2"), name_r("3"), Binary(Addition), Binary(Addition)]));
+
+    assert_eq!(
+        parse_rpn("'#' ',' + # comment", &MathParseOptions::default()),
+        Ok(vec![name_r("'#'"), name_r("','"), Binary(Addition)]));
+
+    assert_eq!(
+        parse_rpn("1 2$ +", &MathParseOptions::default()),
+        Err(InvalidCharacter('$', 3)));
+
+    let multiple_results = MathParseOptions {
+        multiple_rpn_results: true,
+        ..MathParseOptions::default()
+    };
+    assert_eq!(
+        parse_rpn("1 2 3 +", &multiple_results),
+        Ok(vec![name_r("1"), name_r("2"), name_r("3"), Binary(Addition)]));
+
+    assert_eq!(
+        parse_rpn("1 +", &multiple_results),
+        Err(UnbalancedStack));
+
+    assert_eq!(
+        parse_rpn("# nothing", &multiple_results),
+        Err(UnbalancedStack));
+
+    assert_eq!(
+        parse_rpn("1 0 / 2 2 - %", &MathParseOptions::default()),
+        Ok(vec![name_r("1"), name_r("0"), Binary(Division), name_r("2"), name_r("2"), Binary(Subtraction), Binary(Reminder)]));
+
+    assert_eq!(
+        parse_rpn("12 4 2 / /", &MathParseOptions::default()),
+        Ok(vec![name_r("12"), name_r("4"), name_r("2"), Binary(Division), Binary(Division)]));
+
+    assert_eq!(
+        parse_rpn("1 2 randint@3", &MathParseOptions::default()),
+        Err(InvalidFunctionCall("randint".to_string())));
+
+    assert_eq!(
+        parse_rpn("1 1 2 1 sum", &MathParseOptions::default()),
+        Err(InvalidFunctionCall("sum".to_string())));
+
+    assert_eq!(
+        parse_rpn("3 neg 5 not +", &MathParseOptions::default()),
+        Ok(vec![name_r("3"), Unary(crate::UnaryOp::Minus), name_r("5"), Unary(crate::UnaryOp::Not), Binary(Addition)]));
+
+    assert_eq!(crate::MathParse::parse_rpn("0o17 1 +").unwrap().solve_int(None), Ok(16));
+    assert_eq!(crate::MathParse::parse_rpn("0 not").unwrap().solve_int(None), Ok(-1));
+    assert_eq!(crate::MathParse::parse_rpn("0 !").unwrap().solve_int(None), Ok(1));
+    assert_eq!(crate::MathParse::parse_rpn("2#1010 1 + # 2#1").unwrap().solve_int(None), Ok(11));
+    assert_eq!(crate::MathParse::parse_rpn("0o8 1 +").unwrap().solve_int(None), Err(InvalidNumber("0o8".to_string())));
+
+    assert_eq!(
+        parse_rpn("3 ~ 5 ! +", &MathParseOptions::default()),
+        Ok(vec![name_r("3"), Unary(crate::UnaryOp::Not), name_r("5"), Unary(crate::UnaryOp::LogicalNot), Binary(Addition)]));
+
+    assert_eq!(
+        parse_rpn("' ' '+' +", &MathParseOptions::default()),
+        Ok(vec![name_r("' '"), name_r("'+'"), Binary(Addition)]));
 }
 
@@ -0,0 +1,127 @@
+use crate::MathParse;
+use crate::MathParseErrors;
+use crate::MathParseOptions;
+use crate::intern::InternedRPN;
+use std::collections::HashMap;
+
+/* ---------------------------------- Pool ---------------------------------- */
+
+/// Identifier of an expression in an `ExprPool`.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct ExprId(usize);
+
+/// Storage for many parsed expressions where the expressions that parse to
+/// the same RPN actions, such as `x*1.21` and `x * 1.21`, are only kept once.
+///
+/// ```
+/// use math_parse::ExprPool;
+///
+/// let mut pool = ExprPool::new();
+/// let a = pool.intern("x*1.21").unwrap();
+/// let b = pool.intern("(x) * 1.21").unwrap();
+/// assert_eq!(a, b);
+/// assert_eq!(pool.len(), 1);
+/// assert_eq!(pool.get(a).solve_float(None), Err(math_parse::MathParseErrors::InvalidNumber("x".to_string())));
+/// ```
+pub struct ExprPool {
+    options: MathParseOptions,
+    expressions: Vec<MathParse>,
+    ids: HashMap<InternedRPN, ExprId>,
+}
+
+impl ExprPool {
+    /// Create an empty pool.
+    pub fn new() -> Self {
+        Self::with_options(&MathParseOptions::default())
+    }
+
+    /// Create an empty pool whose expressions are parsed with custom options.
+    pub fn with_options(options: &MathParseOptions) -> Self {
+        ExprPool {
+            options: options.clone(),
+            expressions: vec![],
+            ids: HashMap::new(),
+        }
+    }
+
+    /// Parse an expression in infix notation and add it to the pool, unless
+    /// an expression with the same RPN actions is already in it, in which
+    /// case the identifier of that expression is returned. The kept
+    /// expression is the first one added, along with its source text.
+    pub fn intern(&mut self, expression: &str) -> Result<ExprId, MathParseErrors> {
+        let parsed = MathParse::parse_with_options(expression, &self.options)?;
+        if let Some(id) = self.ids.get(&parsed.internal) {
+            return Ok(*id);
+        }
+        let id = ExprId(self.expressions.len());
+        self.ids.insert(parsed.internal.clone(), id);
+        self.expressions.push(parsed);
+        Ok(id)
+    }
+
+    /// Return the expression with the given identifier.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the identifier was given by another pool and is not in this
+    /// one.
+    pub fn get(&self, id: ExprId) -> &MathParse {
+        &self.expressions[id.0]
+    }
+
+    /// Return the number of distinct expressions in the pool.
+    pub fn len(&self) -> usize {
+        self.expressions.len()
+    }
+
+    /// Return true if there is no expression in the pool.
+    pub fn is_empty(&self) -> bool {
+        self.expressions.is_empty()
+    }
+}
+
+impl Default for ExprPool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/* --------------------------------- Testing -------------------------------- */
+
+#[test]
+fn test_pool_deduplication() {
+    let formulas = [
+        "x*1.21", "x * 1.21 + 5", "(a + b) / 2", "a << 3", "sum(i, 1, n, i*i)",
+        "-x", "x % 7", "1", "a*b*c", "rand()",
+    ];
+    let mut pool = ExprPool::new();
+    let mut ids = vec![];
+    for i in 0..10_000 {
+        // Spaces and parenthesis that don't change the parsed form.
+        let formula = match i % 3 {
+            0 => formulas[i % 10].to_string(),
+            1 => format!(" {} ", formulas[i % 10]),
+            _ => format!("({})", formulas[i % 10]),
+        };
+        ids.push(pool.intern(&formula).unwrap());
+    }
+    assert_eq!(pool.len(), 10);
+    for (i, id) in ids.iter().enumerate() {
+        assert_eq!(*id, ids[i % 10]);
+        let expected = MathParse::parse(formulas[i % 10]).unwrap();
+        assert_eq!(pool.get(*id).to_rpn(), expected.to_rpn());
+    }
+
+    let variables = HashMap::from([("x".to_string(), "100".to_string())]);
+    assert_eq!(pool.get(ids[0]).solve_float(Some(&variables)), Ok(121.0));
+
+    assert_eq!(pool.intern("1 +"), Err(MathParseErrors::EmptyLine));
+    assert_ne!(pool.intern("x*1.210").unwrap(), ids[0]);
+    assert_eq!(pool.len(), 11);
+}
+
+#[test]
+fn test_pool_id_traits() {
+    fn check<T: Copy + Send + Sync + 'static>() {}
+    check::<ExprId>();
+}
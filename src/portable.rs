@@ -0,0 +1,353 @@
+use std::f64::consts::{FRAC_2_PI, FRAC_PI_2, FRAC_PI_6, LN_2, LN_10, SQRT_2, TAU};
+
+/* ------------------------------ Portable floats ----------------------------- */
+
+// The functions of this file are made only of additions, multiplications,
+// divisions, square roots, and bit manipulations. IEEE 754 requires them to
+// be correctly rounded, so the results are the same bits on every platform,
+// unlike the functions of the platform's math library. They are used with the
+// `deterministic_floats` option.
+
+/// ln(2) split in a part whose products with small integers are exact and
+/// the rest, as done in fdlibm.
+const LN_2_HI: f64 = 0.6931471803691238;
+const LN_2_LO: f64 = 1.9082149292705877e-10;
+
+/// π/2 split in parts whose products with integers below 2^20 are exact, and
+/// the rest, as done in fdlibm.
+const PIO2_1: f64 = 1.5707963267341256;
+const PIO2_2: f64 = 6.077100506303966e-11;
+const PIO2_3: f64 = 2.0222662487111665e-21;
+const PIO2_3T: f64 = 8.4784276603689e-32;
+
+/// Above this, the arguments of the trigonometric functions are first
+/// reduced with a remainder by 2π, which is less accurate.
+const LARGE_ANGLE: f64 = 1_048_576.0 * FRAC_PI_2;
+
+const SQRT_3: f64 = 1.7320508075688772;
+const TAN_PI_12: f64 = 0.2679491924311227;
+
+/// Multiply by 2 to the power of `k`.
+fn scale(mut x: f64, mut k: i64) -> f64 {
+    let power = |k: i64| f64::from_bits(((k + 1023) as u64) << 52);
+    while k > 1023 {
+        x *= power(1023);
+        k -= 1023;
+    }
+    while k < -1022 {
+        x *= power(-1022);
+        k += 1022;
+    }
+    x * power(k)
+}
+
+/// Split a positive finite float into an integer of 53 bits whose highest
+/// bit is set, and a power of 2.
+fn integer_decode(x: f64) -> (u64, i64) {
+    let bits = x.to_bits();
+    let exponent = ((bits >> 52) & 0x7ff) as i64;
+    let (mut mantissa, mut exponent) = if exponent == 0 {
+        (bits & ((1 << 52) - 1), -1074)
+    } else {
+        ((bits & ((1 << 52) - 1)) | (1 << 52), exponent - 1075)
+    };
+    while mantissa < (1 << 52) {
+        mantissa <<= 1;
+        exponent -= 1;
+    }
+    (mantissa, exponent)
+}
+
+/// Remainder of the division of `x` by `y`, with the sign of `x`, as the `%`
+/// operator on floats. It is exact.
+pub fn fmod(x: f64, y: f64) -> f64 {
+    if x.is_nan() || y.is_nan() || x.is_infinite() || y == 0.0 {
+        return f64::NAN;
+    }
+    if y.is_infinite() || x.abs() < y.abs() {
+        return x;
+    }
+    let (mx, ex) = integer_decode(x.abs());
+    let (my, ey) = integer_decode(y.abs());
+    // As |x| >= |y|, ex >= ey and the remainder is found by bringing the
+    // bits of x 11 at a time, which fits in an u64.
+    let mut remainder = mx % my;
+    let mut shift = ex - ey;
+    while shift > 0 {
+        let step = shift.min(11);
+        remainder = (remainder << step) % my;
+        shift -= step;
+    }
+    scale(remainder as f64, ey).copysign(x)
+}
+
+/// Split a positive finite float into a power of 2 and a mantissa in
+/// `[√2/2, √2)`.
+fn split_log(x: f64) -> (i64, f64) {
+    let (mantissa, exponent) = integer_decode(x);
+    let mut m = f64::from_bits((mantissa & ((1 << 52) - 1)) | (1023 << 52));
+    let mut exponent = exponent + 52;
+    if m > SQRT_2 {
+        m /= 2.0;
+        exponent += 1;
+    }
+    (exponent, m)
+}
+
+/// Natural logarithm of a mantissa in `[√2/2, √2)`, as `2·atanh((m-1)/(m+1))`.
+fn ln_mantissa(m: f64) -> f64 {
+    let s = (m - 1.0) / (m + 1.0);
+    let z = s * s;
+    let mut series = 0.0;
+    for k in (1..=12).rev() {
+        series = z * (1.0 / f64::from(2 * k + 1) + series);
+    }
+    2.0 * s + 2.0 * s * series
+}
+
+/// Apply a logarithm, given its value on a mantissa in `[√2/2, √2)` and its
+/// value for 2 to the power of an integer, on the special values of floats.
+fn log_with(x: f64, mantissa_log: impl Fn(f64) -> f64, exponent_log: impl Fn(i64) -> f64) -> f64 {
+    if x.is_nan() || x < 0.0 {
+        f64::NAN
+    } else if x == 0.0 {
+        f64::NEG_INFINITY
+    } else if x.is_infinite() {
+        f64::INFINITY
+    } else {
+        let (exponent, m) = split_log(x);
+        exponent_log(exponent) + mantissa_log(m)
+    }
+}
+
+pub fn ln(x: f64) -> f64 {
+    log_with(x, ln_mantissa, |exponent| {
+        let exponent = exponent as f64;
+        exponent * LN_2_HI + exponent * LN_2_LO
+    })
+}
+
+/// Logarithm in base 2, exact for the powers of 2.
+pub fn log2(x: f64) -> f64 {
+    log_with(x, |m| ln_mantissa(m) / LN_2, |exponent| exponent as f64)
+}
+
+pub fn log10(x: f64) -> f64 {
+    ln(x) / LN_10
+}
+
+pub fn exp(x: f64) -> f64 {
+    if x.is_nan() {
+        return x;
+    }
+    if x > 709.8 {
+        return f64::INFINITY;
+    }
+    if x < -745.2 {
+        return 0.0;
+    }
+    let k = (x / LN_2).round();
+    let r = (x - k * LN_2_HI) - k * LN_2_LO;
+    let mut series = 1.0;
+    for n in (1..=17).rev() {
+        series = 1.0 + r * series / f64::from(n);
+    }
+    scale(series, k as i64)
+}
+
+/// Power of a float, as `f64::powf`. Integer powers are computed by repeated
+/// multiplications and the other ones as `exp(y·ln(x))`.
+pub fn powf(x: f64, y: f64) -> f64 {
+    if y == 0.0 || x == 1.0 {
+        return 1.0;
+    }
+    if x.is_nan() || y.is_nan() {
+        return f64::NAN;
+    }
+    let integer_power = y.trunc() == y;
+    if x < 0.0 {
+        if !integer_power {
+            return f64::NAN;
+        }
+        let odd = y.abs() < 9_007_199_254_740_992.0 && fmod(y, 2.0) != 0.0;
+        let ret = powf(-x, y);
+        return if odd { -ret } else { ret };
+    }
+    if integer_power && y.abs() <= f64::from(i32::MAX) && x.is_finite() && x != 0.0 {
+        let mut exponent = y.abs() as u32;
+        let mut base = x;
+        let mut ret = 1.0;
+        while exponent != 0 {
+            if exponent & 1 == 1 {
+                ret *= base;
+            }
+            base *= base;
+            exponent >>= 1;
+        }
+        return if y < 0.0 { 1.0 / ret } else { ret };
+    }
+    exp(y * ln(x))
+}
+
+/// Reduce an angle to `[-π/4, π/4]`, returning it with its quadrant.
+fn reduce_angle(x: f64) -> (f64, u8) {
+    let x = if x.abs() > LARGE_ANGLE { fmod(x, TAU) } else { x };
+    let n = (x * FRAC_2_PI).round();
+    let r = (((x - n * PIO2_1) - n * PIO2_2) - n * PIO2_3) - n * PIO2_3T;
+    (r, (n as i64).rem_euclid(4) as u8)
+}
+
+/// Sine and cosine of an angle in `[-π/4, π/4]`.
+fn sin_cos_reduced(r: f64) -> (f64, f64) {
+    let z = r * r;
+    let mut sin = 1.0;
+    let mut cos = 1.0;
+    for k in (1..=10).rev() {
+        sin = 1.0 - z * sin / f64::from(2 * k * (2 * k + 1));
+        cos = 1.0 - z * cos / f64::from((2 * k - 1) * 2 * k);
+    }
+    (r * sin, cos)
+}
+
+/// Sine and cosine of any angle.
+fn sin_cos(x: f64) -> (f64, f64) {
+    if !x.is_finite() {
+        return (f64::NAN, f64::NAN);
+    }
+    let (r, quadrant) = reduce_angle(x);
+    let (sin, cos) = sin_cos_reduced(r);
+    match quadrant {
+        0 => (sin, cos),
+        1 => (cos, -sin),
+        2 => (-sin, -cos),
+        _ => (-cos, sin),
+    }
+}
+
+pub fn sin(x: f64) -> f64 {
+    sin_cos(x).0
+}
+
+pub fn cos(x: f64) -> f64 {
+    sin_cos(x).1
+}
+
+pub fn tan(x: f64) -> f64 {
+    let (sin, cos) = sin_cos(x);
+    sin / cos
+}
+
+/// Arctangent of a value in `[-tan(π/12), tan(π/12)]`.
+fn atan_reduced(u: f64) -> f64 {
+    let z = u * u;
+    let mut series = 0.0;
+    for k in (1..=16).rev() {
+        let sign = if k % 2 == 0 { 1.0 } else { -1.0 };
+        series = z * (sign / f64::from(2 * k + 1) + series);
+    }
+    u + u * series
+}
+
+pub fn atan(x: f64) -> f64 {
+    if x.is_nan() {
+        return x;
+    }
+    if x < 0.0 {
+        return -atan(-x);
+    }
+    let (offset, t, sign) = if x > 1.0 {
+        (FRAC_PI_2, 1.0 / x, -1.0)
+    } else {
+        (0.0, x, 1.0)
+    };
+    let atan_t = if t > TAN_PI_12 {
+        FRAC_PI_6 + atan_reduced((t * SQRT_3 - 1.0) / (t + SQRT_3))
+    } else {
+        atan_reduced(t)
+    };
+    offset + sign * atan_t
+}
+
+/// Arcsine of a value in `[-1, 1]`.
+pub fn asin(x: f64) -> f64 {
+    if x.abs() == 1.0 {
+        return FRAC_PI_2.copysign(x);
+    }
+    atan(x / ((1.0 - x) * (1.0 + x)).sqrt())
+}
+
+/// Arccosine of a value in `[-1, 1]`.
+pub fn acos(x: f64) -> f64 {
+    2.0 * atan(((1.0 - x) / (1.0 + x)).sqrt())
+}
+
+/* --------------------------------- Testing -------------------------------- */
+
+/// Distance in units in the last place between two floats of the same sign.
+#[cfg(test)]
+fn ulps(a: f64, b: f64) -> u64 {
+    a.to_bits().abs_diff(b.to_bits())
+}
+
+#[test]
+fn test_fmod() {
+    for (x, y) in [(7.5, 2.0), (-7.5, 2.0), (7.5, -2.0), (1e300, 3.0), (5e-324, 3e-324), (0.3, 0.1), (1.0, 1e-300), (6.0, 3.0)] {
+        assert_eq!(fmod(x, y).to_bits(), (x % y).to_bits(), "{x} % {y}");
+    }
+    assert_eq!(fmod(2.0, 5.0), 2.0);
+    assert_eq!(fmod(-0.0, 5.0).to_bits(), (-0.0_f64).to_bits());
+    assert_eq!(fmod(2.0, f64::INFINITY), 2.0);
+    assert!(fmod(2.0, 0.0).is_nan());
+    assert!(fmod(f64::INFINITY, 2.0).is_nan());
+}
+
+#[test]
+fn test_accuracy() {
+    type Function = fn(f64) -> f64;
+    let functions: [(Function, Function, &str); 10] = [
+        (sin, f64::sin, "sin"), (cos, f64::cos, "cos"), (tan, f64::tan, "tan"),
+        (atan, f64::atan, "atan"), (asin, f64::asin, "asin"), (acos, f64::acos, "acos"),
+        (ln, f64::ln, "ln"), (log2, f64::log2, "log2"), (log10, f64::log10, "log10"),
+        (exp, f64::exp, "exp"),
+    ];
+    for (portable, std, name) in functions {
+        for i in -1000..=1000 {
+            let x = f64::from(i) / 100.0;
+            let x = match name {
+                "asin" | "acos" => x / 10.0,
+                "ln" | "log2" | "log10" => x.abs() * 1e3 + 1e-3,
+                _ => x,
+            };
+            let (a, b) = (portable(x), std(x));
+            assert!(ulps(a, b) <= 4 || (a - b).abs() < 1e-15, "{name}({x}): {a} {b}");
+        }
+    }
+    for (x, y) in [(2.0, 0.5), (10.0, -2.0), (1.5, 3.0), (-2.0, 3.0), (7.0, 1.0 / 3.0), (0.0, 2.0), (0.0, -1.0), (-8.0, 0.5)] {
+        let (a, b) = (powf(x, y), x.powf(y));
+        assert!(ulps(a, b) <= 4 || (a.is_nan() && b.is_nan()), "{x} ** {y}: {a} {b}");
+    }
+}
+
+#[test]
+fn test_special_values() {
+    assert_eq!(log2(1024.0), 10.0);
+    assert_eq!(log2(0.125), -3.0);
+    assert_eq!(ln(1.0), 0.0);
+    assert_eq!(ln(0.0), f64::NEG_INFINITY);
+    assert!(ln(-1.0).is_nan());
+    assert_eq!(exp(0.0), 1.0);
+    assert_eq!(exp(1000.0), f64::INFINITY);
+    assert_eq!(exp(-1000.0), 0.0);
+    assert_eq!(sin(0.0), 0.0);
+    assert_eq!(cos(0.0), 1.0);
+    assert!(sin(f64::INFINITY).is_nan());
+    assert!(sin(1e300).abs() <= 1.0);
+    assert_eq!(atan(f64::INFINITY), FRAC_PI_2);
+    assert_eq!(asin(-1.0), -FRAC_PI_2);
+    assert_eq!(acos(1.0), 0.0);
+    assert_eq!(acos(-1.0), std::f64::consts::PI);
+    assert_eq!(powf(2.0, 10.0), 1024.0);
+    assert_eq!(powf(-2.0, 3.0), -8.0);
+    assert_eq!(powf(f64::NAN, 0.0), 1.0);
+    assert_eq!(powf(2.0, f64::INFINITY), f64::INFINITY);
+}
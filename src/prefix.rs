@@ -0,0 +1,99 @@
+use crate::MathParseErrors;
+use crate::MathParseOptions;
+use crate::BinaryOp;
+use crate::UnaryOp;
+use crate::RPN;
+use crate::parse_rpn::{read_rpn_elements, check_rpn_valid, unary_word};
+use crate::rpn_stack_manipulation::*;
+
+/* ----------------------------- Prefix notation ---------------------------- */
+
+/// Write RPN actions in prefix notation.
+pub fn rpn_to_prefix(rpn_actions: &[RPN]) -> Result<String, MathParseErrors> {
+    let compute_name = | name: &str | -> Result<String, MathParseErrors> {
+        Ok(name.to_string())
+    };
+    let compute_unary = | value: String, op: UnaryOp | -> Result<String, MathParseErrors> {
+        Ok(format!("{} {value}", unary_word(op)))
+    };
+    let compute_binary = | value_1: String, value_2: String, op: BinaryOp | -> Result<String, MathParseErrors> {
+        Ok(format!("{} {value_1} {value_2}", op.as_str()))
+    };
+    let compute_call = | name: &str, args: Vec<String> | -> Result<String, MathParseErrors> {
+        let mut ret = format!("{name}@{}", args.len());
+        for arg in args {
+            ret.push(' ');
+            ret.push_str(&arg);
+        }
+        Ok(ret)
+    };
+
+    exec_rpn(rpn_actions, &Box::new(compute_name), &compute_unary, &compute_binary, &compute_call)
+}
+
+/// Read an expression in prefix notation and convert it to RPN actions.
+pub fn parse_prefix(expression: &str, options: &MathParseOptions) -> Result<Vec<RPN>, MathParseErrors> {
+    let mut elements = read_rpn_elements(expression, options)?;
+    elements.reverse();
+
+    // Read from the end, the first operand of each operator is on top of the
+    // stack.
+    let compute_name = | name: &str | -> Result<Vec<RPN>, MathParseErrors> {
+        Ok(vec![RPN::Name(name.to_string())])
+    };
+    let compute_unary = | mut value: Vec<RPN>, op: UnaryOp | -> Result<Vec<RPN>, MathParseErrors> {
+        value.push(RPN::Unary(op));
+        Ok(value)
+    };
+    let compute_binary = | value_2: Vec<RPN>, mut value_1: Vec<RPN>, op: BinaryOp | -> Result<Vec<RPN>, MathParseErrors> {
+        value_1.extend(value_2);
+        value_1.push(RPN::Binary(op));
+        Ok(value_1)
+    };
+    let compute_call = | name: &str, args: Vec<Vec<RPN>> | -> Result<Vec<RPN>, MathParseErrors> {
+        let arity = args.len();
+        let mut ret: Vec<RPN> = args.into_iter().rev().flatten().collect();
+        ret.push(RPN::Call(name.to_string(), arity));
+        Ok(ret)
+    };
+
+    let rpn = exec_rpn(&elements, &Box::new(compute_name), &compute_unary, &compute_binary, &compute_call)?;
+    check_rpn_valid(&rpn, options)?;
+    Ok(rpn)
+}
+
+/* --------------------------------- Testing -------------------------------- */
+
+#[test]
+fn test_prefix() {
+    use crate::MathParse;
+    use crate::MathParseErrors::*;
+
+    let prefix = |expression: &str| MathParse::parse(expression).unwrap().to_prefix_string();
+    assert_eq!(prefix("(2+3)*x"), Ok("* + 2 3 x".to_string()));
+    assert_eq!(prefix("-a - ~b"), Ok("- neg a not b".to_string()));
+    assert_eq!(prefix("sum(i, 1, 10, i*i) // 3"), Ok("// sum@4 i 1 10 * i i 3".to_string()));
+    assert_eq!(prefix("1, 2"), Err(UnbalancedStack));
+
+    let solve = |expression: &str| MathParse::parse_prefix(expression)?.solve_int(None);
+    assert_eq!(solve("* + 2 3 4"), Ok(20));
+    assert_eq!(solve("- 10 3"), Ok(7));
+    assert_eq!(solve("neg - 10 3"), Ok(-7));
+    assert_eq!(solve("<< 1 not neg 4"), Ok(8));
+    assert_eq!(solve("not 0"), Ok(-1));
+    assert_eq!(solve("! 0"), Ok(1));
+    assert_eq!(solve("product k 1 5 k # comment"), Ok(120));
+    assert_eq!(solve("+ 1"), Err(UnbalancedStack));
+    assert_eq!(solve("1 2"), Err(UnbalancedStack));
+    assert_eq!(solve("neg"), Err(UnbalancedStack));
+
+    let corpus = ["3-4+(-5)", "(2+3)*2/5", "8 // 3 % 2", "1 << 4 >> 2", "'a' ^ 0x20 | 1 & 3", "-−-+++-7",
+        "product(k, 1, 5, k) - sum(i, 1, 3, sum(j, 1, i, j))", "2.5 * 4", "!-3", "' ' + 1", "1e-3 * 1000"];
+    for expression in corpus {
+        let parsed = MathParse::parse(expression).unwrap();
+        let prefix = parsed.to_prefix_string().unwrap();
+        let reparsed = MathParse::parse_prefix(&prefix).unwrap();
+        assert_eq!(reparsed.to_rpn(), parsed.to_rpn(), "{expression}");
+        assert_eq!(reparsed.solve_float(None), parsed.solve_float(None), "{expression}");
+    }
+}
@@ -0,0 +1,127 @@
+use crate::MathParseOptions;
+use crate::solve::number_from_string;
+use crate::Tree;
+use crate::RPN;
+use std::collections::{HashMap, HashSet};
+
+/* -------------------------------- Renaming -------------------------------- */
+
+/// Summary of the renaming of the variables of an expression.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct RenameReport {
+    /// Number of names that were replaced in the expression.
+    pub replacements: usize,
+    /// Names that were used by more than one variable of the original
+    /// expression after the renaming, such as `b` when renaming `a` to `b` in
+    /// `a + b`. The names are sorted.
+    pub collisions: Vec<String>,
+}
+
+/// Keeps track of the variables met while renaming.
+struct Renamer<'a> {
+    mapping: &'a HashMap<String, String>,
+    options: &'a MathParseOptions,
+    variables: HashSet<String>,
+    replacements: usize,
+}
+
+impl<'a> Renamer<'a> {
+    fn new(mapping: &'a HashMap<String, String>, options: &'a MathParseOptions) -> Self {
+        Renamer{mapping, options, variables: HashSet::new(), replacements: 0}
+    }
+
+    /// Rename a name if it is a variable in the mapping. Numbers are never
+    /// renamed.
+    fn rename(&mut self, name: &mut String) {
+        if number_from_string(name, self.options).is_ok() {
+            return;
+        }
+        if let Some(new_name) = self.mapping.get(name.as_str()) {
+            self.variables.insert(std::mem::replace(name, new_name.clone()));
+            self.replacements += 1;
+        } else {
+            self.variables.insert(name.clone());
+        }
+    }
+
+    /// Make the report, looking for variables that got the same name.
+    fn report(self) -> RenameReport {
+        let mut originals_by_name = HashMap::<&str, usize>::new();
+        for variable in &self.variables {
+            let new_name = self.mapping.get(variable).unwrap_or(variable);
+            *originals_by_name.entry(new_name).or_default() += 1;
+        }
+        let mut collisions = originals_by_name.into_iter()
+            .filter(|(_, count)| *count > 1)
+            .map(|(name, _)| name.to_string())
+            .collect::<Vec<String>>();
+        collisions.sort();
+        RenameReport{replacements: self.replacements, collisions}
+    }
+}
+
+/// Rename the variables in RPN actions.
+pub fn rename_rpn(rpn_actions: &[RPN], mapping: &HashMap<String, String>, options: &MathParseOptions) -> (Vec<RPN>, RenameReport) {
+    let mut renamer = Renamer::new(mapping, options);
+    let mut ret = rpn_actions.to_vec();
+    for action in &mut ret {
+        if let RPN::Name(name) = action {
+            renamer.rename(name);
+        }
+    }
+    (ret, renamer.report())
+}
+
+/// Rename the variables in a tree.
+pub fn rename_tree(tree: &Tree, mapping: &HashMap<String, String>) -> (Tree, RenameReport) {
+    let options = MathParseOptions::default();
+    let mut renamer = Renamer::new(mapping, &options);
+    let mut ret = tree.clone();
+    let mut to_rename = vec![&mut ret];
+    while let Some(node) = to_rename.pop() {
+        match node {
+            Tree::Name(name) => renamer.rename(name),
+            Tree::Unary(_, next) => to_rename.push(next),
+            Tree::Binary(_, next_1, next_2) => {
+                to_rename.push(next_1);
+                to_rename.push(next_2);
+            },
+            Tree::Call(_, args) => to_rename.extend(args.iter_mut()),
+        }
+    }
+    (ret, renamer.report())
+}
+
+/* --------------------------------- Testing -------------------------------- */
+
+#[test]
+fn test_rename_variables() {
+    use crate::MathParse;
+
+    let mapping = HashMap::from([
+        ("rate".to_string(), "interest".to_string()),
+        ("3".to_string(), "three".to_string()),
+        ("0x10".to_string(), "sixteen".to_string()),
+    ]);
+    let parsed = MathParse::parse("rate * rated_power + 3 * 0x10 - rate").unwrap();
+    let (renamed, report) = parsed.rename_variables(&mapping);
+    assert_eq!(format!("{}", renamed.to_tree().unwrap()), "(((interest * rated_power) + (3 * 0x10)) - interest)");
+    assert_eq!(report, RenameReport{replacements: 2, collisions: vec![]});
+
+    let mapping = HashMap::from([("a".to_string(), "b".to_string()), ("c".to_string(), "b".to_string())]);
+    let (renamed, report) = MathParse::parse("a + b + c").unwrap().rename_variables(&mapping);
+    assert_eq!(report, RenameReport{replacements: 2, collisions: vec!["b".to_string()]});
+    let values = HashMap::from([("b".to_string(), "2".to_string())]);
+    assert_eq!(renamed.solve_int(Some(&values)), Ok(6));
+
+    let swap = HashMap::from([("a".to_string(), "b".to_string()), ("b".to_string(), "a".to_string())]);
+    let (renamed, report) = MathParse::parse("a - b").unwrap().rename_variables(&swap);
+    assert_eq!(format!("{}", renamed.to_tree().unwrap()), "(b - a)");
+    assert_eq!(report, RenameReport{replacements: 2, collisions: vec![]});
+
+    let tree = MathParse::parse("max_a(rate, 2) + -rate").unwrap().to_tree().unwrap();
+    let mapping = HashMap::from([("rate".to_string(), "r".to_string()), ("max_a".to_string(), "f".to_string())]);
+    let (renamed, report) = tree.rename_variables(&mapping);
+    assert_eq!(format!("{renamed}"), "(max_a(r, 2) + -r)");
+    assert_eq!(report, RenameReport{replacements: 2, collisions: vec![]});
+}
@@ -16,7 +16,7 @@ enum RPNSteps {
 pub fn parse_rpn(line: &[MathValue]) -> Result<Vec<RPN>, MathParseErrors> {
     let mut rpn_steps = vec![Solve(0)];
     let mut ret = Vec::<RPN>::new();
-    while rpn_steps.len() != 0 {
+    while !rpn_steps.is_empty() {
         rpn_run_step(line, &mut rpn_steps, &mut ret)?;
     }
     Ok(ret)
@@ -26,14 +26,18 @@ fn rpn_run_step(line: &[MathValue], rpn_steps: &mut Vec<RPNSteps>, rpn_ret: &mut
     match rpn_steps.pop() {
         Some(Solve(index)) => rpn_solve(line, rpn_steps, rpn_ret, index),
         Some(OperatorStep(index)) => rpn_operator(line, rpn_ret, index),
-        None => Err(MathParseInternalBug(format!("Error, stack should not have been empty in rpn_solve_step."))),
+        None => Err(MathParseInternalBug("Error, stack should not have been empty in rpn_solve_step.".to_string())),
     }
 }
 
 fn rpn_solve(line: &[MathValue], rpn_steps: &mut Vec<RPNSteps>, rpn_ret: &mut Vec<RPN>, index: usize) -> Result<(), MathParseErrors> {
     match &line[index] {
         MathValue::Name(name) => {
-            let no_whitespace = remove_whitespace(name);
+            let no_whitespace = if name.starts_with('\'') {
+                name.trim().to_string() // Whitespace might be the value of a character literal.
+            } else {
+                remove_whitespace(name)
+            };
             rpn_ret.push(RPN::Name(no_whitespace));
         },
         Operation(_char, offset_1, offset_2) => {
@@ -48,6 +52,12 @@ fn rpn_solve(line: &[MathValue], rpn_steps: &mut Vec<RPNSteps>, rpn_ret: &mut Ve
         ParenOpen(offset) => {
             rpn_steps.push(Solve(add_index_offset(index, *offset)?));
         },
+        FunctionCall(_name, offsets) => {
+            rpn_steps.push(OperatorStep(index));
+            for offset in offsets.iter().rev() {
+                rpn_steps.push(Solve(add_index_offset(index, *offset)?));
+            }
+        },
         TrailingError => {
             return Err(TrailingOperator);
         },
@@ -68,6 +78,7 @@ fn read_operator(line: &[MathValue], index: usize) -> Result<RPN, MathParseError
     match &line[index] {
         Operation(x, _offset_1, _offset_2) => Ok(Binary(BinaryOp::from_char(*x)?)),
         UnaryOperation(x, _offset) => Ok(Unary(UnaryOp::from_char(*x)?)),
+        FunctionCall(name, offsets) => Ok(Call(name.trim().to_string(), offsets.len())),
         x => Err(MathParseInternalBug(format!("{x:?} should not have been handled by rpn_operator."))),
     }
 }
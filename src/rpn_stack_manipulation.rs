@@ -30,7 +30,16 @@ pub fn pop_two<T>(number_stack: &mut Vec<T>) -> Result<(T, T), MathParseErrors>
     Ok((num_2, num_1))
 }
 
-type UnaryExecFn<T> = dyn Fn(T, UnaryOp) -> Result<T, MathParseErrors>;
+/// Pop the given number of elements from the stack, the element on top of the
+/// stack being the last of the returned vector.
+pub fn pop_n<T>(number_stack: &mut Vec<T>, count: usize) -> Result<Vec<T>, MathParseErrors> {
+    if number_stack.len() < count {
+        return Err(UnbalancedStack);
+    }
+    Ok(number_stack.split_off(number_stack.len() - count))
+}
+
+type UnaryExecFn<'a, T> = dyn Fn(T, UnaryOp) -> Result<T, MathParseErrors> + 'a;
 /// Execute the given unary operation on the top element of the stack.
 pub fn execute_unary<T>(number_stack: &mut Vec<T>, op: UnaryOp, exec: &UnaryExecFn<T>) -> Result<(), MathParseErrors> {
     let num = pop_one(number_stack)?;
@@ -39,7 +48,7 @@ pub fn execute_unary<T>(number_stack: &mut Vec<T>, op: UnaryOp, exec: &UnaryExec
     Ok(())
 }
 
-type BinaryExecFn<T> = dyn Fn(T, T, BinaryOp) -> Result<T, MathParseErrors>;
+type BinaryExecFn<'a, T> = dyn Fn(T, T, BinaryOp) -> Result<T, MathParseErrors> + 'a;
 /// Execute the given binary operation on the top two elements of the stack.
 pub fn execute_binary<T>(number_stack: &mut Vec<T>, op: BinaryOp, exec: &BinaryExecFn<T>) -> Result<(), MathParseErrors> {
     let (num_1, num_2) = pop_two(number_stack)?;
@@ -48,28 +57,45 @@ pub fn execute_binary<T>(number_stack: &mut Vec<T>, op: BinaryOp, exec: &BinaryE
     Ok(())
 }
 
+type CallExecFn<'a, T> = dyn Fn(&str, Vec<T>) -> Result<T, MathParseErrors> + 'a;
+/// Execute the given function call on the top elements of the stack.
+pub fn execute_call<T>(number_stack: &mut Vec<T>, name: &str, arity: usize, exec: &CallExecFn<T>) -> Result<(), MathParseErrors> {
+    let args = pop_n(number_stack, arity)?;
+    let computed = exec(name, args)?;
+    number_stack.push(computed);
+    Ok(())
+}
+
 type NameExecFc<'a, T> = dyn Fn(&str) -> Result<T, MathParseErrors> + 'a;
 /// Execute a single RPN action and update the stack of numbers accordingly.
 fn exec_rpn_one_action<T>(number_stack: &mut Vec<T>, action: &RPN,
-   compute_name: &NameExecFc<T>, compute_unary: &UnaryExecFn<T>, compute_binary: &BinaryExecFn<T>) -> Result<(), MathParseErrors> {
+   compute_name: &NameExecFc<T>, compute_unary: &UnaryExecFn<T>, compute_binary: &BinaryExecFn<T>, compute_call: &CallExecFn<T>) -> Result<(), MathParseErrors> {
     match action {
         Name(x) => {
-            number_stack.push(compute_name(&x)?);
+            number_stack.push(compute_name(x)?);
             Ok(())
         },
         Unary(op) => execute_unary(number_stack, *op, compute_unary),
         Binary(op) => execute_binary(number_stack, *op, compute_binary),
+        Call(name, arity) => execute_call(number_stack, name, *arity, compute_call),
     }
 }
 
-/// Execute all RPN actions and return the single element left in the stack.
-pub fn exec_rpn<T>(rpn_actions: &[RPN], compute_name: &NameExecFc<T>, compute_unary: &UnaryExecFn<T>, compute_binary: &BinaryExecFn<T>) -> Result<T, MathParseErrors> {
+/// Execute all RPN actions and return all the elements left in the stack.
+pub fn exec_rpn_stack<T>(rpn_actions: &[RPN], compute_name: &NameExecFc<T>, compute_unary: &UnaryExecFn<T>, compute_binary: &BinaryExecFn<T>, compute_call: &CallExecFn<T>) -> Result<Vec<T>, MathParseErrors> {
     let mut number_stack = Vec::<T>::new();
 
     for action in rpn_actions {
-        exec_rpn_one_action(&mut number_stack, action, compute_name, compute_unary, compute_binary)?;
+        exec_rpn_one_action(&mut number_stack, action, compute_name, compute_unary, compute_binary, compute_call)?;
     }
 
+    Ok(number_stack)
+}
+
+/// Execute all RPN actions and return the single element left in the stack.
+pub fn exec_rpn<T>(rpn_actions: &[RPN], compute_name: &NameExecFc<T>, compute_unary: &UnaryExecFn<T>, compute_binary: &BinaryExecFn<T>, compute_call: &CallExecFn<T>) -> Result<T, MathParseErrors> {
+    let mut number_stack = exec_rpn_stack(rpn_actions, compute_name, compute_unary, compute_binary, compute_call)?;
+
     if number_stack.len() == 1 {
         pop_one(&mut number_stack)
     } else {
@@ -77,6 +103,31 @@ pub fn exec_rpn<T>(rpn_actions: &[RPN], compute_name: &NameExecFc<T>, compute_un
     }
 }
 
+/// Return the index of the first action of each argument of the function call
+/// at the given index. The arguments of the call spread from their start to
+/// the start of the next one, the last one ending at the call itself.
+pub fn argument_starts(rpn_actions: &[RPN], call_index: usize, arity: usize) -> Result<Vec<usize>, MathParseErrors> {
+    let mut ret = vec![0; arity];
+    let mut index = call_index;
+    for arg in (0..arity).rev() {
+        let mut needed = 1; // Number of values still needed to complete the argument.
+        while needed > 0 {
+            if index == 0 {
+                return Err(UnbalancedStack);
+            }
+            index -= 1;
+            needed = needed - 1 + match &rpn_actions[index] {
+                Name(_) => 0,
+                Unary(_) => 1,
+                Binary(_) => 2,
+                Call(_, n) => *n,
+            };
+        }
+        ret[arg] = index;
+    }
+    Ok(ret)
+}
+
 /* --------------------------------- Testing -------------------------------- */
 
 #[test]
@@ -91,3 +142,13 @@ fn test_pop() {
     assert_eq!(pop_one(&mut vec), Err(UnbalancedStack));
 }
 
+
+#[test]
+fn test_argument_starts() {
+    use crate::name_r;
+    use crate::BinaryOp::*;
+    let rpn = [name_r("i"), name_r("1"), name_r("10"), name_r("i"), name_r("i"), Binary(Multiplication), Call("sum".to_string(), 4)];
+    assert_eq!(argument_starts(&rpn, 6, 4), Ok(vec![0, 1, 2, 3]));
+    assert_eq!(argument_starts(&rpn, 6, 5), Err(UnbalancedStack));
+    assert_eq!(argument_starts(&rpn, 5, 2), Ok(vec![3, 4]));
+}
@@ -0,0 +1,146 @@
+use serde::{Deserialize, Deserializer, Serializer};
+
+/* ------------------------------ Serialization ----------------------------- */
+
+/// Floats written as numbers when they are finite. As formats such as JSON
+/// have no NaN nor infinities, those are written as the strings `"NaN"`,
+/// `"inf"`, and `"-inf"`. Any NaN is read back as `f64::NAN`, so its payload
+/// is not kept.
+pub mod float {
+    use super::*;
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum FloatRepr {
+        Number(f64),
+        Name(String),
+    }
+
+    pub fn serialize<S: Serializer>(f: &f64, serializer: S) -> Result<S::Ok, S::Error> {
+        if f.is_nan() {
+            serializer.serialize_str("NaN")
+        } else if f.is_infinite() {
+            serializer.serialize_str(if *f > 0.0 { "inf" } else { "-inf" })
+        } else {
+            serializer.serialize_f64(*f)
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+        match FloatRepr::deserialize(deserializer)? {
+            FloatRepr::Number(f) => Ok(f),
+            FloatRepr::Name(name) => match name.as_str() {
+                "NaN" => Ok(f64::NAN),
+                "inf" => Ok(f64::INFINITY),
+                "-inf" => Ok(f64::NEG_INFINITY),
+                _ => Err(serde::de::Error::custom(format!("`{name}` is not a float"))),
+            },
+        }
+    }
+}
+
+/// The operator suggested by `MathParseErrors::BadOperatorHint`, written as
+/// a string. As the error holds a `&'static str`, it can only be read back
+/// if it is one of the symbols given by `supported_operators`.
+pub mod operator_hint {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(hint: &&'static str, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(hint)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<&'static str, D::Error> {
+        let hint = String::deserialize(deserializer)?;
+        crate::supported_operators().into_iter()
+            .flat_map(|info| info.symbols)
+            .find(|symbol| *symbol == hint)
+            .ok_or_else(|| serde::de::Error::custom(format!("`{hint}` is not an operator")))
+    }
+}
+
+/* --------------------------------- Testing -------------------------------- */
+
+#[cfg(test)]
+use crate::MathParseErrors::*;
+
+#[test]
+fn test_errors_round_trip() {
+    use crate::NumberErrorKind::*;
+    let errors = vec![
+        UnclosedParenthesis,
+        UnopenedParenthesis,
+        EmptyLine,
+        InvalidNumber("x".to_string()),
+        MisplacedOperator('*'),
+        TrailingOperator,
+        IntConversion(f64::NAN),
+        IntConversion(f64::INFINITY),
+        BinaryOpOnFloat(1.5, '&'),
+        BinaryOpOnFloat(f64::NEG_INFINITY, '|'),
+        FunctionOnFloat(-0.25, "popcount".to_string()),
+        ReturnFloatExpectedInt(2.5e300),
+        BadOperatorHint('=', "=="),
+        BadOperatorHint('<', "<<"),
+        UnexpectedZero,
+        UnexpectedNegative,
+        InvalidRPNOperator('('),
+        UnbalancedStack,
+        IncompatibleUnits,
+        UnknownFunction("f".to_string()),
+        InvalidFunctionCall("max".to_string()),
+        TooManyOperations(100),
+        UnknownOperator("<>".to_string()),
+        MalformedNumber("2e".to_string(), MissingExponentDigits),
+        MalformedNumber("1°61'".to_string(), MalformedAngle),
+        MalformedNumber("1.2.3".to_string(), MultipleDots),
+        MalformedNumber("12a".to_string(), UnexpectedChar('a', 2)),
+        InvalidCharacter('$', 3),
+        InvalidFormatSpec(".x".to_string()),
+        NoRandomSource,
+        InvalidBitWidth(12),
+        CannotIsolateVariable("x".to_string()),
+        MisplacedWord("of".to_string()),
+        DomainError("asin".to_string()),
+        CyclicDefinition("a".to_string()),
+        UnusedVariables(vec!["a".to_string(), "b".to_string()]),
+        ShadowedConstant("pi".to_string()),
+        InvalidRewrite("plus".to_string(), "+ +".to_string()),
+        FunctionOverflow("lcm".to_string()),
+        IntegerOverflow(i64::MAX, '+', 1),
+        DecimalOutOfRange("1e30".to_string()),
+        DimensionOverflow,
+        FailedAtValue(3, Box::new(BinaryOpOnFloat(0.5, '&'))),
+        MathParseInternalBug("bug".to_string()),
+    ];
+    for error in errors {
+        let json = serde_json::to_string(&error).unwrap();
+        let back: crate::MathParseErrors = serde_json::from_str(&json).unwrap();
+        // Compared through `Debug` as NaN is not equal to itself.
+        assert_eq!(format!("{back:?}"), format!("{error:?}"), "{json}");
+    }
+}
+
+#[test]
+fn test_errors_json() {
+    assert_eq!(serde_json::to_string(&UnexpectedZero).unwrap(), r#""UnexpectedZero""#);
+    assert_eq!(serde_json::to_string(&IntConversion(f64::NAN)).unwrap(), r#"{"IntConversion":"NaN"}"#);
+    assert_eq!(serde_json::to_string(&IntConversion(-1.5)).unwrap(), r#"{"IntConversion":-1.5}"#);
+    assert_eq!(serde_json::to_string(&BadOperatorHint('=', "==")).unwrap(), r#"{"BadOperatorHint":["=","=="]}"#);
+    assert_eq!(serde_json::from_str::<crate::MathParseErrors>(r#"{"IntConversion":3}"#).unwrap(), IntConversion(3.0));
+
+    // Only hints that are operators can be read back.
+    assert!(serde_json::from_str::<crate::MathParseErrors>(r#"{"BadOperatorHint":["=","=>"]}"#).is_err());
+    assert!(serde_json::from_str::<crate::MathParseErrors>(r#"{"IntConversion":"one"}"#).is_err());
+}
+
+#[test]
+fn test_numbers_round_trip() {
+    use crate::Number::*;
+    assert_eq!(serde_json::to_string(&Int(-3)).unwrap(), r#"{"Int":-3}"#);
+    assert_eq!(serde_json::to_string(&Float(0.5)).unwrap(), r#"{"Float":0.5}"#);
+    for num in [Int(i64::MIN), Int(i64::MAX), Float(0.1), Float(-0.0), Float(f64::MAX), Float(f64::INFINITY), Float(f64::NEG_INFINITY), Float(f64::NAN)] {
+        let json = serde_json::to_string(&num).unwrap();
+        let back: crate::Number = serde_json::from_str(&json).unwrap();
+        assert_eq!(format!("{back:?}"), format!("{num:?}"), "{json}");
+    }
+}
@@ -0,0 +1,432 @@
+use crate::MathParse;
+use crate::MathParseErrors;
+use crate::MathParseErrors::*;
+use crate::MathParseOptions;
+use crate::RPN;
+use crate::BinaryOp::*;
+use crate::solve::*;
+use crate::rpn_stack_manipulation::*;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/* ------------------------------ Eval sessions ----------------------------- */
+
+/// State of an `EvalSession` after it was run.
+#[derive(Debug, PartialEq, Clone)]
+pub enum EvalStatus {
+    /// The expression is solved, with the same result as `solve_number`
+    /// would give.
+    Done(Result<Number, MathParseErrors>),
+    /// The expression is not solved yet, the session should be run again.
+    Paused,
+}
+
+/// Solving of an expression that can be done a few steps at a time, as given
+/// by `MathParse::start_eval`. Each step is a single action on the stack of
+/// numbers, such as reading a number or doing an operation, including the
+/// ones needed to solve named variables and to repeat the last argument of
+/// binder functions.
+///
+/// ```
+/// use math_parse::{MathParse, EvalStatus, Number};
+///
+/// let parsed = MathParse::parse("sum(i, 1, 100, i*i)").unwrap();
+/// let mut session = parsed.start_eval(None);
+/// let mut slices = 1;
+/// while session.run(50) == EvalStatus::Paused {
+///     slices += 1;
+/// }
+/// assert!(slices > 1);
+/// assert_eq!(session.run(50), EvalStatus::Done(Ok(Number::Int(338350))));
+/// ```
+pub struct EvalSession {
+    options: MathParseOptions,
+    map: HashMap<String, String>,
+    /// Value of the named variables already solved.
+    variables: HashMap<String, Number>,
+    /// The expressions being solved, the one on top being solved to compute
+    /// a named variable used by the one below.
+    contexts: Vec<Context>,
+    /// The parts of the expressions being solved, the one on top being the
+    /// one being worked on.
+    frames: Vec<Frame>,
+    result: Option<Result<Number, MathParseErrors>>,
+}
+
+/// RPN actions being solved, along with their binder and conditional
+/// function calls.
+struct Code {
+    rpn_actions: Vec<RPN>,
+    lazy_calls: LazyCalls,
+}
+
+/// State of the solving of an expression, the one being solved or the one
+/// of a named variable.
+struct Context {
+    /// True if the names can be read from the map of variables. Named
+    /// variables can't use other named variables.
+    uses_map: bool,
+    /// Number of operations already performed.
+    operations: usize,
+    /// Variables bound by binder functions, the last one shadowing the
+    /// previous ones.
+    bindings: Vec<(String, Number)>,
+}
+
+/// Part of RPN actions being solved to a single number.
+struct Frame {
+    code: Arc<Code>,
+    index: usize,
+    end: usize,
+    number_stack: Vec<Number>,
+    /// With the `accurate_float_sums` option, the rounding error of the
+    /// float additions made to get each number of the stack.
+    compensations: Vec<f64>,
+    then: Then,
+}
+
+/// What to do with the number a frame is solved to.
+enum Then {
+    /// It is the result of the expression.
+    Finish,
+    /// It is the value of a named variable.
+    Variable(String),
+    /// It is the first value of the variable of a binder function call.
+    BinderFrom(Binder),
+    /// It is the last value of the variable of a binder function call, the
+    /// first one being given.
+    BinderTo(Binder, i64),
+    /// It is the last argument of a binder function call computed for the
+    /// given value of the variable, along with the last value of the
+    /// variable and the accumulated result.
+    BinderBody(Binder, i64, i64, Number),
+    /// It is the condition of a call to the conditional function, given the
+    /// index of the call and the start of each of its arguments.
+    Condition(usize, Vec<usize>),
+    /// It is the result of a call to the conditional function at the given
+    /// index.
+    Branch(usize),
+}
+
+/// Call to a binder function.
+struct Binder {
+    call_index: usize,
+    starts: Vec<usize>,
+    variable: String,
+    init: Number,
+    accumulate: Accumulator,
+}
+
+impl EvalSession {
+    /// Start solving RPN actions.
+    pub(crate) fn new(rpn_actions: &[RPN], map: Option<&HashMap<String, String>>, options: &MathParseOptions) -> Self {
+        let mut session = EvalSession {
+            options: options.clone(),
+            map: map.cloned().unwrap_or_default(),
+            variables: HashMap::new(),
+            contexts: vec![Context{uses_map: true, operations: 0, bindings: vec![]}],
+            frames: vec![],
+            result: None,
+        };
+        if let Err(e) = session.push_code(rpn_actions.to_vec(), Then::Finish) {
+            session.result = Some(Err(e));
+        }
+        session
+    }
+
+    /// Do up to `max_steps` steps of the solving. `EvalStatus::Done` is
+    /// returned once the expression is solved, with its result, and
+    /// `EvalStatus::Paused` is returned if more steps are needed.
+    pub fn run(&mut self, max_steps: u64) -> EvalStatus {
+        for _ in 0..max_steps {
+            if self.result.is_some() {
+                break;
+            }
+            match self.step() {
+                Ok(None) => {},
+                Ok(Some(num)) => self.result = Some(Ok(num.simplified())),
+                Err(e) => self.result = Some(Err(e)),
+            }
+        }
+        match &self.result {
+            Some(result) => EvalStatus::Done(result.clone()),
+            None => EvalStatus::Paused,
+        }
+    }
+
+    /// Start solving new RPN actions as a whole.
+    fn push_code(&mut self, rpn_actions: Vec<RPN>, then: Then) -> Result<(), MathParseErrors> {
+        let lazy_calls = lazy_calls(&rpn_actions)?;
+        let end = rpn_actions.len();
+        let code = Arc::new(Code{rpn_actions, lazy_calls});
+        self.push_frame(code, 0..end, then);
+        Ok(())
+    }
+
+    /// Start solving a part of the RPN actions of the frame on top.
+    fn push_frame(&mut self, code: Arc<Code>, range: std::ops::Range<usize>, then: Then) {
+        self.frames.push(Frame {
+            code,
+            index: range.start,
+            end: range.end,
+            number_stack: vec![],
+            compensations: vec![],
+            then,
+        });
+    }
+
+    fn context(&mut self) -> Result<&mut Context, MathParseErrors> {
+        self.contexts.last_mut().ok_or(MathParseInternalBug("Missing eval context.".to_string()))
+    }
+
+    fn frame(&mut self) -> Result<&mut Frame, MathParseErrors> {
+        self.frames.last_mut().ok_or(MathParseInternalBug("Missing eval frame.".to_string()))
+    }
+
+    /// Count a new operation and error out if there is too many of them.
+    fn count_operation(&mut self) -> Result<(), MathParseErrors> {
+        let max_operations = self.options.max_operations;
+        let context = self.context()?;
+        context.operations += 1;
+        match max_operations {
+            Some(max) if context.operations > max => Err(TooManyOperations(max)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Do a single step, returning the result of the expression if it is the
+    /// last one.
+    fn step(&mut self) -> Result<Option<Number>, MathParseErrors> {
+        let accurate_float_sums = self.options.accurate_float_sums;
+        let frame = self.frame()?;
+        if frame.index == frame.end {
+            let mut frame = self.frames.pop().ok_or(MathParseInternalBug("Missing eval frame.".to_string()))?;
+            let stack_size = frame.number_stack.len();
+            apply_compensations(&mut frame.number_stack, &mut frame.compensations, stack_size);
+            let num = if frame.number_stack.len() == 1 {
+                pop_one(&mut frame.number_stack)?
+            } else {
+                return Err(UnbalancedStack);
+            };
+            return self.give(num, frame.then);
+        }
+
+        self.count_operation()?;
+        let frame = self.frames.last_mut().ok_or(MathParseInternalBug("Missing eval frame.".to_string()))?;
+        if accurate_float_sums {
+            frame.compensations.resize(frame.number_stack.len(), 0.0);
+        }
+        let code = frame.code.clone();
+        if let Some((call_index, starts)) = lazy_call(&code.lazy_calls, frame.index, frame.end) {
+            let name = match &code.rpn_actions[*call_index] {
+                RPN::Call(name, _) => name,
+                action => {
+                    return Err(MathParseInternalBug(format!("{action:?} should have been a function call.")));
+                },
+            };
+            if name == CONDITIONAL_FUNCTION {
+                self.push_frame(code.clone(), starts[0]..starts[1], Then::Condition(*call_index, starts.clone()));
+                return Ok(None);
+            }
+            let (init, accumulate) = binder_function(name).ok_or(MathParseInternalBug(format!("{name} should have been a binder function.")))?;
+            let variable = binder_variable(&code.rpn_actions, name, starts)?;
+            let binder = Binder{call_index: *call_index, starts: starts.clone(), variable, init, accumulate};
+            self.push_frame(code.clone(), starts[1]..starts[2], Then::BinderFrom(binder));
+            return Ok(None);
+        }
+
+        match &code.rpn_actions[frame.index] {
+            RPN::Name(name) => {
+                let num = match self.read_name(name)? {
+                    Some(num) => num,
+                    None => return Ok(None),
+                };
+                self.frame()?.number_stack.push(num);
+            },
+            RPN::Unary(op) => {
+                apply_compensations(&mut frame.number_stack, &mut frame.compensations, 1);
+                execute_unary(&mut frame.number_stack, *op, &compute_unary)?
+            },
+            RPN::Binary(op) => {
+                let compensated = accurate_float_sums
+                    && matches!(op, Addition | Subtraction)
+                    && compensated_sum(&mut frame.number_stack, &mut frame.compensations, *op);
+                if !compensated {
+                    apply_compensations(&mut frame.number_stack, &mut frame.compensations, 2);
+                    execute_binary(&mut frame.number_stack, *op, &|num_1, num_2, op| compute_binary_with(num_1, num_2, op, &self.options))?
+                }
+            },
+            RPN::Call(name, arity) => {
+                apply_compensations(&mut frame.number_stack, &mut frame.compensations, *arity);
+                let args = pop_n(&mut frame.number_stack, *arity)?;
+                frame.number_stack.push(compute_call(name, args, &self.options)?);
+            },
+        }
+        self.frame()?.index += 1;
+        Ok(None)
+    }
+
+    /// Reads a name, the variables bound by binder functions shadowing the
+    /// named variables from the map. If the name is a named variable that is
+    /// not solved yet, its solving is started and `None` is returned.
+    fn read_name(&mut self, name: &str) -> Result<Option<Number>, MathParseErrors> {
+        let context = self.context()?;
+        for (bound_name, value) in context.bindings.iter().rev() {
+            if bound_name == name {
+                return Ok(Some(*value));
+            }
+        }
+        if context.uses_map {
+            if let Some(value) = self.variables.get(name) {
+                return Ok(Some(*value));
+            }
+            if let Some(value) = self.map.get(name) {
+                let parsed = MathParse::parse_with_options(value, &self.options)?;
+                self.contexts.push(Context{uses_map: false, operations: 0, bindings: vec![]});
+                self.push_code(parsed.internal.to_rpn(), Then::Variable(name.to_string()))?;
+                return Ok(None);
+            }
+        }
+        constant_or_number(name, &self.options).map(Some)
+    }
+
+    /// Use the number a frame was solved to. If it is the result of the
+    /// expression, it is returned.
+    fn give(&mut self, num: Number, then: Then) -> Result<Option<Number>, MathParseErrors> {
+        match then {
+            Then::Finish => {
+                return Ok(Some(num));
+            },
+            Then::Variable(name) => {
+                self.contexts.pop();
+                let num = num.simplified();
+                self.variables.insert(name, num);
+                let frame = self.frame()?;
+                frame.number_stack.push(num);
+                frame.index += 1;
+            },
+            Then::BinderFrom(binder) => {
+                let from = num.as_i64_strict()?;
+                let code = self.frame()?.code.clone();
+                let range = binder.starts[2]..binder.starts[3];
+                self.push_frame(code, range, Then::BinderTo(binder, from));
+            },
+            Then::BinderTo(binder, from) => {
+                let to = num.as_i64_strict()?;
+                let init = binder.init;
+                self.next_binder_value(binder, from, to, init)?;
+            },
+            Then::BinderBody(binder, value, to, acc) => {
+                self.context()?.bindings.pop();
+                let acc = (binder.accumulate)(acc, num);
+                if value < to {
+                    self.next_binder_value(binder, value + 1, to, acc)?;
+                } else {
+                    let frame = self.frame()?;
+                    frame.number_stack.push(acc);
+                    frame.index = binder.call_index + 1;
+                }
+            },
+            Then::Condition(call_index, starts) => {
+                let code = self.frame()?.code.clone();
+                let range = if num.is_zero() {
+                    starts[2]..call_index
+                } else {
+                    starts[1]..starts[2]
+                };
+                self.push_frame(code, range, Then::Branch(call_index));
+            },
+            Then::Branch(call_index) => {
+                let frame = self.frame()?;
+                frame.number_stack.push(num);
+                frame.index = call_index + 1;
+            },
+        }
+        Ok(None)
+    }
+
+    /// Start computing the last argument of a binder function call for the
+    /// given value of its variable, or give the accumulated result if the
+    /// value is past the last one.
+    fn next_binder_value(&mut self, binder: Binder, value: i64, to: i64, acc: Number) -> Result<(), MathParseErrors> {
+        if value > to {
+            let frame = self.frame()?;
+            frame.number_stack.push(acc);
+            frame.index = binder.call_index + 1;
+            return Ok(());
+        }
+        self.count_operation()?;
+        let variable = binder.variable.clone();
+        self.context()?.bindings.push((variable, Number::Int(value)));
+        let code = self.frame()?.code.clone();
+        let range = binder.starts[3]..binder.call_index;
+        self.push_frame(code, range, Then::BinderBody(binder, value, to, acc));
+        Ok(())
+    }
+}
+
+/* --------------------------------- Testing -------------------------------- */
+
+#[test]
+fn test_eval_session_slices() {
+    let piece = "sum(i, 1, 20, i * (a + 0.5)) - product(k, 1, 5, k) // 3 + (b << 2) % 7";
+    let expression = vec![piece; 50].join(" + ");
+    let variables = HashMap::from([
+        ("a".to_string(), "3 * 4".to_string()),
+        ("b".to_string(), "sum(i, 1, 10, i)".to_string()),
+    ]);
+    let parsed = MathParse::parse(&expression).unwrap();
+    let expected = parsed.solve_number(Some(&variables));
+    assert!(expected.is_ok());
+
+    for slice in [1, 7, 100] {
+        let mut session = parsed.start_eval(Some(&variables));
+        let mut runs = 1;
+        let mut status = session.run(slice);
+        while status == EvalStatus::Paused {
+            runs += 1;
+            status = session.run(slice);
+        }
+        assert_eq!(status, EvalStatus::Done(expected.clone()));
+        assert!(runs > 1000 / slice);
+        assert_eq!(session.run(slice), EvalStatus::Done(expected.clone()));
+    }
+}
+
+#[test]
+fn test_eval_session_results() {
+    fn run_to_end(expression: &str, variables: Option<&HashMap<String, String>>, options: &MathParseOptions) -> Result<Number, MathParseErrors> {
+        let mut session = MathParse::parse_with_options(expression, options)?.start_eval(variables);
+        loop {
+            if let EvalStatus::Done(result) = session.run(3) {
+                return result;
+            }
+        }
+    }
+
+    let variables = HashMap::from([
+        ("a".to_string(), "2.5".to_string()),
+        ("b".to_string(), "a".to_string()),
+        ("c".to_string(), "1 +".to_string()),
+        ("i".to_string(), "100".to_string()),
+    ]);
+    let expressions = [
+        "1", "a * 2", "b", "c", "x", "1/0", "sum(i, 1, 3, i) + i", "sum(i, 3, 1, i)",
+        "sum(i, 1, 4, product(j, 1, i, j + a))", "sum(1, 1, 2, 1)", "sum(i, 1, 2)",
+        "-(3 // 2) << 2 ^ 0x10", "mask(8) & ~1", "rand()", "0.1 + 0.2 - 0.3", "sum(i, 1, 1000, i)",
+    ];
+    let accurate = MathParseOptions {
+        accurate_float_sums: true,
+        ..MathParseOptions::default()
+    };
+    let limited = MathParseOptions {
+        max_operations: Some(500),
+        ..MathParseOptions::default()
+    };
+    for options in [MathParseOptions::default(), accurate, limited] {
+        for expression in expressions {
+            let expected = MathParse::parse_with_options(expression, &options).and_then(|x| x.solve_number(Some(&variables)));
+            assert_eq!(run_to_end(expression, Some(&variables), &options), expected, "{expression}");
+        }
+    }
+}
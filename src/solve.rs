@@ -1,36 +1,77 @@
 use crate::MathParseErrors;
 use crate::MathParseErrors::*;
+use crate::NumberErrorKind::*;
 use crate::RPN;
+use crate::MathParseOptions;
+use crate::MathParseWarning;
+use crate::UserFunctions;
 use crate::BinaryOp;
 use crate::UnaryOp;
 use crate::BinaryOp::*;
 use crate::UnaryOp::*;
 use crate::rpn_stack_manipulation::*;
 use crate::number_conversion::*;
+use crate::portable;
+use crate::tokenize::is_radix_prefix;
+use std::collections::HashMap;
+use std::cmp::Ordering;
 
 /* ---------------------------------- Maths --------------------------------- */
 
-/// Reads a Names and transform any name being a key in the map to it's value.
-/// If map is None, nothing is done.
-fn read_name(name: &str, map: &dyn Fn(&str) -> Option<String>) -> Result<Number, MathParseErrors> {
-    if let Some(new_name) = map(name) {
-        let num = crate::MathParse::parse(&new_name)?.solve_number(None)?;
-        Ok(num)
-    } else {
-        number_from_string(name)
+/// Solve the value of a named variable.
+pub fn solve_variable(value: &str, options: &MathParseOptions) -> Result<Number, MathParseErrors> {
+    crate::MathParse::parse_with_options(value, options)?.solve_number(None)
+}
+
+/// Apply an unary operation as `compute_unary`, the negation of integers
+/// being checked with the `integer_overflow_errors` option.
+pub fn compute_unary_with(num: Number, op: UnaryOp, options: &MathParseOptions) -> Result<Number, MathParseErrors> {
+    match (num, op) {
+        (Int(i), Minus) if options.integer_overflow_errors => i.checked_neg().map(Int).ok_or(IntegerOverflow(0, '-', i)),
+        _ => compute_unary(num, op),
     }
 }
 
-fn compute_unary(num: Number, op: UnaryOp) -> Result<Number, MathParseErrors> {
+pub fn compute_unary(num: Number, op: UnaryOp) -> Result<Number, MathParseErrors> {
     Ok(match op {
         UnaryOp::Not => (!num)?,
+        LogicalNot   => Number::from_bool(num.is_zero()),
         Minus        => Int(-1) * num,
         Plus         => num,
+        Percent      => (num / Int(100))?,
+        Sqrt         => {
+            num.err_on_negative()?;
+            Float(num.as_f64().sqrt())
+        },
     })
 }
 
-fn compute_binary(num_1: Number, num_2: Number, op: BinaryOp) -> Result<Number, MathParseErrors> {
+/// Apply a binary operation as `compute_binary`, the powers and remainders of
+/// floats being portable with the `deterministic_floats` option, the
+/// remainders being Euclidean with the `euclidean_remainder` option, the
+/// operations on integers being checked with the `integer_overflow_errors`
+/// option, the divisions by a float following IEEE 754 with the
+/// `infinite_float_division` option, and the integer divisions being rounded
+/// down with the `floor_division` option.
+pub fn compute_binary_with(num_1: Number, num_2: Number, op: BinaryOp, options: &MathParseOptions) -> Result<Number, MathParseErrors> {
+    let fmod: fn(f64, f64) -> f64 = if options.deterministic_floats { portable::fmod } else { |s, o| s % o };
+    match op {
+        Exponent if options.deterministic_floats => num_1.pow_with(num_2, portable::powf),
+        Reminder if options.euclidean_remainder => num_1.rem_euclid_with(num_2, fmod),
+        Reminder => num_1.rem_with(num_2, fmod),
+        Division if options.infinite_float_division && matches!(num_2, Float(_)) => Ok(Float(num_1.as_f64() / num_2.as_f64())),
+        Addition | Subtraction | Multiplication if options.integer_overflow_errors => num_1.checked(num_2, op),
+        IntegerDivision => match (num_1, num_2, num_1.integer_div_with(num_2, options.floor_division)?) {
+            (Int(s), Int(o), Float(_)) if options.integer_overflow_errors => Err(IntegerOverflow(s, '⟌', o)),
+            (_, _, ret) => Ok(ret),
+        },
+        _ => compute_binary(num_1, num_2, op),
+    }
+}
+
+pub fn compute_binary(num_1: Number, num_2: Number, op: BinaryOp) -> Result<Number, MathParseErrors> {
     Ok(match op {
+        Exponent        => num_1.pow(num_2)?,
         Multiplication  => num_1 * num_2,
         Division        => (num_1 / num_2)?,
         IntegerDivision => num_1.integer_div(num_2)?,
@@ -39,18 +80,625 @@ fn compute_binary(num_1: Number, num_2: Number, op: BinaryOp) -> Result<Number,
         Subtraction     => num_1 - num_2,
         ShiftLeft       => (num_1 << num_2)?,
         ShiftRight      => (num_1 >> num_2)?,
+        LessThan        => Number::from_bool(num_1.compare(num_2).is_some_and(Ordering::is_lt)),
+        LessOrEqual     => Number::from_bool(num_1.compare(num_2).is_some_and(Ordering::is_le)),
+        GreaterThan     => Number::from_bool(num_1.compare(num_2).is_some_and(Ordering::is_gt)),
+        GreaterOrEqual  => Number::from_bool(num_1.compare(num_2).is_some_and(Ordering::is_ge)),
+        Equal           => Number::from_bool(num_1.compare(num_2).is_some_and(Ordering::is_eq)),
+        NotEqual        => Number::from_bool(!num_1.compare(num_2).is_some_and(Ordering::is_eq)),
         BitwiseAnd      => (num_1 & num_2)?,
         BitwiseOr       => (num_1 | num_2)?,
         BitwiseXor      => (num_1 ^ num_2)?,
+        LogicalAnd      => Number::from_bool(!num_1.is_zero() && !num_2.is_zero()),
+        LogicalOr       => Number::from_bool(!num_1.is_zero() || !num_2.is_zero()),
     })
 }
 
-pub fn math_solve(rpn_actions: &[RPN], map: &dyn Fn(&str) -> Option<String>) -> Result<Number, MathParseErrors> {
-    let compute_name = | name: &str | -> Result<Number, MathParseErrors> {
-        read_name(name, map)
+pub fn math_solve(rpn_actions: &[RPN], map: &dyn Fn(&str) -> Option<String>, options: &MathParseOptions) -> Result<Number, MathParseErrors> {
+    Solver::new(map, options).solve(rpn_actions)
+}
+
+/// Solve RPN actions and return the warnings raised while doing so.
+pub fn math_solve_with_warnings(rpn_actions: &[RPN], map: &dyn Fn(&str) -> Option<String>, options: &MathParseOptions) -> (Result<Number, MathParseErrors>, Vec<MathParseWarning>) {
+    let mut solver = Solver::new(map, options);
+    let result = solver.solve(rpn_actions);
+    (result, solver.warnings)
+}
+
+/// Solve a list of RPN actions that can leave several numbers on the stack,
+/// such as the ones from a list of expressions.
+pub fn math_solve_list(rpn_actions: &[RPN], map: &dyn Fn(&str) -> Option<String>, options: &MathParseOptions) -> Result<Vec<Number>, MathParseErrors> {
+    Solver::new(map, options).solve_stack(rpn_actions)
+}
+
+/* ------------------------------ Accurate sums ----------------------------- */
+
+/// Add or subtract the two numbers on top of the stack using Neumaier's
+/// compensated summation. The rounding error of the operation is added to
+/// the compensations of the operands and kept as the compensation of the
+/// result. Return false without doing anything when none of the numbers is
+/// a float.
+pub fn compensated_sum(number_stack: &mut Vec<Number>, compensations: &mut Vec<f64>, op: BinaryOp) -> bool {
+    let (num_1, num_2) = match number_stack[..] {
+        [.., num_1, num_2] if matches!(num_1, Float(_)) || matches!(num_2, Float(_)) => (num_1, num_2),
+        _ => {
+            return false;
+        },
     };
+    let size = number_stack.len();
+    let sign = if op == Subtraction { -1.0 } else { 1.0 };
+    let (a, b) = (num_1.as_f64(), sign * num_2.as_f64());
+    let sum = a + b;
+    let error = if !sum.is_finite() {
+        0.0
+    } else if a.abs() >= b.abs() {
+        (a - sum) + b
+    } else {
+        (b - sum) + a
+    };
+    let compensation = compensations[size-2] + sign * compensations[size-1] + error;
+    number_stack.truncate(size - 2);
+    number_stack.push(Float(sum));
+    compensations.truncate(size - 2);
+    compensations.push(compensation);
+    true
+}
+
+/// Add their compensation to the `count` numbers on top of the stack before
+/// they are used by an other operation.
+pub fn apply_compensations(number_stack: &mut [Number], compensations: &mut [f64], count: usize) {
+    let start = number_stack.len().saturating_sub(count);
+    for (num, compensation) in number_stack[start..].iter_mut().zip(compensations.iter_mut().skip(start)) {
+        if *compensation != 0.0 {
+            *num = Float(num.as_f64() + *compensation);
+            *compensation = 0.0;
+        }
+    }
+}
+
+/* -------------------------------- Functions ------------------------------- */
+
+/// Functions that bind a variable in their last argument. They are called as
+/// `f(variable, from, to, expression)` and compute the expression for each
+/// integer value of the variable between the two inclusive bounds. The second
+/// element is the initial value of the accumulator, the third is used to
+/// accumulate each computed values.
+pub type Accumulator = fn(Number, Number) -> Number;
+const BINDER_FUNCTIONS: [(&str, Number, Accumulator); 2] = [
+    ("sum",     Int(0), |acc, x| acc + x),
+    ("product", Int(1), |acc, x| acc * x),
+];
+
+pub fn binder_function(name: &str) -> Option<(Number, Accumulator)> {
+    BINDER_FUNCTIONS.iter()
+        .find(|(function_name, _, _)| *function_name == name)
+        .map(|(_, init, accumulate)| (*init, *accumulate))
+}
+
+/// Function the conditional expressions `c ? a : b` are made into, as
+/// `if(c, a, b)`. Only the argument chosen by the condition is solved.
+pub const CONDITIONAL_FUNCTION: &str = "if";
+
+/// Builtin functions that are not binder functions, with their number of
+/// arguments. For the variadic functions, it is the number of arguments used
+/// when it is not written in RPN.
+const BUILTIN_FUNCTIONS: [(&str, usize); 24] = [
+    ("if",      3),
+    ("rand",    0),
+    ("randint", 2),
+    ("mask",    1),
+    ("popcount", 1),
+    ("rotl",    2),
+    ("rotr",    2),
+    ("sqrt",    1),
+    ("abs",     1),
+    ("min",     2),
+    ("max",     2),
+    ("clamp",   3),
+    ("gcd",     2),
+    ("lcm",     2),
+    ("sin",     1),
+    ("cos",     1),
+    ("tan",     1),
+    ("asin",    1),
+    ("acos",    1),
+    ("atan",    1),
+    ("ln",      1),
+    ("log2",    1),
+    ("log10",   1),
+    ("exp",     1),
+];
+
+/// Builtin functions that take any number of arguments, as long as there is
+/// at least one.
+const VARIADIC_FUNCTIONS: [&str; 2] = ["min", "max"];
+
+/// Return the number of arguments taken by a builtin function.
+pub fn builtin_arity(name: &str) -> Option<usize> {
+    binder_function(name).map(|_| 4).or_else(|| {
+        BUILTIN_FUNCTIONS.iter()
+            .find(|(function_name, _)| *function_name == name)
+            .map(|(_, arity)| *arity)
+    })
+}
+
+/// Tell if a builtin function can be called with the given number of
+/// arguments, `None` being returned for functions that are not builtin.
+pub fn builtin_accepts(name: &str, arity: usize) -> Option<bool> {
+    if VARIADIC_FUNCTIONS.contains(&name) {
+        Some(arity > 0)
+    } else {
+        builtin_arity(name).map(|builtin_arity| builtin_arity == arity)
+    }
+}
+
+/// Check that the builtin functions called in RPN actions are given the
+/// number of arguments they take. Other functions are not checked.
+pub fn check_builtin_calls(rpn_actions: &[RPN]) -> Result<(), MathParseErrors> {
+    for action in rpn_actions {
+        if let RPN::Call(name, arity) = action {
+            if builtin_accepts(name, *arity) == Some(false) {
+                return Err(InvalidFunctionCall(name.clone()));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// For each RPN action, the index of the calls to binder functions or to the
+/// conditional function whose arguments start there, along with the start of
+/// each argument. As the condition can itself start with such a call, there
+/// can be many calls for a single action, the outermost one being first.
+pub type LazyCalls = Vec<Vec<(usize, Vec<usize>)>>;
+
+/// Find the calls of RPN actions which arguments are not all solved before the
+/// call, checking their number of arguments.
+pub fn lazy_calls(rpn_actions: &[RPN]) -> Result<LazyCalls, MathParseErrors> {
+    let mut ret = vec![vec![]; rpn_actions.len()];
+    for (index, action) in rpn_actions.iter().enumerate() {
+        if let RPN::Call(name, arity) = action {
+            let expected_arity = if binder_function(name).is_some() {
+                4
+            } else if name == CONDITIONAL_FUNCTION {
+                3
+            } else {
+                continue;
+            };
+            if *arity != expected_arity {
+                return Err(InvalidFunctionCall(name.clone()));
+            }
+            let starts = argument_starts(rpn_actions, index, *arity)?;
+            let first_argument = starts[0];
+            ret[first_argument].insert(0, (index, starts));
+        }
+    }
+    Ok(ret)
+}
+
+/// Return the lazy call whose arguments start at the given index, and which
+/// ends before the given end.
+pub fn lazy_call(lazy_calls: &LazyCalls, index: usize, end: usize) -> Option<&(usize, Vec<usize>)> {
+    lazy_calls[index].iter().find(|(call_index, _)| *call_index < end)
+}
+
+/// Return the name of the variable bound by a call to a binder function,
+/// given the start of each of its arguments.
+pub fn binder_variable(rpn_actions: &[RPN], name: &str, starts: &[usize]) -> Result<String, MathParseErrors> {
+    match &rpn_actions[starts[0]..starts[1]] {
+        [RPN::Name(variable)] if variable.starts_with(|c: char| c.is_alphabetic() || c == '_') => Ok(variable.clone()),
+        _ => Err(InvalidFunctionCall(name.to_string())),
+    }
+}
+
+/// Execute a call to a function that is not a binder function.
+pub fn compute_call(name: &str, args: Vec<Number>, options: &MathParseOptions) -> Result<Number, MathParseErrors> {
+    match (name, &args[..]) {
+        ("rand", []) => Ok(Float(random(options)?)),
+        ("randint", [low, high]) => {
+            let low = i128::from(low.as_i64_strict()?);
+            let high = i128::from(high.as_i64_strict()?);
+            if low > high {
+                return Err(InvalidFunctionCall(name.to_string()));
+            }
+            let span = high - low + 1;
+            let offset = ((random(options)? * span as f64) as i128).min(span - 1);
+            Ok(Int((low + offset) as i64))
+        },
+        ("mask", [bits]) => {
+            let bits = bits.as_i64_strict()?;
+            if bits < 0 {
+                return Err(UnexpectedNegative);
+            }
+            if bits > 64 {
+                return Err(InvalidFunctionCall(name.to_string()));
+            }
+            Ok(Int(u64::MAX.checked_shr(64 - bits as u32).unwrap_or(0) as i64))
+        },
+        ("popcount", [x]) => Ok(Int(i64::from(x.as_bits_int(name)?.count_ones()))),
+        ("rotl" | "rotr", [x, count]) => {
+            let x = x.as_bits_int(name)?;
+            let count = count.as_bits_int(name)?;
+            if count < 0 {
+                return Err(UnexpectedNegative);
+            }
+            let count = (count % 64) as u32;
+            Ok(Int(if name == "rotl" { x.rotate_left(count) } else { x.rotate_right(count) }))
+        },
+        ("sqrt", [x]) => {
+            x.err_on_negative()?;
+            Ok(Float(x.as_f64().sqrt()))
+        },
+        ("abs", [Int(i)]) => Ok(i.checked_abs().map_or(Float(i_to_f(*i).abs()), Int)),
+        ("abs", [Float(f)]) => Ok(Float(f.abs())),
+        ("min" | "max", [first, rest @ ..]) => {
+            let pick_max = name == "max";
+            Ok(rest.iter().fold(*first, |a, b| match (a, b) {
+                (Int(a), Int(b)) => Int(if pick_max { a.max(*b) } else { a.min(*b) }),
+                (a, b) if pick_max => Float(a.as_f64().max(b.as_f64())),
+                (a, b) => Float(a.as_f64().min(b.as_f64())),
+            }))
+        },
+        ("clamp", [Int(x), Int(low), Int(high)]) if low <= high => Ok(Int(*x.clamp(low, high))),
+        ("clamp", [x, low, high]) if low.as_f64() <= high.as_f64() => {
+            Ok(Float(x.as_f64().clamp(low.as_f64(), high.as_f64())))
+        },
+        ("gcd" | "lcm", [a, b]) => {
+            let a = a.as_i64_strict()?.unsigned_abs();
+            let b = b.as_i64_strict()?.unsigned_abs();
+            let ret = if name == "gcd" {
+                Some(gcd(a, b))
+            } else if a == 0 || b == 0 {
+                Some(0)
+            } else {
+                (a / gcd(a, b)).checked_mul(b)
+            };
+            ret.and_then(|ret| i64::try_from(ret).ok())
+                .map(Int)
+                .ok_or_else(|| FunctionOverflow(name.to_string()))
+        },
+        ("sin" | "cos" | "tan" | "atan" | "exp", [x]) => Ok(Float(float_function(name, options)(x.as_f64()))),
+        ("asin" | "acos", [x]) => {
+            let x = x.as_f64();
+            if !(-1.0..=1.0).contains(&x) {
+                return Err(DomainError(name.to_string()));
+            }
+            Ok(Float(float_function(name, options)(x)))
+        },
+        ("ln" | "log2" | "log10", [x]) => {
+            x.err_on_zero()?;
+            x.err_on_negative()?;
+            Ok(Float(float_function(name, options)(x.as_f64())))
+        },
+        ("if", [condition, a, b]) => Ok(if condition.is_zero() { *b } else { *a }),
+        _ if builtin_arity(name).is_some() => Err(InvalidFunctionCall(name.to_string())),
+        _ => Err(UnknownFunction(name.to_string())),
+    }
+}
+
+/// Greatest common divisor of two integers, with `gcd(0, 0)` being 0.
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+type FloatFunction = fn(f64) -> f64;
+
+/// Float functions of the math library of the platform, with their portable
+/// implementation.
+const FLOAT_FUNCTIONS: [(&str, FloatFunction, FloatFunction); 10] = [
+    ("sin",   f64::sin,   portable::sin),
+    ("cos",   f64::cos,   portable::cos),
+    ("tan",   f64::tan,   portable::tan),
+    ("asin",  f64::asin,  portable::asin),
+    ("acos",  f64::acos,  portable::acos),
+    ("atan",  f64::atan,  portable::atan),
+    ("ln",    f64::ln,    portable::ln),
+    ("log2",  f64::log2,  portable::log2),
+    ("log10", f64::log10, portable::log10),
+    ("exp",   f64::exp,   portable::exp),
+];
+
+/// Return the float function of the given name, which is portable with the
+/// `deterministic_floats` option.
+fn float_function(name: &str, options: &MathParseOptions) -> FloatFunction {
+    FLOAT_FUNCTIONS.iter()
+        .find(|(function_name, _, _)| *function_name == name)
+        .map_or(|_| f64::NAN, |(_, native, portable)| if options.deterministic_floats { *portable } else { *native })
+}
+
+/// Get a random number from the random source of the options.
+fn random(options: &MathParseOptions) -> Result<f64, MathParseErrors> {
+    match &options.random_source {
+        Some(source) => Ok(source.next()),
+        None => Err(NoRandomSource),
+    }
+}
+
+/// Return true if the result of RPN actions doesn't depend on named variables
+/// or on random numbers. The variables bound by binder functions are not
+/// named variables.
+pub fn is_constant(rpn_actions: &[RPN], options: &MathParseOptions) -> bool {
+    let uses_random = rpn_actions.iter()
+        .any(|action| matches!(action, RPN::Call(name, _) if name == "rand" || name == "randint"));
+    !uses_random && free_variables(rpn_actions, options).is_empty()
+}
+
+/// List the named variables used by RPN actions, each one once, in the order
+/// of their first use. Numbers, constants, and the variables bound by binder
+/// functions are left out.
+pub fn free_variables(rpn_actions: &[RPN], options: &MathParseOptions) -> Vec<String> {
+    let mut ret: Vec<String> = vec![];
+    for (action, bound) in rpn_actions.iter().zip(bound_names(rpn_actions)) {
+        if let RPN::Name(name) = action {
+            if !bound && constant_or_number(name, options).is_err() && !ret.contains(name) {
+                ret.push(name.clone());
+            }
+        }
+    }
+    ret
+}
+
+/// For each of the RPN actions, tell if it is a name of a variable bound by
+/// a binder function, either where it is bound or where it is used.
+pub fn bound_names(rpn_actions: &[RPN]) -> Vec<bool> {
+    let mut bound = vec![false; rpn_actions.len()];
+    for (index, action) in rpn_actions.iter().enumerate() {
+        match action {
+            RPN::Call(name, 4) if binder_function(name).is_some() => {
+                let Ok(starts) = argument_starts(rpn_actions, index, 4) else {
+                    continue;
+                };
+                let RPN::Name(variable) = &rpn_actions[starts[0]] else {
+                    continue;
+                };
+                bound[starts[0]] = true;
+                for i in starts[3]..index {
+                    bound[i] |= matches!(&rpn_actions[i], RPN::Name(x) if x == variable);
+                }
+            },
+            _ => {},
+        }
+    }
+    bound
+}
+
+/// Keeps track of the state needed to solve RPN actions. As binder functions
+/// needs to compute their last argument many times, the actions are not
+/// executed with `exec_rpn`. A single solver can be used to solve many
+/// expressions with the same variables.
+pub struct Solver<'a> {
+    map: &'a dyn Fn(&str) -> Option<String>,
+    options: &'a MathParseOptions,
+    /// Variables bound by binder functions, the last one shadowing the
+    /// previous ones.
+    bindings: Vec<(String, Number)>,
+    /// Value of the named variables already solved.
+    variables: HashMap<String, Number>,
+    /// Number of operations already performed.
+    operations: usize,
+    /// Warnings raised while solving, without duplicates.
+    warnings: Vec<MathParseWarning>,
+    /// Functions given by the user, called when there is no builtin function
+    /// with the same name.
+    functions: Option<&'a UserFunctions>,
+}
+
+impl<'a> Solver<'a> {
+    pub fn new(map: &'a dyn Fn(&str) -> Option<String>, options: &'a MathParseOptions) -> Self {
+        Solver{map, options, bindings: vec![], variables: HashMap::new(), operations: 0, warnings: vec![], functions: None}
+    }
+
+    /// Make a solver that can also call the given user functions.
+    pub fn with_functions(map: &'a dyn Fn(&str) -> Option<String>, options: &'a MathParseOptions, functions: &'a UserFunctions) -> Self {
+        Solver{functions: Some(functions), ..Self::new(map, options)}
+    }
+
+    /// Solve a new expression, with a fresh count of operations.
+    pub fn solve_expression(&mut self, rpn_actions: &[RPN]) -> Result<Number, MathParseErrors> {
+        self.operations = 0;
+        self.bindings.clear();
+        self.solve(rpn_actions)
+    }
+
+    /// Solve a new expression where a variable is bound to the given value,
+    /// shadowing any named variable from the map with the same name.
+    pub fn solve_with_binding(&mut self, rpn_actions: &[RPN], name: &str, value: Number) -> Result<Number, MathParseErrors> {
+        self.operations = 0;
+        match &mut self.bindings[..] {
+            [(bound_name, bound_value)] if bound_name == name => *bound_value = value,
+            _ => {
+                self.bindings.clear();
+                self.bindings.push((name.to_string(), value));
+            },
+        }
+        self.solve(rpn_actions)
+    }
+
+    /// Solve the given named variables ahead of time, so that their errors
+    /// are found before any expression is solved.
+    pub fn solve_variables(&mut self, names: &[String]) -> Result<(), MathParseErrors> {
+        for name in names {
+            self.read_name(name)?;
+        }
+        Ok(())
+    }
+
+    /// Set the value of a named variable, which is then used instead of the
+    /// one from the map.
+    pub fn set_variable(&mut self, name: &str, value: Number) {
+        self.variables.insert(name.to_string(), value);
+    }
 
-    exec_rpn(rpn_actions, &Box::new(compute_name), &compute_unary, &compute_binary)
+    /// Count a new operation and error out if there is too many of them.
+    fn count_operation(&mut self) -> Result<(), MathParseErrors> {
+        self.operations += 1;
+        match self.options.max_operations {
+            Some(max) if self.operations > max => Err(TooManyOperations(max)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Reads a name, the variables bound by binder functions shadowing the
+    /// named variables from the map.
+    fn read_name(&mut self, name: &str) -> Result<Number, MathParseErrors> {
+        for (bound_name, value) in self.bindings.iter().rev() {
+            if bound_name == name {
+                return Ok(*value);
+            }
+        }
+        if let Some(value) = self.variables.get(name) {
+            return Ok(*value);
+        }
+        if let Some(value) = (self.map)(name) {
+            if constant_value(name, self.options).is_some() {
+                self.warn(MathParseWarning::ShadowedConstant(name.to_string()));
+            }
+            let num = solve_variable(&value, self.options)?;
+            self.variables.insert(name.to_string(), num);
+            Ok(num)
+        } else {
+            constant_or_number(name, self.options)
+        }
+    }
+
+    /// Add a warning if it was not already raised.
+    fn warn(&mut self, warning: MathParseWarning) {
+        if !self.warnings.contains(&warning) {
+            self.warnings.push(warning);
+        }
+    }
+
+    /// Look for the warnings raised by a binary operation.
+    fn check_binary(&mut self, num_1: Number, num_2: Number, op: BinaryOp) {
+        let converted_to_float = match (num_1, num_2) {
+            (Int(_), Float(_)) | (Float(_), Int(_)) => {
+                self.warn(MathParseWarning::MixedNumberTypes(op));
+                true
+            },
+            _ => op == Division,
+        };
+        if converted_to_float {
+            for num in [num_1, num_2] {
+                if let Int(i) = num {
+                    if !i_fits_in_f(i) {
+                        self.warn(MathParseWarning::PrecisionLoss(i));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Solve RPN actions that should leave a single number on the stack.
+    fn solve(&mut self, rpn_actions: &[RPN]) -> Result<Number, MathParseErrors> {
+        let mut number_stack = self.solve_stack(rpn_actions)?;
+        if number_stack.len() == 1 {
+            pop_one(&mut number_stack)
+        } else {
+            Err(UnbalancedStack)
+        }
+    }
+
+    /// Solve RPN actions and return all numbers left on the stack.
+    fn solve_stack(&mut self, rpn_actions: &[RPN]) -> Result<Vec<Number>, MathParseErrors> {
+        let lazy_calls = lazy_calls(rpn_actions)?;
+
+        let mut number_stack = Vec::<Number>::new();
+        // With the `accurate_float_sums` option, the rounding error of the
+        // float additions made to get each number of the stack.
+        let mut compensations = Vec::<f64>::new();
+        let mut index = 0;
+        while index < rpn_actions.len() {
+            self.count_operation()?;
+            if self.options.accurate_float_sums {
+                compensations.resize(number_stack.len(), 0.0);
+            }
+            if let Some((call_index, starts)) = lazy_call(&lazy_calls, index, rpn_actions.len()) {
+                let result = match &rpn_actions[*call_index] {
+                    RPN::Call(name, _) if name == CONDITIONAL_FUNCTION => self.solve_conditional(rpn_actions, *call_index, starts)?,
+                    _ => self.solve_binder(rpn_actions, *call_index, starts)?,
+                };
+                number_stack.push(result);
+                index = call_index + 1;
+                continue;
+            }
+            match &rpn_actions[index] {
+                RPN::Name(name)        => number_stack.push(self.read_name(name)?),
+                RPN::Unary(op)         => {
+                    apply_compensations(&mut number_stack, &mut compensations, 1);
+                    execute_unary(&mut number_stack, *op, &|num, op| compute_unary_with(num, op, self.options))?
+                },
+                RPN::Binary(op)        => {
+                    if let [.., num_1, num_2] = number_stack[..] {
+                        self.check_binary(num_1, num_2, *op);
+                    }
+                    let compensated = self.options.accurate_float_sums
+                        && matches!(op, Addition | Subtraction)
+                        && compensated_sum(&mut number_stack, &mut compensations, *op);
+                    if !compensated {
+                        apply_compensations(&mut number_stack, &mut compensations, 2);
+                        execute_binary(&mut number_stack, *op, &|num_1, num_2, op| compute_binary_with(num_1, num_2, op, self.options))?
+                    }
+                },
+                RPN::Call(name, arity) => {
+                    apply_compensations(&mut number_stack, &mut compensations, *arity);
+                    let args = pop_n(&mut number_stack, *arity)?;
+                    let user_function = self.functions
+                        .filter(|_| builtin_arity(name).is_none())
+                        .and_then(|functions| functions.get(name));
+                    number_stack.push(match user_function {
+                        Some(function) => function(&args)?,
+                        None => compute_call(name, args, self.options)?,
+                    });
+                },
+            }
+            index += 1;
+        }
+        let stack_size = number_stack.len();
+        apply_compensations(&mut number_stack, &mut compensations, stack_size);
+        Ok(number_stack)
+    }
+
+    /// Solve a call to the conditional function, only solving the argument
+    /// chosen by the condition.
+    fn solve_conditional(&mut self, rpn_actions: &[RPN], call_index: usize, starts: &[usize]) -> Result<Number, MathParseErrors> {
+        let condition = self.solve(&rpn_actions[starts[0]..starts[1]])?;
+        if condition.is_zero() {
+            self.solve(&rpn_actions[starts[2]..call_index])
+        } else {
+            self.solve(&rpn_actions[starts[1]..starts[2]])
+        }
+    }
+
+    /// Solve a call to a binder function, given the index of the call and the
+    /// start of each of its arguments.
+    fn solve_binder(&mut self, rpn_actions: &[RPN], call_index: usize, starts: &[usize]) -> Result<Number, MathParseErrors> {
+        let name = if let RPN::Call(name, _) = &rpn_actions[call_index] {
+            name
+        } else {
+            return Err(MathParseInternalBug(format!("{:?} should have been a function call.", rpn_actions[call_index])));
+        };
+        let (init, accumulate) = if let Some(function) = binder_function(name) {
+            function
+        } else {
+            return Err(MathParseInternalBug(format!("{name} should have been a binder function.")));
+        };
+        let variable = binder_variable(rpn_actions, name, starts)?;
+        let from = self.solve(&rpn_actions[starts[1]..starts[2]])?.as_i64_strict()?;
+        let to = self.solve(&rpn_actions[starts[2]..starts[3]])?.as_i64_strict()?;
+        let body = &rpn_actions[starts[3]..call_index];
+
+        let mut ret = init;
+        for value in from..=to {
+            self.count_operation()?;
+            self.bindings.push((variable.clone(), Int(value)));
+            let computed = self.solve(body);
+            self.bindings.pop();
+            ret = accumulate(ret, computed?);
+        }
+        Ok(ret)
+    }
 }
 
 /* --------------------------------- Numbers -------------------------------- */
@@ -59,11 +707,20 @@ use std::ops::*;
 
 /// A type representing the numbers understood by math-parse. Math operation can
 /// be formed with numbers of different types and the time of the result will
-/// be chosen in the most sensible way.
+/// be chosen in the most sensible way. When the result of an operation on
+/// integers does not fit in an `i64`, it is computed as a float.
+///
+/// ```
+/// use math_parse::Number;
+///
+/// assert_eq!((Number::Int(3) + Number::Float(0.5)), Number::Float(3.5));
+/// assert_eq!((Number::Int(i64::MAX) + Number::Int(1)), Number::Float(9223372036854775808.0));
+/// ```
 #[derive(Debug, PartialEq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Number {
     Int(i64),
-    Float(f64),
+    Float(#[cfg_attr(feature = "serde", serde(with = "crate::serialization::float"))] f64),
 }
 use Number::*;
 
@@ -72,7 +729,7 @@ impl Add for Number {
     
     fn add(self, other: Self) -> Self {
         match (self, other) {
-            (Int(s),   Int(o))   => Int(s + o),
+            (Int(s),   Int(o))   => s.checked_add(o).map_or(Float(i_to_f(s) + i_to_f(o)), Int),
             (Float(s), Int(o))   => Float(s + i_to_f(o)),
             (Int(s),   Float(o)) => Float(i_to_f(s) + o),
             (Float(s), Float(o)) => Float(s + o),
@@ -85,7 +742,7 @@ impl Sub for Number {
     
     fn sub(self, other: Self) -> Self {
         match (self, other) {
-            (Int(s),   Int(o))   => Int(s - o),
+            (Int(s),   Int(o))   => s.checked_sub(o).map_or(Float(i_to_f(s) - i_to_f(o)), Int),
             (Float(s), Int(o))   => Float(s - i_to_f(o)),
             (Int(s),   Float(o)) => Float(i_to_f(s) - o),
             (Float(s), Float(o)) => Float(s - o),
@@ -98,7 +755,7 @@ impl Mul for Number {
     
     fn mul(self, other: Self) -> Self {
         match (self, other) {
-            (Int(s),   Int(o))   => Int(s * o),
+            (Int(s),   Int(o))   => s.checked_mul(o).map_or(Float(i_to_f(s) * i_to_f(o)), Int),
             (Float(s), Int(o))   => Float(s * i_to_f(o)),
             (Int(s),   Float(o)) => Float(i_to_f(s) * o),
             (Float(s), Float(o)) => Float(s * o),
@@ -111,12 +768,7 @@ impl Div for Number {
     
     fn div(self, other: Self) -> Result<Self, MathParseErrors> {
         other.err_on_zero()?;
-        match (self, other) {
-            (Int(s),   Int(o))   => Ok(Float(i_to_f(s) / i_to_f(o))),
-            (Float(s), Int(o))   => Ok(Float(s / i_to_f(o))),
-            (Int(s),   Float(o)) => Ok(Float(i_to_f(s) / o)),
-            (Float(s), Float(o)) => Ok(Float(s / o)),
-        }
+        Ok(Float(self.as_f64() / other.as_f64()))
     }
 }
 
@@ -124,13 +776,7 @@ impl Rem for Number {
     type Output = Result<Number, MathParseErrors>;
     
     fn rem(self, other: Self) -> Result<Self, MathParseErrors> {
-        other.err_on_zero()?;
-        match (self, other) {
-            (Int(s),   Int(o))   => Ok(Int(s % o)),
-            (Float(s), Int(o))   => Ok(Float(s % i_to_f(o))),
-            (Int(s),   Float(o)) => Ok(Float(i_to_f(s) % o)),
-            (Float(s), Float(o)) => Ok(Float(s % o)),
-        }
+        self.rem_with(other, |s, o| s % o)
     }
 }
 
@@ -140,7 +786,7 @@ impl std::ops::Not for Number {
     fn not(self) -> Result<Number, MathParseErrors> {
         match self {
             Int(s) => Ok(Int(!s)),
-            Float(s) => Err(BinaryOpOnFloat(s, '!')),
+            Float(s) => Err(BinaryOpOnFloat(s, '~')),
         }
     }
 }
@@ -192,7 +838,8 @@ impl Shl for Number {
         other.err_on_float('≪')?;
         other.err_on_negative()?;
         match (self, other) {
-            (Int(s),   Int(o))   => Ok(Int(s << o)),
+            // Shifting all the bits out gives 0.
+            (Int(s),   Int(o))   => Ok(Int(u32::try_from(o).ok().and_then(|o| s.checked_shl(o)).unwrap_or(0))),
             _                    => Err(MathParseInternalBug("Invalid type check on Shl.".to_string())),
         }
     }
@@ -206,13 +853,46 @@ impl Shr for Number {
         other.err_on_float('≫')?;
         other.err_on_negative()?;
         match (self, other) {
-            (Int(s),   Int(o))   => Ok(Int(s >> o)),
+            // The shift is arithmetic, so shifting all the bits out of a
+            // negative number gives -1.
+            (Int(s),   Int(o))   => Ok(Int(u32::try_from(o).ok().and_then(|o| s.checked_shr(o)).unwrap_or(if s < 0 { -1 } else { 0 }))),
             _                    => Err(MathParseInternalBug("Invalid type check on Shr.".to_string())),
         }
     }
 }
 
+impl From<i64> for Number {
+    fn from(i: i64) -> Self {
+        Int(i)
+    }
+}
+
+impl From<f64> for Number {
+    fn from(f: f64) -> Self {
+        Float(f)
+    }
+}
+
+/// Convert the number to an integer as `Number::as_i64_strict`, failing if
+/// it is a float with a fractional part or too big for an `i64`.
+impl TryFrom<Number> for i64 {
+    type Error = MathParseErrors;
+
+    fn try_from(num: Number) -> Result<Self, Self::Error> {
+        num.as_i64_strict()
+    }
+}
+
 impl Number {
+    /// Return the integer used by the given bitwise function, failing if the
+    /// number is a float.
+    fn as_bits_int(self, function: &str) -> Result<i64, MathParseErrors> {
+        match self {
+            Int(i)   => Ok(i),
+            Float(f) => Err(FunctionOnFloat(f, function.to_string())),
+        }
+    }
+
     /// Return an error related to the given operator if the number is a float.
     fn err_on_float(self, op: char) -> Result<(), MathParseErrors> {
         if let Float(f) = self {
@@ -223,7 +903,7 @@ impl Number {
     }
 
     /// Return true if the number is equal to 0.
-    fn is_zero(self) -> bool {
+    pub(crate) fn is_zero(self) -> bool {
         match self {
             Int(i) => i == 0,
             Float(f) => f == 0.0,
@@ -255,50 +935,776 @@ impl Number {
             Ok(())
         }
     }
-    fn integer_div(self, other: Self) -> Result<Self, MathParseErrors> {
-        other.err_on_zero()?;
-        let s = self - (self % other)?;
-        let s = match s {
-            Int(s) => s,
-            Float(s) => f_to_i(s)?,
+
+    /// Convert a float that holds an integer value to an `Int`.
+    pub fn simplified(self) -> Self {
+        match self {
+            Float(f) => match f_to_i_strict(f) {
+                Ok(i) => Int(i),
+                Err(_) => Float(f),
+            },
+            x => x,
+        }
+    }
+
+    /// Convert the number to a float.
+    pub fn as_f64(self) -> f64 {
+        match self {
+            Int(i)   => i_to_f(i),
+            Float(f) => f,
+        }
+    }
+
+    /// Convert the number to an integer, failing if it is a float with a
+    /// fractional part.
+    pub fn as_i64_strict(self) -> Result<i64, MathParseErrors> {
+        match self {
+            Int(i)   => Ok(i),
+            Float(f) => f_to_i_strict(f),
+        }
+    }
+
+    /// Reinterpret the number as the 64 bits of a two's complement integer,
+    /// so `-1` gives `u64::MAX`. Floats with a fractional part give a
+    /// `ReturnFloatExpectedInt` error.
+    pub fn to_u64_bits(self) -> Result<u64, MathParseErrors> {
+        Ok(self.as_i64_strict()? as u64)
+    }
+
+    /// Raise the number to the power of the other one. An integer raised to
+    /// a positive integer power is an integer, unless it does not fit in an
+    /// `i64`, and other powers are floats. Raising 0 to a negative power is
+    /// an `UnexpectedZero` error, as it is a division by 0.
+    pub fn pow(self, other: Self) -> Result<Self, MathParseErrors> {
+        self.pow_with(other, f64::powf)
+    }
+
+    /// Apply an addition, a subtraction, a multiplication, or an integer
+    /// division, giving an `IntegerOverflow` error when the result of an
+    /// operation on integers does not fit in an `i64`.
+    pub(crate) fn checked(self, other: Self, op: BinaryOp) -> Result<Self, MathParseErrors> {
+        let (Int(s), Int(o)) = (self, other) else {
+            return compute_binary(self, other, op);
         };
-        let o = match other {
-            Int(o) => o,
-            Float(o) => f_to_i(o)?,
+        let (result, symbol) = match op {
+            Addition       => (s.checked_add(o), '+'),
+            Subtraction    => (s.checked_sub(o), '-'),
+            Multiplication => (s.checked_mul(o), '*'),
+            _ => return compute_binary(self, other, op),
         };
-        Ok(Int(s / o))
+        result.map(Int).ok_or(IntegerOverflow(s, symbol, o))
+    }
+
+    /// Raise the number to a power as `pow`, with the given power of floats.
+    pub(crate) fn pow_with(self, other: Self, powf: fn(f64, f64) -> f64) -> Result<Self, MathParseErrors> {
+        if self.is_zero() && other.is_negative() {
+            return Err(UnexpectedZero);
+        }
+        if let (Int(s), Int(o)) = (self, other) {
+            if let Some(ret) = u32::try_from(o).ok().and_then(|o| s.checked_pow(o)) {
+                return Ok(Int(ret));
+            }
+        }
+        Ok(Float(powf(self.as_f64(), other.as_f64())))
+    }
+
+    /// Remainder of the division as the `%` operator, with the given
+    /// remainder of floats.
+    pub(crate) fn rem_with(self, other: Self, fmod: fn(f64, f64) -> f64) -> Result<Self, MathParseErrors> {
+        other.err_on_zero()?;
+        match (self, other) {
+            // Only `i64::MIN % -1` overflows, and its remainder is 0.
+            (Int(s),   Int(o))   => Ok(Int(s.checked_rem(o).unwrap_or(0))),
+            (Float(s), Int(o))   => Ok(Float(fmod(s, i_to_f(o)))),
+            (Int(s),   Float(o)) => Ok(Float(fmod(i_to_f(s), o))),
+            (Float(s), Float(o)) => Ok(Float(fmod(s, o))),
+        }
+    }
+
+    /// Remainder of the Euclidean division, which is never negative, with the
+    /// given remainder of floats.
+    pub(crate) fn rem_euclid_with(self, other: Self, fmod: fn(f64, f64) -> f64) -> Result<Self, MathParseErrors> {
+        Ok(match (self.rem_with(other, fmod)?, other) {
+            (Int(r), Int(o)) if r < 0 => Int(if o < 0 { r - o } else { r + o }),
+            (Float(r), o) if r < 0.0 => Float(r + o.as_f64().abs()),
+            (r, _) => r,
+        })
+    }
+
+    /// Compare two numbers. Integers are compared exactly while the other
+    /// numbers are compared as floats. `None` is returned if one of them is
+    /// NaN.
+    pub fn compare(self, other: Self) -> Option<Ordering> {
+        match (self, other) {
+            (Int(s), Int(o)) => Some(s.cmp(&o)),
+            (s, o) => s.as_f64().partial_cmp(&o.as_f64()),
+        }
+    }
+
+    /// The result of a comparison, 1 when it is true and 0 otherwise.
+    pub fn from_bool(b: bool) -> Self {
+        Int(i64::from(b))
+    }
+
+    /// Integer division, rounded toward 0 as in C or Rust, so `-7 // 2` is
+    /// -3. The result is an `Int` unless it does not fit in an `i64`, as for
+    /// `i64::MIN // -1`.
+    pub fn integer_div(self, other: Self) -> Result<Self, MathParseErrors> {
+        self.integer_div_with(other, false)
+    }
+
+    /// Integer division as `integer_div`, rounded down as in Python instead
+    /// of toward 0 when `floor` is set, so `-7 // 2` is -4.
+    pub(crate) fn integer_div_with(self, other: Self, floor: bool) -> Result<Self, MathParseErrors> {
+        other.err_on_zero()?;
+        let rem = (self % other)?;
+        // The quotient is rounded toward 0, one less is rounded down when the
+        // remainder and the divisor have opposite signs.
+        let step_down = floor && !rem.is_zero() && rem.is_negative() != other.is_negative();
+        match (self, other) {
+            (Int(s), Int(o)) => match s.checked_div(o) {
+                Some(ret) => Ok(Int(ret - i64::from(step_down))),
+                None => Ok(Float(i_to_f(s) / i_to_f(o))),
+            },
+            _ => {
+                // The dividend minus the remainder is a multiple of the
+                // divisor, so their quotient is an integer up to rounding
+                // errors. As for integers, a quotient that does not fit in an
+                // `i64` is kept as a float.
+                let quotient = ((self - rem).as_f64() / other.as_f64()).round();
+                if quotient.is_finite() && !(i_to_f(i64::MIN)..i_to_f(i64::MAX)).contains(&quotient) {
+                    return Ok(Float(quotient - f64::from(u8::from(step_down))));
+                }
+                let multiple = i_to_f(f_to_i((self - rem).as_f64())?);
+                let quotient = f_to_i(multiple / other.as_f64())?;
+                Ok(Int(quotient - i64::from(step_down)))
+            },
+        }
     }
 }
 
 /* ---------------------------------- Utils --------------------------------- */
 
+/// Byte-size suffixes and the value they multiply numbers by.
+const BYTE_SIZE_SUFFIXES: [(&str, i64); 9] = [
+    ("KiB", 1 << 10),
+    ("MiB", 1 << 20),
+    ("GiB", 1 << 30),
+    ("TiB", 1 << 40),
+    ("KB",  1_000),
+    ("MB",  1_000_000),
+    ("GB",  1_000_000_000),
+    ("TB",  1_000_000_000_000),
+    ("B",   1),
+];
+
+/// SI suffixes and the value they multiply numbers by.
+const SI_SUFFIXES: [(&str, i64); 8] = [
+    ("Ki", 1 << 10),
+    ("Mi", 1 << 20),
+    ("Gi", 1 << 30),
+    ("Ti", 1 << 40),
+    ("k",  1_000),
+    ("M",  1_000_000),
+    ("G",  1_000_000_000),
+    ("T",  1_000_000_000_000),
+];
+
+/// Constants that can be used without being in the map of named variables,
+/// unless the `disable_constants` option is set.
+const CONSTANTS: [(&str, f64); 3] = [
+    ("pi",  std::f64::consts::PI),
+    ("e",   std::f64::consts::E),
+    ("tau", std::f64::consts::TAU),
+];
+
+/// Return the value of a builtin constant.
+pub fn constant_value(name: &str, options: &MathParseOptions) -> Option<Number> {
+    if options.disable_constants {
+        return None;
+    }
+    CONSTANTS.iter()
+        .find(|(constant, _)| *constant == name)
+        .map(|(_, value)| Float(*value))
+}
+
+/// Reads a name that is not a named variable, as a constant or a number.
+pub fn constant_or_number(name: &str, options: &MathParseOptions) -> Result<Number, MathParseErrors> {
+    match constant_value(name, options) {
+        Some(num) => Ok(num),
+        None => number_from_string(name, options),
+    }
+}
+
 /// Takes a string and try to return a number for it.
-fn number_from_string(s: &str) -> Result<Number, MathParseErrors> {
-    let converted = if s.len() >= 3 && &s[0..2] == "0x" {
-        i64::from_str_radix(&s[2..], 16)
-    } else {
-        i64::from_str_radix(&s, 10)
+pub fn number_from_string(s: &str, options: &MathParseOptions) -> Result<Number, MathParseErrors> {
+    if s.starts_with('\'') {
+        return char_literal_value(s);
+    }
+    if options.dms_angles && s.contains('°') {
+        return dms_angle_value(s);
+    }
+    if options.apostrophe_separators && s.contains('\'') {
+        let invalid = || InvalidNumber(s.to_string());
+        return number_from_string(&remove_digit_separators(s, '\'').ok_or_else(invalid)?, options).map_err(|_| invalid());
+    }
+    // The suffixes are only read from numbers that are not valid without
+    // them, as `B` or `k` can be digits of `0x1B` or `36#1k`.
+    let plain = number_without_suffix(s);
+    if plain.is_ok() {
+        return plain;
+    }
+    if options.byte_size_suffixes {
+        if let Some(num) = number_with_suffix(s, &BYTE_SIZE_SUFFIXES) {
+            return Ok(num);
+        }
+    }
+    if options.si_suffixes {
+        if let Some(num) = number_with_suffix(s, &SI_SUFFIXES) {
+            return Ok(num);
+        }
+    }
+    plain
+}
+
+/// Reads a number followed by one of the suffixes. Return `None` if there is
+/// no suffix or if the number can't be read.
+fn number_with_suffix(s: &str, suffixes: &[(&str, i64)]) -> Option<Number> {
+    for (suffix, factor) in suffixes {
+        if let Some(num) = s.strip_suffix(suffix) {
+            return match number_without_suffix(num) {
+                Ok(Int(i))   => i.checked_mul(*factor).map(Int),
+                Ok(Float(f)) => Some(Float(f * i_to_f(*factor))),
+                Err(_)       => None,
+            };
+        }
+    }
+    None
+}
+
+/// Reads an angle in degrees, minutes, and seconds such as `12°30'45"` and
+/// return it as a float number of degrees. The minutes and the seconds are
+/// optional.
+fn dms_angle_value(s: &str) -> Result<Number, MathParseErrors> {
+    let malformed = || MalformedNumber(s.to_string(), MalformedAngle);
+    let read = |x: &str| -> Result<f64, MathParseErrors> {
+        if !crate::tokenize::is_decimal_mantissa(x) {
+            return Err(malformed());
+        }
+        x.parse::<f64>().map_err(|_| malformed())
     };
-    if let Ok(num) = converted {
-        Ok(Int(num))
-    } else {
-        if let Ok(num) = s.parse::<f64>() {
-            Ok(Float(num))
-        } else {
-            Err(InvalidNumber(s.to_string()))
+    let (degrees, rest) = s.split_once('°').ok_or_else(malformed)?;
+    let (minutes, rest) = rest.split_once('\'').unwrap_or(("0", rest));
+    let (seconds, rest) = rest.split_once('"').unwrap_or(("0", rest));
+    let (degrees, minutes, seconds) = (read(degrees)?, read(minutes)?, read(seconds)?);
+    if !rest.is_empty() || minutes >= 60.0 || seconds >= 60.0 {
+        return Err(malformed());
+    }
+    Ok(Float(degrees + minutes / 60.0 + seconds / 3600.0))
+}
+
+/// Reads a character literal such as `'a'` or `'\n'` and return its Unicode
+/// code point.
+fn char_literal_value(s: &str) -> Result<Number, MathParseErrors> {
+    let invalid = || InvalidNumber(s.to_string());
+    let content = s.strip_prefix('\'')
+        .and_then(|x| x.strip_suffix('\''))
+        .ok_or_else(invalid)?;
+    let mut chars = content.chars();
+    let value = match (chars.next(), chars.next()) {
+        (Some('\\'), Some(escaped)) => match escaped {
+            'n'  => '\n',
+            't'  => '\t',
+            '\\' => '\\',
+            '\'' => '\'',
+            '0'  => '\0',
+            'x'  => {
+                let code = u8::from_str_radix(chars.as_str(), 16).map_err(|_| invalid())?;
+                return Ok(Int(code as i64));
+            },
+            _ => {
+                return Err(invalid());
+            },
+        },
+        (Some(c), None) => c,
+        _ => {
+            return Err(invalid());
+        },
+    };
+    if chars.next().is_some() {
+        return Err(invalid());
+    }
+    Ok(Int(value as i64))
+}
+
+/// Prefixes of the integers written in another base than 10, with their
+/// base. The hexadecimal prefix can also be written in uppercase.
+const RADIX_PREFIXES: [(&str, u32); 4] = [("0x", 16), ("0X", 16), ("0o", 8), ("0b", 2)];
+
+/// Split an integer written in another base than 10, such as `0x1F` or
+/// `36#ZZ`, into its prefix, its digits, and its base. The base is `None`
+/// when it is written before a `#` but is not between 2 and 36. Return
+/// `None` for the numbers in base 10.
+fn split_radix(s: &str) -> Option<(&str, &str, Option<u32>)> {
+    if let Some((radix, digits)) = s.split_once('#') {
+        let base = radix.parse::<u32>().ok().filter(|x| is_radix_prefix(radix) && (2..=36).contains(x));
+        return Some((&s[..=radix.len()], digits, base));
+    }
+    RADIX_PREFIXES.iter()
+        .find_map(|(prefix, radix)| s.strip_prefix(prefix).map(|digits| (*prefix, digits, Some(*radix))))
+}
+
+/// Tell if the string is an integer written in another base than 10, such
+/// as `0xFF` or `36#ZZ`, whose digits are the 64 bits of the number.
+pub fn is_radix_literal(s: &str) -> bool {
+    matches!(split_radix(s), Some((_, _, Some(_))))
+}
+
+/// Remove the separators between the digits of a number, such as the
+/// underscores of `1_000_000` or `0xFFFF_FFFF`. Return `None` if a separator
+/// is not between two digits.
+fn remove_digit_separators(s: &str, separator: char) -> Option<String> {
+    let (prefix, digits, radix) = match split_radix(s) {
+        Some((prefix, digits, radix)) => (prefix, digits, radix?),
+        None => ("", s, 10),
+    };
+    let chars = digits.chars().collect::<Vec<char>>();
+    for (index, c) in chars.iter().enumerate() {
+        let is_digit = |i: Option<usize>| i.and_then(|i| chars.get(i)).is_some_and(|x| x.is_digit(radix));
+        if *c == separator && !(is_digit(index.checked_sub(1)) && is_digit(Some(index + 1))) {
+            return None;
         }
     }
+    Some(format!("{prefix}{}", digits.replace(separator, "")))
+}
+
+/// Reads a plain number.
+fn number_without_suffix(s: &str) -> Result<Number, MathParseErrors> {
+    if s.contains('_') {
+        let invalid = || InvalidNumber(s.to_string());
+        return number_without_suffix(&remove_digit_separators(s, '_').ok_or_else(invalid)?).map_err(|_| invalid());
+    }
+    if let Some((_, digits, radix)) = split_radix(s) {
+        // `from_str_radix` would accept a sign after the prefix. The digits
+        // are the 64 bits of the number in two's complement, so that
+        // `0xFFFFFFFFFFFFFFFF` is -1.
+        return match radix {
+            Some(radix) if !digits.starts_with(['+', '-']) => u64::from_str_radix(digits, radix)
+                .map(|x| Int(x as i64))
+                .map_err(|_| InvalidNumber(s.to_string())),
+            _ => Err(InvalidNumber(s.to_string())),
+        };
+    }
+    let unsigned = s.strip_prefix(['+', '-']).unwrap_or(s);
+    let mut chars = unsigned.chars();
+    match (chars.next(), chars.next()) {
+        (Some('0'..='9'), _) | (Some('.'), Some('0'..='9')) => scan_decimal(s),
+        // The special values read by `f64::from_str`.
+        _ if ["inf", "infinity", "nan"].iter().any(|x| unsigned.eq_ignore_ascii_case(x)) => s.parse::<f64>()
+            .map(Float)
+            .map_err(|_| InvalidNumber(s.to_string())),
+        _ => Err(InvalidNumber(s.to_string())),
+    }
+}
+
+/// Reads a decimal number: an optional sign and digits with an optional
+/// decimal dot, followed by an optional exponent made of an `e`, an optional sign, and digits. The
+/// number is checked against this grammar before being converted so that
+/// the reason for a malformed number can be given.
+fn scan_decimal(s: &str) -> Result<Number, MathParseErrors> {
+    let malformed = |kind| Err(MalformedNumber(s.to_string(), kind));
+    let mut dot = false;
+    let mut exponent = None; // Position of the exponent marker.
+    let mut exponent_digits = false;
+    for (position, c) in s.char_indices() {
+        match (c, exponent) {
+            ('+' | '-', None) if position == 0 => {},
+            ('0'..='9', None) => {},
+            ('0'..='9', Some(_)) => exponent_digits = true,
+            ('.', None) if dot => return malformed(MultipleDots),
+            ('.', None) => dot = true,
+            ('e' | 'E', None) => exponent = Some(position),
+            ('+' | '-', Some(marker)) if position == marker + 1 => {},
+            _ => return malformed(UnexpectedChar(c, position)),
+        }
+    }
+    if exponent.is_some() && !exponent_digits {
+        return malformed(MissingExponentDigits);
+    }
+    if !dot && exponent.is_none() {
+        if let Ok(num) = s.parse::<i64>() {
+            return Ok(Int(num));
+        }
+    }
+    match s.parse::<f64>() {
+        Ok(num) => Ok(Float(num)),
+        Err(_) => Err(MathParseInternalBug(format!("{s} should have been a valid float."))),
+    }
 }
 
 /* --------------------------------- Testing -------------------------------- */
 
 #[test]
 fn test_reading_numbers() {
-    assert_eq!(number_from_string("100"),  Ok(Int(100)));
-    assert_eq!(number_from_string("0"),    Ok(Int(0)));
-    assert_eq!(number_from_string("0x10"), Ok(Int(0x10)));
-    assert_eq!(number_from_string("2.5"),  Ok(Float(2.5)));
-    assert_eq!(number_from_string("toto"), Err(InvalidNumber("toto".to_string())));
+    let options = MathParseOptions::default();
+    assert_eq!(number_from_string("100",  &options), Ok(Int(100)));
+    assert_eq!(number_from_string("0",    &options), Ok(Int(0)));
+    assert_eq!(number_from_string("0x10", &options), Ok(Int(0x10)));
+    assert_eq!(number_from_string("0x0", &options), Ok(Int(0)));
+    assert_eq!(number_from_string("0X0", &options), Ok(Int(0)));
+    assert_eq!(number_from_string("0XFF", &options), Ok(Int(0xFF)));
+    assert_eq!(number_from_string("0xAb", &options), Ok(Int(0xAB)));
+    assert_eq!(number_from_string("0o17", &options), Ok(Int(15)));
+    assert_eq!(number_from_string("0b1010", &options), Ok(Int(10)));
+    assert_eq!(number_from_string("0xFFFFFFFFFFFFFFFF", &options), Ok(Int(-1)));
+    assert_eq!(number_from_string("0x8000000000000000", &options), Ok(Int(i64::MIN)));
+    assert_eq!(number_from_string("0x10000000000000000", &options), Err(InvalidNumber("0x10000000000000000".to_string())));
+    assert_eq!(number_from_string(&format!("0b{}", "1".repeat(64)), &options), Ok(Int(-1)));
+    assert_eq!(number_from_string("18446744073709551615", &options), Ok(Float(18446744073709551615.0)));
+    assert_eq!(number_from_string("2.5",  &options), Ok(Float(2.5)));
+    assert_eq!(number_from_string("toto", &options), Err(InvalidNumber("toto".to_string())));
+
+    let solve = |expression: &str| crate::MathParse::parse(expression)?.solve_number(None);
+    assert_eq!(solve(".5 + 1"), Ok(Float(1.5)));
+    assert_eq!(solve(".5*.5"), Ok(Float(0.25)));
+    assert_eq!(solve(".5·.5"), Ok(Float(0.25)));
+    assert_eq!(solve("5.·2"), Ok(Int(10)));
+    assert_eq!(solve("5. + .25"), Ok(Float(5.25)));
+    assert_eq!(solve("."), Err(InvalidNumber(".".to_string())));
+    assert_eq!(crate::MathParse::parse_rpn(".5 5. +").unwrap().solve_number(None), Ok(Float(5.5)));
+    assert_eq!(crate::MathParse::parse_rpn(". 1 +").unwrap().solve_number(None), Err(InvalidNumber(".".to_string())));
+
+    let variables = HashMap::from([("mask".to_string(), "0b1100".to_string())]);
+    assert_eq!(crate::MathParse::parse("mask | 0b1").unwrap().solve_number(Some(&variables)), Ok(Int(13)));
+}
+
+#[test]
+fn test_malformed_numbers() {
+    let options = MathParseOptions::default();
+    let malformed = |s: &str, kind| Err(MalformedNumber(s.to_string(), kind));
+    assert_eq!(number_from_string("1.2.3",  &options), malformed("1.2.3", MultipleDots));
+    assert_eq!(number_from_string("1..2",   &options), malformed("1..2", MultipleDots));
+    assert_eq!(number_from_string("1e",     &options), malformed("1e", MissingExponentDigits));
+    assert_eq!(number_from_string("1.5E-",  &options), malformed("1.5E-", MissingExponentDigits));
+    assert_eq!(number_from_string("12a",    &options), malformed("12a", UnexpectedChar('a', 2)));
+    assert_eq!(number_from_string("1e2.5",  &options), malformed("1e2.5", UnexpectedChar('.', 3)));
+    assert_eq!(number_from_string("1e+-2",  &options), malformed("1e+-2", UnexpectedChar('-', 3)));
+    assert_eq!(number_from_string("1e2e3",  &options), malformed("1e2e3", UnexpectedChar('e', 3)));
+    assert_eq!(number_from_string("3é",     &options), malformed("3é", UnexpectedChar('é', 1)));
+    assert_eq!(number_from_string("0x",     &options), Err(InvalidNumber("0x".to_string())));
+    assert_eq!(number_from_string("0xG",    &options), Err(InvalidNumber("0xG".to_string())));
+    assert_eq!(number_from_string("0xg",    &options), Err(InvalidNumber("0xg".to_string())));
+    assert_eq!(number_from_string("0X",     &options), Err(InvalidNumber("0X".to_string())));
+    assert_eq!(number_from_string("0XFFz",  &options), Err(InvalidNumber("0XFFz".to_string())));
+    assert_eq!(number_from_string("0x+5",   &options), Err(InvalidNumber("0x+5".to_string())));
+    for literal in ["_1", "1_", "1__0", "0x_FF", "1_.5", "1._5", "1e_3", "1_e3", "0b1_2"] {
+        assert_eq!(number_from_string(literal, &options), Err(InvalidNumber(literal.to_string())), "{literal}");
+    }
+    for literal in ["1#0", "37#1", "2#102", "36#", "#1", "+2#1", "2#-1", "16#F_"] {
+        assert_eq!(number_from_string(literal, &options), Err(InvalidNumber(literal.to_string())), "{literal}");
+    }
+    assert_eq!(number_from_string("0o8",    &options), Err(InvalidNumber("0o8".to_string())));
+    assert_eq!(number_from_string("0b",     &options), Err(InvalidNumber("0b".to_string())));
+    assert_eq!(number_from_string("0b102",  &options), Err(InvalidNumber("0b102".to_string())));
+    assert_eq!(number_from_string("0b-1",   &options), Err(InvalidNumber("0b-1".to_string())));
+    assert_eq!(number_from_string(".",      &options), Err(InvalidNumber(".".to_string())));
+    assert_eq!(number_from_string("e3",     &options), Err(InvalidNumber("e3".to_string())));
+
+    for (literal, value) in [("12", Int(12)), ("1.5", Float(1.5)), (".5", Float(0.5)), ("5.", Float(5.0)), ("1e3", Float(1000.0)),
+            ("1.5e-3", Float(1.5e-3)), ("2E+2", Float(200.0)), ("0x1F", Int(31)), ("0o17", Int(15)), ("0b101", Int(5)),
+            ("2#1010", Int(10)), ("36#ZZ", Int(1295)), ("36#zz", Int(1295)), ("16#FF_FF", Int(0xFFFF)),
+            ("1_000_000", Int(1_000_000)), ("0xFFFF_FFFF", Int(0xFFFF_FFFF)), ("1_000.5", Float(1_000.5)), ("1.000_5e1_0", Float(1.000_5e10)),
+            ("9223372036854775808", Float(9223372036854775808.0))] {
+        assert_eq!(number_from_string(literal, &options), Ok(value), "{literal}");
+    }
+    assert_eq!(number_from_string("-2", &options), Ok(Int(-2)));
+    assert_eq!(number_from_string("+2.5", &options), Ok(Float(2.5)));
+    assert_eq!(number_from_string("-", &options), Err(InvalidNumber("-".to_string())));
+    assert_eq!(number_from_string("1-2", &options), malformed("1-2", UnexpectedChar('-', 1)));
+    assert_eq!(number_from_string("inf", &options), Ok(Float(f64::INFINITY)));
+    assert!(matches!(number_from_string("NaN", &options), Ok(Float(x)) if x.is_nan()));
+
+    let solve = |expression: &str| crate::MathParse::parse(expression)?.solve_number(None);
+    assert_eq!(solve("1.2.3 + 1"), malformed("1.2.3", MultipleDots));
+    assert_eq!(crate::MathParse::parse_rpn("1 2x +").unwrap().solve_number(None), malformed("2x", UnexpectedChar('x', 1)));
+}
+
+#[test]
+fn test_char_literals() {
+    let solve = |expression: &str| crate::MathParse::parse(expression)?.solve_number(None);
+    assert_eq!(solve("'A' + 1"), Ok(Int(66)));
+    assert_eq!(solve("'\\n'"), Ok(Int(10)));
+    assert_eq!(solve("'\\x7f' - '\\0'"), Ok(Int(0x7F)));
+    assert_eq!(solve("'\\''"), Ok(Int(39)));
+    assert_eq!(solve("' '"), Ok(Int(32)));
+    assert_eq!(solve("'é'"), Ok(Int(0xE9)));
+    assert_eq!(solve("'∑' - '+'"), Ok(Int(0x2211 - 0x2B)));
+    assert_eq!(solve("'ab' + 1"), Err(InvalidNumber("'ab'".to_string())));
+    assert_eq!(solve("'\\q'"), Err(InvalidNumber("'\\q'".to_string())));
+    assert_eq!(solve("'a"), Err(InvalidNumber("'a".to_string())));
+    assert_eq!(solve("''"), Err(InvalidNumber("''".to_string())));
+}
+
+#[test]
+fn test_dms_angles() {
+    let options = MathParseOptions {
+        dms_angles: true,
+        ..MathParseOptions::default()
+    };
+    let malformed = |s: &str| Err(MalformedNumber(s.to_string(), MalformedAngle));
+    assert_eq!(number_from_string("12°30'45\"", &options), Ok(Float(12.0 + 30.0 / 60.0 + 45.0 / 3600.0)));
+    assert_eq!(number_from_string("12°30'", &options), Ok(Float(12.5)));
+    assert_eq!(number_from_string("12°", &options), Ok(Float(12.0)));
+    assert_eq!(number_from_string("1.5°", &options), Ok(Float(1.5)));
+    assert_eq!(number_from_string("0°0'36\"", &options), Ok(Float(0.01)));
+    assert_eq!(number_from_string("12°61'", &options), malformed("12°61'"));
+    assert_eq!(number_from_string("12°30'60\"", &options), malformed("12°30'60\""));
+    assert_eq!(number_from_string("°30'", &options), malformed("°30'"));
+    assert_eq!(number_from_string("12°'", &options), malformed("12°'"));
+    assert_eq!(number_from_string("12°30'45\"1", &options), malformed("12°30'45\"1"));
+    assert_eq!(number_from_string("12°x'", &options), malformed("12°x'"));
+
+    let solve = |expression: &str| crate::MathParse::parse_with_options(expression, &options)?.solve_number(None);
+    assert_eq!(solve("2 * 12°30'"), Ok(Int(25)));
+    assert_eq!(solve("-12°30'+1"), Ok(Float(-11.5)));
+    assert_eq!(solve("90° - 12° 30' 36\""), Ok(Float(77.49)));
+    assert_eq!(solve("'a' + 1"), Ok(Int(98)));
+    assert_eq!(crate::MathParse::parse("12°30'").err(), Some(InvalidCharacter('°', 2)));
+}
+
+#[test]
+fn test_apostrophe_separators() {
+    let options = MathParseOptions {
+        apostrophe_separators: true,
+        ..MathParseOptions::default()
+    };
+    assert_eq!(number_from_string("1'000'000",   &options), Ok(Int(1_000_000)));
+    assert_eq!(number_from_string("0xFFFF'FFFF", &options), Ok(Int(0xFFFF_FFFF)));
+    assert_eq!(number_from_string("1'000.5",     &options), Ok(Float(1000.5)));
+    assert_eq!(number_from_string("1'000_000",   &options), Ok(Int(1_000_000)));
+    assert_eq!(number_from_string("'a'",         &options), Ok(Int(97)));
+    for literal in ["1'", "1''0", "0x'FF", "1'.5", "'1000"] {
+        assert_eq!(number_from_string(literal, &options), Err(InvalidNumber(literal.to_string())), "{literal}");
+    }
+
+    let solve = |expression: &str| crate::MathParse::parse_with_options(expression, &options)?.solve_number(None);
+    assert_eq!(solve("1'000 + 'a'"), Ok(Int(1097)));
+    assert_eq!(solve("2'500 * 2"), Ok(Int(5000)));
+
+    assert_eq!(crate::MathParse::parse("1'000").map(|_| ()), Err(InvalidCharacter('\'', 1)));
+}
+
+#[test]
+fn test_shifts() {
+    let solve = |expression: &str| crate::MathParse::parse(expression)?.solve_number(None);
+    assert_eq!(solve("1 << 63"), Ok(Int(i64::MIN)));
+    assert_eq!(solve("1 << 64"), Ok(Int(0)));
+    assert_eq!(solve("1 << 100000000000"), Ok(Int(0)));
+    assert_eq!(solve("-1 << 63"), Ok(Int(i64::MIN)));
+    assert_eq!(solve("-1 << 64"), Ok(Int(0)));
+    assert_eq!(solve("-3 << 1"), Ok(Int(-6)));
+    assert_eq!(solve("-1 >> 63"), Ok(Int(-1)));
+    assert_eq!(solve("-1 >> 64"), Ok(Int(-1)));
+    assert_eq!(solve("-8 >> 1"), Ok(Int(-4)));
+    assert_eq!(solve("(1 << 63) >> 63"), Ok(Int(-1)));
+    assert_eq!(solve("9223372036854775807 >> 63"), Ok(Int(0)));
+    assert_eq!(solve("9223372036854775807 >> 64"), Ok(Int(0)));
+    assert_eq!(solve("1 << -1"), Err(UnexpectedNegative));
+}
+
+#[test]
+fn test_integer_overflow() {
+    let solve = |expression: &str, options: &MathParseOptions| crate::MathParse::parse_with_options(expression, options)?.solve_number(None);
+    let default = MathParseOptions::default();
+    assert_eq!(solve("9223372036854775807 + 1", &default), Ok(Float(9223372036854775808.0)));
+    assert_eq!(solve("-9223372036854775807 - 2", &default), Ok(Float(-9223372036854775809.0)));
+    assert_eq!(solve("4294967296 * 4294967296", &default), Ok(Float(18446744073709551616.0)));
+    assert_eq!(solve("-(-9223372036854775807 - 1)", &default), Ok(Float(9223372036854775808.0)));
+    assert_eq!(solve("9223372036854775807 + 0", &default), Ok(Int(i64::MAX)));
+    assert_eq!(crate::MathParse::parse("9223372036854775807 + 1").unwrap().solve_int(None), Err(IntConversion(9223372036854775808.0)));
+
+    let checked = MathParseOptions {
+        integer_overflow_errors: true,
+        ..MathParseOptions::default()
+    };
+    assert_eq!(solve("9223372036854775807 + 1", &checked), Err(IntegerOverflow(i64::MAX, '+', 1)));
+    assert_eq!(solve("-9223372036854775807 - 2", &checked), Err(IntegerOverflow(-i64::MAX, '-', 2)));
+    assert_eq!(solve("4294967296 * 4294967296", &checked), Err(IntegerOverflow(1 << 32, '*', 1 << 32)));
+    assert_eq!(solve("-(-9223372036854775807 - 1)", &checked), Err(IntegerOverflow(0, '-', i64::MIN)));
+    assert_eq!(solve("9223372036854775807 + 0.5", &checked), Ok(Float(9223372036854775807.5)));
+    assert_eq!(solve("3 * -4 + 2", &checked), Ok(Int(-10)));
+}
+
+#[test]
+fn test_infinite_float_division() {
+    let options = MathParseOptions {
+        infinite_float_division: true,
+        ..MathParseOptions::default()
+    };
+    let solve = |expression: &str, options: &MathParseOptions| crate::MathParse::parse_with_options(expression, options)?.solve_number(None);
+    assert_eq!(solve("1/0.0", &options), Ok(Float(f64::INFINITY)));
+    assert_eq!(solve("-1/0.0", &options), Ok(Float(f64::NEG_INFINITY)));
+    assert_eq!(solve("1/-0.0", &options), Ok(Float(f64::NEG_INFINITY)));
+    assert!(matches!(solve("0/0.0", &options), Ok(Float(x)) if x.is_nan()));
+    assert_eq!(solve("1/0", &options), Err(UnexpectedZero));
+    assert_eq!(solve("1.5/0", &options), Err(UnexpectedZero));
+    assert_eq!(solve("1 // 0.0", &options), Err(UnexpectedZero));
+    assert_eq!(solve("3/1.5", &options), Ok(Int(2)));
+    assert_eq!(crate::MathParse::parse_rpn_with_options("1 0.0 /", &options).unwrap().solve_number(None), Ok(Float(f64::INFINITY)));
+
+    assert_eq!(solve("1/0.0", &MathParseOptions::default()), Err(UnexpectedZero));
+}
+
+#[test]
+fn test_floor_division() {
+    let floor = MathParseOptions {
+        floor_division: true,
+        ..MathParseOptions::default()
+    };
+    let trunc = MathParseOptions::default();
+    let solve = |expression: &str, options: &MathParseOptions| crate::MathParse::parse_with_options(expression, options)?.solve_number(None);
+    let solve_rpn = |expression: &str, options: &MathParseOptions| crate::MathParse::parse_rpn_with_options(expression, options)?.solve_number(None);
+    let cases = [
+        ("7 // 2",      "7 2 //",         3,  3),
+        ("-7 // 2",     "7 neg 2 //",     -3, -4),
+        ("7 // (-2)",   "7 2 neg //",     -3, -4),
+        ("-7 // (-2)",  "7 neg 2 neg //", 3,  3),
+        ("6 // (-2)",   "6 2 neg //",     -3, -3),
+        ("7.5 // 2.5",  "7.5 2.5 //",     3,  3),
+        ("-7.5 // 2",   "7.5 neg 2 //",   -3, -4),
+        ("7.5 // (-2)", "7.5 2 neg //",   -3, -4),
+        ("-7.5 // (-2)", "7.5 neg 2 neg //", 3, 3),
+        ("1 // 0.3",    "1 0.3 //",       3,  3),
+        ("-1 // 0.3",   "1 neg 0.3 //",   -3, -4),
+    ];
+    for (infix, rpn, truncated, floored) in cases {
+        assert_eq!(solve(infix, &trunc), Ok(Int(truncated)), "{infix}");
+        assert_eq!(solve_rpn(rpn, &trunc), Ok(Int(truncated)), "{rpn}");
+        assert_eq!(solve(infix, &floor), Ok(Int(floored)), "{infix}");
+        assert_eq!(solve_rpn(rpn, &floor), Ok(Int(floored)), "{rpn}");
+    }
+    assert_eq!(solve("-7 // 0", &floor), Err(UnexpectedZero));
+    assert_eq!(solve("-7 // 0.0", &floor), Err(UnexpectedZero));
+    assert_eq!(solve("(-9223372036854775807 - 1) // 3", &floor), Ok(Int(i64::MIN / 3 - 1)));
+    assert_eq!(solve("(-9223372036854775807 - 1) // -1", &floor), Ok(Float(9223372036854775808.0)));
+    assert_eq!(solve("(-9223372036854775808) // -1", &floor), Ok(Float(9223372036854775808.0)));
+}
+
+#[test]
+fn test_number_conversions() {
+    assert_eq!(Number::from(3), Int(3));
+    assert_eq!(Number::from(2.5), Float(2.5));
+    assert_eq!(i64::try_from(Int(-7)), Ok(-7));
+    assert_eq!(i64::try_from(Float(4.0)), Ok(4));
+    assert_eq!(i64::try_from(Float(4.5)), Err(ReturnFloatExpectedInt(4.5)));
+    assert_eq!(i64::try_from(Float(1e300)), Err(IntConversion(1e300)));
+    let converted: Result<i64, _> = Number::from(12.0).try_into();
+    assert_eq!(converted, Ok(12));
+    assert_eq!(Number::from(i64::MIN).as_f64(), -9223372036854775808.0);
+
+    // Results of both types can be kept together.
+    let results = ["1 + 2", "1 / 4", "2 ** 0.5 * 2 ** 0.5"].map(|expression| crate::MathParse::parse(expression).unwrap().solve_number(None));
+    assert_eq!(results[0], Ok(Int(3)));
+    assert_eq!(results[1], Ok(Float(0.25)));
+    assert_eq!(results[2], Ok(Float(2.0000000000000004)));
+}
+
+#[test]
+fn test_min_int_edge_cases() {
+    let min = "(-9223372036854775807 - 1)";
+    let solve = |expression: &str, options: &MathParseOptions| crate::MathParse::parse_with_options(expression, options)?.solve_number(None);
+    let solve_rpn = |expression: &str, options: &MathParseOptions| crate::MathParse::parse_rpn_with_options(expression, options)?.solve_number(None);
+    let default = MathParseOptions::default();
+    assert_eq!(solve(&format!("-{min}"), &default), Ok(Float(9223372036854775808.0)));
+    assert_eq!(solve(&format!("{min} // -1"), &default), Ok(Float(9223372036854775808.0)));
+    assert_eq!(solve(&format!("{min} % (-1)"), &default), Ok(Int(0)));
+    assert_eq!(solve(&format!("{min} % -1"), &default), Ok(Int(0)));
+    assert_eq!(solve("-9223372036854775808 % -1", &default), Ok(Int(0)));
+    assert_eq!(solve(&format!("{min} // 1"), &default), Ok(Int(i64::MIN)));
+    // The literal 9223372036854775808 is a float, whose quotients that don't
+    // fit in an `i64` are floats too. Quotients that fit in an `i64` but are
+    // computed from floats beyond `INTEGRAL_LIMIT` are still errors.
+    assert_eq!(solve("(-9223372036854775808) // -1", &default), Ok(Float(9223372036854775808.0)));
+    assert_eq!(solve("-9223372036854775808 // -1", &default), Ok(Float(9223372036854775808.0)));
+    assert_eq!(solve("1e19 // 1", &default), Ok(Float(1e19)));
+    assert_eq!(solve("(-9223372036854775808) // 1", &default), Err(IntConversion(-9223372036854775808.0)));
+    assert_eq!(solve("1e300 // 1e-300", &default), Err(IntConversion(1e300)));
+    assert_eq!(solve("-(-9223372036854775808)", &default), Ok(Float(9223372036854775808.0)));
+    assert_eq!(solve_rpn("9223372036854775807 neg 1 - neg", &default), Ok(Float(9223372036854775808.0)));
+    assert_eq!(solve_rpn("9223372036854775807 neg 1 - 1 neg //", &default), Ok(Float(9223372036854775808.0)));
+    assert_eq!(solve_rpn("9223372036854775807 neg 1 - 1 neg %", &default), Ok(Int(0)));
+
+    let checked = MathParseOptions {
+        integer_overflow_errors: true,
+        ..MathParseOptions::default()
+    };
+    assert_eq!(solve(&format!("-{min}"), &checked), Err(IntegerOverflow(0, '-', i64::MIN)));
+    assert_eq!(solve(&format!("{min} // -1"), &checked), Err(IntegerOverflow(i64::MIN, '⟌', -1)));
+    assert_eq!(solve(&format!("{min} % (-1)"), &checked), Ok(Int(0)));
+    assert_eq!(solve(&format!("{min} % -1"), &checked), Ok(Int(0)));
+    assert_eq!(solve("7 // 0", &checked), Err(UnexpectedZero));
+    assert_eq!(solve_rpn("9223372036854775807 neg 1 - neg", &checked), Err(IntegerOverflow(0, '-', i64::MIN)));
+    assert_eq!(solve_rpn("9223372036854775807 neg 1 - 1 neg //", &checked), Err(IntegerOverflow(i64::MIN, '⟌', -1)));
+}
+
+#[test]
+fn test_si_suffixes() {
+    let options = MathParseOptions {
+        si_suffixes: true,
+        ..MathParseOptions::default()
+    };
+    assert_eq!(number_from_string("4k",   &options), Ok(Int(4_000)));
+    assert_eq!(number_from_string("16Mi", &options), Ok(Int(16 << 20)));
+    assert_eq!(number_from_string("2G",   &options), Ok(Int(2_000_000_000)));
+    assert_eq!(number_from_string("1.5T", &options), Ok(Float(1.5e12)));
+    assert_eq!(number_from_string("3Gi",  &options), Ok(Int(3 << 30)));
+    assert_eq!(number_from_string("4K",   &options), Err(MalformedNumber("4K".to_string(), UnexpectedChar('K', 1))));
+    assert_eq!(number_from_string("k",    &options), Err(InvalidNumber("k".to_string())));
+    assert_eq!(number_from_string("36#1k", &options), Ok(Int(56)));
+    assert_eq!(number_from_string("36#1M", &options), Ok(Int(58)));
+    assert_eq!(number_from_string("0x1k",  &options), Ok(Int(1_000)));
+
+    let solve = |expression: &str| crate::MathParse::parse_with_options(expression, &options)?.solve_number(None);
+    assert_eq!(solve("4Ki + 1"), Ok(Int(4097)));
+    assert_eq!(solve("2M / 4k"), Ok(Int(500)));
+
+    // Without the option, `4k` can only be a named variable.
+    let variables = HashMap::from([("4k".to_string(), "7".to_string())]);
+    let default = crate::MathParse::parse("4k + 1").unwrap();
+    assert_eq!(default.solve_number(None), Err(MalformedNumber("4k".to_string(), UnexpectedChar('k', 1))));
+    assert_eq!(default.solve_number(Some(&variables)), Ok(Int(8)));
+}
+
+#[test]
+fn test_byte_size_suffixes() {
+    let options = MathParseOptions {
+        byte_size_suffixes: true,
+        ..MathParseOptions::default()
+    };
+    assert_eq!(number_from_string("512B",   &options), Ok(Int(512)));
+    assert_eq!(number_from_string("2KiB",   &options), Ok(Int(2048)));
+    assert_eq!(number_from_string("3MB",    &options), Ok(Int(3_000_000)));
+    assert_eq!(number_from_string("1.5KB",  &options), Ok(Float(1500.0)));
+    assert_eq!(number_from_string("2kb",    &options), Err(MalformedNumber("2kb".to_string(), UnexpectedChar('k', 1))));
+    assert_eq!(number_from_string("MB",     &options), Err(InvalidNumber("MB".to_string())));
+    assert_eq!(number_from_string("0x1B",   &options), Ok(Int(0x1B)));
+    assert_eq!(number_from_string("0xAB",   &options), Ok(Int(0xAB)));
+    assert_eq!(number_from_string("16#1B",  &options), Ok(Int(0x1B)));
+    assert_eq!(number_from_string("12#1B",  &options), Ok(Int(23)));
+    assert_eq!(number_from_string("0x1KB",  &options), Ok(Int(1_000)));
+
+    let solve = |expression: &str| crate::MathParse::parse_with_options(expression, &options)?.solve_number(None);
+    assert_eq!(solve("4GiB / 512KiB"), Ok(Int(8192)));
+    assert_eq!(solve("1GB - 1"), Ok(Int(999_999_999)));
+
+    let variables = std::collections::HashMap::from([
+        ("MB".to_string(), "3".to_string()),
+    ]);
+    let default = crate::MathParse::parse("2MB").unwrap();
+    assert_eq!(default.solve_number(None), Err(MalformedNumber("2MB".to_string(), UnexpectedChar('M', 1))));
+    assert_eq!(crate::MathParse::parse("2*MB").unwrap().solve_number(Some(&variables)), Ok(Int(6)));
+    let with_suffixes = crate::MathParse::parse_with_options("2*MB + 1MB", &options).unwrap();
+    assert_eq!(with_suffixes.solve_number(Some(&variables)), Ok(Int(1_000_006)));
 }
 
 #[test]
@@ -313,9 +1719,11 @@ fn test_read_named_variables() {
         }
     }
 
-    assert_eq!(read_name("3",          &variables), Ok(Int(3)));
-    assert_eq!(read_name("direct_1",   &variables), Ok(Float(1.2)));
-    assert_eq!(read_name("indirect_1", &variables), Err(InvalidNumber("indirect_2".to_string())));
+    let options = MathParseOptions::default();
+    let mut solver = Solver::new(&variables, &options);
+    assert_eq!(solver.read_name("3"),          Ok(Int(3)));
+    assert_eq!(solver.read_name("direct_1"),   Ok(Float(1.2)));
+    assert_eq!(solver.read_name("indirect_1"), Err(InvalidNumber("indirect_2".to_string())));
 }
 
 #[test]
@@ -324,7 +1732,7 @@ fn test_math_compute() {
     use crate::name_r;
     let rpn_actions = [name_r("4"), name_r("3"), name_r("5"), Binary(Subtraction), Binary(Multiplication)];
     fn no_map(_: &str) -> Option<String> {None}
-    let computation = math_solve(&rpn_actions, &no_map).unwrap();
+    let computation = math_solve(&rpn_actions, &no_map, &MathParseOptions::default()).unwrap();
     if let Int(computation) = computation {
         assert_eq!(computation, (3-5)*4);
     } else {
@@ -336,16 +1744,16 @@ fn test_math_compute() {
 fn test_errors() {
     assert_eq!(Int(10) / Int(0), Err(UnexpectedZero));
     assert_eq!(Int(10) >> Int(-1), Err(UnexpectedNegative));
-    assert_eq!(!Float(1.3), Err(BinaryOpOnFloat(1.3, '!')));
-    assert_eq!(Float(-5.5).is_negative(), true);
-    assert_eq!(Float(5.5).is_negative(), false);
+    assert_eq!(!Float(1.3), Err(BinaryOpOnFloat(1.3, '~')));
+    assert!(Float(-5.5).is_negative());
+    assert!(!Float(5.5).is_negative());
 
-    let big_float = (INTEGRAL_LIMIT as f64) * 5.0;
+    let big_float = INTEGRAL_LIMIT * 5.0;
     assert_eq!(Float(big_float).integer_div(Int(10)), Err(IntConversion(big_float)));
-    assert_eq!(Float(-1.0 * big_float).integer_div(Int(10)), Err(IntConversion(big_float * -1.0)));
+    assert_eq!(Float(-big_float).integer_div(Int(10)), Err(IntConversion(-big_float)));
     match Float(f64::NAN).integer_div(Int(10)) {
         Err(IntConversion(x)) => {
-            assert_eq!(x.is_nan(), true);
+            assert!(x.is_nan());
         },
         x => {
             panic!("Didn't expected {x:?}");
@@ -353,3 +1761,386 @@ fn test_errors() {
     }
 }
 
+
+#[test]
+fn test_binder_functions() {
+    let solve = |expression: &str| crate::MathParse::parse(expression)?.solve_number(None);
+    assert_eq!(solve("sum(i, 1, 10, i*i)"), Ok(Int(385)));
+    assert_eq!(solve("product(k, 1, 5, k)"), Ok(Int(120)));
+    assert_eq!(solve("sum(i, 10, 1, i)"), Ok(Int(0)));
+    assert_eq!(solve("product(i, 10, 1, i)"), Ok(Int(1)));
+    assert_eq!(solve("2 + sum(i, 1, 3, sum(j, 1, i, j)) * 2"), Ok(Int(22)));
+    assert_eq!(solve("sum(i, 1, 4.0, i/2)"), Ok(Int(5)));
+    assert_eq!(solve("sum(i, 1, 2.5, i)"), Err(ReturnFloatExpectedInt(2.5)));
+    assert_eq!(solve("sum(i, 1, 2)"), Err(InvalidFunctionCall("sum".to_string())));
+    assert_eq!(solve("sum(1, 1, 2, 1)"), Err(InvalidFunctionCall("sum".to_string())));
+    assert_eq!(solve("sum(i+1, 1, 2, 1)"), Err(InvalidFunctionCall("sum".to_string())));
+    assert_eq!(solve("sum(i, 1, 3, )"), Err(EmptyLine));
+    assert_eq!(solve("foo(1, 2)"), Err(UnknownFunction("foo".to_string())));
+    assert_eq!(solve("foo()"), Err(UnknownFunction("foo".to_string())));
+}
+
+#[test]
+fn test_random_functions() {
+    use crate::{MathParse, RandomSource};
+
+    let mut sequence = [0.0, 0.5, 0.999, 0.25].into_iter().cycle();
+    let options = MathParseOptions {
+        random_source: Some(RandomSource::new(move || sequence.next().unwrap())),
+        ..MathParseOptions::default()
+    };
+    let solve = |expression: &str| MathParse::parse_with_options(expression, &options)?.solve_number(None);
+    assert_eq!(solve("10 + rand()*4"), Ok(Int(10)));
+    assert_eq!(solve("10 + rand()*4"), Ok(Int(12)));
+    assert_eq!(solve("rand()"), Ok(Float(0.999)));
+    assert_eq!(solve("rand() + rand()"), Ok(Float(0.25)));
+    assert_eq!(solve("randint(1, 6)"), Ok(Int(4)));
+    assert_eq!(solve("randint(1, 6)"), Ok(Int(6)));
+    assert_eq!(solve("randint(-3, -3)"), Ok(Int(-3)));
+    assert_eq!(solve("randint(-9223372036854775807, 9223372036854775807)"), Ok(Int(-9223372036854775807)));
+    assert_eq!(solve("randint(6, 1)"), Err(InvalidFunctionCall("randint".to_string())));
+    assert_eq!(solve("randint(1.5, 3)"), Err(ReturnFloatExpectedInt(1.5)));
+    assert_eq!(solve("rand(1)"), Err(InvalidFunctionCall("rand".to_string())));
+    assert_eq!(MathParse::parse_rpn_with_options("1 6 randint", &options).unwrap().solve_number(None), Ok(Int(4)));
+
+    let variables = HashMap::from([("x".to_string(), "rand()".to_string())]);
+    assert_eq!(MathParse::parse_with_options("x - x + x", &options).unwrap().solve_number(Some(&variables)), Ok(Float(0.999)));
+
+    let solve = |expression: &str| MathParse::parse(expression)?.solve_number(None);
+    assert_eq!(solve("rand()"), Err(NoRandomSource));
+    assert_eq!(solve("randint(1, 2)"), Err(NoRandomSource));
+}
+
+#[test]
+fn test_mask() {
+    let solve = |expression: &str| crate::MathParse::parse(expression)?.solve_number(None);
+    assert_eq!(solve("mask(0)"), Ok(Int(0)));
+    assert_eq!(solve("mask(1)"), Ok(Int(1)));
+    assert_eq!(solve("mask(8)"), Ok(Int(0xFF)));
+    assert_eq!(solve("mask(63)"), Ok(Int(i64::MAX)));
+    assert_eq!(solve("mask(64)"), Ok(Int(-1)));
+    assert_eq!(solve("mask(65)"), Err(InvalidFunctionCall("mask".to_string())));
+    assert_eq!(solve("mask(-1)"), Err(UnexpectedNegative));
+    assert_eq!(solve("mask(4.0)"), Ok(Int(15)));
+    assert_eq!(solve("mask(4.5)"), Err(ReturnFloatExpectedInt(4.5)));
+    assert_eq!(solve("mask(1, 2)"), Err(InvalidFunctionCall("mask".to_string())));
+    assert_eq!(crate::MathParse::parse_rpn("12 mask").unwrap().solve_int(None), Ok(0xFFF));
+}
+
+#[test]
+fn test_bit_functions() {
+    let solve = |expression: &str| crate::MathParse::parse(expression)?.solve_number(None);
+    assert_eq!(solve("popcount(0xFF)"), Ok(Int(8)));
+    assert_eq!(solve("popcount(0)"), Ok(Int(0)));
+    assert_eq!(solve("popcount(-1)"), Ok(Int(64)));
+    assert_eq!(solve("rotl(0x80000000, 1)"), Ok(Int(0x100000000)));
+    assert_eq!(solve("rotl(-9223372036854775807 - 1, 1)"), Ok(Int(1)));
+    assert_eq!(solve("rotr(1, 1)"), Ok(Int(i64::MIN)));
+    assert_eq!(solve("rotr(0x1234, 4)"), Ok(Int(0x4000000000000123)));
+    assert_eq!(solve("rotl(0x1234, 64)"), Ok(Int(0x1234)));
+    assert_eq!(solve("rotl(0x1234, 68)"), Ok(Int(0x12340)));
+    assert_eq!(solve("rotr(rotl(0x1234, 13), 13)"), Ok(Int(0x1234)));
+    assert_eq!(solve("rotl(1, -1)"), Err(UnexpectedNegative));
+    assert_eq!(solve("popcount(2.5)"), Err(FunctionOnFloat(2.5, "popcount".to_string())));
+    assert_eq!(solve("rotl(4.0, 1)"), Err(FunctionOnFloat(4.0, "rotl".to_string())));
+    assert_eq!(solve("rotr(4, 1.5)"), Err(FunctionOnFloat(1.5, "rotr".to_string())));
+
+    let solve_rpn = |expression: &str| crate::MathParse::parse_rpn(expression)?.solve_number(None);
+    assert_eq!(solve_rpn("0xFF popcount"), Ok(Int(8)));
+    assert_eq!(solve_rpn("0x80000000 1 rotl"), Ok(Int(0x100000000)));
+    assert_eq!(solve_rpn("0x1234 4 rotr@2"), Ok(Int(0x4000000000000123)));
+}
+
+#[test]
+fn test_gcd_lcm() {
+    let solve = |expression: &str| crate::MathParse::parse(expression)?.solve_number(None);
+    assert_eq!(solve("gcd(12, 18)"), Ok(Int(6)));
+    assert_eq!(solve("lcm(4, 6)"), Ok(Int(12)));
+    assert_eq!(solve("gcd(-12, 18)"), Ok(Int(6)));
+    assert_eq!(solve("lcm(4, -6)"), Ok(Int(12)));
+    assert_eq!(solve("gcd(0, 5)"), Ok(Int(5)));
+    assert_eq!(solve("gcd(0, 0)"), Ok(Int(0)));
+    assert_eq!(solve("lcm(0, 5)"), Ok(Int(0)));
+    assert_eq!(solve("gcd(12.0, 18)"), Ok(Int(6)));
+    assert_eq!(solve("gcd(12.5, 18)"), Err(ReturnFloatExpectedInt(12.5)));
+    assert_eq!(solve("lcm(2**40, 2**40 + 1)"), Err(FunctionOverflow("lcm".to_string())));
+    assert_eq!(solve("lcm(2**31, 2**31 + 1)"), Ok(Int((1 << 62) + (1 << 31))));
+    assert_eq!(solve("gcd(-9223372036854775807 - 1, 0)"), Err(FunctionOverflow("gcd".to_string())));
+    assert_eq!(solve("gcd(-9223372036854775807 - 1, 6)"), Ok(Int(2)));
+    assert_eq!(solve("gcd(1)"), Err(InvalidFunctionCall("gcd".to_string())));
+    assert_eq!(crate::MathParse::parse_rpn("4 6 lcm").unwrap().solve_int(None), Ok(12));
+}
+
+#[test]
+fn test_variadic_functions() {
+    let solve = |expression: &str| crate::MathParse::parse(expression)?.solve_number(None);
+    assert_eq!(solve("min(1, 2, 3)"), Ok(Int(1)));
+    assert_eq!(solve("max(3, -1, 7, 2)"), Ok(Int(7)));
+    assert_eq!(solve("max(1, 2.5, 2)"), Ok(Float(2.5)));
+    assert_eq!(solve("min(1, 2.5, 2) + 0.25"), Ok(Float(1.25)));
+    assert_eq!(solve("max(5)"), Ok(Int(5)));
+    assert_eq!(crate::MathParse::parse("min()").err(), Some(InvalidFunctionCall("min".to_string())));
+    assert_eq!(crate::MathParse::parse("1 + max()").err(), Some(InvalidFunctionCall("max".to_string())));
+    assert_eq!(crate::MathParse::parse("clamp(1, 2)").err(), Some(InvalidFunctionCall("clamp".to_string())));
+
+    assert_eq!(solve("clamp(12, 0, 10)"), Ok(Int(10)));
+    assert_eq!(solve("clamp(-3, 0, 10)"), Ok(Int(0)));
+    assert_eq!(solve("clamp(4, 0, 10)"), Ok(Int(4)));
+    assert_eq!(solve("clamp(4.5, 0, 10)"), Ok(Float(4.5)));
+    assert_eq!(solve("clamp(12, 0, 9.5)"), Ok(Float(9.5)));
+    assert_eq!(solve("clamp(1, 10, 0)"), Err(InvalidFunctionCall("clamp".to_string())));
+    assert_eq!(solve("clamp(1.5, 10, 0)"), Err(InvalidFunctionCall("clamp".to_string())));
+
+    let variables = std::collections::HashMap::from([
+        ("a".to_string(), "4".to_string()),
+        ("b".to_string(), "9".to_string()),
+    ]);
+    assert_eq!(crate::MathParse::parse("max(a, b)").unwrap().solve_int(Some(&variables)), Ok(9));
+
+    let solve_rpn = |expression: &str| crate::MathParse::parse_rpn(expression)?.solve_number(None);
+    assert_eq!(solve_rpn("1 2 3 min@3"), Ok(Int(1)));
+    assert_eq!(solve_rpn("1 2 max"), Ok(Int(2)));
+    assert_eq!(solve_rpn("1 max@1"), Ok(Int(1)));
+    assert_eq!(solve_rpn("1 2 max@0"), Err(InvalidFunctionCall("max".to_string())));
+    assert_eq!(solve_rpn("12 0 10 clamp"), Ok(Int(10)));
+}
+
+#[test]
+fn test_math_functions() {
+    let solve = |expression: &str| crate::MathParse::parse(expression)?.solve_number(None);
+    assert_eq!(solve("sqrt(2) + abs(-3)"), Ok(Float(2.0_f64.sqrt() + 3.0)));
+    assert_eq!(solve("sqrt(16)"), Ok(Int(4)));
+    assert_eq!(solve("sqrt(0.25)"), Ok(Float(0.5)));
+    assert_eq!(solve("sqrt(-1)"), Err(UnexpectedNegative));
+    assert_eq!(solve("abs(-2.5)"), Ok(Float(2.5)));
+    assert_eq!(solve("abs(7)"), Ok(Int(7)));
+    assert_eq!(solve("abs(-9223372036854775807 - 1)"), Ok(Float(9223372036854775808.0)));
+    assert_eq!(solve("min(4, 7)"), Ok(Int(4)));
+    assert_eq!(solve("max(4, 7)"), Ok(Int(7)));
+    assert_eq!(solve("min(4, 3.5)"), Ok(Float(3.5)));
+    assert_eq!(solve("max(-1.5, -2)"), Ok(Float(-1.5)));
+    assert_eq!(solve("max(min(1, 2), abs(-5)) * 2"), Ok(Int(10)));
+    assert_eq!(solve("min(1)"), Ok(Int(1)));
+    assert_eq!(solve("sqrt()"), Err(InvalidFunctionCall("sqrt".to_string())));
+    assert_eq!(solve("cbrt(8)"), Err(UnknownFunction("cbrt".to_string())));
+    assert_eq!(crate::MathParse::parse_rpn("4 7 max sqrt").unwrap().solve_number(None), Ok(Float(7.0_f64.sqrt())));
+}
+
+#[test]
+fn test_square_root_operator() {
+    let solve = |expression: &str| crate::MathParse::parse(expression)?.solve_number(None);
+    assert_eq!(solve("√9 + 1"), Ok(Int(4)));
+    assert_eq!(solve("√(2)"), Ok(Float(2.0_f64.sqrt())));
+    assert_eq!(solve("√√16"), Ok(Int(2)));
+    assert_eq!(solve("-√4 ** 2"), Ok(Int(4)));
+    assert_eq!(solve("√(a)"), Err(InvalidNumber("a".to_string())));
+    assert_eq!(solve("√-4"), Err(UnexpectedNegative));
+    assert_eq!(solve("√(1 - 2)"), Err(UnexpectedNegative));
+    assert_eq!(solve("2√4"), Err(MisplacedOperator('√')));
+    assert_eq!(solve("4√"), Err(MisplacedOperator('√')));
+
+    let parsed = crate::MathParse::parse("√(x + 1) * √y").unwrap();
+    assert_eq!(format!("{}", parsed.to_tree().unwrap()), "(√(x + 1) * √y)");
+    let rpn = crate::rpn_slice_to_string(&parsed.to_rpn().unwrap());
+    assert_eq!(rpn, "x 1 + √ y √ *");
+    assert_eq!(crate::MathParse::parse_rpn(&rpn).unwrap().to_rpn(), parsed.to_rpn());
+}
+
+#[test]
+fn test_trigonometric_functions() {
+    let solve = |expression: &str| crate::MathParse::parse(expression)?.solve_float(None);
+    assert!((solve("sin(3.14159/2)").unwrap() - 1.0).abs() < 1e-9);
+    assert_eq!(solve("cos(0)"), Ok(1.0));
+    assert_eq!(solve("tan(1)"), Ok(1.0_f64.tan()));
+    assert_eq!(solve("asin(1)"), Ok(std::f64::consts::FRAC_PI_2));
+    assert_eq!(solve("acos(-1)"), Ok(std::f64::consts::PI));
+    assert_eq!(solve("atan(1) * 4"), Ok(std::f64::consts::PI));
+    assert_eq!(solve("asin(2)"), Err(DomainError("asin".to_string())));
+    assert_eq!(solve("acos(-1.5)"), Err(DomainError("acos".to_string())));
+    assert_eq!(solve("sin(1, 2)"), Err(InvalidFunctionCall("sin".to_string())));
+    assert_eq!(crate::MathParse::parse("sin(0.5)").unwrap().solve_number(None), Ok(Float(0.5_f64.sin())));
+
+    let parsed = crate::MathParse::parse("sin(x) + cos(2 * y)").unwrap();
+    assert_eq!(format!("{}", parsed.to_tree().unwrap()), "(sin(x) + cos((2 * y)))");
+    let rpn = crate::rpn_slice_to_string(&parsed.to_rpn().unwrap());
+    assert_eq!(rpn, "x sin@1 2 y * cos@1 +");
+    assert_eq!(crate::MathParse::parse_rpn(&rpn).unwrap().to_rpn(), parsed.to_rpn());
+}
+
+#[test]
+fn test_logarithms() {
+    let solve = |expression: &str| crate::MathParse::parse(expression)?.solve_number(None);
+    assert_eq!(solve("log2(1024)"), Ok(Int(10)));
+    assert_eq!(solve("log10(0.001)"), Ok(Int(-3)));
+    assert_eq!(solve("ln(exp(2))"), Ok(Int(2)));
+    assert_eq!(solve("exp(1)"), Ok(Float(std::f64::consts::E)));
+    assert_eq!(solve("ln(2)"), Ok(Float(std::f64::consts::LN_2)));
+    assert_eq!(solve("ln(0)"), Err(UnexpectedZero));
+    assert_eq!(solve("log2(-8)"), Err(UnexpectedNegative));
+    assert_eq!(solve("log10(0.0)"), Err(UnexpectedZero));
+    assert_eq!(solve("exp()"), Err(InvalidFunctionCall("exp".to_string())));
+
+    let parsed = crate::MathParse::parse("log10(x) * exp(-y)").unwrap();
+    assert_eq!(format!("{}", parsed.to_tree().unwrap()), "(log10(x) * exp(-y))");
+    assert_eq!(crate::rpn_slice_to_string(&parsed.to_rpn().unwrap()), "x log10@1 y neg exp@1 *");
+}
+
+#[test]
+fn test_accurate_float_sums() {
+    let expression = vec!["0.1"; 1000].join("+");
+    let accurate = MathParseOptions {
+        accurate_float_sums: true,
+        ..MathParseOptions::default()
+    };
+    let naive = crate::MathParse::parse(&expression).unwrap().solve_float(None).unwrap();
+    assert_ne!(naive, 100.0);
+    assert_eq!(crate::MathParse::parse_with_options(&expression, &accurate).unwrap().solve_float(None), Ok(100.0));
+
+    let solve = |expression: &str| crate::MathParse::parse_with_options(expression, &accurate)?.solve_number(None);
+    assert_eq!(solve("1e100 + 1 - 1e100"), Ok(Int(1)));
+    assert_eq!(solve("(0.1 + 0.2 + 0.3) * 10"), Ok(Int(6)));
+    assert_eq!(solve("1 + 2 - 4"), Ok(Int(-1)));
+    assert_eq!(solve("1.5 - 0.5 - 1"), Ok(Int(0)));
+    assert_eq!(solve("1e308 + 1e308 - 1e308"), Ok(Float(f64::INFINITY)));
+    assert_eq!(crate::MathParse::parse("1e100 + 1 - 1e100").unwrap().solve_number(None), Ok(Int(0)));
+}
+
+#[test]
+fn test_deterministic_floats() {
+    let options = MathParseOptions {
+        deterministic_floats: true,
+        ..MathParseOptions::default()
+    };
+    let solve = |expression: &str| crate::MathParse::parse_with_options(expression, &options)?.solve_number(None);
+    assert_eq!(solve("sin(0.5) + ln(3)"), Ok(Float(portable::sin(0.5) + portable::ln(3.0))));
+    assert_eq!(solve("2 ** 0.5"), Ok(Float(portable::powf(2.0, 0.5))));
+    assert_eq!(solve("7.5 % (-2)"), Ok(Float(1.5)));
+    assert_eq!(solve("log2(2 ** 40)"), Ok(Int(40)));
+    assert_eq!(solve("2 ** 62"), Ok(Int(1 << 62)));
+    assert_eq!(solve("7 % 0.0"), Err(UnexpectedZero));
+    assert_eq!(solve("asin(2)"), Err(DomainError("asin".to_string())));
+    assert_eq!(solve("ln(0)"), Err(UnexpectedZero));
+}
+
+#[test]
+fn test_euclidean_remainder() {
+    let solve = |expression: &str, euclidean_remainder: bool| {
+        let options = MathParseOptions {
+            euclidean_remainder,
+            ..MathParseOptions::default()
+        };
+        crate::MathParse::parse_with_options(expression, &options)?.solve_number(None)
+    };
+    assert_eq!(solve("-7 % 3", false), Ok(Int(-1)));
+    assert_eq!(solve("7 % 3", true), Ok(Int(1)));
+    assert_eq!(solve("-7 % 3", true), Ok(Int(2)));
+    assert_eq!(solve("7 % (-3)", true), Ok(Int(1)));
+    assert_eq!(solve("-7 % (-3)", true), Ok(Int(2)));
+    assert_eq!(solve("-6 % 3", true), Ok(Int(0)));
+    assert_eq!(solve("-9223372036854775807 % (-9223372036854775807 - 1)", true), Ok(Int(1)));
+    assert_eq!(solve("7.5 % 2", true), Ok(Float(1.5)));
+    assert_eq!(solve("-7.5 % 2", true), Ok(Float(0.5)));
+    assert_eq!(solve("7.5 % (-2)", true), Ok(Float(1.5)));
+    assert_eq!(solve("-7.5 % (-2.0)", true), Ok(Float(0.5)));
+    assert_eq!(solve("-7 % 0", true), Err(UnexpectedZero));
+    assert_eq!(solve("-7.5 % 0.0", true), Err(UnexpectedZero));
+    assert_eq!(solve("-7 // 3", true), Ok(Int(-2)));
+
+    let options = MathParseOptions {
+        euclidean_remainder: true,
+        deterministic_floats: true,
+        ..MathParseOptions::default()
+    };
+    let solve = |expression: &str| crate::MathParse::parse_with_options(expression, &options)?.solve_number(None);
+    assert_eq!(solve("-7.5 % 2"), Ok(Float(0.5)));
+}
+
+#[test]
+fn test_binder_shadowing() {
+    let variables = std::collections::HashMap::from([
+        ("i".to_string(), "100".to_string()),
+    ]);
+    let solve = |expression: &str| crate::MathParse::parse(expression)?.solve_number(Some(&variables));
+    assert_eq!(solve("sum(i, 1, 3, i)"), Ok(Int(6)));
+    assert_eq!(solve("i + sum(i, 1, 3, i) + i"), Ok(Int(206)));
+    assert_eq!(solve("sum(i, 1, 2, sum(i, 1, i, i))"), Ok(Int(4)));
+    assert_eq!(solve("sum(j, 1, 2, i)"), Ok(Int(200)));
+}
+
+#[test]
+fn test_operation_limit() {
+    let options = MathParseOptions {
+        max_operations: Some(1000),
+        ..MathParseOptions::default()
+    };
+    let solve = |expression: &str| crate::MathParse::parse_with_options(expression, &options)?.solve_number(None);
+    assert_eq!(solve("sum(i, 1, 100, i)"), Ok(Int(5050)));
+    assert_eq!(solve("sum(i, 1, 10000000, i)"), Err(TooManyOperations(1000)));
+    assert_eq!(solve("sum(i, 1, 100000, sum(j, 1, 100000, j))"), Err(TooManyOperations(1000)));
+}
+
+#[test]
+fn test_variables_solved_once() {
+    let calls = std::cell::Cell::new(0);
+    let map = |name: &str| -> Option<String> {
+        if name == "a" {
+            calls.set(calls.get() + 1);
+            Some("2*3".to_string())
+        } else {
+            None
+        }
+    };
+    let options = MathParseOptions::default();
+    let mut solver = Solver::new(&map, &options);
+    let rpn = crate::MathParse::parse("a*a + 1").unwrap().internal.to_rpn();
+    assert_eq!(solver.solve_expression(&rpn), Ok(Int(37)));
+    let rpn = crate::MathParse::parse("a - 1").unwrap().internal.to_rpn();
+    assert_eq!(solver.solve_expression(&rpn), Ok(Int(5)));
+    assert_eq!(calls.get(), 1);
+}
+
+#[test]
+fn test_is_constant() {
+    let options = MathParseOptions::default();
+    let constant = |expression: &str| is_constant(&crate::MathParse::parse(expression).unwrap().to_rpn().unwrap(), &options);
+    assert!(constant("60*60*24"));
+    assert!(constant("1/0"));
+    assert!(constant("-'a' + 0x10 * 1.5e3"));
+    assert!(constant("sum(i, 1, 10, i*i)"));
+    assert!(constant("sum(i, 1, 3, product(j, 1, i, j))"));
+    assert!(constant("mask(4)"));
+    assert!(!constant("x*1.21"));
+    assert!(!constant("sum(i, 1, 10, i*j)"));
+    assert!(!constant("sum(i, 1, n, i)"));
+    assert!(!constant("sum(i, 1, 3, i) + i"));
+    assert!(!constant("rand()"));
+    assert!(!constant("randint(1, 6) * 0"));
+    assert!(constant("2 * pi"));
+}
+
+#[test]
+fn test_constants() {
+    use std::f64::consts::{PI, E, TAU};
+    let solve = |expression: &str, map: Option<&HashMap<String, String>>, options: &MathParseOptions| {
+        crate::MathParse::parse_with_options(expression, options)?.solve_float(map)
+    };
+    let options = MathParseOptions::default();
+    assert_eq!(solve("pi", None, &options), Ok(PI));
+    assert_eq!(solve("e ** 2", None, &options), Ok(E * E));
+    assert_eq!(solve("tau / 2 - pi", None, &options), Ok(0.0));
+    assert_eq!(solve("sum(pi, 1, 3, pi)", None, &options), Ok(6.0));
+    assert_eq!(solve("Pi", None, &options), Err(InvalidNumber("Pi".to_string())));
+    assert_eq!(solve("2e1 + e1", None, &options), Err(InvalidNumber("e1".to_string())));
+
+    // The map has the priority.
+    let map = HashMap::from([("r".to_string(), "2".to_string()), ("e".to_string(), "0.5".to_string())]);
+    assert_eq!(solve("2*pi*r", Some(&map), &options), Ok(4.0 * PI));
+    assert_eq!(solve("e * 4", Some(&map), &options), Ok(2.0));
+    let mut session = crate::MathParse::parse("e * tau").unwrap().start_eval(Some(&map));
+    assert_eq!(session.run(100), crate::EvalStatus::Done(Ok(Float(TAU / 2.0))));
+
+    let options = MathParseOptions{disable_constants: true, ..MathParseOptions::default()};
+    assert_eq!(solve("2*pi", None, &options), Err(InvalidNumber("pi".to_string())));
+    assert_eq!(solve("e * 4", Some(&map), &options), Ok(2.0));
+    assert!(!is_constant(&crate::MathParse::parse("2 * pi").unwrap().to_rpn().unwrap(), &options));
+}
@@ -0,0 +1,320 @@
+use crate::MathParseErrors;
+use crate::MathParseErrors::*;
+use crate::RPN;
+use crate::tokenize::MathValue;
+use crate::tokenize::MathValue::*;
+use crate::tokenize::{math_token_with_positions, split_word_operators};
+use crate::parse::{unary_parse, is_function_name};
+use crate::rpn_stack_manipulation::*;
+use std::ops::Range;
+
+/* ---------------------------------- Spans --------------------------------- */
+
+/// Byte ranges of the expression each node of a tree is read from, as given
+/// by `MathParse::to_tree_with_spans`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct SpanMap {
+    /// The nodes, children first, so that the root is the last one. Keeping
+    /// them flat lets deep trees be compared and dropped without recursion.
+    nodes: Vec<SpanNode>,
+}
+
+/// Span of a node of a tree, along with the index of its children.
+#[derive(Debug, PartialEq, Clone)]
+struct SpanNode {
+    span: Range<usize>,
+    children: Vec<usize>,
+}
+
+impl SpanMap {
+    /// Byte range of the node found by following the path from the root of
+    /// the tree. Each element of the path is the index of a child: 0 for the
+    /// operand of an unary operator, 0 or 1 for the left and right operands
+    /// of a binary operator, and the index of an argument for a function
+    /// call. The empty path is the root. `None` is returned if there is no
+    /// such node or if the expression was not parsed from infix notation.
+    ///
+    /// The span of an operator covers its operands and the parenthesis
+    /// around them.
+    pub fn get(&self, path: &[usize]) -> Option<Range<usize>> {
+        let mut node = self.nodes.last()?;
+        for index in path {
+            node = &self.nodes[*node.children.get(*index)?];
+        }
+        Some(node.span.clone())
+    }
+
+    /// A map with no spans.
+    pub(crate) fn empty() -> Self {
+        SpanMap{nodes: vec![]}
+    }
+}
+
+/// Find the span of the nodes of the tree made from the RPN actions by
+/// matching them with the tokens of the expression they were parsed from.
+pub fn tree_spans(expression: &str, rpn_actions: &[RPN]) -> Result<SpanMap, MathParseErrors> {
+    let (tokens, positions) = math_token_with_positions(expression);
+    let (tokens, positions) = split_word_operators(tokens, positions);
+    let tokens = Tokens::new(expression, tokens, positions)?;
+
+    let mut nodes = Vec::<SpanNode>::with_capacity(rpn_actions.len());
+    // The index of the nodes being built, with the indexes of their first and
+    // last tokens.
+    let mut stack = Vec::<(usize, usize, usize)>::new();
+    let mut leaves = tokens.leaves.iter();
+    let mut next_leaf = || leaves.next().copied().ok_or(MathParseInternalBug("Missing leaf when computing spans.".to_string()));
+    for action in rpn_actions {
+        let (first, last, children) = match action {
+            RPN::Name(_) => {
+                let leaf = next_leaf()?;
+                (leaf, leaf, vec![])
+            },
+            RPN::Call(_, 0) => {
+                let name = next_leaf()?;
+                (name, tokens.matching_paren(name + 1)?, vec![])
+            },
+            RPN::Unary(crate::UnaryOp::Percent) => {
+                let (first, last, operand) = pop_one(&mut stack)?;
+                (first, last + 1, vec![operand])
+            },
+            RPN::Unary(_) => {
+                let (first, last, operand) = pop_one(&mut stack)?;
+                let operator = first.checked_sub(1).ok_or(MathParseInternalBug("Missing unary operator when computing spans.".to_string()))?;
+                (operator, last, vec![operand])
+            },
+            RPN::Binary(_) => {
+                let ((first, _, lhs), (_, last, rhs)) = pop_two(&mut stack)?;
+                (first, last, vec![lhs, rhs])
+            },
+            RPN::Call(_, 3) if stack.len() >= 3 && tokens.question_marks.get(stack[stack.len() - 3].1 + 1) == Some(&true) => {
+                // Conditional expression, with no name nor parenthesis.
+                let args = pop_n(&mut stack, 3)?;
+                (args[0].0, args[2].1, args.into_iter().map(|arg| arg.2).collect())
+            },
+            RPN::Call(_, arity) => {
+                let args = pop_n(&mut stack, *arity)?;
+                let close = args.last().map_or(0, |arg| arg.1) + 1;
+                let name = tokens.matching_paren(close)? - 1;
+                (name, close, args.into_iter().map(|arg| arg.2).collect())
+            },
+        };
+        let (first, last) = tokens.extend(first, last);
+        let span = tokens.spans[first].start..tokens.spans[last].end;
+        stack.push((first, last, nodes.len()));
+        nodes.push(SpanNode{span, children});
+    }
+    match stack.pop() {
+        Some(_) if stack.is_empty() => Ok(SpanMap{nodes}),
+        _ => Err(UnbalancedStack),
+    }
+}
+
+/// Find the span of each name used by the RPN actions, in the order of the
+/// actions.
+pub fn name_spans(expression: &str, rpn_actions: &[RPN]) -> Result<Vec<Range<usize>>, MathParseErrors> {
+    let (tokens, positions) = math_token_with_positions(expression);
+    let (tokens, positions) = split_word_operators(tokens, positions);
+    let tokens = Tokens::new(expression, tokens, positions)?;
+    let mut leaves = tokens.leaves.iter();
+    let mut ret = vec![];
+    for action in rpn_actions {
+        let leaf = match action {
+            RPN::Name(_) | RPN::Call(_, 0) => leaves.next().ok_or(MathParseInternalBug("Missing leaf when computing spans.".to_string()))?,
+            _ => continue,
+        };
+        if let RPN::Name(_) = action {
+            ret.push(tokens.spans[*leaf].clone());
+        }
+    }
+    Ok(ret)
+}
+
+/// Information about the tokens of an expression needed to find spans.
+struct Tokens {
+    /// Byte range of each token, trailing whitespace excluded.
+    spans: Vec<Range<usize>>,
+    /// For each parenthesis, the index of the matching one.
+    matching_parens: Vec<Option<usize>>,
+    /// True for the tokens that are parenthesis used to group an expression.
+    /// The parenthesis of function calls are not.
+    grouping_parens: Vec<bool>,
+    /// True for the unary operators removed when parsing, such as the two
+    /// `-` in `--a`.
+    removed_unary: Vec<bool>,
+    /// True for the `?` of conditional expressions.
+    question_marks: Vec<bool>,
+    /// Index of the tokens that are leaves of the tree, in order: the names
+    /// and the names of functions called without arguments.
+    leaves: Vec<usize>,
+}
+
+impl Tokens {
+    fn new(expression: &str, tokens: Vec<MathValue>, positions: Vec<usize>) -> Result<Self, MathParseErrors> {
+        let mut unary = tokens.clone();
+        unary_parse(&mut unary)?;
+
+        let mut spans = Vec::with_capacity(tokens.len());
+        let mut matching_parens = vec![None; tokens.len()];
+        let mut grouping_parens = vec![false; tokens.len()];
+        let mut removed_unary = vec![false; tokens.len()];
+        let question_marks = tokens.iter().map(|token| *token == Operator('?')).collect();
+        let mut leaves = vec![];
+        let mut open_parens = vec![];
+        for (index, token) in tokens.iter().enumerate() {
+            let position = positions[index];
+            let is_call_name = matches!(token, Name(name) if is_function_name(name))
+                && tokens.get(index + 1) == Some(&Operator('('));
+            spans.push(match token {
+                Name(name) => position..(position + name.trim_end().len()),
+                _ => position..(position + expression[position..].chars().next().map_or(0, char::len_utf8)),
+            });
+            match token {
+                Name(_) if !is_call_name || tokens.get(index + 2) == Some(&Operator(')')) => {
+                    leaves.push(index);
+                },
+                Operator('(') => {
+                    open_parens.push(index);
+                    grouping_parens[index] = !matches!(tokens[..index].last(), Some(Name(name)) if is_function_name(name));
+                },
+                Operator(')') => {
+                    if let Some(open) = open_parens.pop() {
+                        matching_parens[open] = Some(index);
+                        matching_parens[index] = Some(open);
+                        grouping_parens[index] = grouping_parens[open];
+                    }
+                },
+                Operator(_) => {
+                    removed_unary[index] = unary[index] == ParenOpen(1);
+                },
+                _ => {},
+            }
+        }
+        Ok(Tokens{spans, matching_parens, grouping_parens, removed_unary, question_marks, leaves})
+    }
+
+    fn matching_paren(&self, index: usize) -> Result<usize, MathParseErrors> {
+        match self.matching_parens.get(index) {
+            Some(Some(x)) => Ok(*x),
+            _ => Err(MathParseInternalBug(format!("Token {index} should have been a parenthesis."))),
+        }
+    }
+
+    /// Extend the tokens of a node to the unary operators removed before it
+    /// and the parenthesis around it.
+    fn extend(&self, mut first: usize, mut last: usize) -> (usize, usize) {
+        while first > 0 {
+            if self.removed_unary[first - 1] {
+                first -= 1;
+            } else if self.grouping_parens[first - 1] && self.matching_parens[first - 1] == Some(last + 1) {
+                first -= 1;
+                last += 1;
+            } else {
+                break;
+            }
+        }
+        (first, last)
+    }
+}
+
+/* --------------------------------- Testing -------------------------------- */
+
+#[test]
+fn test_tree_spans() {
+    let spans = |expression: &str| crate::MathParse::parse(expression).unwrap().to_tree_with_spans().unwrap().1;
+
+    let map = spans("(a+1)*b");
+    assert_eq!(map.get(&[]), Some(0..7));
+    assert_eq!(map.get(&[0]), Some(0..5));
+    assert_eq!(map.get(&[0, 0]), Some(1..2));
+    assert_eq!(map.get(&[0, 1]), Some(3..4));
+    assert_eq!(map.get(&[1]), Some(6..7));
+    assert_eq!(map.get(&[2]), None);
+    assert_eq!(map.get(&[1, 0]), None);
+
+    let map = spans("a and not b");
+    assert_eq!(map.get(&[]), Some(0..11));
+    assert_eq!(map.get(&[0]), Some(0..1));
+    assert_eq!(map.get(&[1]), Some(6..11));
+    assert_eq!(map.get(&[1, 0]), Some(10..11));
+    let map = spans("a mod -b");
+    assert_eq!(map.get(&[]), Some(0..8));
+    assert_eq!(map.get(&[1]), Some(6..8));
+
+    // `×` is 2 bytes long, `−` is 3 bytes long, and `//` is made of 2 tokens.
+    let map = spans("x × (−y // 2)");
+    assert_eq!(map.get(&[]), Some(0..16));
+    assert_eq!(map.get(&[0]), Some(0..1));
+    assert_eq!(map.get(&[1]), Some(5..16));
+    assert_eq!(map.get(&[1, 0]), Some(6..10));
+    assert_eq!(map.get(&[1, 0, 0]), Some(9..10));
+    assert_eq!(map.get(&[1, 1]), Some(14..15));
+
+    let map = spans(" ( (a) ) + sum(i, 1, (3), --i) - rand() ");
+    assert_eq!(map.get(&[]), Some(1..39));
+    assert_eq!(map.get(&[0]), Some(1..30));
+    assert_eq!(map.get(&[0, 0]), Some(1..8));
+    assert_eq!(map.get(&[0, 0, 0]), None);
+    assert_eq!(map.get(&[0, 1]), Some(11..30));
+    assert_eq!(map.get(&[0, 1, 0]), Some(15..16));
+    assert_eq!(map.get(&[0, 1, 2]), Some(21..24));
+    assert_eq!(map.get(&[0, 1, 3]), Some(26..29));
+    assert_eq!(map.get(&[1]), Some(33..39));
+
+    let map = spans("---a * !!b");
+    assert_eq!(map.get(&[]), Some(0..10));
+    assert_eq!(map.get(&[0]), Some(0..4));
+    assert_eq!(map.get(&[0, 0]), Some(3..4));
+    assert_eq!(map.get(&[1]), Some(7..10));
+    assert_eq!(map.get(&[1, 0]), Some(8..10));
+    assert_eq!(map.get(&[1, 0, 0]), Some(9..10));
+
+    let map = spans("x ? (y) : if(z, 1, 2)");
+    assert_eq!(map.get(&[]), Some(0..21));
+    assert_eq!(map.get(&[0]), Some(0..1));
+    assert_eq!(map.get(&[1]), Some(4..7));
+    assert_eq!(map.get(&[2]), Some(10..21));
+    assert_eq!(map.get(&[2, 0]), Some(13..14));
+
+    let map = spans("a + (b)% * 2");
+    assert_eq!(map.get(&[]), Some(0..12));
+    assert_eq!(map.get(&[1]), Some(4..12));
+    assert_eq!(map.get(&[1, 0]), Some(4..8));
+    assert_eq!(map.get(&[1, 0, 0]), Some(4..7));
+
+    let map = crate::MathParse::parse_rpn("1 2 +").unwrap().to_tree_with_spans().unwrap().1;
+    assert_eq!(map.get(&[]), None);
+}
+
+#[test]
+fn test_spans_parse_to_nodes() {
+    use crate::Tree;
+
+    /// Check that the text of the span of each node is parsed to that node.
+    fn check(expression: &str, tree: &Tree, spans: &SpanMap, path: &mut Vec<usize>) {
+        let span = spans.get(path).unwrap();
+        let parsed = crate::MathParse::parse(&expression[span]).unwrap().to_tree().unwrap();
+        assert_eq!(&parsed, tree, "{expression} {path:?}");
+        let children = match tree {
+            Tree::Name(_) => vec![],
+            Tree::Unary(_, operand) => vec![&**operand],
+            Tree::Binary(_, lhs, rhs) => vec![&**lhs, &**rhs],
+            Tree::Call(_, args) => args.iter().collect(),
+        };
+        for (index, child) in children.into_iter().enumerate() {
+            path.push(index);
+            check(expression, child, spans, path);
+            path.pop();
+        }
+    }
+
+    for expression in [
+        "1", "((1))", "-(-(2))", "--3 + ---4", "a << 2 >> (b & c | d ^ e)", "5 // -(3 % 2)",
+        "f(x) * -sum(i, 1, (((10))), i*i)", "product(k, 1, 4, -(k)) + rand() // randint(1, 6)",
+        "!(x | !!~y) × z ÷ 2 − w", "'(' + ' ' * 1.5e-3", "(a + (b + (c + (d))))",
+        "a > 1 ? -b : (c) ? d : e", "(a ? b : c) ? if(d, e, f) : g", "if(a ? b : c, d, e)",
+        "2(3 + x)(y)", "(a+1)(a-1) b", "50% + (a)% * f(x)%%", "-x% ** 2 % 3",
+    ] {
+        let (tree, spans) = crate::MathParse::parse(expression).unwrap().to_tree_with_spans().unwrap();
+        check(expression, &tree, &spans, &mut vec![]);
+    }
+}
@@ -0,0 +1,168 @@
+use crate::MathParse;
+use crate::MathParseErrors;
+use crate::MathParseErrors::*;
+use crate::MathParseOptions;
+use crate::RPN;
+use crate::solve::{Number, Solver, bound_names};
+use std::collections::HashMap;
+
+/* ---------------------------- Systems of formulas --------------------------- */
+
+/// Solve named formulas that can use each other, returning the value of each
+/// of them. The formulas can also use the variables of `inputs`, a formula
+/// shadowing an input with the same name. Each formula is parsed and solved
+/// only once, after the formulas it uses.
+///
+/// A formula that uses itself, directly or through other formulas, gives a
+/// `CyclicDefinition` error naming one of the formulas of the cycle. The
+/// variables bound by binder functions, such as the `i` of `sum(i, 1, 3, i)`,
+/// are not uses of the formula with the same name.
+///
+/// ```
+/// use math_parse::{solve_system, Number};
+/// use std::collections::HashMap;
+///
+/// let definitions = HashMap::from([
+///     ("subtotal".to_string(), "price*qty".to_string()),
+///     ("tax".to_string(), "subtotal*0.2".to_string()),
+///     ("total".to_string(), "subtotal+tax".to_string()),
+/// ]);
+/// let inputs = HashMap::from([
+///     ("price".to_string(), "15".to_string()),
+///     ("qty".to_string(), "4".to_string()),
+/// ]);
+/// let values = solve_system(&definitions, &inputs).unwrap();
+/// assert_eq!(values["subtotal"], Number::Int(60));
+/// assert_eq!(values["total"], Number::Int(72));
+/// ```
+pub fn solve_system(definitions: &HashMap<String, String>, inputs: &HashMap<String, String>) -> Result<HashMap<String, Number>, MathParseErrors> {
+    let options = MathParseOptions::default();
+    // Sorted so that the same error is given for the same definitions.
+    let mut names: Vec<&str> = definitions.keys().map(String::as_str).collect();
+    names.sort_unstable();
+
+    let mut formulas = HashMap::<&str, (Vec<RPN>, Vec<&str>)>::new();
+    for name in &names {
+        let rpn = MathParse::parse_with_options(&definitions[*name], &options)?.internal.to_rpn();
+        let mut dependencies = vec![];
+        for (action, bound) in rpn.iter().zip(bound_names(&rpn)) {
+            if let RPN::Name(used) = action {
+                if bound {
+                    continue;
+                }
+                if let Some((used, _)) = definitions.get_key_value(used) {
+                    if !dependencies.contains(&used.as_str()) {
+                        dependencies.push(used.as_str());
+                    }
+                }
+            }
+        }
+        formulas.insert(name, (rpn, dependencies));
+    }
+
+    let map_function = |s: &str| -> Option<String> {
+        inputs.get(s).cloned()
+    };
+    let mut solver = Solver::new(&map_function, &options);
+    let mut ret = HashMap::new();
+    for name in solving_order(&names, &formulas)? {
+        let value = solver.solve_expression(&formulas[name].0)?.simplified();
+        solver.set_variable(name, value);
+        ret.insert(name.to_string(), value);
+    }
+    Ok(ret)
+}
+
+/// Sort the formulas so that each one comes after the ones it uses.
+fn solving_order<'a>(names: &[&'a str], formulas: &HashMap<&'a str, (Vec<RPN>, Vec<&'a str>)>) -> Result<Vec<&'a str>, MathParseErrors> {
+    /// Where a formula is in the sorting.
+    #[derive(PartialEq)]
+    enum Visit {
+        /// The formulas it uses are being sorted.
+        Started,
+        /// It is in the order.
+        Done,
+    }
+
+    let mut visits = HashMap::<&str, Visit>::new();
+    let mut order = vec![];
+    for name in names {
+        if visits.contains_key(name) {
+            continue;
+        }
+        // The formulas being sorted, with the index of the next formula
+        // they use to look at.
+        let mut stack = vec![(*name, 0)];
+        visits.insert(name, Visit::Started);
+        while let Some((current, next)) = stack.last_mut() {
+            let dependencies = &formulas[*current].1;
+            if let Some(dependency) = dependencies.get(*next) {
+                *next += 1;
+                match visits.get(dependency) {
+                    Some(Visit::Started) => return Err(CyclicDefinition(dependency.to_string())),
+                    Some(Visit::Done) => {},
+                    None => {
+                        visits.insert(dependency, Visit::Started);
+                        stack.push((dependency, 0));
+                    },
+                }
+            } else {
+                visits.insert(current, Visit::Done);
+                order.push(*current);
+                stack.pop();
+            }
+        }
+    }
+    Ok(order)
+}
+
+/* --------------------------------- Testing -------------------------------- */
+
+#[cfg(test)]
+fn map(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+    pairs.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect()
+}
+
+#[test]
+fn test_solve_system() {
+    let definitions = map(&[("subtotal", "price*qty"), ("tax", "subtotal*0.2"), ("total", "subtotal+tax")]);
+    let inputs = map(&[("price", "2.5"), ("qty", "4")]);
+    let values = solve_system(&definitions, &inputs).unwrap();
+    assert_eq!(values.len(), 3);
+    assert_eq!(values["subtotal"], Number::Int(10));
+    assert_eq!(values["tax"], Number::Int(2));
+    assert_eq!(values["total"], Number::Int(12));
+
+    // Formulas shadow inputs.
+    let inputs = map(&[("y", "100")]);
+    let definitions = map(&[("x", "y*y"), ("y", "3"), ("z", "x + x")]);
+    assert_eq!(solve_system(&definitions, &inputs).map(|values| values["z"]), Ok(Number::Int(18)));
+
+    // An input referencing an other input.
+    let definitions = map(&[("area", "width*height")]);
+    let inputs = map(&[("width", "3"), ("height", "1+1")]);
+    assert_eq!(solve_system(&definitions, &inputs).map(|values| values["area"]), Ok(Number::Int(6)));
+    assert_eq!(solve_system(&definitions, &HashMap::new()), Err(InvalidNumber("width".to_string())));
+    assert_eq!(solve_system(&HashMap::new(), &inputs), Ok(HashMap::new()));
+}
+
+#[test]
+fn test_solve_system_cycles() {
+    let definitions = map(&[("a", "b + 1"), ("b", "c * 2"), ("c", "a - 3"), ("d", "4")]);
+    let cycle = solve_system(&definitions, &HashMap::new());
+    assert!(matches!(&cycle, Err(CyclicDefinition(name)) if ["a", "b", "c"].contains(&name.as_str())), "{cycle:?}");
+    assert_eq!(solve_system(&map(&[("x", "x + 1")]), &HashMap::new()), Err(CyclicDefinition("x".to_string())));
+    assert_eq!(solve_system(&map(&[("x", "1 +")]), &HashMap::new()), Err(EmptyLine));
+
+    // Variables bound by binder functions are not uses of the formulas.
+    assert_eq!(solve_system(&map(&[("a", "sum(a, 1, 3, a)")]), &HashMap::new()).map(|values| values["a"]), Ok(Number::Int(6)));
+    let definitions = map(&[("i", "product(i, 1, 4, i) + n"), ("n", "sum(i, 1, 2, i * i)")]);
+    let values = solve_system(&definitions, &HashMap::new()).unwrap();
+    assert_eq!((values["i"], values["n"]), (Number::Int(29), Number::Int(5)));
+    assert_eq!(solve_system(&map(&[("a", "sum(i, 1, a, i)")]), &HashMap::new()), Err(CyclicDefinition("a".to_string())));
+
+    // Long chains of formulas don't overflow the stack.
+    let mut definitions: HashMap<String, String> = (1..20000).map(|i| (format!("v{i}"), format!("v{} + 1", i - 1))).collect();
+    definitions.insert("v0".to_string(), "0".to_string());
+    assert_eq!(solve_system(&definitions, &HashMap::new()).map(|values| values["v19999"]), Ok(Number::Int(19999)));
+}
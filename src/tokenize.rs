@@ -1,6 +1,24 @@
-const MATH_CHARS: [char; 23] = ['+', '-', '−', '*', '×', '·', '/', '∕', '⁄', '÷', '(', ')', '%', '⟌', '!', '~', '^', '&', '|', '≪', '<', '>', '≫'];
+use crate::MathParseErrors;
+use crate::MathParseOptions;
 
-#[derive(Debug, PartialEq)]
+pub(crate) const MATH_CHARS: [char; 36] = ['+', '-', '−', '*', '↑', '×', '·', '/', '∕', '⁄', '÷', '(', ')', '%', '⟌', '!', '~', '√', '^', '⊕', '&', '|', '≪', '<', '>', '≫', '≤', '≥', '=', '⩵', '≠', '∧', '∨', '?', ':', ','];
+
+/// Words that can be written instead of binary operators, with the operator
+/// they stand for.
+const BINARY_WORDS: [(&str, char); 6] = [("and", '&'), ("or", '|'), ("xor", '^'), ("mod", '%'), ("shl", '≪'), ("shr", '≫')];
+
+/// Word that can be written instead of the unary operator `~`.
+const NOT_WORD: &str = "not";
+
+/// Characters used in angles written in degrees, minutes, and seconds.
+const DMS_CHARS: [char; 3] = ['°', '\'', '"'];
+
+/// Characters that can't be seen, such as the byte order mark or the zero
+/// width spaces found in text pasted from spreadsheets or web pages. They are
+/// ignored between tokens.
+const INVISIBLE_CHARS: [char; 5] = ['\u{FEFF}', '\u{200B}', '\u{200C}', '\u{200D}', '\u{2060}'];
+
+#[derive(Debug, PartialEq, Clone)]
 pub enum MathValue<'a> {
     // Values used in parsing
     /// A slice of the input string. As only a single string is used, the single
@@ -20,6 +38,9 @@ pub enum MathValue<'a> {
     /// The start of a group in parenthesis. The `isize` if the offset between
     /// the `ParenOpen` and the start of the inside of the parenthesis.
     ParenOpen(isize),
+    /// A function call. The `&str` is the name of the function and each
+    /// `isize` is the offset from the `FunctionCall` to one of its arguments.
+    FunctionCall(&'a str, Vec<isize>),
     /// The end of a group in parenthesis. The `usize` is the number of elements
     /// inside of the group needed to fly back to the beginning if the
     /// parenthesis group during parsing.
@@ -33,72 +54,398 @@ pub enum MathValue<'a> {
 use MathValue::*;
 
 /// Tokenise a line of math expression into a vector of `MathValue`.
+#[cfg(test)]
 pub fn math_token<'a>(s: &'a str) -> Vec<MathValue<'a>> {
+    math_token_with_positions(s).0
+}
 
-    /// Reads name and operators in a line of math.
-    fn token_base<'a>(s: &'a str) -> Vec<MathValue<'a>> {
-        let mut ret = Vec::<MathValue>::new();
-        let mut new_name_index = !0; // Word that we are writing, !0 indicate we were not writing anything.
-        let mut current_index = 0;
-
-        for c in s.chars() {
-            if is_in(c, &MATH_CHARS) {
-                if new_name_index != !0 { // We were writing a work
-                    ret.push(Name(&s[new_name_index..current_index]));
-                    new_name_index = !0;
-                }
-                ret.push(Operator(c));
-            } else if new_name_index == !0 && !c.is_whitespace() {
-                new_name_index = current_index;
+/// Tokenise a line of math expression into a vector of `MathValue` and return
+/// alongside it the byte position in the line of the start of each token.
+pub fn math_token_with_positions<'a>(s: &'a str) -> (Vec<MathValue<'a>>, Vec<usize>) {
+    let (mut ret, positions) = math_token_base(s);
+    combine_complex_tokens(&mut ret, &positions);
+    (ret, positions)
+}
+
+/// Reads name and operators in a line of math, without combining the
+/// operators made of several characters. The byte position of each token is
+/// returned as with `math_token_with_positions`.
+pub fn math_token_base<'a>(s: &'a str) -> (Vec<MathValue<'a>>, Vec<usize>) {
+    let mut ret = Vec::<MathValue>::new();
+    let mut positions = Vec::<usize>::new();
+    let mut new_name_index = !0; // Word that we are writing, !0 indicate we were not writing anything.
+    let mut literal_end = 0; // Index of the end of the last character literal.
+
+    for (current_index, c) in s.char_indices() {
+        if current_index < literal_end {
+            continue;
+        }
+        if c == '\'' && new_name_index == !0 {
+            literal_end = current_index + char_literal_len(&s[current_index..]);
+            match s[literal_end..].chars().next() {
+                Some(next) if !next.is_whitespace() && !is_in(next, &MATH_CHARS) => {
+                    // Garbage after the literal, it will be read as a single invalid name.
+                    new_name_index = current_index;
+                },
+                _ => {
+                    ret.push(Name(&s[current_index..literal_end]));
+                    positions.push(current_index);
+                },
             }
-            current_index += c.len_utf8();
+        } else if (c == '+' || c == '-') && new_name_index != !0
+            && s[new_name_index..current_index].rsplit(char::is_whitespace).next().is_some_and(is_decimal_mantissa_exponent)
+            && s[current_index+1..].starts_with(|next: char| next.is_ascii_digit()) {
+            // Sign of the exponent of a number in scientific notation, kept in the name.
+        } else if is_in(c, &MATH_CHARS) {
+            if new_name_index != !0 { // We were writing a work
+                ret.push(Name(name_slice(s, new_name_index, current_index)));
+                positions.push(new_name_index);
+                new_name_index = !0;
+            }
+            ret.push(Operator(c));
+            positions.push(current_index);
+        } else if new_name_index == !0 && !c.is_whitespace() && !is_invisible(c) {
+            new_name_index = current_index;
         }
+    }
 
-        if new_name_index != !0 { // We were writing a work
-            ret.push(Name(&s[new_name_index..]));
+    if new_name_index != !0 { // We were writing a work
+        ret.push(Name(name_slice(s, new_name_index, s.len())));
+        positions.push(new_name_index);
+    }
+    ret.push(TrailingError);
+    positions.push(s.len());
+    (ret, positions)
+}
+
+/// Combine complex math symbols such as // to make operators. Only
+/// characters written next to each other are combined, so that `/ /` stays
+/// two divisions as needed in Reverse Polish notation.
+pub fn combine_complex_tokens(line: &mut [MathValue], positions: &[usize]) {
+    for i in 1..line.len() {
+        if positions[i] != positions[i-1] + 1 {
+            continue;
+        }
+        let previous_op = if let Operator(c) = line[i-1] {
+            Some(c)
+        } else {
+            None
+        };
+        let current_op = if let Operator(c) = line[i] {
+            Some(c)
+        } else {
+            None
+        };
+        match (previous_op, current_op) {
+            (Some('*'), Some('*')) => {
+                line[i-1] = Operator('↑');
+                line[i] = ParenOpen(1);
+            },
+            (Some('/'), Some('/')) => {
+                line[i-1] = Operator('⟌');
+                line[i] = ParenOpen(1); // Here and for the wollowing tokens, the ParenOpen(1) is used as a pointer to the next token, acting as if one of the two tokens of the complex is removed.
+            },
+            (Some('<'), Some('<')) => {
+                line[i-1] = Operator('≪');
+                line[i] = ParenOpen(1);
+            },
+            (Some('>'), Some('>')) => {
+                line[i-1] = Operator('≫');
+                line[i] = ParenOpen(1);
+            },
+            (Some('<'), Some('=')) => {
+                line[i-1] = Operator('≤');
+                line[i] = ParenOpen(1);
+            },
+            (Some('>'), Some('=')) => {
+                line[i-1] = Operator('≥');
+                line[i] = ParenOpen(1);
+            },
+            (Some('='), Some('=')) => {
+                line[i-1] = Operator('⩵');
+                line[i] = ParenOpen(1);
+            },
+            (Some('!'), Some('=')) => {
+                line[i-1] = Operator('≠');
+                line[i] = ParenOpen(1);
+            },
+            (Some('&'), Some('&')) => {
+                line[i-1] = Operator('∧');
+                line[i] = ParenOpen(1);
+            },
+            (Some('|'), Some('|')) => {
+                line[i-1] = Operator('∨');
+                line[i] = ParenOpen(1);
+            },
+            (_, _) => {},
         }
-        ret.push(TrailingError);
-        ret
     }
+}
 
-    /// Combine complex math symbols such as // to make operators
-    fn token_complex(line: &mut [MathValue]) {
-        for i in 1..line.len() {
-            let previous_op = if let Operator(c) = line[i-1] {
-                Some(c)
-            } else {
-                None
-            };
-            let current_op = if let Operator(c) = line[i] {
-                Some(c)
-            } else {
-                None
+/// Split the names made of several words around the words that stand for
+/// operators in infix notation, such as `and` in `3 and 5`. Only whole words are operators, so
+/// `android` is left alone. The words of binary operators must follow a
+/// value, either a word of the same name or a closing parenthesis, while
+/// `not` must not follow a value and must be followed by something. A word
+/// that is not where its operator could be stays a part of the name.
+///
+/// A `%` followed by a `+` or a `-` written directly before its operand, as
+/// in `7 % -3`, is also marked as a remainder, so that it is not read as a
+/// percentage as in `50% + 0.25`.
+pub fn split_word_operators<'a>(tokens: Vec<MathValue<'a>>, positions: Vec<usize>) -> (Vec<MathValue<'a>>, Vec<usize>) {
+    let mut ret = Vec::with_capacity(tokens.len());
+    let mut ret_positions = Vec::with_capacity(positions.len());
+    for index in 0..tokens.len() {
+        let Name(name) = tokens[index] else {
+            ret.push(tokens[index].clone());
+            ret_positions.push(positions[index]);
+            if tokens[index] == Operator('%') && is_signed_operand(&tokens, &positions, index + 1) {
+                ret.push(ParenOpen(1));
+                ret_positions.push(positions[index] + 1);
+            }
+            continue;
+        };
+        if name.starts_with('\'') {
+            ret.push(Name(name));
+            ret_positions.push(positions[index]);
+            continue;
+        }
+        let next_is_value = matches!(tokens.get(index + 1), Some(Operator('(' | '-' | '−' | '+' | '!' | '~' | '√')));
+        let words: Vec<&str> = name.split_whitespace().collect();
+        let mut value_before = index > 0 && tokens[index - 1] == Operator(')');
+        // Start of the words kept as a name, not yet pushed.
+        let mut pending: Option<usize> = None;
+        let push_pending = |ret: &mut Vec<MathValue<'a>>, ret_positions: &mut Vec<usize>, pending: &mut Option<usize>, end: usize| {
+            if let Some(start) = pending.take() {
+                ret.push(Name(&name[start..end]));
+                ret_positions.push(positions[index] + start);
+            }
+        };
+        for (word_index, word) in words.iter().enumerate() {
+            let start = word.as_ptr() as usize - name.as_ptr() as usize;
+            let binary = BINARY_WORDS.iter().find(|(binary_word, _)| binary_word == word).map(|(_, op)| *op);
+            let op = match binary {
+                Some(op) if value_before => Some(op),
+                _ if *word == NOT_WORD && !value_before && (word_index + 1 < words.len() || next_is_value) => Some('~'),
+                _ => None,
             };
-            match (previous_op, current_op) {
-                (Some('/'), Some('/')) => {
-                    line[i-1] = Operator('⟌');
-                    line[i] = ParenOpen(1); // Here and for the wollowing tokens, the ParenOpen(1) is used as a pointer to the next token, acting as if one of the two tokens of the complex is removed.
-                },
-                (Some('<'), Some('<')) => {
-                    line[i-1] = Operator('≪');
-                    line[i] = ParenOpen(1);
+            match op {
+                Some(op) => {
+                    push_pending(&mut ret, &mut ret_positions, &mut pending, start);
+                    ret.push(Operator(op));
+                    ret_positions.push(positions[index] + start);
+                    if op == '%' {
+                        // As for the operators combined from two characters, so
+                        // that `mod` is never read as a percentage.
+                        ret.push(ParenOpen(1));
+                        ret_positions.push(positions[index] + start + 1);
+                    }
+                    value_before = false;
                 },
-                (Some('>'), Some('>')) => {
-                    line[i-1] = Operator('≫');
-                    line[i] = ParenOpen(1);
+                None => {
+                    pending.get_or_insert(start);
+                    value_before = true;
                 },
-                (_, _) => {},
             }
         }
+        if pending == Some(0) {
+            ret.push(Name(name));
+            ret_positions.push(positions[index]);
+        } else {
+            push_pending(&mut ret, &mut ret_positions, &mut pending, name.len());
+        }
     }
+    (ret, ret_positions)
+}
 
-    let mut ret = token_base(s);
-    token_complex(&mut ret);
-    ret
+/// Tell if the token at the index is a `+` or a `-` directly followed by the
+/// token after it, such as the sign of `-3`.
+fn is_signed_operand(tokens: &[MathValue], positions: &[usize], index: usize) -> bool {
+    match (tokens.get(index), positions.get(index + 1)) {
+        (Some(Operator(c @ ('+' | '-' | '−'))), Some(next)) => positions[index] + c.len_utf8() == *next && tokens[index + 1] != TrailingError,
+        _ => false,
+    }
+}
+
+/// Tokenise a line of math expression and check that all the names in it are
+/// made of valid characters, unless the options allow any character in names.
+/// Characters that are not valid give an `InvalidCharacter` error with their
+/// byte position in the line. `extra_chars` are characters that are also
+/// valid in names in the context of the caller. The byte position of each
+/// token is returned as with `math_token_with_positions`.
+pub fn math_token_checked<'a>(s: &'a str, options: &MathParseOptions, extra_chars: &[char]) -> Result<(Vec<MathValue<'a>>, Vec<usize>), MathParseErrors> {
+    let (tokens, positions) = math_token_with_positions(s);
+    check_token_names(&tokens, &positions, options, extra_chars)?;
+    Ok((tokens, positions))
+}
+
+/// Check the names in tokens as `math_token_checked` does.
+pub fn check_token_names(tokens: &[MathValue], positions: &[usize], options: &MathParseOptions, extra_chars: &[char]) -> Result<(), MathParseErrors> {
+    if !options.permissive_identifiers {
+        let mut extra_chars = extra_chars.to_vec();
+        if options.dms_angles {
+            extra_chars.extend(DMS_CHARS);
+        }
+        if options.apostrophe_separators {
+            extra_chars.push('\'');
+        }
+        for (token, position) in tokens.iter().zip(positions) {
+            if let Name(name) = token {
+                check_name_chars(name, *position, &extra_chars)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Check that a name is made of letters, digits, `_`, `.`, and whitespace.
+/// Character literals are not checked as they can hold any character. The
+/// sign of the exponent of a number in scientific notation and the `#` after
+/// the radix of a number such as `2#1010` are accepted as they are part of
+/// the name.
+fn check_name_chars(name: &str, position: usize, extra_chars: &[char]) -> Result<(), MathParseErrors> {
+    if name.starts_with('\'') {
+        return Ok(());
+    }
+    let mut previous = ' ';
+    for (index, c) in name.char_indices() {
+        let valid = c.is_alphanumeric() || c == '_' || c == '.' || c.is_whitespace()
+            || ((c == '+' || c == '-') && (previous == 'e' || previous == 'E'))
+            || (c == '#' && name[..index].rsplit(char::is_whitespace).next().is_some_and(is_radix_prefix))
+            || is_in(c, extra_chars);
+        if !valid || c.is_control() && !c.is_whitespace() {
+            return Err(MathParseErrors::InvalidCharacter(c, position + index));
+        }
+        previous = c;
+    }
+    Ok(())
+}
+
+/// Rewrite the words of the names of a line of math with the given function,
+/// returning the rewritten line or `None` if no word is rewritten. A word
+/// can only be rewritten into a single name or operator, operators being
+/// surrounded by spaces to keep them apart from their neighbours.
+pub fn rewrite_words(s: &str, rewriter: &dyn Fn(&str) -> Option<String>) -> Result<Option<String>, MathParseErrors> {
+    let mut ret = String::new();
+    let mut copied = 0;
+    for token in math_token_base(s).0 {
+        let Name(name) = token else {
+            continue;
+        };
+        if name.starts_with('\'') {
+            continue;
+        }
+        for word in name.split_whitespace() {
+            let Some(rewrite) = rewriter(word) else {
+                continue;
+            };
+            let tokens: Vec<MathValue> = math_token_with_positions(&rewrite).0.into_iter()
+                .filter(|token| !matches!(token, ParenOpen(1) | TrailingError))
+                .collect();
+            let spaced = match tokens[..] {
+                [Name(name)] if !name.contains(char::is_whitespace) => false,
+                [Operator(_)] => true,
+                _ => return Err(MathParseErrors::InvalidRewrite(word.to_string(), rewrite)),
+            };
+            let start = word.as_ptr() as usize - s.as_ptr() as usize;
+            let end = start + word.len();
+            ret.push_str(&s[copied..start]);
+            if spaced && s[..start].ends_with(|c: char| !c.is_whitespace()) {
+                ret.push(' ');
+            }
+            ret.push_str(rewrite.trim());
+            if spaced && s[end..].starts_with(|c: char| !c.is_whitespace()) {
+                ret.push(' ');
+            }
+            copied = end;
+        }
+    }
+    if copied == 0 {
+        return Ok(None);
+    }
+    ret.push_str(&s[copied..]);
+    Ok(Some(ret))
 }
 
 /* ---------------------------------- Utils --------------------------------- */
 
+/// The name written between the given positions. When it ends with invisible
+/// characters, they are left out along with the whitespace around them, so
+/// that only the invisible characters inside of names are errors.
+fn name_slice(s: &str, start: usize, end: usize) -> &str {
+    let name = &s[start..end];
+    let trimmed = name.trim_end_matches(|c: char| c.is_whitespace() || is_invisible(c));
+    if name[trimmed.len()..].contains(INVISIBLE_CHARS) {
+        trimmed
+    } else {
+        name
+    }
+}
+
+/// Rewrite an expression written with a decimal comma, as in `3,14`, into
+/// one written with a decimal point. The commas become points and the
+/// semicolons, which then separate lists and function arguments, become
+/// commas. Character literals are kept as they are.
+pub fn swap_decimal_comma(s: &str) -> String {
+    let mut ret = String::with_capacity(s.len());
+    let mut literal_end = 0; // Index of the end of the last character literal.
+    let mut previous = ' ';
+    for (index, c) in s.char_indices() {
+        if index < literal_end {
+            ret.push(c);
+        } else {
+            if c == '\'' && (previous.is_whitespace() || is_math_char(previous)) {
+                literal_end = index + char_literal_len(&s[index..]);
+            }
+            ret.push(match c {
+                ',' => '.',
+                ';' => ',',
+                _ => c,
+            });
+        }
+        previous = c;
+    }
+    ret
+}
+
+/// Takes a string starting with a `'` and return the length of the character
+/// literal at its start, closing `'` included. If the literal is not closed,
+/// the whole string is considered to be the literal.
+pub fn char_literal_len(s: &str) -> usize {
+    let mut escaped = false;
+    for (index, c) in s.char_indices().skip(1) {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == '\'' {
+            return index + 1;
+        }
+    }
+    s.len()
+}
+
+/// Return true if the string is made of decimal digits and an optional
+/// decimal point, as the mantissa of a number in scientific notation.
+pub fn is_decimal_mantissa(s: &str) -> bool {
+    s.chars().any(|c| c.is_ascii_digit()) && s.chars().all(|c| c.is_ascii_digit() || c == '.')
+}
+
+/// Return true if the string can be the radix of a number such as `36#ZZ`,
+/// which is the part before the `#`.
+pub fn is_radix_prefix(s: &str) -> bool {
+    !s.is_empty() && s.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Return true if the string is a mantissa followed by an exponent marker,
+/// such as `1.5e`. The mantissa can have digit separators, as in `1_000e`.
+fn is_decimal_mantissa_exponent(s: &str) -> bool {
+    match s.strip_suffix(['e', 'E']) {
+        Some(mantissa) => is_decimal_mantissa(&mantissa.replace('_', "")),
+        None => false,
+    }
+}
+
 /// Return true if the element is in the slice
 fn is_in<T: Eq>(a: T, set: &[T]) -> bool {
     for elem in set {
@@ -109,6 +456,17 @@ fn is_in<T: Eq>(a: T, set: &[T]) -> bool {
     false
 }
 
+/// Return true if the character is one of the invisible characters ignored
+/// between tokens.
+pub fn is_invisible(c: char) -> bool {
+    is_in(c, &INVISIBLE_CHARS)
+}
+
+/// Return true if the character is one of the math chars.
+pub fn is_math_char(c: char) -> bool {
+    is_in(c, &MATH_CHARS)
+}
+
 /// Return true if the given string contains any math char
 pub fn contains_math_char(s: &str) -> bool {
     s.contains(MATH_CHARS)
@@ -125,3 +483,96 @@ fn test_math_token() {
     assert_eq!(math_token(math_line), vec![Operator('+'), name_p("4"), Operator('/'), name_p("88"), Operator('*'), name_p("toto"), TrailingError]);
 }
 
+#[test]
+fn test_char_literal_token() {
+    assert_eq!(math_token("'+'+1"), vec![name_p("'+'"), Operator('+'), name_p("1"), TrailingError]);
+    assert_eq!(math_token("'\\'' ' '"), vec![name_p("'\\''"), name_p("' '"), TrailingError]);
+    assert_eq!(math_token("'a'bc*2"), vec![name_p("'a'bc"), Operator('*'), name_p("2"), TrailingError]);
+    assert_eq!(math_token("'ab"), vec![name_p("'ab"), TrailingError]);
+    assert_eq!(math_token("it's"), vec![name_p("it's"), TrailingError]);
+}
+
+
+#[test]
+fn test_exponent_sign_token() {
+    assert_eq!(math_token("1e-3+2"), vec![name_p("1e-3"), Operator('+'), name_p("2"), TrailingError]);
+    assert_eq!(math_token("1.5E+10"), vec![name_p("1.5E+10"), TrailingError]);
+    assert_eq!(math_token("e-3"), vec![name_p("e"), Operator('-'), name_p("3"), TrailingError]);
+    assert_eq!(math_token("x1e-3"), vec![name_p("x1e"), Operator('-'), name_p("3"), TrailingError]);
+    assert_eq!(math_token("0x1e-3"), vec![name_p("0x1e"), Operator('-'), name_p("3"), TrailingError]);
+    assert_eq!(math_token("1e+x"), vec![name_p("1e"), Operator('+'), name_p("x"), TrailingError]);
+    assert_eq!(math_token("1_000e-3"), vec![name_p("1_000e-3"), TrailingError]);
+    assert_eq!(math_token("x e-3"), vec![name_p("x e"), Operator('-'), name_p("3"), TrailingError]);
+}
+
+#[test]
+fn test_token_positions() {
+    let (tokens, positions) = math_token_with_positions("ab + (2×c)");
+    assert_eq!(tokens, vec![name_p("ab "), Operator('+'), Operator('('), name_p("2"), Operator('×'), name_p("c"), Operator(')'), TrailingError]);
+    assert_eq!(positions, vec![0, 3, 5, 6, 7, 9, 10, 11]);
+}
+
+#[test]
+fn test_invalid_characters() {
+    use crate::MathParseErrors::InvalidCharacter;
+    let options = MathParseOptions::default();
+    assert_eq!(math_token_checked("a$#@b + 1", &options, &[]), Err(InvalidCharacter('$', 1)));
+    assert_eq!(math_token_checked("1 + a\0b", &options, &[]), Err(InvalidCharacter('\0', 5)));
+    assert_eq!(math_token_checked("température+1", &options, &[]).map(|x| x.0.len()), Ok(4));
+    assert!(math_token_checked("1.5e-3 + 0x1F + x_2 + 'é'", &options, &[]).is_ok());
+    assert!(math_token_checked("sum@2", &options, &['@']).is_ok());
+
+    let permissive = MathParseOptions {
+        permissive_identifiers: true,
+        ..MathParseOptions::default()
+    };
+    assert_eq!(math_token_checked("a$#@b + 1", &permissive, &[]), Ok(math_token_with_positions("a$#@b + 1")));
+    assert_eq!(math_token_checked("36#ZZ + 1", &options, &[]), Ok(math_token_with_positions("36#ZZ + 1")));
+    assert_eq!(math_token_checked("a#1", &options, &[]), Err(InvalidCharacter('#', 1)));
+    assert_eq!(math_token_checked("#1", &options, &[]), Err(InvalidCharacter('#', 0)));
+}
+
+#[test]
+fn test_invisible_characters() {
+    use crate::MathParseErrors::InvalidCharacter;
+    let options = MathParseOptions::default();
+
+    // Ignored between tokens.
+    let (tokens, positions) = math_token_with_positions("\u{FEFF}3+\u{200B}4\u{2060} \u{200D}");
+    assert_eq!(tokens, vec![name_p("3"), Operator('+'), name_p("4"), TrailingError]);
+    assert_eq!(positions, vec![3, 4, 8, 16]);
+    assert_eq!(math_token("a \u{FEFF} * b "), vec![name_p("a"), Operator('*'), name_p("b "), TrailingError]);
+
+    // Errors inside of names.
+    assert_eq!(math_token("1\u{200B}2 + 1"), vec![name_p("1\u{200B}2 "), Operator('+'), name_p("1"), TrailingError]);
+    assert_eq!(math_token_checked("1\u{200B}2 + 1", &options, &[]), Err(InvalidCharacter('\u{200B}', 1)));
+    assert_eq!(math_token_checked("x\u{200C} y", &options, &[]), Err(InvalidCharacter('\u{200C}', 1)));
+}
+
+#[test]
+fn test_swap_decimal_comma() {
+    assert_eq!(swap_decimal_comma("3,14 * 2"), "3.14 * 2");
+    assert_eq!(swap_decimal_comma("max(1,5; 2); ,5"), "max(1.5, 2), .5");
+    assert_eq!(swap_decimal_comma("',' + ';'"), "',' + ';'");
+}
+
+#[test]
+fn test_rewrite_words() {
+    use crate::MathParseErrors::InvalidRewrite;
+    let rewriter = |word: &str| match word {
+        "DIV" => Some("//".to_string()),
+        "POW" => Some(" ** ".to_string()),
+        "x" => Some("y".to_string()),
+        "TWO" => Some("1 1".to_string()),
+        "BAD" => Some("a+b".to_string()),
+        _ => None,
+    };
+    assert_eq!(rewrite_words("10 DIV 3", &rewriter), Ok(Some("10 // 3".to_string())));
+    assert_eq!(rewrite_words("DIVIDEND+1", &rewriter), Ok(None));
+    assert_eq!(rewrite_words("(a)DIV(b)", &rewriter), Ok(Some("(a) // (b)".to_string())));
+    assert_eq!(rewrite_words("a *POW b", &rewriter), Ok(Some("a * ** b".to_string())));
+    assert_eq!(rewrite_words("x*(x+1)", &rewriter), Ok(Some("y*(y+1)".to_string())));
+    assert_eq!(rewrite_words("'x' + x", &rewriter), Ok(Some("'x' + y".to_string())));
+    assert_eq!(rewrite_words("TWO", &rewriter), Err(InvalidRewrite("TWO".to_string(), "1 1".to_string())));
+    assert_eq!(rewrite_words("1 + BAD", &rewriter), Err(InvalidRewrite("BAD".to_string(), "a+b".to_string())));
+}
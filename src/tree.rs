@@ -5,6 +5,7 @@ use crate::MathParseErrors;
 use crate::BinaryOp;
 use crate::UnaryOp;
 use crate::rpn_stack_manipulation::*;
+use std::fmt;
 
 fn compute_unary(num: Tree, op: UnaryOp) -> Result<Tree, MathParseErrors> {
     let boxed = Box::new(num);
@@ -17,6 +18,10 @@ fn compute_binary(num_1: Tree, num_2: Tree, op: BinaryOp) -> Result<Tree, MathPa
     Ok(Binary(op, boxed_1, boxed_2))
 }
 
+fn compute_call(name: &str, args: Vec<Tree>) -> Result<Tree, MathParseErrors> {
+    Ok(Call(name.to_string(), args))
+}
+
 /// Reads a line of math that contains only values, operations, and parenthesis
 /// and returns a computed result.
 pub fn parse_to_tree(rpn_actions: &[RPN]) -> Result<Tree, MathParseErrors> {
@@ -24,7 +29,153 @@ pub fn parse_to_tree(rpn_actions: &[RPN]) -> Result<Tree, MathParseErrors> {
         Ok(Name(name.to_string()))
     };
 
-    exec_rpn(rpn_actions, &Box::new(compute_name), &compute_unary, &compute_binary)
+    exec_rpn(rpn_actions, &Box::new(compute_name), &compute_unary, &compute_binary, &compute_call)
+}
+
+/// Convert RPN actions that can describe a list of expressions into a tree for
+/// each element of the list.
+pub fn parse_to_trees(rpn_actions: &[RPN]) -> Result<Vec<Tree>, MathParseErrors> {
+    let compute_name = | name: &str | -> Result<Tree, MathParseErrors> {
+        Ok(Name(name.to_string()))
+    };
+
+    exec_rpn_stack(rpn_actions, &Box::new(compute_name), &compute_unary, &compute_binary, &compute_call)
+}
+
+/* ----------------------------- Iterative traits ---------------------------- */
+
+// The derived implementations of the traits of `Tree` would be recursive and
+// could overflow the stack on deep trees, so they are written with an explicit
+// stack instead.
+
+impl Clone for Tree {
+    fn clone(&self) -> Self {
+        enum Step<'a> {
+            Visit(&'a Tree),
+            Build(&'a Tree),
+        } use Step::*;
+
+        let mut steps = vec![Visit(self)];
+        let mut built = Vec::<Tree>::new();
+        while let Some(step) = steps.pop() {
+            match step {
+                Visit(Name(name)) => built.push(Name(name.clone())),
+                Visit(node) => {
+                    steps.push(Build(node));
+                    match node {
+                        Name(_) => {},
+                        Unary(_, next) => steps.push(Visit(next)),
+                        Binary(_, next_1, next_2) => {
+                            steps.push(Visit(next_2));
+                            steps.push(Visit(next_1));
+                        },
+                        Call(_, args) => steps.extend(args.iter().rev().map(Visit)),
+                    }
+                },
+                Build(node) => {
+                    let tree = match node {
+                        Name(name) => Name(name.clone()),
+                        Unary(op, _) => Unary(*op, Box::new(built.pop().unwrap_or(Name(String::new())))),
+                        Binary(op, _, _) => {
+                            let next_2 = built.pop().unwrap_or(Name(String::new()));
+                            let next_1 = built.pop().unwrap_or(Name(String::new()));
+                            Binary(*op, Box::new(next_1), Box::new(next_2))
+                        },
+                        Call(name, args) => Call(name.clone(), built.split_off(built.len() - args.len())),
+                    };
+                    built.push(tree);
+                },
+            }
+        }
+        built.pop().unwrap_or(Name(String::new()))
+    }
+}
+
+impl PartialEq for Tree {
+    fn eq(&self, other: &Self) -> bool {
+        let mut to_compare = vec![(self, other)];
+        while let Some(pair) = to_compare.pop() {
+            match pair {
+                (Name(a), Name(b)) if a == b => {},
+                (Unary(op_a, a), Unary(op_b, b)) if op_a == op_b => to_compare.push((a, b)),
+                (Binary(op_a, a_1, a_2), Binary(op_b, b_1, b_2)) if op_a == op_b => {
+                    to_compare.push((a_2, b_2));
+                    to_compare.push((a_1, b_1));
+                },
+                (Call(name_a, args_a), Call(name_b, args_b)) if name_a == name_b && args_a.len() == args_b.len() => {
+                    to_compare.extend(args_a.iter().zip(args_b).rev());
+                },
+                _ => return false,
+            }
+        }
+        true
+    }
+}
+
+impl Drop for Tree {
+    /// Move the subtrees out of the tree being dropped so that none of them
+    /// is dropped with subtrees still in it.
+    fn drop(&mut self) {
+        fn take_children(tree: &mut Tree, to_drop: &mut Vec<Tree>) {
+            match tree {
+                Name(_) => {},
+                Unary(_, next) => to_drop.push(std::mem::replace(&mut **next, Name(String::new()))),
+                Binary(_, next_1, next_2) => {
+                    to_drop.push(std::mem::replace(&mut **next_1, Name(String::new())));
+                    to_drop.push(std::mem::replace(&mut **next_2, Name(String::new())));
+                },
+                Call(_, args) => to_drop.append(args),
+            }
+        }
+
+        let mut to_drop = vec![];
+        take_children(self, &mut to_drop);
+        while let Some(mut tree) = to_drop.pop() {
+            take_children(&mut tree, &mut to_drop);
+        }
+    }
+}
+
+impl fmt::Debug for Tree {
+    /// Show the tree as the derived implementation would, without the
+    /// alternate pretty-printed form.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        enum Step<'a> {
+            S(&'static str),
+            T(&'a Tree),
+        } use Step::*;
+
+        let mut steps = vec![T(self)];
+        while let Some(step) = steps.pop() {
+            match step {
+                S(s) => write!(f, "{s}")?,
+                T(Name(name)) => write!(f, "Name({name:?})")?,
+                T(Unary(op, next)) => {
+                    write!(f, "Unary({op:?}, ")?;
+                    steps.push(S(")"));
+                    steps.push(T(next));
+                },
+                T(Binary(op, next_1, next_2)) => {
+                    write!(f, "Binary({op:?}, ")?;
+                    steps.push(S(")"));
+                    steps.push(T(next_2));
+                    steps.push(S(", "));
+                    steps.push(T(next_1));
+                },
+                T(Call(name, args)) => {
+                    write!(f, "Call({name:?}, [")?;
+                    steps.push(S("])"));
+                    for (i, arg) in args.iter().enumerate().rev() {
+                        steps.push(T(arg));
+                        if i != 0 {
+                            steps.push(S(", "));
+                        }
+                    }
+                },
+            }
+        }
+        Ok(())
+    }
 }
 
 /* --------------------------------- Testing -------------------------------- */
@@ -46,3 +197,14 @@ fn test_parse_to_tree() {
                     Box::new(name_t("titi"))))));
 }
 
+
+#[test]
+fn test_tree_traits() {
+    let tree = crate::MathParse::parse("-a * f(1, b) + g()").unwrap().to_tree().unwrap();
+    assert_eq!(format!("{tree:?}"),
+        r#"Binary(Addition, Binary(Multiplication, Unary(Minus, Name("a")), Call("f", [Name("1"), Name("b")])), Call("g", []))"#);
+    assert_eq!(tree.clone(), tree);
+    for other in ["-a * f(1, b) - g()", "-a * f(1) + g()", "-a * f(1, c) + g()", "!a * f(1, b) + g()", "-a * h(1, b) + g()", "a"] {
+        assert_ne!(crate::MathParse::parse(other).unwrap().to_tree().unwrap(), tree, "{other}");
+    }
+}
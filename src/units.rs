@@ -0,0 +1,381 @@
+use crate::MathParseErrors;
+use crate::MathParseErrors::*;
+use crate::RPN;
+use crate::MathParseOptions;
+use crate::BinaryOp;
+use crate::UnaryOp;
+use crate::BinaryOp::*;
+use crate::UnaryOp::*;
+use crate::rpn_stack_manipulation::*;
+use crate::solve::Number;
+use crate::solve::{number_from_string, constant_value, compute_binary_with};
+use std::fmt;
+
+/* ------------------------------- Dimensions ------------------------------- */
+
+/// Exponents of the base SI dimensions of a quantity. A dimensionless number
+/// have all its exponents at 0.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
+pub struct Dimensions {
+    /// Exponent of the length, in meters.
+    pub length: i8,
+    /// Exponent of the time, in seconds.
+    pub time: i8,
+    /// Exponent of the mass, in kilograms.
+    pub mass: i8,
+}
+
+impl Dimensions {
+    const LENGTH: Dimensions = Dimensions{length: 1, time: 0, mass: 0};
+    const TIME:   Dimensions = Dimensions{length: 0, time: 1, mass: 0};
+    const MASS:   Dimensions = Dimensions{length: 0, time: 0, mass: 1};
+
+    /// Return true if all the exponents are 0.
+    pub fn is_dimensionless(&self) -> bool {
+        *self == Dimensions::default()
+    }
+
+    /// Dimensions of a product, or `None` if the exponents don't fit in an
+    /// `i8`.
+    fn mul(self, other: Self) -> Option<Self> {
+        Some(Dimensions {
+            length: self.length.checked_add(other.length)?,
+            time:   self.time.checked_add(other.time)?,
+            mass:   self.mass.checked_add(other.mass)?,
+        })
+    }
+
+    /// Dimensions of a quotient, or `None` if the exponents don't fit in an
+    /// `i8`.
+    fn div(self, other: Self) -> Option<Self> {
+        Some(Dimensions {
+            length: self.length.checked_sub(other.length)?,
+            time:   self.time.checked_sub(other.time)?,
+            mass:   self.mass.checked_sub(other.mass)?,
+        })
+    }
+
+    /// Square root of the dimensions, or `None` if an exponent is odd.
+    fn sqrt(self) -> Option<Self> {
+        if self.length % 2 != 0 || self.time % 2 != 0 || self.mass % 2 != 0 {
+            return None;
+        }
+        Some(Dimensions {
+            length: self.length / 2,
+            time:   self.time   / 2,
+            mass:   self.mass   / 2,
+        })
+    }
+
+    /// Dimensions raised to an integer power, or `None` if the exponents
+    /// don't fit in an `i8`.
+    fn pow(self, exponent: i8) -> Option<Self> {
+        Some(Dimensions {
+            length: self.length.checked_mul(exponent)?,
+            time:   self.time.checked_mul(exponent)?,
+            mass:   self.mass.checked_mul(exponent)?,
+        })
+    }
+}
+
+impl fmt::Display for Dimensions {
+    /// Show the dimensions as a list of SI base units, such as `m s^-1`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut first = true;
+        for (unit, exponent) in [("m", self.length), ("kg", self.mass), ("s", self.time)] {
+            if exponent == 0 {
+                continue;
+            }
+            if !first {
+                write!(f, " ")?;
+            }
+            first = false;
+            if exponent == 1 {
+                write!(f, "{unit}")?;
+            } else {
+                write!(f, "{unit}^{exponent}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/* ---------------------------------- Units --------------------------------- */
+
+/// Units that can be written after a number. The factor converts the unit to
+/// the SI base unit of its dimension.
+const UNITS: [(&str, f64, Dimensions); 5] = [
+    ("m",   1.0,    Dimensions::LENGTH),
+    ("s",   1.0,    Dimensions::TIME),
+    ("g",   1.0e-3, Dimensions::MASS),
+    ("min", 60.0,   Dimensions::TIME),
+    ("h",   3600.0, Dimensions::TIME),
+];
+
+/// Prefixes that can be put in front of the `m`, `s`, and `g` units.
+const PREFIXES: [(&str, f64); 11] = [
+    ("G",  1.0e9),
+    ("M",  1.0e6),
+    ("k",  1.0e3),
+    ("h",  1.0e2),
+    ("da", 1.0e1),
+    ("d",  1.0e-1),
+    ("c",  1.0e-2),
+    ("m",  1.0e-3),
+    ("µ",  1.0e-6),
+    ("u",  1.0e-6),
+    ("n",  1.0e-9),
+];
+
+/// Look for a unit with an optional prefix and return its factor and
+/// dimensions.
+fn read_unit(unit: &str) -> Option<(f64, Dimensions)> {
+    for (name, factor, dimensions) in &UNITS {
+        if unit == *name {
+            return Some((*factor, *dimensions));
+        }
+    }
+    for (prefix, prefix_factor) in &PREFIXES {
+        if let Some(base) = unit.strip_prefix(prefix) {
+            for (name, factor, dimensions) in &UNITS[0..3] {
+                if base == *name {
+                    return Some((prefix_factor * factor, *dimensions));
+                }
+            }
+        }
+    }
+    None
+}
+
+/* -------------------------------- Quantities ------------------------------- */
+
+/// A number with its dimensions. The value is always expressed in SI base
+/// units.
+#[derive(Debug, PartialEq, Copy, Clone)]
+struct Quantity {
+    value: f64,
+    dimensions: Dimensions,
+}
+
+impl Quantity {
+    fn dimensionless(value: f64) -> Self {
+        Quantity{value, dimensions: Dimensions::default()}
+    }
+
+    /// Return an error if the two quantities can't be added together.
+    fn err_on_incompatible(self, other: Self) -> Result<(), MathParseErrors> {
+        if self.dimensions == other.dimensions {
+            Ok(())
+        } else {
+            Err(IncompatibleUnits)
+        }
+    }
+}
+
+/// Reads a literal in the form `<number><unit>` or a plain number.
+fn quantity_from_string(s: &str, options: &MathParseOptions) -> Result<Quantity, MathParseErrors> {
+    if let Ok(num) = number_from_string(s, options) {
+        return Ok(Quantity::dimensionless(num.as_f64()));
+    }
+    let unit_start = s.char_indices()
+        .rev()
+        .take_while(|(_, c)| c.is_alphabetic())
+        .last()
+        .map(|(i, _)| i);
+    let (number, unit) = match unit_start {
+        Some(i) if i > 0 => (&s[..i], &s[i..]),
+        _ => {
+            return Err(InvalidNumber(s.to_string()));
+        },
+    };
+    let value = match number_from_string(number, options) {
+        Ok(num) => num.as_f64(),
+        Err(_) => {
+            return Err(InvalidNumber(s.to_string()));
+        },
+    };
+    match read_unit(unit) {
+        Some((factor, dimensions)) => Ok(Quantity{value: value * factor, dimensions}),
+        None => Err(InvalidNumber(s.to_string())),
+    }
+}
+
+/// Reads a name, transforming it with the map if needed.
+fn read_name(name: &str, map: &dyn Fn(&str) -> Option<String>, options: &MathParseOptions) -> Result<Quantity, MathParseErrors> {
+    if let Some(new_name) = map(name) {
+        let parsed = crate::MathParse::parse_with_options(&new_name, options)?;
+        quantity_solve(&parsed.internal.to_rpn(), &|_| None, options)
+    } else if let Some(num) = constant_value(name, options) {
+        Ok(Quantity::dimensionless(num.as_f64()))
+    } else {
+        quantity_from_string(name, options)
+    }
+}
+
+fn compute_unary(num: Quantity, op: UnaryOp) -> Result<Quantity, MathParseErrors> {
+    match op {
+        UnaryOp::Not => Err(BinaryOpOnFloat(num.value, '~')),
+        LogicalNot   => Ok(Quantity::dimensionless(if num.value == 0.0 { 1.0 } else { 0.0 })),
+        Minus        => Ok(Quantity{value: -num.value, dimensions: num.dimensions}),
+        Plus         => Ok(num),
+        Percent      => Ok(Quantity{value: num.value / 100.0, dimensions: num.dimensions}),
+        Sqrt         => {
+            if num.value < 0.0 {
+                return Err(UnexpectedNegative);
+            }
+            let dimensions = num.dimensions.sqrt().ok_or(IncompatibleUnits)?;
+            Ok(Quantity{value: num.value.sqrt(), dimensions})
+        },
+    }
+}
+
+fn compute_binary(num_1: Quantity, num_2: Quantity, op: BinaryOp, options: &MathParseOptions) -> Result<Quantity, MathParseErrors> {
+    let bitwise_error = |c: char| Err(BinaryOpOnFloat(num_1.value, c));
+    match op {
+        Exponent => {
+            // Only dimensionless quantities and integer powers of
+            // quantities with dimensions make sense.
+            if !num_2.dimensions.is_dimensionless() {
+                return Err(IncompatibleUnits);
+            }
+            let dimensions = if num_1.dimensions.is_dimensionless() {
+                num_1.dimensions
+            } else if num_2.value.fract() != 0.0 {
+                return Err(IncompatibleUnits);
+            } else if (f64::from(i8::MIN)..=f64::from(i8::MAX)).contains(&num_2.value) {
+                num_1.dimensions.pow(num_2.value as i8).ok_or(DimensionOverflow)?
+            } else {
+                return Err(DimensionOverflow);
+            };
+            let value = compute_binary_with(Number::Float(num_1.value), Number::Float(num_2.value), Exponent, options)?;
+            Ok(Quantity{value: value.as_f64(), dimensions})
+        },
+        Addition => {
+            num_1.err_on_incompatible(num_2)?;
+            Ok(Quantity{value: num_1.value + num_2.value, dimensions: num_1.dimensions})
+        },
+        Subtraction => {
+            num_1.err_on_incompatible(num_2)?;
+            Ok(Quantity{value: num_1.value - num_2.value, dimensions: num_1.dimensions})
+        },
+        Multiplication => Ok(Quantity{
+            value: num_1.value * num_2.value,
+            dimensions: num_1.dimensions.mul(num_2.dimensions).ok_or(DimensionOverflow)?,
+        }),
+        Division => {
+            if num_2.value == 0.0 {
+                return Err(UnexpectedZero);
+            }
+            Ok(Quantity{
+                value: num_1.value / num_2.value,
+                dimensions: num_1.dimensions.div(num_2.dimensions).ok_or(DimensionOverflow)?,
+            })
+        },
+        Reminder => {
+            num_1.err_on_incompatible(num_2)?;
+            let value = compute_binary_with(Number::Float(num_1.value), Number::Float(num_2.value), Reminder, options)?;
+            Ok(Quantity{value: value.as_f64(), dimensions: num_1.dimensions})
+        },
+        IntegerDivision => {
+            num_1.err_on_incompatible(num_2)?;
+            let value = Number::Float(num_1.value).integer_div_with(Number::Float(num_2.value), options.floor_division)?;
+            Ok(Quantity::dimensionless(value.as_f64()))
+        },
+        LessThan | LessOrEqual | GreaterThan | GreaterOrEqual | Equal | NotEqual => {
+            num_1.err_on_incompatible(num_2)?;
+            let value = crate::solve::compute_binary(Number::Float(num_1.value), Number::Float(num_2.value), op)?;
+            Ok(Quantity::dimensionless(value.as_f64()))
+        },
+        LogicalAnd | LogicalOr => {
+            let value = crate::solve::compute_binary(Number::Float(num_1.value), Number::Float(num_2.value), op)?;
+            Ok(Quantity::dimensionless(value.as_f64()))
+        },
+        ShiftLeft  => bitwise_error('≪'),
+        ShiftRight => bitwise_error('≫'),
+        BitwiseAnd => bitwise_error('&'),
+        BitwiseOr  => bitwise_error('|'),
+        BitwiseXor => bitwise_error('^'),
+    }
+}
+
+fn compute_call(name: &str, _args: Vec<Quantity>) -> Result<Quantity, MathParseErrors> {
+    Err(UnknownFunction(name.to_string()))
+}
+
+fn quantity_solve(rpn_actions: &[RPN], map: &dyn Fn(&str) -> Option<String>, options: &MathParseOptions) -> Result<Quantity, MathParseErrors> {
+    let compute_name = | name: &str | -> Result<Quantity, MathParseErrors> {
+        read_name(name, map, options)
+    };
+    let compute_binary = | num_1: Quantity, num_2: Quantity, op: BinaryOp | -> Result<Quantity, MathParseErrors> {
+        compute_binary(num_1, num_2, op, options)
+    };
+
+    exec_rpn(rpn_actions, &Box::new(compute_name), &compute_unary, &compute_binary, &compute_call)
+}
+
+/// Solve a list of RPN actions where numbers can be followed by units.
+pub fn math_solve_quantity(rpn_actions: &[RPN], map: &dyn Fn(&str) -> Option<String>, options: &MathParseOptions) -> Result<(f64, Dimensions), MathParseErrors> {
+    let quantity = quantity_solve(rpn_actions, map, options)?;
+    Ok((quantity.value, quantity.dimensions))
+}
+
+/* --------------------------------- Testing -------------------------------- */
+
+#[cfg(test)]
+fn solve(expression: &str) -> Result<(f64, Dimensions), MathParseErrors> {
+    crate::MathParse::parse(expression)?.solve_quantity(None)
+}
+
+#[test]
+fn test_read_units() {
+    let options = MathParseOptions::default();
+    assert_eq!(quantity_from_string("3", &options),    Ok(Quantity::dimensionless(3.0)));
+    assert_eq!(quantity_from_string("3m", &options),   Ok(Quantity{value: 3.0, dimensions: Dimensions::LENGTH}));
+    assert_eq!(quantity_from_string("2km", &options),  Ok(Quantity{value: 2000.0, dimensions: Dimensions::LENGTH}));
+    assert_eq!(quantity_from_string("5kg", &options),  Ok(Quantity{value: 5.0, dimensions: Dimensions::MASS}));
+    assert_eq!(quantity_from_string("2min", &options), Ok(Quantity{value: 120.0, dimensions: Dimensions::TIME}));
+    assert_eq!(quantity_from_string("3xyz", &options), Err(InvalidNumber("3xyz".to_string())));
+    assert_eq!(quantity_from_string("m", &options),    Err(InvalidNumber("m".to_string())));
+}
+
+#[test]
+fn test_solve_quantity() {
+    let (value, dimensions) = solve("3m + 20cm").unwrap();
+    assert!((value - 3.2).abs() < 1e-12);
+    assert_eq!(dimensions, Dimensions::LENGTH);
+
+    assert_eq!(solve("3m + 2s"), Err(IncompatibleUnits));
+    assert_eq!(solve("10m / 2s"), Ok((5.0, Dimensions{length: 1, time: -1, mass: 0})));
+    assert_eq!(solve("6m / 3m"), Ok((2.0, Dimensions::default())));
+    assert_eq!(solve("3m & 1m"), Err(BinaryOpOnFloat(3.0, '&')));
+    assert_eq!(solve("(3m) ** 2"), Ok((9.0, Dimensions{length: 2, time: 0, mass: 0})));
+    assert_eq!(solve("2s ** -1"), Ok((0.5, Dimensions{length: 0, time: -1, mass: 0})));
+    assert_eq!(solve("4 ** 0.5"), Ok((2.0, Dimensions::default())));
+    assert_eq!(solve("4m ** 0.5"), Err(IncompatibleUnits));
+    assert_eq!(solve("2 ** 3m"), Err(IncompatibleUnits));
+    assert_eq!(solve("√(9m * 4m)"), Ok((6.0, Dimensions::LENGTH)));
+    assert_eq!(solve("√4s"), Err(IncompatibleUnits));
+    assert_eq!(solve("√-4"), Err(UnexpectedNegative));
+    assert_eq!(format!("{}", solve("10m / 2s").unwrap().1), "m s^-1");
+
+    // The exponents of the dimensions are between -128 and 127.
+    let meters = |count: usize| vec!["1m"; count].join(" * ");
+    assert_eq!(solve(&meters(127)).map(|x| x.1), Ok(Dimensions{length: 127, time: 0, mass: 0}));
+    assert_eq!(solve(&meters(128)), Err(DimensionOverflow));
+    assert_eq!(solve(&format!("1 / ({}) / 1m", meters(127))).map(|x| x.1), Ok(Dimensions{length: -128, time: 0, mass: 0}));
+    assert_eq!(solve(&format!("1 / ({}) / 1m / 1m", meters(127))), Err(DimensionOverflow));
+    assert_eq!(solve("1m ** 127").map(|x| x.1), Ok(Dimensions{length: 127, time: 0, mass: 0}));
+    assert_eq!(solve("1m ** -128").map(|x| x.1), Ok(Dimensions{length: -128, time: 0, mass: 0}));
+    assert_eq!(solve("1m ** 128"), Err(DimensionOverflow));
+    assert_eq!(solve("(1m * 1m) ** 64"), Err(DimensionOverflow));
+    assert_eq!(solve("1m ** 1e300"), Err(DimensionOverflow));
+}
+
+#[test]
+fn test_quantity_variables() {
+    let variables = std::collections::HashMap::from([
+        ("width".to_string(), "2m".to_string()),
+    ]);
+    let parsed = crate::MathParse::parse("width * 50cm").unwrap();
+    assert_eq!(parsed.solve_quantity(Some(&variables)), Ok((1.0, Dimensions{length: 2, time: 0, mass: 0})));
+}
@@ -8,5 +8,45 @@ fn test_huge_int() {
     assert_eq!(math_parse::MathParse::parse(HUGE_INT_EXPR).unwrap().solve_int(None).unwrap(), -289);
 }
 
+/// Go through the whole public surface with a deep expression, none of the
+/// steps should recurse on its depth.
+fn check_deep(expression: &str) {
+    use math_parse::*;
+    use std::collections::HashMap;
+
+    let parsed = MathParse::parse(expression).unwrap();
+    let tree = parsed.to_tree().unwrap();
+    let copy = tree.clone();
+    assert!(copy == tree);
+    let other = MathParse::parse(&format!("{expression}+1")).unwrap().to_tree().unwrap();
+    assert!(other != tree);
+
+    // Parsing back the nested parenthesis of the formatted tree would be too
+    // slow, so the formatted forms are only checked to be complete.
+    assert_eq!(format!("{tree}"), tree.to_expression_string());
+    assert!(tree.to_expression_string().len() >= expression.len());
+    assert!(format!("{tree:?}").len() > expression.len());
+    assert!(parsed.solve_float(None).is_ok());
+
+    let rpn = rpn_slice_to_string(&parsed.to_rpn().unwrap());
+    assert_eq!(MathParse::parse_rpn(&rpn).unwrap().to_tree(), Ok(copy));
+    let (renamed, _) = tree.rename_variables(&HashMap::from([("1".to_string(), "2".to_string())]));
+    assert!(renamed == tree); // Numbers are not renamed.
+    let (_, spans) = parsed.to_tree_with_spans().unwrap();
+    assert_eq!(spans.get(&[]), Some(0..expression.len()));
+    assert_eq!(spans.clone(), spans);
+
+    drop((parsed, tree, other, renamed, spans));
+}
+
+#[test]
+fn test_deep_trees() {
+    // Left-nested binary operators of the lowest precedence, which are
+    // parsed quickly, and right-nested unary ones. 20000 levels are enough
+    // to overflow the stack of a recursive implementation.
+    check_deep(&vec!["1"; 20_000].join("||"));
+    check_deep(&("-!".repeat(10_000) + "1"));
+}
+
 const HUGE_INT_EXPR: &str = "4%20-3%20-6+3%20-6//1+7//1+9//1+2-4-2-2//1+9%20-6//1+10+10+2+9//1+3-6%20-11-8//1+8+3-9+6+6-7%20-8+11+7+2-11-8+7%20-6%20-8//1+2-11%20-3%20-10%20-6%20-7+9+2+10-5//1+6-9-9%20-10-10-2%20-4+11%20-10+3%20-9//1+6//1+8%20-11+6%20-3+2+10//1+9%20-4//1+9+11+11%20-7%20-2-9-8+8-8-4%20-5%20-9+6//1+2%20-7+11%20-6+9//1+3-11//1+6%20-9%20-2%20-2-6%20-2+8//1+5%20-3+9//1+2//1+8-9-7//1+9-7-7+5+8//1+6+11+10//1+6+11%20-9-11//1+11+11%20-4-3-8//1+11//1+3+5%20-8-2-5//1+4//1+11%20-10+11%20-9-3%20-10+5+9//1+7-2%20-7%20-2//1+2-2%20-4//1+8-6%20-8%20-2//1+10+11//1+2//1+2%20-5%20-7%20-4+3-11//1+10//1+9-11+6+3//1+5+5-2%20-5%20-2%20-7%20-5%20-4%20-5-7//1+8-9//1+5%20-8%20-2-8%20-6+4%20-2%20-4-11%20-4-8//1+5-4+7//1+7-11-3%20-9%20-10-7-3+4%20-8-10+5%20-8-10+3+8-4//1+8-7%20-11+11%20-2-5-3%20-8+3-3+9-4+11-8-7%20-5-11+9+2//1+7//1+5%20-7-5+3%20-9//1+4+3%20-10%20-11+11%20-5+5//1+4+4//1+5-5%20-10//1+4//1+11//1+8+11-3//1+2%20-11%20-7-4-8+9+9+9//1+5-9%20-2-10-9%20-9+2//1+9-2%20-8//1+8//1+2//1+11//1+8//1+6//1+11%20-3//1+4%20-6+3//1+11+5+3%20-10//1+6%20-3+4-3%20-10+11+6%20-3+9-4//1+4-9+3-7%20-11+6+6+4//1+10%20-6-9-11//1+6//1+9%20-7-7%20-10+3%20-11-5//1+2+5//1+9//1+9+5%20-2+8%20-2-10+3+6-7-6%20-6-8%20-4//1+9-9-10//1+10%20-11+9+10%20-6+5-7-3//1+4//1+2//1+7+4-9+5+9+7+6-4+6+2+6+4-6%20-2%20-9%20-5%20-10//1+6-2%20-7-2%20-6-10%20-5-8+10%20-4-2-10//1+7+2-3%20-3//1+8//1+10%20-4//1+10%20-3//1+9%20-9-2//1+5%20-9//1+9-7%20-3%20-11//1+8%20-5-10%20-11//1+4-8%20-9+8%20-4+11%20-8%20-3%20-3%20-9//1+4+2%20-11+4%20-10//1+11+8//1+4//1+10+7-4%20-2//1+2-5+4%20-4-6%20-5-3-6//1+3//1+2-3//1+6%20-2%20-2+2%20-4%20-9+10%20-3//1+2-5+2%20-8-11+10+11-10//1+2-3-3//1+10+5+6%20-9+10//1+7+8%20-9%20-10//1+10%20-2%20-3//1+6+11+4-2%20-6%20-4//1+6+9%20-2-3//1+10+11+5-7//1+7//1+7-7%20-9//1+5%20-4-11+9-6-11%20-5+7%20-3//1+6-9+5-11%20-3-7%20-8-10-10+8//1+5//1+9+5%20-2//1+11+7+11%20-11-2//1+11-11//1+10%20-3+7%20-8-4-6//1+3+3%20-9+11-7%20-11%20-2-2%20-10//1+8-2//1+6%20-5//1+5+10+3%20-6%20-6+6+8-6-8//1+7-5//1+9//1+2%20-7+6+10//1+6+8+7//1+8+10+5//1+7-2//1+8//1+2-9%20-7+2-6-3//1+10+4+3-8//1+9-2+8-7+3%20-2+2-7+10//1+8+5-9+10%20-10-3-3%20-9//1+4+2-10//1+3+8%20-10+5-8+8-4//1+2-9//1+8//1+6+7+6//1+7+9//1+8%20-6+6-3-6//1+5-5//1+10+5%20-3//1+6-8%20-5-4//1+6%20-11+4//1+9//1+4%20-6//1+6-11-11+3-11%20-4//1+7%20-8-11%20-6-6%20-2-11+11+8-11-2-7%20-11+4%20-5+7//1+6-5-5//1+4//1+7-6-7-5//1+5-7+8-9%20-6-8-7//1+9%20-6-3+5%20-2-6//1+10+2+5//1+11-9-4//1+4%20-6-6+7//1+8+9%20-11+6%20-8-10%20-4%20-9-10//1+5//1+8//1+9+11+6-8+7+3//1+9//1+10//1+6-7%20-6%20-2-7-11//1+8%20-6-7%20-6-6-9//1+4%20-6+6+4%20-8+7//1+6+9-3%20-6+8%20-9+2+11-10-10%20-2//1+4-6//1+7//1+7-8-5-6+5-3%20-10//1+8-4//1+8+9+10-4-2+5-3-4-10//1+2%20-10+11//1+8//1+2+11-2-4//1+7-4%20-9-2-9+9//1+11//1+3//1+4+2-3+6+4%20-3%20-6%20-8+6//1+5-5-9%20-8%20-5+11-4-2//1+2-11+2+2//1+7-11+6-4-2-3//1+3-3-10-6-8+11%20-7%20-11+9-6+6+5-6%20-11+10+2//1+10//1+10+10//1+3-10-9-2%20-6%20-5%20-9-11+8-3-7%20-3%20-6%20-5+4%20-5//1+8+6%20-10-11+2-5-8+10+2//1+6%20-11%20-3+2%20-4+7//1+7+7//1+5%20-9+6+3+9%20-9%20-3-6//1+8-8%20-4-5-10+7-5+8%20-5//1+7+5+5%20-4//1+3-8//1+9-2%20-4//1+8+5-3-6%20-4+11//1+4-9%20-8//1+11//1+3-7//1+8//1+6+6//1+4%20-6%20-4//1+5-8+8-11%20-9%20-8//1+11//1+2+3-8-2+10//1+3%20-6%20-5%20-10-2//1+5//1+11-7-7%20-7+9//1+8//1+2-4-8//1+5%20-6+10-11%20-4//1+7//1+11//1+11-8//1+5%20-10-3+5+9-9//1+5%20-8%20-3//1+7//1+2-3+4-7%20-11-3-5//1+5-10-9//1+7%20-3+5%20-3+11%20-7%20-6+7//1+4%20-10+7//1+4//1+9+4-4+11//1+11-9%20-6//1+5//1+7+6+5+4+8%20-2%20-6-7//1+11+6+6%20-9-11-2+2+8-7//1+4-2+8-8+10+4-11+2//1+9//1+5%20-9//1+5%20-9%20-8%20-5+8+9%20-11%20-6-5-11+7%20-7//1+4-6%20-10+9//1+3+10-4%20-7+8+3%20-7%20-10-2%20-10%20-5-4+6-11//1+3-6+4+9-6+2//1+9//1+11-5%20-4+11-6//1+7%20-5+10%20-6//1+7//1+10//1+8//1+9-8%20-11%20-6+3+8%20-10//1+11//1+5%20-11-9//1+6%20-8//1+3-4//1+5-8+9+4-5//1+6+9-11%20-6+8//1+5%20-5+9-5//1+7+7+5//1+9-4%20-3+7-11//1+9//1+4//1+5%20-11-3+10//1+8-8%20-10%20-2+11%20-2//1+10+3+7+11//1+6%20-9+9+3//1+8//1+8+7//1+8%20-6-8%20-9%20-11-7%20-5//1+8+5%20-11%20-4+3%20-10%20-2+4-10//1+10//1+8-2//1+10-7-8//1+10//1+10+4-4//1+3//1+3+4//1+9%20-6+7-6//1+10%20-2//1+7-5//1+7//1+2-7//1+9//1+9%20-8-10-5%20-5+9%20-10+4-5//1+10//1+8-4%20-9%20-11%20-5+4-6%20-6-6-5//1+11%20-4//1+9+5%20-8-6%20-11+4+10%20-2+4%20-8+3//1+10-10-9%20-10-3-10-8+7-8//1+9+10+6+5%20-10+9//1+8%20-3//1+8//1+9+9//1+6+10+2+6+4%20-6-9+5-5//1+10//1+2+7-7//1+11%20-3//1+6//1+8%20-9//1+2-2-11//1+2//1+9//1+3%20-7-10+6%20-10+9//1+8%20-10+4+9%20-5%20-6+10//1+8+4//1+10//1+7%20-6-9+4%20-4%20-7+4%20-4%20-5%20-8-2+11%20-2//1+3+7-2//1+7+11-9+8+11%20-11%20-2%20-11%20-5-7+5-11-3-6+3//1+4-3+3-11-2-11+4//1+9+3+7-3%20-2-7+3%20-8%20-4-6-3//1+4+2-4%20-10+5%20-5+5-2-2%20-3%20-8//1+6//1+11-3-6-5+11%20-2%20-6+9-4-4//1+4-5-6-11+10+3-3+9%20-9+5%20-6//1+11+2+8-3+4%20-4+6+6%20-2%20-4//1+7//1+9+5//1+4%20-5-7//1+5-11-9-7//1+7%20-4//1+7-6//1+4-9//1+3+9%20-10-5%20-8+9+11+2-6//1+9+11//1+4%20-3-7%20-8//1+10+10//1+11+4//1+8//1+3%20-3-6//1+3//1+2+6//1+7+10%20-11//1+4//1+6%20-11//1+8+11-7%20-7//1+2+9//1+6//1+3-7-8-8%20-9-5+4-2%20-2+8%20-11//1+6%20-9+11+9%20-10+3+7+7+6+2-2-4%20-6%20-11+2%20-4//1+2+11%20-9//1+6//1+6-7%20-4%20-5//1+11+8//1+4+3+11+2%20-8//1+5+7%20-9%20-7+2%20-4//1+3//1+9+4//1+10//1+8-9-9-10-6+10%20-9+5-4-11%20-3+2//1+2-10-8+8%20-5+5-6%20-4//1+5//1+6//1+9%20-3%20-6-11%20-3-11-7+9-7+2//1+10%20-7-11%20-2+9+4+4-7-4-3%20-4%20-3-2//1+9+3-11//1+11+2%20-8%20-4+10-9-7//1+3%20-5-3-5//1+5-10-4//1+2%20-5-6+6//1+8-5-7-5//1+3+8//1+4+4+8%20-4-3%20-8-8-8-6//1+2%20-3-2+2//1+2//1+4//1+6//1+3%20-6+2-10+9-11-2+9-2//1+8%20-6+3//1+5+11%20-9%20-8+10+9%20-8//1+5%20-11%20-5%20-3-11-3-8+10//1+9+11%20-7//1+8%20-2//1+6%20-9//1+3-5%20-4+6%20-3%20-9+6-2//1+11%20-6+6%20-8+6+7-2//1+11+11%20-7//1+10-8-3-3-11-4//1+9//1+7//1+10//1+3+9+10//1+3-3%20-2-2+11-10%20-6//1+6//1+4+6-3-7//1+6-5-3+9+10+8//1+10%20-4+3-4%20-11-7+6//1+8+4-4+4//1+10//1+8-11+5+7-9+2-5//1+6%20-10-10+11%20-5-11-8+8%20-4-9-3%20-4//1+3-10+7+9//1+8%20-9-10%20-6+9//1+8-4+4//1+4%20-9//1+3-2//1+11-6//1+7%20-11//1+9//1+9%20-5-7%20-11%20-11//1+4%20-6//1+9-6+10%20-2//1+2%20-11-11+9-6%20-3+7//1+9%20-9-7//1+2//1+6//1+7+11//1+3//1+2//1+6%20-5//1+5%20-9%20-7-9+3//1+6+6+3%20-11-11+2%20-6+4-5+3//1+8-9//1+10//1+8+7%20-5%20-8+9-10//1+5-4//1+6-9+4%20-2//1+3//1+9+10//1+5+5%20-5%20-3-9//1+10//1+2-3+7%20-6%20-6-3%20-9%20-8+10+6%20-6-9%20-7+6-10-7//1+5//1+6%20-6-6%20-6%20-3//1+5+10-11%20-3-3-10+2+9//1+4//1+10%20-4-3+3%20-8+6%20-8-3-8+6//1+11%20-3-9+3-4+10-6-6//1+5-11+3%20-6%20-10%20-5%20-10//1+4//1+5//1+4//1+5%20-7-6+8%20-5-9//1+3-9//1+4+9+7+11-2%20-11//1+2-7-2-5-2//1+2-2+10//1+10+6%20-9+4-5%20-7//1+2+10//1+5+10+3//1+8-10-10-3%20-6-2-10-6//1+5%20-6+2+9+6%20-10//1+5-3//1+10%20-5-7%20-7+3%20-9-6+10//1+8%20-6%20-9%20-6-3+2-2//1+4//1+2-10+3%20-9%20-5+10//1+2%20-9-5+2%20-6%20-10-8-2%20-9-10+11+9-9+5+6+4//1+4%20-3//1+3//1+2%20-2%20-8+11%20-9+3-8+3-10%20-6+8//1+11+4%20-9//1+4+8-4//1+11-8-8-11%20-9%20-3%20-9-3//1+9//1+10-7//1+3%20-10//1+8%20-8//1+11//1+4%20-5+11%20-3+11%20-6+9%20-4%20-2%20-3%20-4%20-9+6%20-11//1+8-3//1+3+7-8%20-5-4%20-7+4%20-4+6%20-8//1+10//1+9//1+6//1+8-11-7//1+8-5-4//1+8//1+7%20-5-11//1+2%20-7//1+10+8%20-8-9%20-5-4%20-4-8-8-9//1+11%20-4+10%20-2+8+11-5//1+8+3%20-3//1+11%20-7-9-2//1+6-4//1+11+9//1+2-3%20-7-10+5%20-6//1+5+3-11+5//1+3//1+6+7+9-10//1+8-8%20-11+3%20-2%20-9-3%20-7+8%20-8//1+10%20-5%20-3+6+8//1+11%20-8//1+8+5%20-8-6-3-7-7-7-3%20-7-3//1+9+9%20-7+7//1+3//1+8//1+9+4%20-2//1+8-7%20-11-4-4//1+7-8//1+8+9//1+3+7//1+11%20-11-5-7+4+10%20-5+4//1+8-6%20-4-10-3-3%20-4%20-8%20-5-2//1+8-3+8%20-4//1+3%20-6//1+10//1+8//1+4+11//1+6+7-8+3-11//1+2//1+7//1+9-11+11+4%20-8//1+8%20-4%20-10%20-7-9%20-3//1+5+3%20-10%20-10-8-4%20-8%20-9-4%20-2%20-3//1+4+11//1+8-6%20-3-4%20-11+5//1+4-9-11+11-8-9-7//1+10%20-4//1+10+10%20-10%20-8-11//1+3//1+4//1+11//1+8//1+2//1+8%20-7+9//1+11%20-4%20-11%20-8//1+11//1+6-7//1+4+8%20-6%20-3+10//1+2-6-4//1+7%20-7%20-7-5//1+3+5-4%20-3//1+11-3-7-6%20-8-6//1+7-7%20-11//1+2%20-5-8+6%20-5+4%20-7%20-9+3//1+5%20-11+8-10+10+2%20-8-8//1+9%20-5//1+5+11+10+3//1+11%20-9+3//1+3%20-7+11%20-7+3//1+6-5-9%20-2+11-11//1+10%20-7%20-2+10//1+2//1+11%20-6%20-5+7//1+3//1+9+7//1+8-6%20-4-9-9//1+10+6-8%20-11//1+6-11+4-3%20-3//1+3%20-11//1+9+7%20-9//1+8//1+4+5+4+4-4+3%20-8//1+11%20-7-3-2-8%20-7//1+4+10-7+5+3%20-2%20-7+7-10//1+5%20-5//1+5%20-9%20-2+11-8-6//1+8+2%20-2-8-5%20-11//1+6%20-3%20-8-9+9-2%20-3+8+6-2//1+5+7+11+8-6//1+8%20-5//1+11//1+3-2-4-7+6+6//1+8%20-11-5+3%20-11+6-5+2%20-11//1+11+9-11+7-9%20-9+9+11//1+7+11+10//1+3+2-4-11-2-5+7%20-8+8//1+6-4+7//1+3%20-6%20-6//1+8//1+3%20-3-11-5-2%20-10+11//1+10-11+8+6+8-10-10+4-6//1+2+3-2+10%20-10//1+11+8-5+10+4-10//1+2-8-3//1+4-7//1+11-3+8+2%20-5-3+8%20-4-4//1+11%20-3%20-6//1+5+6//1+8+3//1+10%20-5+4-5//1+8-8%20-7%20-5+8%20-2+7+10%20-3%20-8+2//1+3-2//1+11+3//1+3%20-10-10-5+5+9%20-6+10//1+8+2-4+11-11+7//1+2//1+6%20-10%20-3%20-8-3%20-9-11%20-7//1+2%20-3//1+4//1+11+2//1+4%20-4+8+7-3-11+11-3//1+3%20-11//1+8%20-11//1+11//1+9//1+8-11-2//1+11//1+7-10-2-6%20-8//1+8+7%20-9//1+10-5%20-8%20-6+7//1+9%20-7-11+4-4-4-8-9-3+7-9-6+6%20-3//1+4%20-3//1+3//1+4+2%20-10%20-10//1+3-10%20-8%20-8+3//1+2%20-8-8%20-9+2-5//1+10+6+2+2%20-7+9+4//1+3%20-7-10+4//1+8%20-5//1+6-5-10-8//1+8+2%20-7%20-5+5%20-8//1+7//1+10+3-4+2-2+9//1+10+5%20-9+9//1+8//1+7+11%20-7%20-9+9%20-2//1+6%20-11//1+9+10%20-6//1+5//1+6//1+8%20-6%20-2+6-3-11%20-8+8+3%20-9-3-4%20-10+3//1+11%20-10-3%20-11//1+9%20-11%20-10//1+2%20-9+8//1+9//1+9-11+11+9//1+10-4-6+9-2+10%20-2+8+9//1+4%20-2//1+9%20-10-3//1+6+3-2-11//1+2-4-5+10-5%20-11//1+2%20-4+7-9%20-6+5-11%20-5%20-11%20-9//1+4%20-8+11//1+7+2+2//1+8+6+3//1+10-5%20-4%20-4//1+6+8+6%20-8%20-10+9-6-11//1+11-7-2-2//1+10//1+4-4%20-10-5//1+8+3+9+11//1+8+4//1+8-6%20-5-7-4//1+2-5+8-10-2%20-2//1+11+2%20-7//1+8//1+8+11//1+8%20-2+4+8%20-2//1+7//1+9-9//1+11%20-9//1+11%20-5+5%20-11-11+6-8-5+3%20-7%20-11%20-8-9%20-2//1+6+3%20-4%20-10%20-2+5//1+11-3%20-3%20-8//1+4//1+3-3-6+4//1+8+4+9-9%20-2-8-10+6+11//1+9+2-11+2//1+2//1+2+4-7+10%20-7%20-3-4%20-7//1+7//1+7//1+9+4+10%20-4%20-9%20-8%20-8+5+5%20-6-3%20-11+3-8//1+5-4//1+5+9//1+10-4+4-6%20-6+4+10//1+8-8-8+4//1+5//1+9-6%20-8+4%20-11%20-4%20-2-7%20-3//1+4//1+4+9//1+4-3-11+11+3+3-11+8+7//1+6%20-10-5+8+11-5%20-2//1+9+7-9-9%20-6//1+5//1+7-4+4+9+5%20-2%20-6-8-4//1+7+5+2%20-4//1+5%20-8-7//1+11%20-4+8-2+8+6//1+4+11//1+8//1+8%20-3%20-3%20-5//1+5//1+3//1+2//1+4+8-3+4%20-11%20-9%20-6-2+2//1+3%20-11+6%20-11-10-3//1+4-10-8%20-8%20-9//1+10-10%20-3%20-7+6+7%20-3+2+5+6%20-7-2%20-9-5+4//1+9-3-7+10//1+11+9+9-6%20-6-6-8//1+11//1+7%20-5%20-3-2//1+3//1+5-5//1+3+4-4%20-3-4%20-6-7//1+9%20-2//1+11//1+5-3+5+8%20-5//1+6+11-8%20-6-2-8//1+3//1+9-3//1+10//1+2-3+10-6+10//1+7+2+2+11%20-8+11%20-2-3%20-6-7//1+8%20-7-8//1+11%20-3-10%20-4-6%20-5-3+3-3-4//1+5%20-4-8-3//1+10-5%20-9%20-11%20-5%20-4%20-9%20-2//1+9-3-5+11-8//1+4+9%20-3+6-5+10+10+9//1+5%20-7//1+9+10%20-11+2%20-2//1+7-7//1+11-6//1+4-3%20-8//1+8+7+11-7+11+2-6%20-2%20-6//1+5-3-8//1+8-9//1+6//1+10//1+5+8//1+6//1+2-6-10//1+10+2-3//1+8-7-8//1+9-5+4%20-2//1+4%20-3//1+3+7-8//1+7+7//1+8//1+7//1+7%20-7//1+2-10%20-6//1+8+11%20-8-7+7%20-5//1+8-4//1+6%20-9%20-11//1+2//1+3//1+8-7%20-6%20-7%20-10-8-2//1+3-4%20-3%20-8-7%20-11+11%20-2//1+3%20-6+11//1+6%20-8+8%20-2+7-5%20-4-3//1+6-2+7+2//1+11-9-9//1+8+2-5//1+9-8%20-2//1+6+4+7-4+6//1+5//1+2+9//1+2-2//1+5%20-7-7%20-4+10-7+5%20-3%20-3//1+2//1+7-7+11//1+5//1+9//1+5+3%20-9//1+5%20-2//1+9//1+11+10%20-8-9//1+6%20-9+2+11//1+6-8//1+3//1+8-8+6+4%20-2%20-7+5-10%20-9+4%20-10%20-8//1+3-5+11-3-3//1+4-7+5-3-9%20-10//1+11%20-9-10+2//1+8-8+5-10-11//1+6//1+8//1+5%20-9//1+6-10%20-4%20-10+9//1+2-7-10+9-7//1+9%20-2%20-3//1+4%20-7%20-9%20-10%20-2-9//1+6//1+2-11-2%20-11%20-11//1+6//1+2%20-9//1+7%20-10//1+10//1+4%20-3%20-10%20-5%20-4%20-9//1+7+8%20-9-3//1+11%20-10-4+11%20-6+10-5-11//1+11%20-2//1+2-6-10-11-5-5-3+7-10+4-7+9%20-9//1+6-10%20-6+10//1+4//1+10+2-8-10+8-3+6+4//1+4+2+5%20-9%20-3%20-6//1+7+10+2-4+8//1+3+6-7+4-10+3+4//1+7+7//1+2+3+7-3//1+3-7//1+8-9+2%20-6%20-6%20-8%20-4+4+10+5%20-9+7%20-5//1+10//1+11-4%20-3%20-4-4//1+8//1+3%20-6%20-7%20-11+2-11+11-5//1+8+8+7+4+7//1+6+10//1+7%20-10+5%20-10+7+9%20-10+8%20-4-8//1+9+7%20-2+3+2+2+5+11-8+7-7//1+11-4%20-4//1+7%20-9%20-7%20-3-10%20-8%20-11%20-9%20-10+10+6+11-4%20-2//1+10+7//1+11-4+6+4%20-9-2+2//1+11-7%20-8-6%20-2+6-8%20-10+4-11%20-6+4//1+5-10//1+8+11+7+10+5-5+7-8-2//1+5//1+6-5//1+10+6%20-4-11+10//1+2+10//1+6-10%20-7+6-8-9-4%20-11//1+10+11//1+11//1+2-2%20-10%20-6%20-7-8//1+9//1+9+4-2+10%20-5+10-11//1+5+2//1+3//1+9//1+6+8%20-2+5//1+10//1+6+4+9%20-10-8%20-10-10//1+7-2%20-4//1+10%20-10+11//1+4//1+4+6+9%20-4-5-4+7%20-11-5//1+7+3+6//1+8%20-3%20-2%20-11+11%20-10+7//1+3%20-4//1+3//1+11-6//1+10-11//1+11+7%20-4%20-6%20-11+8+8%20-11//1+2%20-6-3//1+8-3%20-2//1+5%20-11%20-7//1+9//1+4-9//1+8-6%20-7%20-4%20-2%20-7-5//1+7-9//1+6-4%20-3-2%20-11%20-7%20-3-8%20-7-9-4//1+9-5//1+2//1+2+4+6-7%20-4-2+4+6%20-8//1+5+11%20-6//1+2-10-4-10//1+11%20-9//1+5+6-9+10+9//1+5+3+10-8-9-8%20-6//1+4%20-2-11//1+10-11//1+6+6//1+7-11-10+7-2+10%20-4-4//1+9%20-8//1+9//1+10%20-11+10//1+8//1+4%20-11-10+11-5//1+5//1+10+3%20-7//1+3+8-6-8-4+11//1+3-5-6%20-6//1+2//1+3%20-7+7//1+4%20-5//1+8+4+5-3+6-3-7+8+5%20-8-3//1+10-11%20-11//1+9%20-2+7//1+9-10//1+9-4%20-8-6-5%20-11+3//1+3-3-5+8+11%20-2+2//1+10//1+6%20-8%20-4//1+2//1+10-9+2//1+9-7%20-5%20-11-4%20-9//1+2%20-6-8-10+4-9%20-4%20-4%20-10+2%20-3-3+3%20-5%20-9+3-5//1+11-7//1+9+9%20-11//1+5%20-11+5//1+5-2%20-6%20-2%20-9%20-7//1+9+9//1+6+3+7-10//1+7%20-4//1+9//1+9//1+5%20-9//1+8-8%20-10+9-3%20-9//1+4+2//1+4+9+11+5+7+4+8%20-3%20-4//1+8%20-4+6//1+4-8+5%20-6%20-9-5//1+6-5+3-3-10+6//1+8-5+7+8//1+8-4-2-6+7+7+4%20-7//1+8-3%20-8+2//1+6+6+9//1+10%20-4+3//1+3%20-3//1+4+3+6-10//1+2//1+5%20-3-3%20-4%20-5%20-11%20-10+10+3%20-7-10//1+10+8//1+3%20-10//1+8%20-3%20-9-10//1+9+7//1+3%20-5%20-8-10//1+10+4+6+8+3+5-11+7%20-7+11%20-9//1+6%20-11%20-5%20-9-8+7+10-5//1+9%20-2//1+8+8//1+10%20-3+7%20-2%20-5-5%20-11-9-7+7//1+4+10//1+5-10//1+5-9-10+7+10%20-3%20-7+11-8+11%20-5+9//1+7//1+2//1+2//1+4//1+11//1+9%20-4//1+6%20-5-2-4//1+7%20-9+10%20-5//1+10+7+6-9//1+7%20-11-5+7//1+4%20-11+5//1+10//1+7+6-10-2%20-2%20-11-6//1+3-7//1+11+11+11-3%20-10%20-10+11//1+4+5+8-8+10//1+7+2%20-5-3-11+3-6-4%20-4+6%20-7//1+6+2+8//1+11%20-6%20-4-4//1+8%20-3-7//1+7-7//1+8-2-11-3+6//1+5//1+8//1+8+8-5-11+8-11//1+5//1+7//1+10%20-10-9%20-3-5-3-3//1+4%20-5-10%20-5-7+11-10//1+5+11+2+4+9-4-7%20-11%20-6+2+6%20-7%20-5+4//1+10%20-6-5-6-8-11-5//1+2//1+2-4//1+9%20-10//1+11+8//1+4+7//1+3%20-4//1+6+10%20-11//1+2-10//1+2%20-9+7%20-10%20-8%20-6%20-6+3-8-5//1+2-8%20-8+4%20-5-2%20-3+2+4//1+4+7-4+7-2+11//1+3%20-8-9%20-4%20-6-5//1+8+3-7-8%20-2%20-7//1+9-4//1+11+2-10//1+11+9+11-2-9+4//1+4%20-2+2-6//1+10+11+5//1+5+9//1+8%20-11+10%20-5%20-11+5+5-7-2+5%20-2%20-10//1+8-7+10-6//1+10+6//1+5+11//1+9+2%20-4+3%20-2+4%20-9+5//1+4//1+8%20-9//1+8//1+5+8-2%20-8-3%20-6%20-9%20-8-4//1+7//1+8//1+4//1+5%20-2//1+2-8//1+8%20-4+8%20-4-8+11%20-4-11//1+5//1+11%20-5//1+3%20-6%20-2//1+11+2+5//1+8%20-2-9-6+3-8//1+9+9//1+6%20-10//1+2-3+5+11//1+7-11+11-4%20-11+4%20-7%20-10//1+3%20-10%20-7-3-11+7%20-2+11+2+9//1+11-9-3-5//1+9+8%20-3//1+4+3//1+7-11-5-11+4-2%20-3%20-3+11//1+11-6+3%20-3-9-4//1+3//1+3-5%20-9%20-11+11%20-8%20-11%20-4-6%20-10-4%20-4+6//1+10//1+2-2%20-4-9-2%20-2%20-2//1+10+11//1+3+8//1+11+4+2-9//1+7//1+11%20-7-4+11+5%20-11-7//1+4//1+8%20-6//1+8+3//1+10%20-2//1+4%20-7%20-10//1+11+5//1+3//1+9+9%20-9+10-9%20-11%20-4//1+9-9//1+2-7%20-3+11%20-6+11+8//1+9%20-2//1+3-8+10%20-3%20-2//1+5-4%20-9//1+6+5%20-8-5+6%20-6+2-6//1+9%20-9//1+8%20-11-6+7%20-8-9%20-5%20-2%20-8//1+11-10%20-8//1+9-8%20-3-6%20-8//1+7%20-3-7%20-11-6-9+7%20-9+6%20-7-10%20-10-4//1+7-3//1+6-7-10+7+2-10//1+11-11//1+10-11%20-4-8-11-11+6//1+11+7//1+5//1+6%20-2//1+8-10//1+7-6+11//1+11//1+5//1+11+10+3+5%20-5-5//1+3//1+7+5%20-10//1+8//1+6+9+2+6//1+6-6-6-10//1+4+5%20-2+3%20-9%20-4+3+3-7+2//1+8%20-8-8//1+9-7-4//1+8//1+6//1+10%20-9%20-10%20-5+8%20-11%20-2%20-5%20-9//1+4+10%20-10%20-8+6-7-6//1+9-4+5-3+8-6-2-3//1+5+7//1+5%20-6%20-4+8-5%20-3//1+4+2-2%20-6-3//1+11+10-4%20-7-7%20-5+5-2%20-4-7+3//1+10%20-5+9%20-10//1+5+3-11//1+10-10%20-2//1+7%20-7+6-7+10//1+2-11-8+5//1+6-5//1+7-11+5+6//1+3//1+9-2%20-8+6-6+5%20-6-3-8//1+4//1+6%20-9//1+11+9+6//1+3//1+9+6//1+11//1+10%20-6%20-4//1+3%20-7//1+9+5+11+3//1+7%20-8-5%20-2%20-7%20-5-3%20-9+4-2%20-6%20-8-2//1+6%20-9-6-10//1+2+11+6%20-11-11%20-10//1+5%20-11//1+5+3//1+8+9-3+2+2+5//1+9%20-2+11-6%20-4-8+3-9//1+5%20-11-10%20-4-6%20-6+4+3//1+8-5-2+5//1+2//1+2//1+8+7+9+4//1+3//1+2%20-7//1+4//1+6+6//1+7-6-5-10%20-5-9//1+6-3//1+8-11-10-10+5//1+5-11%20-2//1+4//1+11%20-10//1+7-4+8%20-4+10-8%20-6//1+4+4//1+4//1+4//1+6+11-5-6-4%20-9%20-6%20-2-6%20-11//1+10-10%20-11//1+3+7%20-5+5-7//1+7-4%20-2-6//1+10//1+5//1+2+6+10%20-9+4//1+3//1+3+3-6%20-11%20-5-5//1+4-6-11+10//1+3%20-9%20-3%20-5//1+9-4-11+10-3+3-10+3-6//1+8-7-2//1+5%20-2%20-11%20-9%20-6-11+4-11+6%20-7%20-3//1+6%20-2//1+10//1+7%20-5-4+11-8-2+2//1+3%20-8//1+2+7-11%20-6+10+5-10+8//1+4%20-2-4-7//1+7//1+2-4+8%20-5-8%20-4-10+2%20-7-10+3//1+4+6//1+2%20-5%20-8-4+7//1+7//1+2-6-11-7-6%20-2+10+6//1+5//1+5-9//1+2+6//1+11//1+7%20-8%20-2//1+2%20-2-8+10%20-9//1+7-9%20-6+3+6//1+6+2-10+2//1+3%20-6//1+9%20-9+2-7%20-6%20-3-7//1+8-11-5+9-11%20-5%20-4+3+10//1+3//1+8+6+2//1+11-6+4//1+8//1+11%20-4+10-6//1+7%20-9-8-7-10%20-3%20-7//1+2+2%20-10+6+4%20-7-9+2+7+9//1+6-6%20-10//1+9%20-6-2%20-9-3//1+2-8-9+5%20-2//1+8%20-3%20-10-6//1+9//1+6-8+5%20-2//1+9-6+10-5-4+2//1+6+7-4%20-2+4//1+3//1+4-11%20-10+6+10%20-3%20-7-3//1+5%20-2+5-6//1+8%20-9//1+8%20-9//1+7%20-10+9+9%20-9%20-3//1+9-2%20-6+7-7%20-6%20-10//1+8//1+7+10%20-11-9%20-8//1+3+7%20-9//1+3%20-4//1+11%20-3-2+6-10-8-6+4+3//1+7%20-3+3%20-3//1+6+3+6+7//1+3-7+3-11//1+7+7+4+9-3%20-4//1+11-9+6//1+6+10%20-8//1+2//1+2+6+7%20-4-7//1+3//1+5//1+11-2+10%20-9-7-4%20-10//1+3+6+5+9//1+10%20-4-2//1+9%20-5%20-8//1+6//1+3-8+7%20-5%20-10%20-11//1+3+11+5+3//1+7+8//1+2//1+7-7//1+11%20-11%20-7//1+4%20-9-3%20-4//1+10-7-4//1+2-6//1+10-11-5+7%20-10%20-2%20-4+9-5-6+3%20-10-10+3+8%20-2%20-8%20-4+9//1+2//1+4//1+11//1+11+3+10%20-6%20-9+11//1+8%20-7//1+6+9+5+9%20-7+2%20-3+3%20-5%20-3+4+5-4//1+4+5%20-11-10//1+3//1+4//1+4-3+9-8-9-7-11+3+11//1+10//1+4%20-7+4//1+3-8%20-6//1+5+6//1+2//1+3+3//1+7+5//1+8-2//1+6+10%20-7%20-3-8+4+11//1+7//1+5//1+4-4-8-5-3%20-11//1+10%20-3%20-4+5//1+4%20-7//1+4+10%20-3//1+4-8//1+6-5%20-4-4//1+11-5-4//1+6//1+8+4%20-8//1+8+2%20-8+2%20-7%20-5//1+8-3%20-6+2+5//1+3%20-10%20-11//1+5%20-4-3-8+6%20-3%20-8//1+3//1+11-9%20-3-10-6+3//1+3-7%20-3//1+2//1+4%20-11%20-7%20-8-4//1+10-2%20-9//1+7-11%20-11//1+11-10-5+4%20-4//1+3-9//1+6-7%20-11+11%20-3%20-8%20-4%20-4%20-4%20-2+4-4-10-10-4%20-7+7-3//1+3%20-10//1+7+8-5//1+11-9+4%20-5%20-6+9+11//1+4%20-8//1+5+8%20-10//1+2+7+7%20-6+4%20-5%20-10%20-10-9%20-2%20-8%20-8%20-2+6-7-9+6-8//1+6%20-11+8+8-5+4-6//1+11//1+5-5+8-5-5%20-5//1+4+3%20-8+10//1+10//1+7//1+4-2//1+9%20-9+4//1+7//1+9//1+8%20-4%20-5-3-6%20-7%20-2%20-9-5//1+2+11-7//1+11//1+9%20-10//1+8//1+4//1+3//1+9+3+4+4+3%20-8+8//1+6//1+5//1+3+7//1+3//1+2-11-6-11%20-4-3%20-9+2%20-8//1+7//1+11-5+7//1+5%20-2+3%20-3//1+8+10-10//1+11-5//1+8//1+2%20-4%20-7-5//1+11%20-6+4-8-6+6//1+7%20-7-11-6+7//1+2-9%20-2%20-9//1+5-9+11//1+4-8+5%20-4+3-5+4-3%20-4%20-3//1+10+2//1+5+3+8//1+6-10+10-2+10//1+5-8//1+7%20-8-8//1+5-4-4//1+9//1+5+4%20-5%20-9//1+7+8//1+11%20-8%20-6//1+10-6//1+9%20-8%20-10-7//1+6-11+10+3-10//1+3%20-4%20-10+11%20-8-8+8%20-5+5-2%20-9//1+5//1+5//1+6//1+2+10+10-2+10%20-10%20-9//1+10+8%20-6+3%20-5-9%20-9%20-4-7+10%20-6//1+2//1+3+9//1+2//1+4//1+4+5+6%20-2+5//1+3+9//1+7%20-2//1+8-3-5%20-9+11+8+11//1+5-10+5//1+6%20-9%20-7-8-10%20-6-5+5//1+6//1+6+4+11+7%20-5+5+5%20-2+11+4-10-4//1+3%20-7+4-4%20-5%20-2%20-11+5-6%20-9//1+5//1+4+5//1+4-6+10//1+7//1+7%20-8%20-8+6%20-4%20-10+8//1+6//1+11//1+7+9%20-6-10-7//1+5//1+2//1+10+4//1+8//1+5//1+6-11-11%20-2+3-5-11+3-10-4-5+3-8//1+2-7-7//1+2-6+11+4//1+10+5//1+8+11+9+8-9-2-10-9//1+2//1+3-10-7-11-8%20-9%20-9-8//1+3-9%20-6%20-10%20-3+8+4-2%20-2+3//1+8//1+4-3//1+3-7-5//1+10+7-2%20-6+5-5+6-6%20-4%20-11-6//1+11-4+10%20-10//1+11//1+7+10%20-7%20-11%20-10%20-10+5//1+7//1+4+5//1+4+4-9//1+5//1+3%20-4//1+6+9-10//1+11%20-11%20-2-3//1+7+7-8//1+7%20-3-4//1+3%20-9-11-5+3%20-7//1+3%20-6//1+5//1+9%20-10+6-9%20-4+7//1+2-2%20-5+5-8-11//1+8//1+6//1+4-9//1+9%20-4//1+7%20-4//1+9-4%20-6-3+8%20-6//1+2-3+8+4%20-5//1+9//1+2+5-9-3//1+8-11+3//1+7//1+10%20-11//1+8//1+5+8-2%20-11//1+11//1+2//1+5+11+3%20-4//1+7+2-2-11//1+11%20-11%20-10%20-5//1+11+3//1+2%20-9//1+4-7+11//1+5//1+7+6%20-9+6+2//1+7+6-3-6%20-4+3//1+8-2+6+9-9+11//1+10//1+11//1+2+10%20-8+4+6+10-6-10+11//1+2+4+2+11+6+6//1+2//1+10//1+3//1+9//1+6-8-7-11+11%20-8-5+4//1+10%20-9-2+11+8-6+7%20-6+11%20-7-9+5-7-5+3//1+2+11+7%20-4//1+10//1+2//1+7+11+9//1+8%20-3-8-5-5-9-7+8%20-7+9//1+10+8//1+9-2-5//1+7-2-3%20-9//1+9//1+5%20-5//1+5%20-8%20-5//1+7-6+6+11//1+2//1+3%20-8//1+6%20-3-11//1+5-4//1+6//1+6-8-4//1+10+11-5//1+9+7%20-2+2+10-4%20-9%20-6-8%20-3%20-2//1+2-11+3%20-8+3//1+9+5%20-10//1+3%20-7-8%20-11//1+3+7+10//1+8+9%20-11%20-2//1+2-8%20-5//1+3-4//1+5//1+4+10%20-9%20-5-9%20-9-8-11-4-7-6+10//1+11+9+4+5//1+6+2%20-6+3-10-4%20-2+5+4+10%20-8+8//1+3%20-8%20-10-5+4-10//1+9-7%20-8//1+7+6-8+2+6-8//1+10%20-3//1+7//1+10%20-2%20-3//1+10%20-11%20-8+8-7+3%20-7%20-9//1+7-11-6%20-2+9-9//1+5-4-8%20-10//1+10//1+2+4//1+7+6//1+10+10//1+3%20-10+10+4-4%20-2+10-8//1+6-9-11-2%20-2-11+7+11%20-8-5-2%20-10//1+2+7%20-10//1+6//1+4-7-8//1+6+2//1+6-8-11+8+10-5//1+5-11-9//1+5%20-10%20-10-7+4-6+9//1+3//1+9-9-6-4%20-4-6//1+8%20-6+2+6//1+8%20-11%20-5//1+6-10+4%20-11//1+2-5-11+10%20-9+8%20-7-7+10//1+7-8-11+10%20-10%20-7//1+7+3%20-9-6//1+2-9+6+9-7+2+2//1+3+9+3+10-7//1+5//1+2%20-11%20-7-6+6+3-3//1+8//1+9%20-11+6//1+7-8//1+10+4%20-2%20-11%20-5%20-11//1+7%20-8//1+5//1+9+5+7%20-8-7//1+2+10//1+4//1+10+3//1+3%20-10%20-8%20-4%20-11-5-3-9+4//1+5%20-9//1+6+6+6+8-9-6-9//1+6-8+9//1+6-5%20-8//1+4%20-8+4//1+11-11-9%20-4//1+7//1+2+8%20-11//1+9//1+11+11-7%20-3%20-8+5//1+8//1+7//1+5+6-3%20-10+3%20-7//1+7//1+5-11-2%20-2-9%20-8+8-8%20-10-5//1+2//1+10%20-6+7//1+8-5+4+5-6+11//1+10-7+6//1+9%20-7+8//1+2//1+6+3+8%20-2%20-8+2//1+2//1+8+7%20-4%20-11+6+10-11//1+7%20-9+2%20-7+9+8//1+3%20-10%20-2%20-11-10-10-8+10//1+5+4-11-9//1+3//1+3-11-8-8-6%20-2//1+10//1+4//1+9//1+11-5+2%20-8+8//1+11-6//1+4%20-10//1+11+11-5%20-10-7//1+10+11-3%20-8//1+4%20-5//1+10+2+11//1+3//1+10%20-11//1+4//1+11%20-11%20-4%20-9+2%20-7%20-8//1+6+4//1+11%20-4+9+5-11//1+2//1+5//1+8+8+5%20-4%20-4+5%20-7+11//1+8-6//1+2-3+3+7%20-10//1+2//1+11%20-7+5//1+5//1+10%20-6-4%20-9-9+8%20-8//1+9//1+11-10%20-9//1+7//1+3//1+10//1+11//1+4-6-8//1+3//1+10+5-3%20-7+5+6//1+9-6//1+8%20-5//1+3-3//1+4//1+5%20-11-4+10%20-11+6+3//1+5-11//1+6-8+9//1+7-3%20-6+7+11+11-8%20-6//1+9%20-5%20-6%20-3+5+8-2//1+2+6-11+6//1+11-9-9%20-3//1+2//1+2+2-2//1+4-8//1+6-5+4//1+5//1+11-9-9%20-4//1+10//1+9-3+4%20-9+11//1+3//1+4%20-7-10+4-11%20-3-2%20-10-2//1+7%20-9-11%20-8-6%20-6%20-2%20-2-4+5+11+2+7-4-4%20-10+11%20-3+2+6//1+10-4-6%20-5//1+4%20-11-4%20-7+11-5%20-5%20-3-8//1+6//1+10%20-10+10+3//1+10%20-8+3//1+10%20-5+5//1+4%20-11//1+5+7//1+6//1+6//1+9//1+6//1+6//1+9-10%20-4-4+11+6%20-9//1+6//1+7//1+2%20-8+10-9//1+4+4-5%20-4//1+4-6%20-5%20-11%20-5//1+11%20-10//1+5//1+6%20-4//1+6-9+8-11%20-4+8%20-7+2-4//1+4//1+3%20-6//1+10//1+8%20-3%20-6+3//1+3%20-3%20-11//1+5//1+10%20-4%20-9//1+4-4//1+7//1+3-4//1+8+11+3-2-8%20-4-9+3%20-11//1+2//1+5-6+6+5//1+2-7-5+11%20-6//1+2+11-9-4%20-4-7//1+4//1+10-11//1+9%20-5+4//1+2//1+8-8-5//1+4+5%20-10%20-4-11%20-6+11+8+11-4%20-9+6//1+4-4+3-10-3//1+5//1+10-6%20-8-11%20-2-9%20-8+10+10//1+6+10+4-10-6+11+8//1+8//1+11%20-5//1+8%20-6%20-3%20-4+7//1+9-11//1+9+3//1+4//1+11//1+2+11//1+5//1+5%20-3+6%20-9//1+2+6-3+2//1+6+9%20-5+5%20-10+5-9-11//1+9%20-5+6-9-3-5%20-4+4-2//1+6//1+11%20-3-4//1+3+7//1+2-3//1+9-6%20-2%20-5//1+7+5%20-3-4+8%20-11+8//1+7%20-2+10//1+5-10-2%20-2-4+5-4%20-2+4-6+10+10%20-6//1+2%20-11%20-4//1+2//1+5-2//1+10%20-5%20-8//1+3-11%20-3-5+5+9%20-3//1+7%20-6//1+11//1+7+6+11+3-4//1+6+7%20-2//1+2//1+10//1+10%20-3-5//1+4//1+8%20-3+2-7-9-6%20-5+7//1+10+6-9-9//1+5+5%20-4//1+7//1+6+4//1+7+3-6-5-5%20-7%20-7%20-11//1+3+9+9-7%20-3%20-3%20-8%20-3+7-2+6%20-11//1+10%20-5-8+2//1+4%20-8%20-3+6-2%20-5+8%20-2-9//1+9//1+10+7//1+7//1+6+4-9%20-5//1+2+10%20-2-10//1+6//1+11+7%20-5//1+2-7%20-9+11-9//1+7%20-3-7+10//1+5+3+3-8-11//1+11+4%20-11+3//1+5//1+3%20-8//1+2//1+7%20-4+7-5+10-4-3//1+5%20-11+8//1+10%20-6-8-4-9+2-3%20-3+8-3//1+3-2-3%20-10//1+9//1+3-4+5-4-9%20-11-9-7//1+11%20-9+11+10//1+6-2+4+9%20-10%20-6//1+2-4-2%20-5+7-11+2-3+10-3%20-9+8-10//1+5-7%20-3-9%20-5-8//1+10%20-9-9-8+3-5%20-5-4//1+4-2+6//1+10-9%20-8%20-8//1+10-8-5-4-2+11-6%20-9%20-10//1+3+8-7+10-5+11+3%20-9%20-10-7%20-3%20-5-5-5-8%20-10//1+8//1+7//1+9+7%20-4-11+8%20-2-10//1+3-5%20-6-9-7+4-10+11//1+6+7//1+11//1+5-8%20-8-11-10//1+5%20-5%20-2%20-3//1+6-10-9-10%20-9//1+8%20-5//1+5-3//1+9-4//1+5%20-6%20-6-11//1+6+11//1+5//1+7//1+11%20-10-2//1+4+3//1+9%20-8+7%20-10//1+7//1+8+4+5+11+3//1+7%20-4%20-9-9+11+8-4//1+9%20-10//1+7%20-3-2-6//1+6//1+6-2%20-9-11-7//1+2%20-2-10-8-7-4-7-3+6//1+5-10//1+10+11//1+3%20-2-7+6//1+8-6%20-2-8-10//1+2//1+5+6%20-4%20-10%20-4+4-3%20-11-11//1+3+6+5-2+4+8+3+11-7+6+9%20-8-2//1+8//1+8%20-6%20-4+3-2+4+9%20-5+5-6-5-6+9%20-11%20-5%20-4%20-10%20-9-10-3+3-3-5//1+11-2//1+8-9-6//1+5-7+9-2//1+8-2//1+10//1+8+4+7-7+11-5%20-4%20-5//1+7%20-10//1+11//1+2//1+7+3-11//1+9+2-5-4%20-10//1+2//1+9%20-5%20-8+6+8%20-7-4//1+7//1+11%20-2-6+9+10+3//1+11//1+8%20-9%20-2%20-11+5-4//1+4//1+3%20-5-2//1+2//1+11-5//1+9//1+2%20-8%20-6//1+2//1+10+5//1+5//1+5+4%20-7%20-5//1+6-8//1+3-5//1+9//1+3-7//1+3//1+2-6+10+7-11-10-6+7//1+3+4//1+4//1+9-10-8//1+5+4-4-8+5+5%20-8//1+8-8//1+6-11//1+4+9%20-3-2-8//1+9+7-7//1+11+6+10+2+11+2+9+8+3//1+4+4%20-7-2-4+7+9+11//1+4+11-10+9-3-10//1+8-4%20-4%20-11+3//1+9+3+4//1+3-8+9-4%20-5//1+9//1+7//1+11+11%20-10+8%20-3//1+4//1+7%20-6//1+5%20-2%20-7//1+2//1+7-8%20-8+10+6%20-4+9+9-2//1+4%20-4-3//1+4%20-10+9+11+4%20-9+5-11-6-9%20-2//1+8%20-3%20-3-3-10-6//1+8+2//1+2+3//1+5+10+5%20-2%20-8%20-3%20-5+8//1+5%20-5-5%20-5%20-9+2//1+9//1+9+5-5-2//1+2+11%20-5//1+8-11+10//1+4//1+7%20-7-11+9+10//1+3%20-10-7+8%20-4%20-2//1+4%20-2%20-7//1+6+3%20-10//1+11+8%20-2//1+6%20-8%20-7//1+8//1+6%20-2//1+5%20-9+3//1+7%20-7-6//1+9%20-6//1+5+2//1+3+10-10+3%20-2%20-4//1+10+5%20-3-2-7+9+8//1+5-7+6//1+9%20-3-4-6+11//1+9//1+11+11//1+4+7//1+4%20-4+5+3+7+3+2+4+2//1+6%20-4-2%20-3+8-3%20-5//1+2+7%20-10+4+10-6-4%20-5+4//1+5%20-5+5-5%20-2%20-6+6-7-6+2%20-5//1+5%20-11//1+10+10-9%20-5-3%20-11-4%20-8+11+3-10+6%20-2-9+10%20-2%20-6+2-9%20-7-8//1+4+9//1+10%20-7-4//1+5-9-7-3//1+10%20-4//1+7%20-9%20-8//1+6-11%20-6%20-4+11+10-3%20-8+9//1+8%20-3%20-9%20-9%20-7+3+7//1+6-7+10%20-10+2+9+11%20-5//1+6+2+8+8%20-7-6-2-3%20-8+11-2%20-2%20-3%20-4//1+9-8-11//1+6+4%20-10%20-11//1+8-5+8%20-7//1+7//1+9+8%20-3//1+10-11-6+7//1+5+9+11//1+3%20-2%20-6//1+8//1+10//1+10%20-4//1+11//1+10//1+8//1+8//1+9-11//1+11//1+8+10%20-6//1+3+11%20-10%20-10+9//1+4//1+7-11%20-3-9-4%20-4%20-8-2-8+9-10//1+3//1+7-7//1+2%20-3+2%20-4//1+2+10%20-6//1+11%20-8%20-8//1+10-8%20-7%20-11//1+3+2%20-3%20-9//1+8//1+6%20-7-7+3%20-3%20-8+6-6+3+6-11+4%20-2//1+3%20-4//1+5-2%20-8//1+7%20-2-9%20-10//1+7%20-10%20-11+10%20-5-5%20-5%20-3%20-10-11-6-7//1+7+8//1+8//1+11-8-5+6+7+2//1+9//1+11-5-7-11-7//1+2%20-7-4+11+2%20-7%20-9+10+10%20-4-7-10+8-11//1+6%20-8//1+2%20-6//1+10-11-8//1+7%20-11%20-8+5//1+6+11%20-3//1+9//1+2+11%20-9%20-2+3-2+4-2-3-10+10-2%20-10+5+2+6%20-7+2%20-5//1+2%20-7//1+8//1+11-11%20-4//1+7%20-10//1+2-2-7-10%20-2+2-7+3-8+7+3%20-8//1+2-8%20-2//1+11%20-6-10//1+9-3-2//1+9+3-5//1+7-3+7//1+4+7-10%20-2+8-2-3%20-8-6//1+3+4-11%20-10-4%20-2-4%20-9+8-10//1+2-8+7-3-9//1+9%20-9//1+5-6//1+3-10%20-9-9//1+11+10%20-4%20-8//1+7//1+2//1+10%20-10-5+6+2+4//1+3//1+11+4//1+7//1+8+7+11+5//1+8+6%20-8+6//1+11-6-4-10//1+2-8%20-3-8//1+9//1+4//1+5//1+4//1+4-4//1+4%20-4+3//1+7%20-6-2-7+10-8+2%20-11-11+11+9+3+3-10+7-10-8+9//1+4-3+11-9//1+10-10+11//1+5//1+2//1+5//1+8+8//1+2-8+5+11//1+6+6+3-7//1+9+7//1+9//1+10-5+8+11+5%20-10//1+2+10//1+8%20-5-9%20-2%20-8+3//1+2+11-8//1+4//1+6+10+11-8//1+4%20-4+8%20-8-9+9%20-2+2+7-7-2-6-5-9+10-8//1+11-5+10%20-5%20-4%20-10-3//1+3%20-2//1+7-6%20-6%20-4%20-4-5+9-4%20-3%20-2//1+10-5//1+8//1+5//1+11+11-7//1+5-4+10%20-9-5-8-5%20-4+7//1+8%20-4%20-6//1+6-7+11-7+11+5//1+7+6-9//1+4-4-5%20-10+2+6%20-7%20-3-8-11-6+9-6//1+5+4%20-10//1+7//1+6%20-11%20-4//1+7//1+8-5+10%20-11+3//1+5+9+5%20-10%20-10-11//1+2%20-3//1+5%20-3-7%20-4%20-3+11+8+9%20-7%20-5%20-7-5%20-2+2//1+8%20-11//1+6-5-5%20-8//1+4+8%20-8+5%20-4+9%20-2-10//1+10-6//1+10//1+3%20-10+6//1+9-8+3-2+9%20-11//1+4+11-10%20-2%20-10//1+5+11//1+6//1+3-10%20-11-11-6+11//1+11//1+6//1+8+3-11%20-3-4-4//1+6-7-11-11//1+3%20-7-6//1+7-5+3//1+9+8//1+6%20-6//1+9%20-8-10-2//1+8%20-6-2%20-4//1+11+4//1+2//1+11+3+3-6//1+7-3//1+3%20-2-4-7//1+2+4//1+5-6+3-9//1+5//1+5+9-4//1+9+2+11%20-7//1+8//1+10%20-4%20-4//1+3-9//1+5//1+9//1+11-7//1+11-11%20-5//1+5//1+11+7//1+10%20-3+2+2%20-6-11-9-8+6+8%20-7-10+4+10-11%20-8%20-10-11%20-11%20-4//1+8//1+2+7%20-6-3-9-6//1+7//1+5%20-4+4%20-4//1+8%20-5+7-8%20-6//1+10+9%20-7//1+4%20-5//1+10+11//1+4//1+5-11-2%20-4%20-3-3+6-8-7%20-9%20-6-5-10+6+6%20-5//1+2%20-9%20-6+2//1+9+11//1+11+5%20-7//1+9-5//1+3%20-6%20-3%20-11//1+8//1+9%20-6%20-8//1+2%20-2//1+3//1+7-8//1+8%20-7-11-7%20-4+2%20-8//1+3//1+2-2%20-2-5%20-6-11-9-10-2%20-2+9-3//1+6+11//1+4-10//1+5-5+11%20-4//1+6-5-4%20-7//1+5//1+2+8-4%20-9%20-11//1+10//1+11-7+6+11//1+7+2%20-10+9+8+9-7%20-10%20-7//1+3%20-7-8-11-10+9%20-6-4//1+9-8//1+5//1+10%20-6+6-9-5%20-3//1+3+8+4//1+9+8//1+9%20-4+5-5//1+11-11+10+4%20-4+7-6+3//1+5%20-7%20-4+9//1+2//1+11//1+5//1+5//1+6+7%20-2+8%20-6-5+3-6+6%20-8-10+2//1+11//1+6%20-3//1+10-8+3-8-3%20-6+11-10%20-2+11%20-5%20-2%20-6%20-10-5%20-2+8//1+6-5//1+11+11//1+4-10%20-3//1+8-11-10-9-7//1+11//1+3%20-2+9%20-4//1+2-8-7-11-9-5%20-8+11+10+7-9+6%20-2%20-9%20-11+10//1+6-4%20-5//1+10+4%20-4-10%20-10-5+2//1+2%20-2+8+11%20-7//1+11+11+2//1+5-7-8+5-9%20-9//1+2%20-5-2%20-5-11%20-2+8-7//1+2+2+8+9+6%20-10+9+5%20-5//1+4%20-2%20-4%20-8//1+7//1+5+4+11+5+5%20-7+4%20-3%20-3%20-3%20-8//1+8//1+3+7-2-9//1+7//1+3%20-5+8-8+4-9+10%20-9//1+5//1+3%20-8//1+5//1+10%20-8-5+7//1+9%20-5%20-4//1+6//1+7+9+2//1+9//1+3+10%20-10%20-8%20-3-3//1+7+3-2//1+8-2%20-8//1+4-10%20-3%20-6%20-2+9%20-7-7-9-6-3+7%20-11-9-6-6+6%20-8//1+3//1+2+8//1+7//1+2//1+11-9+6-6//1+3+3%20-4-10+9-5+5-10%20-5+11+6-3+2%20-4-4//1+10%20-11+10-8//1+2+7%20-9+10+10%20-6//1+10//1+5//1+5//1+6-11//1+5-8//1+9%20-9-2//1+4-3%20-10//1+11%20-9%20-5-3+8%20-2-11-9%20-9//1+8%20-2%20-4//1+8-10//1+9+5+3-8%20-11//1+9//1+11-8//1+8-11+10//1+7-5%20-5+6+9%20-8-8//1+2%20-4+8-6//1+2-5%20-3+3-10+6%20-8-4%20-4//1+10//1+10%20-4-2+7%20-2//1+10-7+10+3%20-9-9//1+7+8%20-6%20-6%20-10//1+2%20-7-6//1+11-6+4//1+11+3//1+5+5-11-3%20-6%20-4-5//1+2%20-10%20-10-4-7%20-11%20-7//1+2//1+10%20-5-3-9-10+5%20-5+6-6//1+2%20-8-10+9+2+11//1+7//1+9//1+7//1+9%20-11+7//1+11%20-10+6%20-10//1+7//1+6+7%20-9%20-7-3//1+2-10//1+6+11//1+11+6//1+8//1+2//1+2+9-9+7%20-11-2%20-11//1+2-10//1+10-7-7-5-10%20-6+7//1+8%20-4%20-3-8-2%20-6%20-8+6+7%20-4//1+7-2%20-9%20-3-10//1+9%20-2%20-2+11-6+10+2%20-2//1+9+5-7//1+5//1+10-10%20-2//1+7+10+10%20-11%20-11//1+10//1+2//1+4+8//1+2//1+7+2//1+11%20-10+2+2+2+2-4%20-10//1+8-7%20-3+5+6+5+4+7-9%20-9-7+7+11+10+7//1+5%20-2//1+11-10//1+3+11-4+7%20-7-6-5-3+8-10+10+9//1+3+2-2//1+4%20-11//1+10+7//1+2+7%20-2-4//1+8-7+2-6%20-11+6-6-6//1+10//1+7%20-6-6+10%20-2%20-7%20-6//1+3%20-11%20-3%20-4//1+9//1+3+4+8-7//1+8-6%20-11-3%20-8//1+8+8//1+7+2+2-9+7%20-11-3%20-6%20-7%20-6+8%20-2-8//1+7+11+11-4//1+7//1+8%20-5//1+10%20-10%20-10+6+5+5%20-8//1+5%20-10+10+9-3+2//1+11%20-8-5-3//1+11+9//1+10-8%20-9//1+10+5-2//1+10%20-5//1+8+8-6//1+3-9%20-4//1+7+7%20-4//1+3+8//1+8//1+7//1+9%20-3+11//1+4+8+3%20-5-2-3-8+11+11//1+3+8+3%20-11-3//1+5//1+8-2+3%20-8//1+6%20-5//1+10%20-5//1+7-6+6+6%20-10+6+10+3//1+9//1+2-4%20-10%20-2%20-11+10-4-2//1+4//1+11//1+9+10//1+7%20-7-6//1+2-4+11+5//1+2//1+4+11%20-7%20-2+3//1+8-9//1+4//1+5%20-10%20-7//1+5+4//1+5+9//1+11//1+7-10//1+6+6//1+9//1+11+3%20-5+8%20-3-4%20-10-8-7//1+6+10//1+2-7//1+3//1+11//1+2+3%20-4+9%20-4-5%20-7//1+6%20-8+5+3+4-7//1+10//1+5//1+9%20-9-4%20-2//1+2-7+3-6-9%20-10%20-5-10-7+9-11//1+3%20-2%20-8-6%20-2//1+10//1+5+4+4-2%20-10//1+3%20-10//1+10+9+8%20-9+6//1+3%20-9%20-11+3%20-4%20-3-4%20-11%20-3%20-2//1+10-2-3//1+5+4-4//1+4//1+3-5%20-7-7%20-5-11//1+7+6+4%20-2%20-2+5-10+10%20-10%20-4+9%20-8//1+11+9%20-5-3%20-11-2-8%20-4//1+9-6//1+7//1+10%20-7-3//1+9+4//1+6//1+11%20-9+11%20-10-6-2+5%20-6%20-4+7%20-5%20-11//1+7+3+9+8%20-11%20-11-6+10-7-9%20-5%20-4//1+5%20-9-3-8//1+7+5%20-7-4+8//1+3%20-11-5+11//1+11+7-10+8-3-10-10-7-8//1+10%20-4+4-5//1+9-9//1+10//1+5%20-5-7+4//1+2+7%20-11//1+2-11-11+10//1+9//1+7%20-7+9//1+6%20-9//1+5//1+7-9%20-2-10-4%20-11+8+3//1+7%20-4//1+7+10//1+8+4//1+3+4%20-9+10+8+11-5+9+11//1+8+5+10%20-11%20-2+2-9-9%20-11//1+9%20-7//1+4//1+10+5//1+8-11+8+6+6//1+6%20-6//1+8//1+4//1+10%20-4-8+7//1+11%20-11-3+11//1+6%20-8-8%20-4+10%20-6%20-6+10-11-5-2-3+9+2-4%20-3%20-10%20-4//1+4%20-4+4+9+9//1+4%20-11%20-9//1+6%20-4%20-3//1+2-5//1+6-3+9-7//1+7+9-3-4%20-5-4+2//1+10%20-4%20-5//1+10//1+10+11%20-11%20-10%20-4%20-9%20-7//1+9+11-4//1+4-2-3-11//1+9%20-8//1+4+5%20-6//1+10//1+9-9-7+3+11-5%20-6-3//1+10+5-4+2%20-3-2+3+4//1+6%20-3-8+6+9//1+8%20-7%20-4-3-3//1+9-5-6-6//1+11+8%20-4+11-11+8//1+9//1+2-11+3+8+8//1+5+8//1+7+8//1+3%20-11+5%20-8%20-7-7+6//1+7%20-10-9-8%20-9-6-3%20-7%20-4//1+3+9-11%20-11//1+7%20-8%20-8%20-8//1+4+9+4+4%20-4+6+9//1+2//1+8//1+11%20-3+7//1+7//1+4%20-11+9-3-10-5-8-8//1+6-6//1+9-9%20-4+9%20-10+10-3%20-10%20-8//1+4//1+7+5-7+7+11+10//1+9+5//1+7%20-11+3-8+10//1+5//1+4%20-10-7%20-11-8//1+3-5%20-3+6+2-2//1+7//1+7+6%20-4//1+10-7//1+10//1+8-9%20-6%20-9//1+4-6+2//1+4%20-8-6-7//1+4//1+8-5//1+2//1+3+8+7-8-4//1+5-2+3%20-11%20-6%20-5//1+7//1+9-10+4-5+7%20-9-9%20-3-4//1+3+2-8-7%20-9-5-4//1+7//1+7%20-6-2+4-9+7-8//1+3+11//1+5//1+9%20-3-11+7%20-8+4%20-6+7-6+6-11+3%20-2-10%20-2+9//1+2%20-5+3%20-10%20-5+2-10+7//1+5//1+7-9-8-8%20-5%20-4//1+4+4//1+5%20-3%20-9+10-6%20-7%20-7+4-6+4-4//1+3-3-3-2%20-11+2-6//1+5//1+9%20-4-6+9%20-10%20-2%20-7-9%20-8+11-11//1+5%20-9-9-10%20-10-9//1+6//1+9%20-3+10+10-7//1+11+3+5//1+5//1+4+10+5//1+6-7+4-6%20-11//1+4%20-9+11+10%20-8%20-4-6%20-3-2//1+7%20-6+4%20-3%20-4-11%20-4%20-5//1+11-4%20-6-10-6-11-10-11%20-9%20-8-3//1+3-5+7%20-9%20-10%20-11%20-4%20-2%20-6%20-10%20-6+9+8+6//1+7-3-3+11+4+2+7+10-5-3+11+7%20-9-7+2%20-5+3//1+7%20-3-4//1+4%20-10-3+5//1+4-8//1+5%20-2//1+6//1+2%20-11-9+5+3//1+9%20-9%20-5+5-6%20-9//1+4%20-10+6//1+3//1+2+5-2//1+4+4//1+5%20-3%20-5+6+2-3//1+6+9+11%20-4%20-6-7-4%20-10//1+6+10-9%20-8%20-10+2//1+10+5+5-11//1+4//1+9//1+5+7+2-4+4-11+9+3+7%20-8%20-4-6%20-4-10-2//1+8+8//1+10-11%20-9%20-10-10%20-4-6+8%20-6-4//1+11+5-5//1+5%20-7//1+4//1+8//1+10+11+7-7%20-2//1+4-9+5-5+7%20-7%20-11//1+3%20-10-6%20-9+3%20-9-6-3-5-10-11//1+7-4//1+3%20-3//1+10-6-6+11+3-5%20-6//1+4//1+3%20-10-4%20-10-11%20-8%20-11-6//1+2+7//1+3-11+7-10%20-11+5//1+6//1+9%20-4-5-2+8%20-6-8//1+9//1+7%20-9%20-2%20-9//1+6//1+2%20-10//1+11//1+3//1+3//1+6+6+10%20-3-8%20-10%20-4+9-8%20-8-6%20-8+7//1+3+3%20-10+4//1+4//1+7//1+2%20-9-11%20-8+4+6-6%20-10-7-7+8//1+4-4//1+6%20-10//1+4%20-2-5-4%20-4+11%20-11//1+2-2//1+11+3+11//1+5%20-2-3//1+11+2-2%20-3%20-6+2+4-3+9+4+2+4//1+3+9//1+9//1+4//1+9+4//1+6//1+10+6+3+2//1+3-4+5%20-9%20-2+7%20-8-6%20-11//1+6+8//1+11+8%20-5-6//1+11-11//1+2-9//1+10-6+6//1+2//1+11-11-2-4//1+4-11//1+9%20-4+11+8+5%20-4%20-4+10%20-2-9-9+11//1+8+4+5%20-9+11+10+3//1+6-11+3+9//1+10//1+2%20-9+10//1+5-8-11-8//1+8-7-11%20-9-8+7-10//1+2+8%20-3//1+9//1+3//1+7//1+9-9+9//1+2%20-7//1+6+4//1+11%20-5//1+2//1+4//1+7//1+11-3//1+5//1+2//1+11+3%20-9//1+4-2//1+7+6%20-11-5-3-4//1+8-5-9-11//1+5%20-11+4//1+6%20-2//1+10//1+2//1+6+8%20-10//1+9-10//1+3-8%20-7-6-3-5%20-10//1+6//1+3-11%20-3-10//1+5%20-6//1+8%20-3-6-8%20-2//1+10%20-6//1+7%20-6-11//1+11+9//1+2%20-6//1+5+7-6+5//1+10-9+7-6+2%20-3-5//1+7+9+11-2%20-4-10+4%20-4%20-6%20-7//1+5+3+9-5-8//1+2+10-8%20-3%20-8%20-5//1+2+9//1+5-10-9//1+5-11//1+7//1+4//1+11//1+11%20-2%20-10+8%20-2+2//1+8%20-4-11%20-4//1+5//1+7+6//1+3%20-9%20-10+3+11-4%20-10//1+6-10+10+10-6//1+2%20-4+8-8//1+5+4-5//1+10%20-9%20-4%20-5//1+5//1+11%20-5%20-4+2-9//1+2+9%20-7//1+2+4%20-3-2%20-10%20-4-11+2//1+5+2%20-5//1+5-2-5-3//1+3%20-8//1+3+3//1+10//1+8-3-11//1+7+4//1+10+5%20-3+2+5%20-6-7-2//1+4-9//1+3//1+2//1+5-10//1+4//1+9%20-4//1+5-11//1+11+8%20-7%20-4%20-11//1+8+2-10//1+6-8%20-9+6//1+2+5%20-3%20-11%20-9-10-11-9-11+6-11+10+8//1+6//1+5%20-6//1+3-6//1+6%20-8-8-9+2%20-3//1+8//1+7//1+10//1+6%20-8-7%20-2%20-2%20-5//1+8-4-7//1+5-9+10-3-5//1+3-11%20-3+6%20-7//1+6+4+2+3+2+10//1+9%20-6-5//1+9%20-4+9%20-8+7%20-8%20-6%20-5%20-6%20-10%20-6%20-10//1+4+10+4+5-3%20-2%20-5%20-4-5-8+3+2//1+11//1+11%20-9-5%20-7+5-4+9%20-5//1+8-3//1+7+6-3%20-11+9//1+2%20-9//1+11//1+4%20-11//1+2-11%20-9//1+3-9%20-4//1+4//1+9%20-3-6-10%20-8%20-3+3//1+4//1+3+11+3%20-3-8//1+11+2%20-4-10//1+6+4%20-9-9//1+10%20-7-11-4//1+5//1+3%20-5+2-11%20-8-8//1+2+11-11%20-10//1+4%20-5//1+2-4-10-4//1+8//1+3//1+9+5%20-11+7-3//1+2%20-8-8//1+8//1+5//1+7+11//1+2//1+8+6%20-6%20-11+11-11%20-2%20-10+5%20-4//1+9//1+10+4-2%20-5%20-6//1+5//1+11-7//1+4%20-11%20-6%20-10+7//1+3+10+7-7//1+10//1+6-3-8//1+10+11+2//1+9-8-9-6//1+7%20-4+6-7%20-5+2-8%20-10+5%20-5//1+11-11+9//1+2+4+3+5//1+11-2//1+7+4-7//1+9+3%20-9+6-2%20-11//1+3-5+3+6%20-11//1+3%20-7+10%20-9%20-6%20-3%20-6-6-2//1+9%20-11//1+3//1+11-11//1+4//1+4%20-4-5-11+10+9+3%20-11+5-11-5%20-8//1+5%20-11-7+11+6+10//1+9//1+9%20-8-5//1+6-9%20-7+7//1+11+7%20-8-4%20-4+2-5+2+10-10+7//1+9//1+4-7%20-8//1+7//1+4//1+4+4-2-6%20-11//1+5%20-3%20-6%20-8%20-4+10+10//1+3-5-5+3-6+11%20-3+11-6+4//1+8//1+9-5+2+3//1+3//1+5%20-4%20-3+2+4+5//1+7%20-11%20-9+10+4+3%20-8//1+5-8+8-3-5+11-3%20-3//1+6-11%20-9+2-2-6//1+5//1+9-11-6//1+10+9+4//1+5-2-11//1+5%20-6//1+7//1+5+11+8+8+3+7-2+5-9+9//1+2//1+10-3//1+9-7%20-9//1+10//1+4%20-3%20-3-5%20-5//1+6-4%20-6//1+9%20-5//1+11//1+7-10+6//1+2+7+9+9%20-5//1+2//1+4//1+9%20-4%20-5-11//1+2//1+7+2//1+5%20-3+3//1+2//1+10-5+9-4//1+8-7-6%20-7//1+8//1+9-9%20-5//1+11+8-7-5+3//1+8%20-2-4-2-9+11-7//1+10+3%20-8-8+8-7%20-10+2%20-11+9-2%20-5%20-7+2-10%20-10%20-3//1+2%20-9//1+3//1+4%20-11+5+11+6+9-3-11+7-8//1+4+2+2-5//1+4//1+2//1+10+11%20-4-8//1+11-9-11-8//1+6//1+3+8//1+6%20-7%20-11%20-3+5%20-7+2+8-5//1+6%20-4+10+3//1+4+5+11//1+3//1+5-4-7%20-7//1+4+5//1+3%20-6+10%20-7//1+8-7%20-7//1+5//1+9//1+2-9//1+10%20-11-7%20-3%20-11+6%20-3%20-2+10//1+4-6+10%20-7+9%20-2//1+7+4//1+7%20-2%20-2//1+4%20-10%20-7%20-7-11//1+11-3%20-3//1+5+11//1+10-2%20-9-6-8+9+2+4//1+4%20-7+6+2+8+8%20-4%20-5-9//1+9+8+5+9+11%20-10+8-11-8+7//1+11+7%20-5+2%20-5+3+11%20-3%20-9-10+4+5+8//1+4-6+7%20-6+11+11-9%20-2//1+7//1+9-6//1+4//1+10//1+2//1+10%20-10-4%20-2//1+5+6+8+3-6-8//1+2-11%20-6-6//1+7%20-8+3+8//1+3%20-8%20-2%20-10%20-11-6%20-10+3-4%20-11//1+10+9%20-4-3+4%20-2+3+9//1+3+6+10+7//1+3//1+9//1+4+6%20-7+11-7%20-7-11+2-7-4+7//1+11%20-9+11-10//1+11+4-8//1+9//1+7+10//1+4+9//1+9+5-10+8+7+10//1+3+7-2-9%20-4%20-7//1+8%20-10-2+3+6//1+7-10+11//1+6+10//1+4-7-8%20-6-8+4+3-2//1+2%20-6+11-3+6%20-5-2%20-6+9-4%20-2//1+3-4%20-3-3//1+7-7-10+8-6//1+9%20-8%20-10//1+7+5//1+11//1+8+11+10%20-9//1+5//1+8-10//1+8//1+6+9//1+8-5%20-10-8+4-3//1+6%20-4//1+3%20-2//1+7%20-7//1+3//1+4+10//1+11//1+11+7%20-11//1+4%20-3%20-7-11//1+5%20-7+7%20-6-6%20-9-10-10//1+10//1+6-9%20-9+6%20-8//1+9-6%20-3+3-11%20-9%20-5+11%20-5//1+9//1+5//1+3%20-10%20-11-7-5%20-3//1+10+7%20-4%20-9//1+5+6%20-2+6+3+8//1+11%20-10+5//1+5+6//1+5//1+5//1+2%20-10-5%20-3//1+5+3-6-10%20-3-5+7//1+2%20-9-8//1+3//1+6%20-9//1+10+5-3%20-10//1+5-8%20-11//1+2//1+4%20-4//1+7+11-6+11//1+11+3//1+3+6%20-4+4//1+3%20-2//1+2-11//1+2+2-11-2%20-11//1+5%20-11-4-2//1+11+8%20-6-2//1+4%20-5//1+4+3+4//1+5-7+10-6+8-6-11-7+10+5%20-7-5+10%20-10//1+10//1+9+2%20-5%20-5+11//1+4%20-5%20-4+2//1+9+10+5//1+3%20-10+4//1+2%20-3%20-4-10+4-5+3%20-2-8-10//1+3-3%20-4-11+6+3-2//1+4-4-6%20-8+5+5%20-4+5+11-3%20-3%20-7%20-2-2+4//1+11%20-8+8+4%20-6%20-11+8%20-3-6//1+5//1+4-4//1+4//1+11-11-7%20-8//1+7+5-3+9%20-9%20-10%20-10%20-11//1+2-5//1+10//1+6+7-4%20-2//1+11//1+10-10%20-2+3%20-2//1+10+11-7+11+4%20-6-10//1+8+11%20-7-10-11//1+4//1+7-11-4//1+4+3//1+2-3+7%20-9%20-8%20-10-7//1+5+2%20-3-2%20-4-8+7//1+2%20-5//1+2%20-2%20-6//1+8%20-9%20-6-11%20-11//1+8//1+9//1+5%20-9%20-3%20-2%20-3+10-2%20-3%20-3//1+9-2-6%20-10//1+11%20-8-4//1+8+7%20-2-7%20-4-11-4%20-2%20-11//1+10//1+8%20-9+7%20-8//1+4%20-5%20-11//1+5%20-3-4%20-2-7-2%20-8-6%20-10+6%20-10+10+5-10+8+10//1+8+3//1+10//1+6//1+2%20-2%20-11-9-3-7+9-2//1+10+8+9+3-4+3+3//1+2-10//1+6+5+7//1+11-9+6//1+10+7+10+6%20-6-7%20-2%20-6//1+11%20-9%20-11//1+2%20-2-10+2-10%20-9+3-3%20-2-6+9%20-7-3-6-2+8+10-9//1+5//1+4-8%20-10%20-6+4%20-4//1+9//1+10%20-7-6%20-3//1+8%20-3%20-2%20-9//1+8-2-10-5+3//1+9-6-9+4-10-11-9+3+11-10+7+3//1+4//1+3+7%20-9-8%20-4//1+8-7-4%20-2-9-5+2//1+9//1+5//1+4-10-7%20-8-10-5//1+5%20-3%20-11+9//1+11+3%20-11-4%20-4+11-9+8-7+8//1+5//1+9-4-9-9+5%20-6//1+2%20-10//1+11%20-2+2%20-8+2%20-11//1+9//1+7+3+7%20-3+5%20-3%20-7+2+6//1+7+6//1+6+9//1+11//1+6-10+6-10//1+10-11-7//1+7%20-6+10-6+7//1+4//1+6//1+6-10+9//1+8-8-2-9//1+9+6-7//1+10-7//1+3//1+5%20-4//1+4%20-4+4+5-8//1+10%20-5-2%20-5+5+6//1+6%20-2-9-9//1+3+11+9//1+3//1+8//1+4%20-3%20-7%20-3%20-2+10-9%20-11%20-3%20-8//1+8+11-6-3//1+2%20-5//1+3//1+6-5-6%20-11%20-4//1+3//1+6%20-9%20-9%20-8-5-7-2-10//1+11+3+10+6-5%20-9//1+5-4%20-11//1+4-10%20-4-4//1+2//1+11-2-8//1+4-3%20-3%20-2-10-8+6%20-3+6+10//1+5-4%20-9-2%20-3%20-9+11+6%20-2%20-9-10%20-6+9+10//1+5-4//1+7//1+10//1+3%20-6-4//1+4+10//1+6//1+6+11//1+11+3%20-11-10%20-6-11%20-2%20-9-5//1+7+9%20-11%20-5-5%20-3//1+9+11-11+10-10%20-5-3%20-6//1+10-3-8-4%20-4%20-4//1+7+6-7+4-3+10%20-11%20-4//1+5%20-11//1+8-10-11%20-3//1+8%20-2+9//1+6%20-5-4+4+7//1+4%20-9//1+10+2//1+4-3-8+3-2//1+5//1+7+11//1+9//1+11%20-2%20-9%20-7%20-11%20-5-5-7+3//1+3+2+10//1+7-5%20-5%20-2-2+3//1+3+9//1+11//1+4//1+3+11-8-2//1+2%20-9%20-2%20-10+9%20-3-3%20-6-6%20-10%20-7+3-2+10-4%20-9//1+7+6//1+11//1+4%20-6+10+9%20-4//1+11-6+10+11//1+7+8//1+7-11//1+3+7+6%20-11+8//1+11%20-4+7//1+2//1+10+3+5-11%20-11-10//1+3//1+10%20-6//1+3%20-5//1+2+5+4+8%20-9+7%20-2+6%20-5%20-10%20-2//1+5//1+4-6-7-5-3+8//1+5%20-5+3%20-8//1+3-7//1+8//1+7//1+4-9//1+4%20-4+8%20-6%20-7+8%20-6%20-9%20-8//1+9//1+4//1+7-2+10+6-4//1+11//1+5//1+10+8+10//1+9-8-8//1+2%20-7//1+7-3+7//1+11+6%20-9-11-3+2//1+8-10%20-3-2//1+5+11//1+10+5+4//1+4%20-10+6%20-5-10+11//1+8//1+11-4-2%20-9-3%20-4%20-6%20-6-3+2//1+7%20-9+5-3+7+2//1+10//1+7+11-11-2//1+5+4-2//1+8%20-5//1+4-2%20-4//1+4//1+7+3-3+2-5+6+9%20-4-3//1+11+8+4-5//1+7-2-4%20-10//1+8%20-10//1+6//1+4-4//1+2%20-10+5//1+3+2%20-9//1+3%20-11//1+9//1+8+8%20-4+5//1+2//1+8+8//1+6//1+2//1+10+2%20-11//1+5+7//1+8%20-7%20-5//1+4+10-4+2-6-2+2%20-9//1+5%20-8-6-11-10//1+11//1+5+7+4%20-2+5-10%20-7%20-9//1+10+6+7+2-3//1+10%20-2%20-4+3//1+9//1+9%20-7//1+10%20-5%20-4-4%20-2+7%20-3//1+6+7-6+7//1+8+2//1+7//1+6+5//1+4//1+9//1+11+8+2//1+6%20-11//1+5-4-11//1+8//1+7//1+6//1+8+9%20-4//1+7%20-10-5-4%20-2//1+7-10%20-9//1+9+10-6+4%20-8-2//1+10%20-10//1+6//1+5//1+8//1+5//1+11+4//1+10-8+10%20-8%20-3-6+6//1+4//1+6+6//1+10//1+6//1+6%20-5//1+7//1+2+11-8//1+2-2%20-3//1+11-6//1+3%20-5//1+11%20-2%20-3+9//1+4+11-8//1+6%20-9-6-4+10-4-4%20-8%20-11//1+3+5//1+10%20-7//1+11+9-4-8+7%20-6//1+7%20-8+9//1+5-6%20-10//1+9-2+11//1+5+5+11-5%20-8%20-6%20-6//1+11//1+11-8%20-11+6-5%20-5//1+11%20-8-5%20-11+8%20-9+4-11+7//1+2//1+7%20-7//1+7%20-7%20-9//1+7-8+4//1+10//1+11+2%20-5//1+5//1+3+9-3%20-4-11+5//1+3//1+6//1+11%20-4%20-5+4-5%20-2+2+7%20-8%20-4%20-10-2-2%20-5//1+8-9%20-4%20-9//1+7//1+7-7%20-5+9+4-5%20-3+7+10-2-6-9//1+5+9+10%20-9%20-4%20-3//1+8+2-8//1+9//1+9//1+3-8-2+3%20-2-5%20-8-7-8%20-4//1+7%20-9-10+6+4-4%20-10//1+7%20-9%20-11%20-5%20-11+2-7-8-2-7+2%20-5//1+4//1+4//1+9%20-2//1+7-8//1+2+2-5+9//1+10-9+10+2-10%20-10//1+4-6%20-5+3-6%20-2-2%20-10+3+5-11+8+9-2%20-9+6//1+8%20-5+5//1+9-10+2%20-7//1+7%20-8%20-6%20-4%20-4%20-11-3//1+2%20-9%20-3+4-6+9//1+5+2-2//1+6-5+3%20-2%20-6//1+3//1+4%20-4%20-4+5//1+3//1+11-9//1+8//1+2//1+3-5//1+2//1+2%20-4-7+5%20-4-7-7//1+6%20-6+11-8-6%20-6%20-5+9%20-11%20-2-4%20-8//1+3//1+4+11//1+11//1+10//1+2-9%20-6+2-7%20-2%20-10%20-6%20-2-10%20-9//1+2//1+2-5+11+3-8-5+7+3-3+9+10%20-3-9+7%20-4+6+11//1+10%20-7+2%20-11+3%20-5%20-10%20-6//1+4-2//1+9-7+2//1+3-3//1+7%20-2//1+2+11+4-10//1+7%20-2%20-2%20-2+6-3//1+11%20-6-2+9-3+2//1+11//1+8-6+9+11%20-6%20-2-7-8+9%20-6-5-10-10//1+3-3+4//1+9-6+9//1+10+2-3+4%20-6%20-10//1+5-4-11//1+9//1+7//1+3-5//1+3//1+3+2+2%20-6%20-5-11-10+10+6//1+6%20-10+4//1+6//1+10+6-8//1+5%20-11+2//1+5+7//1+8//1+3+4%20-2%20-2-9-4+6-9-7+11//1+2+5-6-8-8%20-4-6%20-4-9-3-4//1+5//1+4//1+4+4%20-10//1+9%20-11+7//1+3-6+3%20-8//1+8//1+9//1+5+10%20-9%20-4//1+10+10+2//1+7//1+11%20-2-10+7-6-10+2//1+9-9-11+10+5//1+8-5//1+9%20-6//1+6%20-11//1+3%20-7%20-11-9%20-10%20-11//1+3+2-9+8//1+4%20-7%20-3+3//1+6%20-7//1+10%20-3//1+5%20-4-4%20-7%20-11//1+3%20-11-11%20-8+7+5%20-5+4//1+10%20-10%20-11%20-11//1+4%20-3-11-10+7-9-6+2+8//1+4//1+4+9-8-9-3+2-3//1+5+11-5%20-7-6-10//1+10+9+11-3-8//1+3//1+3%20-6%20-6-8//1+10-5%20-9%20-2%20-4-2//1+7-8-10//1+2//1+8-6-10%20-8+8+6-4+7+4-10-9%20-9+8-2//1+10+10-8%20-7//1+10%20-10+10-5-2%20-7//1+3+4//1+5-8+3//1+9-10//1+3-9//1+5//1+7+4+10-7-6//1+10-6//1+7%20-8//1+3//1+4-9+6-2//1+5-9+4-5//1+4-5-10%20-9//1+2-6-8//1+8-4%20-8%20-6//1+10-3+6-11-8+6+6-8%20-3-7//1+5+8%20-3//1+9+8//1+11%20-4-8//1+6-9//1+2//1+11-9//1+3-5%20-7//1+3+5//1+6-5%20-3+6+9//1+10-5//1+4%20-6%20-10+8-11//1+2+6%20-6+5-3//1+6%20-3//1+6+11%20-2//1+3//1+6-9//1+3%20-8+10-5//1+10//1+5//1+2%20-6%20-4-10-2-8+2+10//1+8//1+3%20-5-4-11+7+2%20-7//1+10%20-8%20-7%20-9//1+8-7%20-2%20-5//1+9%20-3//1+7-4+9+10+3-5%20-6+2//1+5%20-6-7-8+2%20-11%20-4//1+6+5%20-2//1+5+3//1+5-2%20-2+5%20-5+10//1+2%20-9+11//1+4%20-6-3-8+8+3+11+9-11-10-2//1+2//1+10%20-6%20-7+3-6+9-6-11//1+9-2+10%20-7+6+5+6+2-3+4-4//1+3//1+3%20-8%20-7+7-4+10-7+5-8+3-8-2+3%20-4+8//1+11//1+7+6%20-7%20-7-2-4%20-3%20-7+7//1+6//1+4+8%20-8%20-6-2//1+9-6//1+7-3%20-3//1+8//1+11-2%20-9//1+5%20-3//1+2+4//1+3+4%20-3%20-4+4+2%20-8-7-6%20-9+5+11%20-10%20-11+8+6//1+11-2//1+3-8-6-4-4//1+4+10-8//1+9//1+5-7+10+4-2-6%20-9-9+4-3-3+6//1+7-11//1+11%20-2%20-7//1+3//1+2//1+6+3+8%20-6+9-6%20-11//1+3%20-9//1+8-11-3%20-10-8//1+7//1+6//1+11%20-5//1+5//1+5-3+8%20-8%20-9%20-5//1+6-10+3+3//1+3//1+8-8+4%20-10//1+7+9-9%20-9+7%20-9-2%20-3//1+9//1+10-3+10%20-3%20-3-11%20-3-6+4-2-6+5+9+5//1+8+11+4%20-9-9%20-10-5+3-11-2+10//1+9%20-11%20-11%20-4-9%20-5//1+11%20-9//1+10%20-10%20-5%20-9%20-3//1+9//1+9//1+9%20-7+9-8//1+4//1+3//1+8%20-10//1+6+4-6+7%20-2%20-10//1+2//1+7%20-5//1+3+9+3%20-10-4//1+9-2//1+4%20-4//1+9-10-3+10+9%20-11//1+2-3+2%20-7%20-2-7-10//1+5+2//1+6//1+11+5-11//1+7-11-7//1+5+10+10//1+4%20-11//1+10+5//1+9+10+5//1+8%20-4//1+3//1+2+11+3%20-5//1+8//1+9//1+9+6+5//1+8-11//1+8+2+4+4-8//1+5%20-9%20-3%20-4%20-10-5//1+6//1+2-5//1+11//1+10//1+10+8%20-8+7-4+11//1+2%20-10%20-11%20-10+10//1+6//1+3//1+8//1+6-6+8-9%20-6%20-4%20-5+9%20-6//1+3-6-5%20-2%20-2%20-8//1+9-3//1+8//1+11+5-6-2//1+11+3+3-8-4+3+11+3+6+11+3+3%20-8%20-4%20-4+7+5%20-2-5-4+4-4+5%20-7+11%20-6-2-9%20-11+11//1+6%20-5%20-9+6+9//1+11+2//1+7//1+6-9+9-10-7%20-9-8//1+9%20-2//1+11+8+3%20-6+7//1+8-7//1+9//1+6+9//1+8%20-8%20-2%20-10-9+3//1+9+11+9//1+10%20-4+11%20-3+7+10-4-8//1+7//1+7//1+6-11+8-10+2%20-9-4-8//1+2+7//1+11-6//1+6+3%20-8-9+2//1+6//1+11//1+9//1+5%20-9+4+9-11-8%20-9-4//1+7-2//1+11//1+7-2+9//1+4-10%20-4+5+8+5%20-8+2+5%20-7//1+8-8%20-4-9-2+5-3-11%20-6%20-2+3%20-7//1+7+11%20-4-5+4+11+10+10-11%20-6//1+11+8-8//1+9-9%20-6-5-2-4-4-8%20-8%20-2-5-11+4-6%20-6%20-7+2//1+10+6+5%20-9+11+11//1+4+6//1+6%20-6%20-11+10-8%20-7//1+11-7+2-10//1+7-3+11%20-4%20-4%20-8+5//1+4//1+3//1+8+10//1+4//1+8//1+2%20-9//1+5+8//1+3+5-11+6//1+7//1+8//1+5%20-5%20-3//1+4+10-6+2-6%20-2//1+2%20-10//1+8%20-3%20-4-5//1+6+10%20-10//1+9%20-11%20-11+5%20-11%20-2%20-10//1+8-2%20-2%20-8//1+9-6+3-7+2%20-9//1+9-5//1+9+4%20-3+9-11//1+9+2//1+8-5+10//1+5%20-4-6%20-11+2//1+3//1+10//1+5%20-4//1+3//1+9+2%20-2%20-5//1+6+8%20-9-7+10//1+7+5-4%20-5-7%20-3+9-6//1+2-3//1+11//1+9%20-7%20-11//1+11+10//1+6%20-2-6+8-3-9-6-9-4//1+5%20-2%20-4-10-9+9%20-3+8%20-5-5+8-9-10+10+4%20-10-10//1+9//1+6//1+9+9//1+8+5+6-11%20-8-4//1+8//1+10-2+4//1+8+8//1+6+4%20-6+11-6%20-11%20-10//1+2%20-6//1+6-3%20-11%20-3//1+5//1+11//1+7//1+2//1+4//1+6-10+9+5//1+8//1+10+5+3//1+10//1+5//1+5-7+11+4+4%20-11%20-9-7+6%20-10//1+4//1+2-8%20-7-8//1+9+4%20-10%20-10//1+9-8+8+11//1+10//1+9//1+8+7%20-7-5+6+11//1+5+4+7//1+4%20-4-4+10+10-3//1+2-11-8//1+6%20-6//1+4//1+4+7+3//1+3%20-6+5+9//1+11%20-8//1+6%20-5//1+7-8//1+5-8-11%20-11%20-10//1+2-5//1+6+9%20-9//1+10-10%20-7//1+2+2//1+4//1+11+11//1+7-6%20-6%20-5-9%20-7%20-5%20-3+10-4%20-3%20-6//1+7+8-8//1+9//1+3-7+6+4-8%20-2-6-5-6+6+11+8+5//1+11%20-10+2-11+7//1+4%20-2//1+10+10%20-10+2+3%20-2+2-11-5-9%20-6+9//1+6%20-9%20-7+11%20-10+8-5//1+2-2%20-5//1+9+10%20-7//1+11+7+4+11//1+7-9-9//1+11-9%20-6+7%20-4+7%20-4//1+6%20-6-6-10-4-11+3%20-5+7//1+2%20-4%20-11+8+9+3+4//1+3%20-9//1+11-4%20-6+11%20-11%20-9//1+9%20-7-8//1+7//1+4//1+10+11%20-2%20-9+2-2+7%20-4-10-11+2-2+9%20-5-6//1+8%20-8%20-3-4+4%20-7%20-8//1+5%20-4+5+6%20-6-5%20-3%20-5%20-9-10//1+8//1+10+8//1+3-11+3//1+9//1+4-7+7//1+6-4%20-5+7-5-5%20-7%20-3//1+5%20-7+3%20-9-7%20-2-6-2+11//1+4+3-9+6-4%20-2+6+5//1+3%20-9+3//1+3-11%20-11-3+2+3-9+2-10%20-8%20-6+8+11+3+3%20-7//1+3-10%20-5+11-10%20-5%20-5%20-7//1+9+8-11%20-9//1+11+7+6//1+2//1+11//1+11-5+11%20-3//1+7%20-7-8//1+6%20-8//1+4+6-7+8%20-7+4-9//1+3+8%20-3%20-2%20-10%20-2//1+10-5%20-5-3//1+10-11-9-6-9%20-7-7+5%20-9%20-10-8//1+8//1+2-10%20-8%20-8-7//1+10//1+4+11//1+7%20-5%20-10+4%20-2+6//1+2+11//1+6-10-7%20-5%20-7-4-11+8//1+11%20-9%20-10-5%20-2%20-11%20-3+8+7%20-4-8%20-9+11%20-4-11-7-6%20-8-3%20-4-6-3+9%20-3+3%20-11-9%20-11+3-9+11%20-7+7//1+10+8+7+8-3+10%20-6+9-6+10//1+9-5-3-4-10%20-4-2//1+10-8-7//1+11+2+8+11//1+6%20-9%20-11-6%20-2//1+7//1+7+5//1+8%20-3%20-11-11%20-2//1+8+5+4%20-9//1+5%20-5-3%20-4//1+8%20-10-4-3%20-10%20-4+11%20-6-3%20-2-2+2-9%20-9-9%20-4-10//1+5+2+7%20-11%20-11%20-8%20-11%20-6-7+2%20-3+3//1+11//1+4-6-6+7-3-5//1+8-8-10%20-4+8-7%20-2+2+5+11-4%20-5//1+11%20-5-6-5%20-3%20-7//1+9//1+11%20-2-8+10+9//1+2%20-4//1+7//1+9-9%20-7+9//1+8-9+5-5//1+10-11-11%20-10+11+3-7%20-4%20-10%20-10-5-6//1+6%20-5%20-11+7%20-4%20-9+8%20-11%20-9//1+3+6-8//1+2-2%20-4+5//1+4//1+7-2-10%20-4-4%20-7//1+8-3+11+3%20-2+2%20-10//1+11+8%20-8%20-8-11+8+2-10//1+2-4-6+10%20-9%20-9//1+10-11-10+11//1+8%20-10+4%20-11//1+5%20-5-11+11-7+11-3%20-11//1+8//1+6%20-5+5%20-11//1+5+8//1+11-8-9//1+6-3//1+6//1+9%20-4+4+10//1+4%20-10+7%20-3+7//1+6//1+11//1+8-5//1+7//1+2%20-4//1+6//1+5-10-4%20-11+10//1+2//1+5-5-5+4%20-5-2+9-7%20-4-8//1+5%20-3+5//1+5//1+10-7-4//1+3%20-9//1+8-3%20-10-9+8//1+10-8//1+11-9+4+4//1+5+3//1+9//1+4-3-11+9-6+7//1+8//1+6%20-7%20-3%20-3-3%20-9+4%20-3//1+11//1+11//1+4%20-9-5+2%20-2+7-11//1+6+4-9%20-5+3+10//1+11+10%20-8-4//1+9+3//1+8-8-6+9%20-10-9+7//1+9%20-5-7%20-3%20-8//1+3%20-2-11+11//1+9%20-7//1+10%20-4%20-6//1+8%20-6%20-6%20-10+3-4//1+11-11+11-2+10%20-5//1+6+10-5+5//1+9//1+4%20-8%20-4//1+3//1+3%20-11%20-3+7+6+11%20-7%20-5//1+8//1+5+9-3%20-8-5%20-9-8-10-6+3-9-7+6%20-9%20-8-3%20-11%20-9+9//1+3-2%20-8%20-9%20-11+5%20-4%20-10+9-5+7-10-2-6+4%20-3+8//1+8+9+7%20-4-10-6//1+9-8+10+7//1+2+6//1+11+7//1+4//1+10//1+6//1+9-5-3//1+2%20-2%20-4%20-2%20-3//1+8+7+6-8//1+2%20-5//1+3//1+7%20-8//1+6-4-2//1+10%20-2-9%20-9+9+9-5//1+6-3+11%20-7%20-2-9%20-5%20-8%20-11+9+8+2-2+7+10%20-7+6%20-9+7+2%20-5+10-10%20-11-5+9-9-3//1+9+7+10//1+3+10-10//1+11+5//1+11-4//1+10+3%20-6//1+5-11%20-7+9-10-10-9%20-2//1+2+7-4//1+5-6+11%20-10//1+5-11%20-9//1+6+9+9//1+3+3//1+4+10-8%20-4%20-11-5//1+3-8//1+2-7-2-2-8+4+6//1+2-4-8%20-3%20-5-4%20-8%20-10+8//1+11+3+10-2%20-6+10-2-3%20-9+8%20-10%20-3%20-10//1+4%20-6%20-4-5%20-4//1+9+10%20-6//1+5//1+7-5//1+9+5//1+7//1+5%20-10//1+5%20-9//1+8//1+8%20-5-3+6+6%20-11+11-7-11//1+9-8-2-10//1+8%20-5+3//1+11//1+5%20-10-6//1+5-3+11%20-8-10%20-3-5-3+5+7+4+4//1+8%20-6//1+3%20-3-6//1+8+5-7-11//1+7%20-4-2%20-4-8//1+6//1+9//1+7//1+10%20-9-10//1+4%20-10-4+6+6%20-11-2//1+3%20-5//1+11//1+3%20-11%20-11-6//1+9//1+9-7%20-2%20-11%20-9%20-10%20-6//1+4//1+4+8+7//1+7-5//1+4-8+3//1+7//1+9-7-11//1+4//1+3%20-6%20-9+8-3%20-11-9//1+9+2%20-9-5+10%20-4%20-4+8-4%20-3%20-4+3//1+7%20-2//1+2%20-11+5//1+3//1+10%20-8+6-5%20-6//1+11-5//1+10%20-10//1+6-6+11+6-8//1+11//1+4-2//1+2+7-4//1+9%20-2-6+4+6-2-2-8+5+8+3+11-3-8//1+6%20-8+8%20-11%20-3%20-7+3+10//1+3+10//1+5+2%20-7-10-8+2-2-11%20-11//1+6+5-4//1+2+5%20-5//1+4-7//1+6//1+9+3-8//1+4-7//1+11//1+9+8-2%20-11%20-6+5-8-8+8//1+6//1+4-4%20-11//1+6-9//1+3+5+9+5+6//1+7%20-8+11-10%20-3%20-7+3%20-6-8+5-11-8//1+2+7%20-5//1+5//1+4%20-9-3+8%20-4-3+6%20-7%20-3%20-3%20-5+5+8%20-8-11%20-9//1+9-3//1+6-9//1+9+5-2-4%20-7-10-9+2+3%20-3%20-11-10-8+8-9-2-8-8%20-9-4//1+11%20-10+11-7+2-4%20-8-4%20-9+8%20-4%20-2+9+5//1+7+6//1+4%20-11+6+5//1+2+5-6+5%20-11//1+8+11-3+2//1+4+2%20-11%20-6//1+2-6%20-10//1+4+7-10%20-10-8+2//1+2-2-9+9//1+5%20-7//1+2//1+5-5+7%20-7+10//1+7+5+4//1+9+2+9%20-7-8%20-11//1+6//1+11%20-11-6%20-8//1+8-5%20-4+3+10//1+2+4-10+10+9+10-11//1+6%20-7+9%20-5-3//1+8+8+9//1+7+9//1+7+8-9-3+7+8-6-5-4//1+3+10+11+8+10-9-8-10%20-4%20-5//1+7%20-8//1+6%20-7%20-9-6//1+11//1+10-4-9%20-11//1+2%20-4//1+9+10//1+7%20-7%20-6%20-3-6-8%20-10+8%20-7//1+8//1+8%20-3//1+9%20-6//1+3+8-6+9-11%20-4-4//1+6//1+4+10//1+8+11//1+11%20-2+8+5//1+6+11+8-11//1+10//1+8//1+10%20-9+8+11+3+9%20-5%20-4+2//1+3-5+9+4+7+3%20-4%20-10%20-5+4%20-9-5//1+2+7-4-10//1+4-3-6+5+9//1+3-8+11%20-9%20-2-6%20-10//1+11-2//1+10+9-3%20-4-7+2%20-2//1+2-10%20-10%20-6%20-2-4+4%20-5//1+2+10//1+6-4+10%20-7+2+10-7-7+7//1+11-10-11+2-5//1+8%20-6//1+10//1+6%20-4//1+11//1+2//1+3+11//1+2+9%20-3-8//1+2//1+9%20-9//1+3-3+6%20-6-6%20-11%20-2//1+3+7+4%20-7+11//1+8-2%20-6+7%20-11+10%20-8-7+2+2%20-3+9+7+9+9-7//1+10//1+7+6//1+9%20-4%20-9+9-3+2%20-5//1+11%20-3//1+6+5//1+2+8//1+5-4//1+2%20-11-10-4%20-2//1+11//1+6+10+3%20-11+10+7+4+6+7-8%20-3%20-11-10+11-5%20-11%20-5-6-7//1+6-8//1+11-11%20-3+3%20-5+9//1+7+2//1+3%20-2//1+10-10//1+8%20-2//1+11%20-5+8%20-4//1+4//1+3+6//1+10-10-3//1+3-6%20-2//1+7//1+11+10+7//1+3//1+6+10%20-2//1+4+10//1+6%20-11-7//1+4-9//1+2//1+2//1+7+9%20-11//1+4//1+2%20-4-3-10-5%20-10+4%20-11+6+5//1+7%20-6//1+8//1+8%20-4+8-5+2-9%20-10-11+3%20-2+8+2//1+6//1+4%20-7+7%20-11%20-8%20-2//1+7%20-8%20-6//1+7+9//1+9%20-11+3%20-8//1+9+8-9//1+6%20-4-8+7%20-9-7//1+7%20-10//1+7-10+5//1+8+2-10%20-10//1+10%20-4%20-9//1+2+10//1+10%20-6//1+2%20-11%20-7//1+4+7//1+3%20-9+4-5//1+3//1+6%20-3//1+9%20-3%20-10//1+9+4+8-9+11-9-4%20-9//1+9//1+4-10+9-8-11%20-7-8//1+2//1+8-3//1+3//1+8-2//1+5//1+7%20-9+4-11//1+9-5-6%20-9%20-2-8-11-10-7%20-4//1+7//1+8+6%20-5+10+11//1+9%20-7//1+9+11+7-10%20-5+9-8+4//1+11%20-4+5-7%20-11+11+8-9+5//1+4+5//1+3-7%20-8%20-9+7-6//1+5%20-8-8//1+10//1+11%20-10//1+9//1+10%20-6//1+4-4+10%20-7+4-6%20-9//1+7-5-11+3+3//1+3+3//1+8%20-2-3//1+8%20-7+9%20-2%20-8//1+8+3//1+4-9%20-4+8%20-8%20-11-10+7%20-4//1+6-9//1+4-3%20-2%20-5//1+8%20-5//1+3//1+9%20-9//1+8+5%20-7//1+6+5%20-4%20-2+4+4+8%20-4%20-3+4%20-7+5%20-2+6//1+7-9+3%20-4%20-6%20-8%20-5//1+9-4//1+2%20-7-11+6%20-7//1+6%20-10%20-6%20-10%20-8+10-3%20-7+5//1+5%20-2//1+7+11//1+3//1+6//1+2-11%20-7//1+9%20-2+2-10-7//1+3//1+5//1+11-4+7%20-9//1+3-10+8+4%20-6-4%20-8+4-8//1+5-11-4%20-11%20-7-6+8%20-5//1+2+3-3%20-9+7+8//1+8+3+8//1+6-7+9+6//1+3+4%20-3//1+11//1+4%20-6//1+2%20-9-4+10-9%20-6+9//1+6-9-2//1+2//1+11+3%20-4-6//1+5-11-3%20-11%20-10-9+6-11+9%20-4%20-5-6+7//1+2//1+4+4-11//1+3//1+7-8-3//1+4%20-10-6-8-8%20-5%20-3+5//1+11-5%20-8//1+4+3%20-6+8//1+2//1+7+7%20-10//1+6//1+4//1+4%20-11+7+11//1+10//1+3//1+5%20-10%20-7//1+10%20-10+10+2+5+4%20-5+7//1+9+11+2//1+3+3-4+8+11-7+6-8-2%20-7-8+3//1+10-6-10-10//1+6+10//1+2//1+4//1+4%20-3-11-2%20-10//1+10//1+6//1+5%20-10+6-2%20-4%20-3//1+4%20-9%20-7-11//1+7-7+10-4%20-7//1+11%20-5%20-8%20-3-4//1+11+6+9%20-2%20-2//1+6+4%20-7%20-2-8-9%20-4+2%20-9-8-4+3%20-10%20-9+2-4-4%20-11+4%20-7%20-2%20-6%20-6-4//1+9+3-7+8//1+7+11-7%20-9//1+11+6%20-11//1+10+6//1+5%20-9-4%20-9+3+10-2%20-7+11+5%20-11+6-2%20-10//1+6%20-10+7-6//1+8%20-8//1+9%20-9-8%20-5//1+10+2-8+8+11+6//1+8+9%20-4//1+4+6+9+5//1+11//1+5+9//1+6-6%20-11//1+11+9-9%20-9//1+6+6%20-11+10//1+5%20-6+10+5%20-6+6+2-4-11+6+10%20-5//1+7+5//1+4%20-5+10%20-5//1+3//1+7%20-5+9+10-8+9+6+4//1+8%20-5+7%20-11%20-4-9+3%20-11//1+8%20-7%20-4+7//1+6-2//1+8%20-9//1+7%20-6+4%20-4//1+3//1+4%20-3+11%20-10%20-3+9+3%20-5//1+11%20-10+11%20-4-11//1+8%20-8-5+6-6-3//1+3-9-6-10%20-9+4//1+10-8%20-6-2+10-5-5%20-9//1+8//1+2//1+10%20-8//1+11+8%20-3//1+2%20-10//1+3%20-8%20-10+8-6+10+4-6+2-2-9//1+7%20-8%20-8+3//1+8//1+6-10//1+7+8//1+8+10+5+7%20-11%20-5+5%20-7%20-11%20-9-8%20-6-7%20-9//1+5//1+6+4+11%20-7+9%20-6%20-10-10-2+8+9%20-11%20-3+5%20-4%20-3-3-5//1+3%20-2//1+10+8-4%20-2%20-8%20-7//1+7+11//1+6+4+9//1+6%20-10+5+5//1+5-10//1+7//1+2//1+4%20-5-7//1+7+7%20-3%20-8%20-8-5//1+7-11//1+9-11-5//1+6+9%20-3+4+4%20-8%20-8%20-7%20-9//1+3+10//1+5//1+4%20-4+10+8%20-5-11//1+11//1+2//1+7%20-9-7+2%20-9-10%20-11-10%20-8+10-4%20-8//1+4//1+8%20-10//1+11-3+10-2+3//1+9-4%20-10%20-11-9+2-6-5+11//1+2-3%20-11-7//1+7//1+3+2%20-4%20-10+5-7//1+7-10+8+6%20-2-7-3-8-10+10%20-3-11-6//1+9-9+8+4%20-10%20-5-9//1+8//1+3+10+7//1+10-7//1+9//1+2%20-6+3%20-8+8%20-7//1+7%20-10-4%20-7-3//1+11+5-4//1+6%20-9+6%20-8//1+8-3-8%20-5-4%20-7+6-10%20-3-8//1+3//1+3-3-7+9//1+5+5-7%20-5+4//1+10-5//1+8+10-6%20-4//1+4%20-6%20-3+8-3//1+9//1+8%20-3+11-9+10-11-5+5//1+10-6//1+9+4+9-9+4-3//1+2+3//1+6-9%20-11//1+2+10//1+11-4+7-5-3//1+10+5%20-5//1+9//1+6+7//1+11//1+9%20-9%20-4%20-9//1+4%20-3-11-8-7//1+2-8-5-4%20-2-4%20-8%20-6+7//1+10%20-2%20-6-4+7+10-11%20-9%20-9%20-7-2+4%20-9+9+4//1+6-11//1+2%20-10%20-6%20-8-6-3-4%20-9-4%20-11-4-10-7//1+6+5-7+4+4%20-11//1+11//1+9%20-8%20-2%20-4//1+10+3//1+2-6//1+11//1+3//1+4+3+2+7%20-3//1+7-11-2//1+8//1+11//1+5%20-3-6//1+8+5//1+6+4+9-3-9+9%20-11+10+7%20-2-4%20-11+5+11+4%20-6%20-2%20-7%20-5+4-9+6-5-3%20-7//1+7-3-10+7//1+2%20-3+2%20-7//1+2%20-10+4-11+3%20-5%20-11%20-3%20-2//1+3-8%20-2+10%20-7-7-11%20-7%20-2%20-7-8-4-3//1+4//1+10-9+9//1+9//1+3-7//1+8%20-3-4-11%20-6+8%20-4+10%20-3//1+5+11%20-7%20-7//1+5+11-7-3//1+4//1+11-11-9%20-5-4//1+10-4%20-8//1+2+10-2%20-5-10-8+8//1+6-2-11%20-2-9+11//1+2//1+10%20-10+2+4+6+6//1+7-3-2//1+11%20-10%20-4//1+8%20-11%20-10+4%20-8//1+2//1+6//1+8-6%20-3//1+11//1+7-2%20-3+10%20-10+3%20-3%20-3-5//1+11//1+2%20-4+2%20-9+6%20-8//1+6-9//1+3//1+9+5+4%20-8//1+6-3//1+3-10+8//1+7-10-7-6%20-10-5-9+3%20-5-8-11-6//1+9+4//1+2+5%20-4%20-11+6//1+7+8%20-7+5-9+2-9-7//1+8+7-4-9%20-3+5%20-6+10%20-6//1+11%20-6+9-9+11%20-8//1+4+7-2//1+3-7//1+9-3//1+4+9+7//1+2//1+2+3-6%20-8//1+2%20-6-7//1+5-3+6//1+10+5+10-5+10//1+8//1+7+2//1+6+11%20-10-4//1+4%20-2-10%20-6//1+4//1+5//1+7//1+2-10//1+6-11+8-2%20-7%20-9-2%20-11+10//1+6//1+9%20-8%20-10%20-11%20-10%20-7%20-2//1+2+3-5-3//1+2-11%20-4//1+6%20-7+6+10//1+10//1+5%20-8-3+8-9//1+3+9+5//1+11//1+3//1+5%20-6+8-9//1+9+4-10-4%20-5%20-3%20-10%20-4-9+3%20-8-5-10+7+10%20-9//1+8+6//1+10//1+11//1+4%20-10+2-8-2-2-5-11-2%20-2//1+9%20-9%20-5+11%20-5//1+6//1+3-3//1+6+2//1+7+4+3+3-6+7%20-3-6-5%20-10%20-10//1+4%20-5%20-10//1+3//1+9%20-3+10%20-9-7//1+2+2%20-6%20-11//1+2+9%20-6-6+10%20-9-10//1+4+8%20-11+8//1+3%20-7//1+11//1+4%20-3//1+10//1+3%20-7-6%20-3%20-9%20-9//1+8+4//1+2%20-2%20-10-11//1+6-7-6%20-9//1+6//1+6+11-8+11+11+9%20-3%20-4//1+9+10-11-10%20-6%20-5%20-9//1+6%20-11%20-11%20-8%20-9-4%20-11-8-8-10+10%20-4//1+3-5%20-3+8-6+5+6-4//1+3-3-10+5//1+7+7-8+10+2+11+3%20-2-4%20-9-8-6%20-6//1+8//1+6//1+9-5%20-10%20-3%20-7+4+2-11%20-4//1+9//1+2-5//1+2//1+5-10//1+6%20-5//1+7-3%20-5%20-10//1+8//1+3//1+5-2%20-11%20-7//1+7-3-6%20-6//1+5//1+7//1+8%20-9%20-2-6%20-4+4+7%20-11-4-9-11-6-9+6+11+11+7//1+3%20-7+10+8//1+7//1+5//1+7+9//1+6//1+11+9-4-7-2+5//1+3-11+9%20-3-8//1+10+4//1+9//1+9%20-4%20-7-4+5//1+4-7//1+11+10-6+11//1+11-4%20-8+11-6+2-2-10-10+3%20-4+4//1+5//1+8%20-7//1+7+2//1+9%20-7%20-2-10+9%20-11+2//1+11+9//1+2//1+2%20-3+9%20-9%20-9//1+6%20-8+4-2%20-8%20-6+8-8-11+9%20-6-7%20-11//1+3//1+3//1+11%20-2//1+6//1+11+2//1+10-10-6//1+7+2+3-2%20-5//1+4//1+4-4+7//1+2+9%20-3//1+11-5-8+5%20-8%20-6-3+2//1+4-4//1+7-7%20-8-10-9-7//1+9%20-5+7-6//1+3//1+9//1+4//1+9-9//1+7//1+10-8+11//1+9%20-7-6%20-10+3-2+9//1+11-2%20-9//1+10-8//1+11+6//1+2%20-5-8%20-2-11+11+4+6//1+4+10-5+9+6+10//1+11%20-6%20-2-5//1+7%20-5//1+10//1+8//1+10//1+9//1+3+2-2%20-10//1+8-6+5%20-10//1+10%20-6-9%20-7//1+3%20-4%20-4-11-11-11%20-10+11%20-11%20-3-8+10%20-4%20-6+6//1+4-4%20-7+9-2//1+7-5-4//1+3//1+7+4%20-3//1+4+6//1+10//1+6+4+8//1+8%20-8%20-6+3-9//1+7%20-9+3+7+8//1+2+11-3%20-7%20-2+10-2-2+11-8+9%20-8+7+11//1+5//1+10//1+10+9-9//1+2+7%20-9//1+2//1+4%20-8-6-2-4//1+10//1+6-8%20-9//1+10+2+5+11-11%20-7-11//1+10-4//1+5-8-6%20-4-3-3-9//1+5//1+3//1+2+4%20-2-4+8%20-3//1+6//1+10-9-5//1+3%20-3%20-5+6%20-8%20-3-6%20-9-4//1+10+8+6%20-6+5//1+9-2-2+5%20-3+7%20-5//1+10+10%20-2-9%20-9+6+3%20-9-3-7%20-8//1+8%20-3%20-6+7//1+8+8//1+4+9%20-2-4-7-2//1+5-8%20-3//1+9//1+8//1+5+10%20-4-6+7%20-2//1+4-9//1+7%20-3%20-3-9-5%20-3//1+7//1+4-9//1+10-6+9%20-10//1+4%20-5%20-4+2+2-3+9-7+6//1+9//1+2+7-7//1+3//1+5+7%20-9-5%20-7-4%20-3-7%20-7+4//1+8-11+8-6//1+8+2//1+8-6-9//1+7%20-6//1+4-3+11//1+11+7//1+9-6+6//1+7%20-11-8%20-11+2//1+11//1+4%20-5//1+8-10//1+3-5%20-2%20-7+5//1+7%20-5%20-4//1+10//1+5//1+3//1+7-11-3%20-6//1+10%20-9-11-10//1+8%20-2//1+4//1+4%20-4%20-3-11+2%20-7//1+9-11-2//1+2+9-7//1+4+4+7%20-10%20-3%20-11//1+6//1+11%20-10+11-7//1+2%20-8//1+9%20-4+9//1+4-3//1+7+6//1+5-10-9+3%20-8-3+2%20-6+8+3%20-8//1+9+5-3%20-3//1+3//1+10//1+11%20-8-2-10-6%20-9-10+10+7-11//1+2%20-8+7+3-9-8-7-8//1+9//1+2-11-3%20-8-8%20-7+6+7-6-6-7//1+4%20-2//1+3%20-2//1+10-9+7+2%20-10+4%20-3%20-11+7+3-7-3%20-7%20-3+7//1+8-9//1+6//1+4%20-10+6%20-5+3-5//1+8//1+7//1+8+7-9+5//1+4+4//1+10%20-3%20-8+3//1+5-7%20-7+11%20-3+8//1+9//1+2%20-11//1+5+3%20-10%20-9+5%20-4%20-7//1+5//1+9%20-11//1+2+4+10%20-7-2-5-9-8%20-8%20-11-8//1+7%20-4-6-5+2+5+6//1+7-2//1+7+5+3+11-8-8//1+7%20-6+8//1+10%20-10+9//1+4%20-9%20-6-9+4%20-5+8+4%20-6//1+11-2%20-10%20-4-10+7//1+11%20-9-4+11//1+6+11//1+8+9//1+4+7+2%20-10-8+5-10//1+7-5+10//1+2-4//1+2+5%20-3+4%20-7//1+2+11%20-4+3%20-10-11+11+3%20-10//1+9//1+9%20-6//1+11//1+5-6+3%20-9%20-4+3-5%20-5-5//1+3//1+10+6+4+9//1+9+9+5%20-10+10//1+11+2%20-2//1+7+10%20-10%20-9//1+7-2-3%20-4%20-3%20-4-8%20-8//1+4-8+6//1+9-8%20-10%20-3//1+9+7//1+6+2//1+5%20-2-5+7+9%20-3+9-7//1+5+9%20-2%20-9-3//1+10+8//1+2%20-3-11%20-6-7-2//1+5-7//1+8-5%20-6//1+8+4%20-11//1+6//1+6%20-4-7-6%20-10-8//1+2%20-8-5//1+2//1+2//1+7%20-2//1+11+4//1+5-9%20-9-2-10//1+9//1+8%20-8//1+7%20-9+3-11-7%20-7%20-2+6+3%20-7+9//1+6-3-2+7-8+10%20-5-10-10//1+10-9%20-9-3%20-2%20-7+9+8//1+6//1+6+2+10%20-7+2//1+5-8%20-3-11//1+8//1+6+6-2-10+7%20-3-11//1+6-7%20-2%20-2//1+5%20-3+10%20-6%20-11+3%20-8+10//1+11//1+3//1+10-7+9+5%20-9%20-7+5//1+5-7+9+10//1+2%20-11+2-6-6-3-3-9%20-10//1+5%20-10-8-8//1+9%20-10%20-9//1+7//1+4//1+4-8//1+8+2+9%20-5-2+10//1+10//1+10+5+4+9-10-8%20-5%20-9%20-5%20-4//1+10//1+2%20-8%20-11-11//1+4//1+11+7-8+8+8%20-6//1+7//1+3//1+2//1+7%20-2-2+7+10//1+7//1+10%20-7+5//1+5+2-11+5%20-4%20-2-5%20-3-7+8+4+3%20-10+2//1+3-4%20-8//1+9-3-7//1+11+3-10+7%20-9+11//1+4-6%20-8-8%20-5//1+9%20-4//1+5-11%20-7%20-4//1+5//1+4-11+6+11-7%20-6+7%20-3//1+5%20-6-5+2//1+5%20-9-5-4%20-7+6%20-11+11//1+5%20-9-3//1+6//1+3//1+6%20-11-8%20-7//1+10+9//1+11%20-2-3+10-11%20-2%20-5+6-5%20-7-4//1+10+11//1+7-8+5+5-2-2+5%20-8-2-4+8%20-3//1+2-7//1+11+4%20-11//1+7-11-8//1+4%20-9+9//1+6//1+8//1+10-4%20-6+10+2//1+3%20-5%20-2-4%20-8%20-5-7-4-9%20-11+6//1+3%20-5-7-5//1+7%20-3//1+7//1+3+9+6+6-11+9+2-4+10%20-2%20-4%20-4-9+10%20-7-9+3-3+8+4+3+4//1+10//1+2+10%20-10//1+10+3//1+10%20-5+2+11+10//1+11-7%20-8%20-6+4//1+11%20-5//1+7//1+7+11+9//1+4-2-4-4//1+9//1+6//1+5%20-11//1+11%20-10+5//1+4%20-8//1+9-6//1+6-8-4+11+3-7-7//1+3-4+8+11-11+5%20-6//1+4%20-4+5%20-4+4+8%20-9//1+7+6//1+11%20-5//1+8//1+4-7%20-9-10%20-6-5+3+4%20-6+2-7%20-10%20-8%20-5+7-3+7-3+7+6//1+7+5%20-8-7//1+6+7+8-4-11%20-8+4//1+10+11//1+4//1+5+7//1+11-11+3//1+6-3+7+5+7+5//1+10//1+2+5-4%20-9-10+2//1+9%20-4+9-6//1+4-7%20-8-4//1+3//1+2//1+10+3%20-2+2-4//1+5+11%20-9+7+4-3//1+8%20-8+11//1+8+2//1+6+6-9//1+9-3+5+9//1+3//1+10//1+9-7%20-4//1+7//1+2//1+2-5//1+6+7//1+6//1+10%20-6+4+10-6+8-4%20-2%20-6+2-11-7%20-5%20-7%20-3%20-11%20-3-2//1+5%20-5//1+2%20-7%20-10+2+4//1+3%20-6//1+9+8+10//1+4%20-6-7-11+6%20-8-8-10-8%20-7%20-6-11-8-5+10//1+2//1+6//1+8%20-4%20-11//1+2+9-10+8-2//1+10-7%20-5%20-6-4//1+10-6+3//1+10-9%20-10%20-5+2+9//1+8//1+7-5//1+3-9-10+7-10//1+6-11//1+10+2+10//1+6-6//1+11//1+6-10%20-9%20-2%20-5+3-3%20-2+5+8%20-7//1+3%20-9//1+8-11%20-2-4%20-4%20-11+2%20-2-4//1+7%20-3//1+4//1+2//1+4//1+4%20-6//1+9+5-4//1+9//1+11-6+2//1+9+4%20-6-9//1+8//1+3%20-11%20-4//1+8+4%20-2%20-7//1+6//1+10%20-6//1+8%20-10%20-5+9//1+9%20-8//1+11%20-4%20-8%20-6//1+3+3-3+6-10//1+2//1+8%20-7+11+3+9%20-7+6+3-2%20-7%20-7//1+5%20-11%20-11+9//1+7-4-9%20-9//1+10-10%20-10//1+6%20-9%20-2%20-6+2-10//1+3//1+7-2-7+6+2-4+3+4-2//1+4//1+11+4+11//1+8%20-7//1+6%20-8%20-3-6%20-3+7//1+3+5//1+11-10-9//1+11//1+7+5//1+11//1+8//1+10+6+11//1+2+11-11//1+9-9%20-4+7+10//1+9//1+10%20-10%20-3+9-5//1+3//1+6%20-9+8%20-7-2//1+4//1+4%20-7//1+7%20-11-10+4//1+6//1+4+8-2+3+5%20-3+5+7%20-8//1+5+3%20-10%20-8-11//1+8%20-9//1+11%20-7//1+11//1+8+3//1+9%20-7%20-9+8//1+11+3+5+7+11//1+9+5+8-7//1+6-2//1+7%20-5-7-5-3+5-10-4%20-7//1+4-7-6+3+4//1+5%20-5+2%20-3-4+2%20-7-8-6%20-4%20-7%20-7-2%20-5+3%20-11-6%20-10+7+4%20-8%20-9+2-2+2%20-6-4//1+4-9%20-6+10-7//1+8//1+2+5//1+4-8+10//1+4-11-6%20-5+8//1+5-2-10//1+6//1+11-8%20-7+4+11-9+11%20-11//1+8//1+7+5//1+3//1+6%20-11-3-9//1+2//1+8-5-4%20-2//1+7-9//1+6-2+11%20-3-10%20-6+10-7%20-9%20-9%20-3//1+4//1+7%20-9//1+5-3//1+3//1+2//1+5%20-3//1+11+9//1+5-11//1+7-7//1+2-5-11//1+8%20-7%20-2%20-3%20-10//1+11%20-10%20-8+4%20-10-2-2-5-9//1+9//1+8+6%20-8+11%20-6//1+2//1+5//1+11//1+9+11+8%20-5//1+2//1+3+7%20-11//1+10-6//1+4%20-9+3%20-6//1+2%20-5-8+8-7+4%20-9+2+10%20-7-9+9+6%20-8//1+6%20-2-4//1+2+4//1+11%20-4-10%20-9//1+8+9//1+7//1+9+2-4-8//1+9-3-8-5-3-11//1+10//1+4-10-11-10//1+9+7-7%20-11//1+9%20-5-5%20-11%20-10-2//1+4-2%20-6//1+7-3+8-6//1+11%20-3%20-5//1+10//1+5+5//1+7%20-8//1+4+9//1+3+6%20-10+7//1+10//1+5%20-9%20-2-6+4%20-10-8//1+9+2%20-9//1+8%20-3+10-7%20-2-6-5%20-7%20-3+9//1+8+10+7+7%20-11-2-11-4-6-10-10//1+11+11-10%20-3//1+3+8-10-2//1+9+11-9//1+11%20-9-3//1+10+5%20-7%20-10-10-3%20-6-7%20-2//1+7%20-2//1+11//1+7+4%20-10+2+10//1+5-9+6+2%20-2%20-5//1+6//1+6-7%20-9//1+5+6%20-11%20-9//1+2%20-10%20-8-9%20-11-6+3%20-2+8+5+5-5+5//1+10%20-2-4-11-6-3-4-5%20-9%20-3//1+5+9+5%20-8%20-8//1+2//1+9%20-6//1+5+8-4-6-8//1+4%20-5-4+5+9%20-4//1+10-4-10//1+11-3+7//1+10%20-11//1+5+9%20-5%20-11//1+5-11%20-4+8+9//1+3-7+9//1+6%20-9+2//1+5+9//1+2%20-6%20-5+11%20-4%20-11-6-11-5-2%20-3-9%20-9//1+6-11+3-7-4//1+8//1+8//1+7%20-4+5-5%20-2//1+3+3+10+4//1+6-2%20-2-6%20-9-5//1+2+10+4-5+4-11+4%20-11+10%20-10%20-8+9//1+10//1+5%20-11+10%20-5+11+6+4+10//1+7//1+10%20-4%20-5-8//1+4-5%20-10-10%20-4%20-9-6-2+6%20-10+8//1+11+6%20-11+8%20-3-9//1+4//1+10//1+3-11%20-3%20-4-2%20-7-10//1+9//1+11%20-10-5-6%20-10//1+10%20-3%20-3+10-5-8+11%20-4-5%20-4//1+7-11-10+7-11+8-9+2+9//1+5%20-7-8-8-10//1+9-4//1+9-6//1+2//1+11+7%20-3%20-7+6%20-8+3-6+8-5//1+8//1+3%20-6%20-11+2-9//1+9//1+5//1+9//1+3-9//1+7//1+5-10%20-7-9//1+2+9%20-7//1+6//1+9+11//1+7-9%20-4+5//1+2-7%20-2-4+3+4//1+11-3+6-7//1+8%20-7-3%20-6%20-3-2%20-8-10-4%20-3+11-5+10%20-8//1+11-7+9%20-3%20-2%20-10//1+5+4+10+6%20-7-4-2+5-11-9-6%20-10%20-10-9-8//1+11//1+4//1+7//1+4-2%20-9//1+10%20-4+3+5-7%20-7-6%20-11%20-11%20-4-2%20-11//1+5+10%20-4+7//1+11-4+11%20-5%20-3%20-9%20-6+7%20-8%20-11-4//1+6%20-9%20-6+4+7//1+2-6-3-6%20-10-4-10+6//1+10%20-10-10%20-6+8-2-6-10-6%20-5+8-11%20-3+3-11-4+2%20-10+10%20-7//1+5%20-7%20-7//1+6%20-10-7//1+6+11%20-3-9//1+8%20-2+11%20-2//1+2-4+11//1+6%20-7+3-7+7%20-5+11%20-2//1+5-3-5//1+6%20-5%20-4%20-10%20-8%20-7//1+3-9+3-8-4-3+7%20-6%20-2+3-10-9-11%20-10-2-7+7//1+11//1+9%20-10+5+5+6+5//1+6%20-3+7-8%20-8+8+8+8-10+9//1+7%20-6%20-3%20-8//1+8%20-2+8+3+8+4-2-2//1+8-5+2+2%20-4-7//1+9%20-4-6//1+9%20-3//1+8%20-9+5+6//1+7//1+2%20-4%20-2//1+6-3//1+3%20-8+9%20-7+9//1+9%20-9+10%20-8//1+5+2//1+3+6-2+3+7-3+7+9%20-2%20-7-8//1+6+6+3+2%20-8//1+2%20-11+7-10//1+2+11+2//1+8-5+7%20-3-9-10//1+3//1+2+11-3//1+2%20-7-8+4//1+2//1+5+9//1+4+11//1+8+9+6-3%20-9+8//1+8%20-3+3//1+9+11+11//1+6%20-5-4//1+6//1+5//1+7-6-9%20-3-11%20-4+5//1+2-3+3//1+2-2-11+6%20-8%20-10//1+10-3%20-2//1+2//1+2+5%20-3//1+3+10%20-2-4//1+3%20-3//1+2+7%20-10%20-7-4-8+11%20-9-7-8-3%20-3+2//1+10//1+4-6//1+8//1+5-11+7%20-6%20-6-4%20-5+10-3//1+4//1+7//1+5+9//1+8%20-6%20-10+11-2//1+6+9%20-3%20-10-7-9//1+10-8+7//1+4+5//1+10+2-11//1+2+11+8//1+7-8+10%20-6-11-7%20-9-7%20-9%20-10+2-3-4//1+6//1+6%20-2%20-9+4+10//1+8+7//1+10+5//1+3+8-7-3//1+9%20-8+5%20-9+8%20-2+4//1+9%20-5+3-9%20-2%20-4-8%20-11+2%20-5-10%20-10%20-4%20-2+8+2%20-5+8//1+3+6//1+7-10//1+10%20-11-10//1+11-4-10+2-4-11+11+3%20-5%20-4-5//1+6+4//1+4-5-4%20-6-5%20-7//1+8//1+2+7%20-7+10//1+10+7+5%20-6%20-8%20-8+4%20-3+3//1+3//1+2+7//1+10-6+9//1+3+5+11+11+8//1+3%20-3-7//1+9//1+8%20-9-11+5+8//1+2-11-3%20-7+8+4%20-7-5-7%20-9-8-7-2+5%20-10+4+9-6%20-6+3-11+8%20-7-3-11//1+4-2+3+8//1+4-2+5//1+7+6//1+3%20-5-7//1+6+6%20-3-10-6%20-4+9-2//1+7//1+7-4-3+8-4-4+8-2+8-8//1+2%20-7%20-8-11%20-9-6%20-10+5-2//1+4-6//1+4-10//1+6-2//1+10//1+5-2+4+3+7//1+8+4-5%20-4+5%20-10%20-2-2-9%20-4-6-6-4-9%20-6%20-5-11-8+7%20-11//1+4-10-8//1+7+3%20-7+2//1+8//1+9-6+5+10-2%20-2-5%20-6//1+11+5-11-5//1+5+3//1+6%20-4-10%20-9%20-7-4%20-7%20-8-7+7%20-4+3-7-4//1+5+7+10//1+10-4%20-5+2%20-8%20-9+9//1+7//1+2-6-6-5%20-8%20-5+9%20-7+10%20-6//1+2+7//1+9-9%20-6%20-3-9+7+11%20-8-3//1+4%20-6-2-9+3//1+8//1+8//1+7%20-10+6%20-7-7-10%20-11+8//1+11+7-9%20-2%20-3%20-10-2%20-3-6+11+11//1+6%20-10//1+2//1+7//1+11//1+8%20-9+11+5-9%20-10//1+5-3-9//1+11-11+11-2-2%20-9%20-10//1+2-9//1+6//1+8//1+9//1+9%20-6//1+4%20-10+3-6+3%20-9%20-5-3-8//1+11+3%20-7-4+3//1+7%20-4//1+5-5//1+8+9//1+9//1+11+5//1+5-11//1+7%20-5+8//1+3%20-3-3//1+8-11//1+5//1+5+8+7-5-9+3-2//1+4-8%20-2+3-9%20-4+6//1+6-9%20-7-2-3//1+3%20-5//1+2+8//1+11//1+3+10//1+5%20-2+3//1+3//1+3-9-11-6-11-5%20-8+7+6-4+6%20-10+4//1+9//1+11-5-4+8-6//1+3%20-4-8-9+5-6+4-8-6+10%20-6+2//1+10%20-5-10+2-10+5-2+3%20-10%20-6+7%20-10+6-2-7//1+7-6-7//1+11+8-10%20-5-11-10%20-2-5//1+10+5%20-5%20-5-4-4-8-10-6+11%20-10+5//1+10//1+10+5-5%20-3-10+11//1+10//1+7%20-4-7-7//1+5+3%20-3+11%20-2+7+6+4//1+2-6+5//1+5%20-7//1+10//1+7+11//1+9-11+5+8//1+4%20-2//1+10+11-8//1+2+5+8%20-10-6//1+7%20-4//1+7-8//1+3+11%20-5//1+10%20-9%20-7-3+7//1+5%20-11-8//1+5//1+7//1+3//1+8-8-8//1+6+8//1+2%20-4%20-9-11+8+4-10%20-7%20-3+10+5%20-8//1+2%20-6-11//1+4%20-4%20-7-7%20-7-9-2+9//1+4-3-6%20-3%20-11//1+11+8//1+7-7//1+6%20-7+3+4//1+10%20-3+11+11%20-3//1+2-11//1+9+7+11%20-2//1+8//1+3+10//1+9+6%20-9+10-10//1+11//1+8+10-4//1+11%20-2//1+10-2%20-5+7+6%20-3//1+3-11-11//1+8+3//1+11-4-8%20-11-6//1+10%20-8%20-2//1+6-5//1+2//1+2+11-9%20-7+8%20-9+4//1+4//1+10-8+5//1+6//1+4%20-7-3-7+3//1+3//1+11-3-7-2-2-8//1+5+6%20-8-7-11+4//1+11+3%20-5-8+8//1+3-10%20-11//1+4-5%20-8-4%20-8%20-10%20-5-11//1+3%20-7+6//1+5//1+3-6%20-5+5//1+8-10//1+9//1+6-10%20-2//1+8//1+5+2-9%20-5//1+9-5%20-2+3//1+6+10//1+9//1+7%20-8%20-9%20-11+11+7-11+11%20-2//1+3+2//1+6//1+3//1+7%20-8-5%20-7+10-5//1+6%20-6//1+9+8-8//1+10%20-9%20-9%20-4-8+6-6%20-10-8%20-6+8+2+11%20-5%20-7//1+8-9%20-2+9//1+4+8%20-7//1+9+11+2%20-3+8%20-2//1+10//1+8%20-4+6%20-8//1+3+6+7%20-11%20-5//1+2%20-5//1+5//1+2-11-6%20-2-9%20-3%20-3//1+11//1+8%20-2%20-7%20-8%20-10+4+8-10+4+4%20-6-6-6-8+3+8+7//1+9//1+6%20-10//1+6+6-4//1+10-7-8//1+7+10//1+6//1+10-10%20-6-10%20-4-10//1+7+9+5%20-4+8%20-8//1+9%20-7-4%20-6-2%20-3+3-4-10+9+10//1+8+7-11-2%20-6//1+6-11%20-9+5+4-6%20-5-8//1+11+2+7-6//1+2-10%20-3//1+8%20-6+6//1+3%20-9+9+7%20-10%20-7-2-6+6//1+2+9-6-7+4-2//1+5%20-11+10-7//1+5//1+3+2+7+8+10-3-10-8-4%20-2//1+2//1+5+2+6//1+10-11//1+3%20-10%20-9+8//1+8%20-11%20-10-10+7-6%20-3-3//1+5//1+10-6//1+9-8%20-5//1+6+2%20-6+5//1+6-7//1+3//1+7%20-6-5%20-11+5%20-2-7+4+11//1+8//1+8%20-3+3-9-3//1+9//1+11-3%20-2%20-9-7//1+8-8-9%20-10-8+2+6+9//1+3+4//1+11-2-7//1+9%20-9+8%20-6//1+2%20-5%20-11//1+6//1+3+5-6//1+7//1+3%20-5-2+10-11-8//1+5//1+2%20-7%20-9-3+5%20-2-10+3//1+4+3-7%20-8+4%20-8-9-2-5-7//1+5//1+9+3//1+3-5+7-7+7+4//1+9+5+10-10//1+8//1+9%20-6-2//1+9//1+2-5//1+8+6%20-3+8%20-9-2//1+11//1+8%20-10+5-4-8//1+9%20-11//1+7//1+7+8//1+7//1+4//1+8%20-11%20-3+3+3%20-4+7+7%20-10-10//1+4+2%20-4%20-10+3+3%20-10//1+10//1+6+9%20-3%20-4%20-4+10+10//1+8+9+4%20-8%20-5+5+4%20-6+9//1+9//1+2%20-8+9+6+5//1+2//1+11//1+2-6+11//1+4%20-2%20-5+9%20-5//1+11+3+2+7-6-7+6%20-9-5//1+8+2%20-8%20-3//1+6//1+4//1+11%20-8+5-3%20-5%20-5//1+5-8//1+8//1+2//1+4//1+5%20-4+9-10//1+4+2//1+4+4-8%20-8//1+10%20-5-3%20-10//1+2+2-2-2%20-5%20-7%20-4-10//1+10//1+7+9%20-4%20-4%20-3%20-6-7%20-7%20-9%20-6//1+2//1+7-9//1+2//1+10+9//1+4-3//1+10//1+7+11-6-6%20-2+11+8+5-3-11-4-2-4%20-9+9-3+8-11-5-6+2%20-9//1+11//1+4-11-2%20-4-8-2-11//1+6%20-5%20-7//1+7+7%20-8+5//1+9//1+10%20-2//1+8-8+11-2//1+11//1+6-5//1+7+2+5-8+5+11+10-9//1+3-6-9%20-9+11%20-9//1+3%20-8+7+4//1+5//1+8-7-4%20-11-9%20-8%20-9+9+2+4-7+9//1+11+8//1+11%20-5%20-5+10-7%20-6%20-9%20-3-7%20-4%20-2%20-10//1+8+6+2//1+6//1+8+9+7//1+9%20-7%20-2%20-11//1+4//1+3-4%20-5+2+5%20-10+9-10-7-10+6%20-2+10+9%20-6//1+10-7-5%20-9-11//1+11//1+9%20-10%20-2+5-6-4%20-5+2-8-3//1+8//1+5+8//1+4-4+2-4//1+11+3%20-5+2//1+2%20-9-10//1+4//1+7-11+6-10-6-5+5-4-10-7%20-7-3//1+11//1+8+2-8+5%20-2%20-9-3%20-8-8+3-3%20-4//1+6%20-3+6+5+3+4//1+5%20-7%20-8%20-3//1+3-5-5+9-6%20-3//1+4-2//1+2%20-3//1+8+3%20-8-2%20-11%20-3%20-2+2-8+11-2+9%20-3%20-9+3-10+11//1+11%20-5%20-5+5+8+2%20-3+2//1+11%20-11//1+3//1+2+2-3//1+9//1+7//1+2%20-10+6%20-3+10+9//1+2+10+5//1+5-4+4-10//1+9%20-4//1+3//1+4%20-2%20-10%20-3-7+6-11+11-5%20-11//1+9//1+6-2%20-11-9-11//1+4//1+4//1+3%20-10-4-7//1+9+7+7-4-10%20-4%20-10-8+2%20-8+9+2-10+8//1+10+4+9+4+7-8%20-4+6%20-4-5%20-10//1+9%20-2//1+3+3-4-9%20-4+10+4+7%20-3%20-5//1+11//1+4//1+6//1+7%20-10+10//1+4//1+3//1+8%20-5%20-2//1+2+11%20-3%20-11-5//1+5-7-3//1+7%20-5//1+7%20-10//1+3//1+2//1+7//1+5%20-5%20-3%20-5-9%20-3%20-7-9//1+8//1+7-10+8+9+6-11+3+9%20-3+10-3+5%20-7+3//1+8+2//1+2%20-5-4-11-4//1+11-10%20-2+9-9//1+7%20-7-9-2+3-3//1+2%20-3+4//1+10%20-8-8+11+7//1+11-9+9//1+8-7%20-6//1+7%20-9%20-5%20-8//1+6//1+5//1+8%20-10-10//1+7-3-11//1+4+8%20-11+9//1+9//1+6%20-2-7-6+3-4-10+5%20-7//1+11-10%20-6//1+9-9//1+4%20-3+11%20-3+8//1+3%20-3%20-6-3//1+11%20-3+10+5+6-9-9-5%20-8//1+8%20-5+9-3-7//1+9+9-2+5//1+3+2%20-7-6%20-5-5-8-5+4//1+6//1+5%20-9-11+7%20-8+6%20-4-10+9%20-8//1+9-8%20-7-3%20-4-8%20-3//1+7//1+10%20-9//1+6+11%20-2//1+4//1+3%20-8//1+2+9+10//1+8//1+8+8-6//1+6%20-10-11//1+2-2-6+3//1+10//1+2//1+7%20-5+4+4+2+10//1+9-9-9//1+2//1+5+9//1+2//1+6//1+6-3+10%20-7-8-4-9//1+2//1+10-10-3//1+7//1+11-3//1+2+8-2+9//1+11//1+9%20-5%20-2+2+5%20-10+4+5//1+6//1+3%20-9%20-6+4+11+11%20-5//1+10-3+11-9-9%20-7+2-10//1+3-9+7%20-11//1+10%20-3%20-5%20-5//1+2+6%20-6+10%20-7//1+2-11-3%20-8//1+4%20-5-5+6%20-9-5-9+3-3+7//1+9+8//1+4%20-5+5%20-7-7+10+3-10//1+9//1+8%20-6+7//1+2+2%20-7+4//1+4%20-3%20-11%20-6%20-2%20-9-11-6%20-6%20-7-4%20-9//1+7%20-7+3+10-5-9%20-3//1+2+5%20-4-10-9%20-11-10//1+5%20-9%20-10%20-5%20-7+11//1+8//1+3%20-10//1+4-11+3%20-2-4+2//1+8+6//1+2+5//1+11-7%20-8%20-11+3-7%20-7//1+9+5//1+3%20-8-11+10+9-4%20-8-10%20-3-9+2+2+6//1+5-9//1+4+5//1+10-11%20-11%20-8-8+4+11-7%20-3//1+6//1+2%20-4%20-6%20-2%20-7+4%20-4-4//1+9-7+10//1+6+11%20-11-11-7-7-8%20-10//1+7%20-7%20-9+8+10%20-10%20-4//1+8+3%20-3//1+3%20-10+8+3+9%20-8%20-9+10+3%20-4%20-6-9//1+2//1+8//1+8+2-3%20-3//1+9%20-5-9+3%20-2//1+4-8+6+5//1+11+11-3%20-5-6+2-10//1+4+8-2-11+9//1+4+9//1+11+6+5//1+9+8-8%20-7+5-5-9+7%20-10+3+4-4//1+6//1+11-9-3%20-5+9//1+10-4%20-11//1+3-11//1+9-3//1+4//1+9//1+8-5-9-4-4-8-10+10-6//1+9%20-3%20-5-8";
 const BIG_INT_EXPR: &str = "7-3-10-2%20-6+10-4+2//1+7//1+8//1+7%20-3//1+7-6//1+2%20-2%20-2%20-7+8%20-11+2-6%20-3//1+7//1+5-4-10//1+9+11+5//1+3+5%20-7%20-5//1+8+5%20-5%20-2//1+6//1+5//1+8//1+6%20-2//1+6%20-9//1+2%20-2//1+8-5//1+9//1+4//1+6//1+8-7%20-11-9//1+6//1+9+6%20-8//1+2+5//1+6-2//1+7%20-3%20-7//1+8-7-4%20-6+4%20-8-10%20-9+9%20-10%20-4+6%20-3+2+2%20-4%20-11-9%20-11%20-2%20-8-6//1+3+10%20-9%20-3//1+3//1+7-10-2+4%20-3//1+6%20-11%20-9%20-7-7%20-5-6%20-2-11+11%20-6+10-5+3%20-5+5//1+5-8%20-11+11%20-2+11-8+4-4//1+4//1+8+6//1+4+2%20-2%20-7%20-6//1+6%20-5-7%20-8-10%20-9-10%20-9//1+5//1+8%20-3//1+11-3%20-10//1+6-3//1+8+5//1+10+4-11+5//1+5+2%20-4//1+9-10-5%20-6%20-8+9//1+9-9//1+9//1+8+3//1+11%20-9+10//1+8%20-6%20-5%20-10//1+10-5//1+5%20-4+2//1+8//1+11//1+6%20-10+8+5//1+8+3+7%20-4//1+9-4//1+8//1+8-6+10+2//1+2+7-8-5";
@@ -0,0 +1,22 @@
+use math_parse::*;
+
+const FLOAT_BITS: &str = include_str!("float_bits.txt");
+
+#[test]
+fn test_deterministic_float_bits() {
+    let options = MathParseOptions {
+        deterministic_floats: true,
+        ..MathParseOptions::default()
+    };
+    let lines = FLOAT_BITS.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'));
+    for line in lines {
+        let (expression, bits) = line.rsplit_once(" = ").unwrap_or_else(|| panic!("`{line}` has no bits"));
+        let bits = u64::from_str_radix(bits.trim_start_matches("0x"), 16).unwrap_or_else(|e| panic!("`{line}`: {e}"));
+        let result = MathParse::parse_with_options(expression, &options)
+            .and_then(|parsed| parsed.solve_float(None))
+            .unwrap_or_else(|e| panic!("`{expression}` can't be solved: {e}"));
+        assert_eq!(result.to_bits(), bits, "`{expression}` is {result}, which is not {}", f64::from_bits(bits));
+    }
+}
@@ -0,0 +1,77 @@
+use math_parse::*;
+use std::collections::HashMap;
+
+const GOLDEN_CORPUS: &str = include_str!("golden_corpus.txt");
+
+/// The expressions of the golden corpus.
+fn corpus() -> Vec<&'static str> {
+    GOLDEN_CORPUS.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .collect()
+}
+
+fn variables() -> HashMap<String, String> {
+    HashMap::from([
+        ("a".to_string(), "7".to_string()),
+        ("b".to_string(), "-3".to_string()),
+        ("c".to_string(), "12".to_string()),
+    ])
+}
+
+/// Check that a formatted expression is solved to the same result as the
+/// original one. If `exact` is true, it must also be parsed back to the same
+/// RPN actions, while in infix notation chains of unary operators such as
+/// `---a` are reduced when parsing.
+fn check_same(original: &MathParse, formatted: &str, reparsed: Result<MathParse, MathParseErrors>, format: &str, exact: bool) {
+    let reparsed = reparsed.unwrap_or_else(|e| panic!("{format} `{formatted}` can't be parsed: {e}"));
+    if exact {
+        assert_eq!(reparsed.to_rpn(), original.to_rpn(), "{format} `{formatted}`");
+    }
+    let variables = variables();
+    assert_eq!(reparsed.solve_value(Some(&variables)), original.solve_value(Some(&variables)), "{format} `{formatted}`");
+}
+
+#[test]
+fn test_corpus_round_trip() {
+    for expression in corpus() {
+        let parsed = MathParse::parse(expression).unwrap_or_else(|e| panic!("`{expression}` can't be parsed: {e}"));
+        assert!(parsed.solve_value(Some(&variables())).is_ok(), "`{expression}` can't be solved");
+        let tree = parsed.to_tree().unwrap();
+
+        let infix = format!("{tree}");
+        check_same(&parsed, &infix, MathParse::parse(&infix), "Tree Display", false);
+
+        let infix = tree.to_expression_string();
+        check_same(&parsed, &infix, MathParse::parse(&infix), "Tree::to_expression_string", false);
+
+        let rpn = rpn_slice_to_string(&parsed.to_rpn().unwrap());
+        check_same(&parsed, &rpn, MathParse::parse_rpn(&rpn), "rpn_slice_to_string", true);
+
+        let prefix = parsed.to_prefix_string().unwrap();
+        check_same(&parsed, &prefix, MathParse::parse_prefix(&prefix), "to_prefix_string", true);
+    }
+}
+
+#[test]
+fn test_corpus_covers_every_operator() {
+    let parsed = corpus().into_iter()
+        .map(|expression| MathParse::parse(expression).unwrap())
+        .collect::<Vec<_>>();
+    for info in supported_operators() {
+        for symbol in info.symbols {
+            assert!(GOLDEN_CORPUS.contains(symbol), "`{symbol}` is not in the golden corpus");
+        }
+        if info.op == OpKind::Unary(UnaryOp::Plus) {
+            continue; // Removed when parsing.
+        }
+        let used = parsed.iter().any(|x| {
+            let (unary, binary) = x.operators();
+            match info.op {
+                OpKind::Unary(op) => unary.contains(&op),
+                OpKind::Binary(op) => binary.contains(&op),
+            }
+        });
+        assert!(used, "{:?} is not in the golden corpus", info.op);
+    }
+}